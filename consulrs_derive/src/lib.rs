@@ -1,5 +1,5 @@
 #[macro_use]
-extern crate synstructure;
+extern crate quote;
 extern crate proc_macro;
 
 mod error;
@@ -29,15 +29,78 @@ fn endpoint_derive(s: synstructure::Structure) -> proc_macro2::TokenStream {
         return Error::new(Span::call_site(), "May only be used on with structs").into_tokens();
     }
 
-    s.gen_impl(quote! {
+    let builder = format_ident!("{}Builder", s.ast().ident);
+    let featured = s.gen_impl(quote! {
         use crate::api::features::{FeaturedEndpoint, Features};
 
         gen impl FeaturedEndpoint for @Self {
             fn features(&self) -> Option<Features> {
                 self.features.clone()
             }
+
+            fn set_features(&mut self, features: Option<Features>) {
+                self.features = features;
+            }
         }
-    })
+    });
+
+    quote! {
+        #featured
+
+        impl #builder {
+            /// Serves the request from the agent cache using the given
+            /// [CacheOptions][crate::api::features::CacheOptions], keeping any
+            /// other configured features.
+            pub fn cached(
+                &mut self,
+                options: crate::api::features::CacheOptions,
+            ) -> &mut Self {
+                let mut features = self.features.clone().flatten().unwrap_or_default();
+                features.cached = Some(options.header());
+                self.features = Some(Some(features));
+                self
+            }
+
+            /// Sets the [ConsistencyMode][crate::api::features::ConsistencyMode]
+            /// of the request, keeping any other configured features.
+            pub fn consistency(
+                &mut self,
+                mode: crate::api::features::ConsistencyMode,
+            ) -> &mut Self {
+                let mut features = self.features.clone().flatten().unwrap_or_default();
+                features.mode = Some(mode);
+                self.features = Some(Some(features));
+                self
+            }
+
+            /// Sets the [Filter][crate::api::features::Filter] expression of the
+            /// request, keeping any other configured features.
+            pub fn filter(&mut self, filter: crate::api::features::Filter) -> &mut Self {
+                let mut features = self.features.clone().flatten().unwrap_or_default();
+                features.filter = Some(filter.into());
+                self.features = Some(Some(features));
+                self
+            }
+
+            /// Sets the timeout of the request, overriding the timeouts
+            /// configured on the client, keeping any other configured features.
+            pub fn request_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+                let mut features = self.features.clone().flatten().unwrap_or_default();
+                features.timeout = Some(timeout);
+                self.features = Some(Some(features));
+                self
+            }
+
+            /// Sets the ID sent with the request to correlate it with the
+            /// logs of Consul, overriding any generated by the client.
+            pub fn request_id<S: Into<String>>(&mut self, id: S) -> &mut Self {
+                let mut features = self.features.clone().flatten().unwrap_or_default();
+                features.request_id = Some(id.into());
+                self.features = Some(Some(features));
+                self
+            }
+        }
+    }
 }
 
 synstructure::decl_derive!([QueryEndpoint] => endpoint_derive);
@@ -0,0 +1,202 @@
+//! Manages Consul [ACLs](https://www.consul.io/api-docs/acl).
+use rustify::endpoint::Endpoint;
+
+use crate::{
+    api::{
+        self,
+        acl::{
+            common::{ACLAccess, ACLAuthorization, ACLReplicationStatus, ACLResource, ACLToken},
+            requests::{
+                AuthorizeRequest, AuthorizeRequestBuilder, BootstrapRequest,
+                BootstrapRequestBuilder, LoginRequest, LoginRequestBuilder, LogoutRequest,
+                LogoutRequestBuilder, ReplicationStatusRequest, ReplicationStatusRequestBuilder,
+            },
+        },
+        features::FeaturedEndpoint,
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+pub mod auth_method;
+pub mod binding_rule;
+pub mod policy;
+pub mod role;
+pub mod rules;
+pub mod token;
+
+/// Checks whether the ACL token of the client grants each of the given
+/// permissions, returning them with `allow` set.
+///
+/// Consul resolves the token the same way it does when authorizing requests,
+/// including its roles, service and node identities, and the default policy.
+///
+/// See [AuthorizeRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn authorize(
+    client: &impl Client,
+    permissions: &[ACLAuthorization],
+    opts: Option<&mut AuthorizeRequestBuilder>,
+) -> Result<ApiResponse<Vec<ACLAuthorization>>, ClientError> {
+    let mut t = AuthorizeRequest::builder();
+    let data = serde_json::to_vec(permissions)
+        .map_err(|e| ClientError::JsonSerializeError { source: e })?;
+    let endpoint = opts.unwrap_or(&mut t).data(data).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Bootstraps the ACL system, returning the initial management token.
+///
+/// If the ACL system has already been bootstrapped a
+/// [ClientError::ACLBootstrapError] is returned containing the reset index.
+/// Bootstrapping can be allowed again by writing the reset index to the
+/// `acl-bootstrap-reset` file in the data directory of the leader.
+///
+/// See [BootstrapRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn bootstrap(
+    client: &impl Client,
+    opts: Option<&mut BootstrapRequestBuilder>,
+) -> Result<ApiResponse<ACLToken>, ClientError> {
+    let mut t = BootstrapRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint)
+        .await
+        .map_err(|e| match e {
+            ClientError::APIError {
+                code: 403,
+                index,
+                message: Some(m),
+                request_id,
+                retry_after,
+            } => match parse_reset_index(&m) {
+                Some(reset_index) => ClientError::ACLBootstrapError { reset_index },
+                None => ClientError::APIError {
+                    code: 403,
+                    index,
+                    message: Some(m),
+                    request_id,
+                    retry_after,
+                },
+            },
+            e => e,
+        })
+}
+
+/// Returns whether the ACL token of the client grants the given access to the
+/// given resource, i.e. `write` access to the key `app/config`.
+///
+/// The segment names the resource and is ignored for resources which don't
+/// have any, such as `operator`. See [authorize] for checking several
+/// permissions at once.
+#[instrument(skip(client), err)]
+pub async fn can(
+    client: &impl Client,
+    resource: ACLResource,
+    segment: &str,
+    access: ACLAccess,
+) -> Result<bool, ClientError> {
+    let permission = ACLAuthorization {
+        access,
+        resource,
+        segment: Some(segment.to_string()),
+        ..Default::default()
+    };
+    let res = authorize(client, &[permission], None).await?;
+    Ok(res.response.iter().all(|p| p.allow == Some(true)))
+}
+
+/// Exchanges a bearer token (i.e. a Kubernetes service account token or a
+/// JWT) for a new Consul ACL token using the given auth method.
+///
+/// See [LoginRequest]
+#[instrument(skip(client, bearer_token, opts), err)]
+pub async fn login(
+    client: &impl Client,
+    auth_method: &str,
+    bearer_token: &str,
+    opts: Option<&mut LoginRequestBuilder>,
+) -> Result<ApiResponse<ACLToken>, ClientError> {
+    let mut t = LoginRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .auth_method(auth_method)
+        .bearer_token(bearer_token)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Destroys the given ACL token, which must have been created by [login].
+///
+/// The token is sent in place of the token configured on the client.
+///
+/// See [LogoutRequest]
+#[instrument(skip(client, token, opts), err)]
+pub async fn logout(
+    client: &impl Client,
+    token: &str,
+    opts: Option<&mut LogoutRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = LogoutRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    let mut middle = client.middle(endpoint.features());
+    middle.token = Some(token.into());
+    endpoint
+        .with_middleware(&middle)
+        .exec(&api::intercepted(client, client.http()))
+        .await
+        .map_err(api::parse_err)
+        .map(api::parse_empty)?
+}
+
+/// Returns the status of ACL replication in the datacenter.
+///
+/// See [ReplicationStatusRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn replication_status(
+    client: &impl Client,
+    opts: Option<&mut ReplicationStatusRequestBuilder>,
+) -> Result<ApiResponse<ACLReplicationStatus>, ClientError> {
+    let mut t = ReplicationStatusRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Checks that the ACL token of the client grants every given permission,
+/// failing with a [ClientError::MissingPermissionsError] listing those it
+/// doesn't.
+///
+/// This is meant to be called on startup, so that an application fails fast
+/// with a clear error instead of on the first request its token can't make.
+///
+/// See [authorize]
+#[instrument(skip(client), err)]
+pub async fn require(
+    client: &impl Client,
+    permissions: &[ACLAuthorization],
+) -> Result<(), ClientError> {
+    let res = authorize(client, permissions, None).await?;
+    let missing: Vec<String> = res
+        .response
+        .iter()
+        .filter(|p| p.allow != Some(true))
+        .map(|p| p.to_string())
+        .collect();
+    match missing.is_empty() {
+        true => Ok(()),
+        false => Err(ClientError::MissingPermissionsError { missing }),
+    }
+}
+
+/// Parses the reset index out of the error returned when bootstrapping is no
+/// longer allowed, i.e. "ACL bootstrap no longer allowed (reset index: 13)".
+fn parse_reset_index(message: &str) -> Option<u64> {
+    let start = message.find("reset index: ")? + "reset index: ".len();
+    let rest = &message[start..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
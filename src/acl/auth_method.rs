@@ -0,0 +1,90 @@
+use crate::{
+    api::{
+        self,
+        acl::{
+            common::ACLAuthMethod,
+            requests::{
+                CreateAuthMethodRequest, CreateAuthMethodRequestBuilder, DeleteAuthMethodRequest,
+                DeleteAuthMethodRequestBuilder, ListAuthMethodsRequest,
+                ListAuthMethodsRequestBuilder, ReadAuthMethodRequest, ReadAuthMethodRequestBuilder,
+                UpdateAuthMethodRequest, UpdateAuthMethodRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Creates a new ACL auth method of the given type (i.e. "kubernetes").
+///
+/// See [CreateAuthMethodRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn create(
+    client: &impl Client,
+    name: &str,
+    ty: &str,
+    opts: Option<&mut CreateAuthMethodRequestBuilder>,
+) -> Result<ApiResponse<ACLAuthMethod>, ClientError> {
+    let mut t = CreateAuthMethodRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).ty(ty).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Deletes the ACL auth method with the given name.
+///
+/// See [DeleteAuthMethodRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut DeleteAuthMethodRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = DeleteAuthMethodRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists all ACL auth methods.
+///
+/// See [ListAuthMethodsRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListAuthMethodsRequestBuilder>,
+) -> Result<ApiResponse<Vec<ACLAuthMethod>>, ClientError> {
+    let mut t = ListAuthMethodsRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the ACL auth method with the given name.
+///
+/// See [ReadAuthMethodRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut ReadAuthMethodRequestBuilder>,
+) -> Result<ApiResponse<ACLAuthMethod>, ClientError> {
+    let mut t = ReadAuthMethodRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the ACL auth method with the given name.
+///
+/// The type of the auth method is required when updating it.
+///
+/// See [UpdateAuthMethodRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn update(
+    client: &impl Client,
+    name: &str,
+    ty: &str,
+    opts: Option<&mut UpdateAuthMethodRequestBuilder>,
+) -> Result<ApiResponse<ACLAuthMethod>, ClientError> {
+    let mut t = UpdateAuthMethodRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).ty(ty).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
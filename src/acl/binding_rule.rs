@@ -0,0 +1,108 @@
+use crate::{
+    api::{
+        self,
+        acl::{
+            common::{ACLBindType, ACLBindingRule},
+            requests::{
+                CreateBindingRuleRequest, CreateBindingRuleRequestBuilder,
+                DeleteBindingRuleRequest, DeleteBindingRuleRequestBuilder, ListBindingRulesRequest,
+                ListBindingRulesRequestBuilder, ReadBindingRuleRequest,
+                ReadBindingRuleRequestBuilder, UpdateBindingRuleRequest,
+                UpdateBindingRuleRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Creates a new ACL binding rule for the given auth method.
+///
+/// See [CreateBindingRuleRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn create(
+    client: &impl Client,
+    auth_method: &str,
+    bind_type: ACLBindType,
+    bind_name: &str,
+    opts: Option<&mut CreateBindingRuleRequestBuilder>,
+) -> Result<ApiResponse<ACLBindingRule>, ClientError> {
+    let mut t = CreateBindingRuleRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .auth_method(auth_method)
+        .bind_type(bind_type)
+        .bind_name(bind_name)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Deletes the ACL binding rule with the given ID.
+///
+/// See [DeleteBindingRuleRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete(
+    client: &impl Client,
+    id: &str,
+    opts: Option<&mut DeleteBindingRuleRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = DeleteBindingRuleRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).id(id).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists all ACL binding rules.
+///
+/// See [ListBindingRulesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListBindingRulesRequestBuilder>,
+) -> Result<ApiResponse<Vec<ACLBindingRule>>, ClientError> {
+    let mut t = ListBindingRulesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the ACL binding rule with the given ID.
+///
+/// See [ReadBindingRuleRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read(
+    client: &impl Client,
+    id: &str,
+    opts: Option<&mut ReadBindingRuleRequestBuilder>,
+) -> Result<ApiResponse<ACLBindingRule>, ClientError> {
+    let mut t = ReadBindingRuleRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).id(id).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the ACL binding rule with the given ID.
+///
+/// The auth method, bind type, and bind name of the rule are required when
+/// updating it.
+///
+/// See [UpdateBindingRuleRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn update(
+    client: &impl Client,
+    id: &str,
+    auth_method: &str,
+    bind_type: ACLBindType,
+    bind_name: &str,
+    opts: Option<&mut UpdateBindingRuleRequestBuilder>,
+) -> Result<ApiResponse<ACLBindingRule>, ClientError> {
+    let mut t = UpdateBindingRuleRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .id(id)
+        .auth_method(auth_method)
+        .bind_type(bind_type)
+        .bind_name(bind_name)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
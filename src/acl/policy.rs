@@ -0,0 +1,103 @@
+use crate::{
+    api::{
+        self,
+        acl::{
+            common::ACLPolicy,
+            requests::{
+                CreatePolicyRequest, CreatePolicyRequestBuilder, DeletePolicyRequest,
+                DeletePolicyRequestBuilder, ListPoliciesRequest, ListPoliciesRequestBuilder,
+                ReadPolicyByNameRequest, ReadPolicyByNameRequestBuilder, ReadPolicyRequest,
+                ReadPolicyRequestBuilder, UpdatePolicyRequest, UpdatePolicyRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Creates a new ACL policy.
+///
+/// See [CreatePolicyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn create(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut CreatePolicyRequestBuilder>,
+) -> Result<ApiResponse<ACLPolicy>, ClientError> {
+    let mut t = CreatePolicyRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Deletes the ACL policy with the given ID.
+///
+/// See [DeletePolicyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete(
+    client: &impl Client,
+    id: &str,
+    opts: Option<&mut DeletePolicyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = DeletePolicyRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).id(id).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists all ACL policies.
+///
+/// See [ListPoliciesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListPoliciesRequestBuilder>,
+) -> Result<ApiResponse<Vec<ACLPolicy>>, ClientError> {
+    let mut t = ListPoliciesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the ACL policy with the given ID.
+///
+/// See [ReadPolicyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read(
+    client: &impl Client,
+    id: &str,
+    opts: Option<&mut ReadPolicyRequestBuilder>,
+) -> Result<ApiResponse<ACLPolicy>, ClientError> {
+    let mut t = ReadPolicyRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).id(id).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the ACL policy with the given name.
+///
+/// See [ReadPolicyByNameRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read_by_name(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut ReadPolicyByNameRequestBuilder>,
+) -> Result<ApiResponse<ACLPolicy>, ClientError> {
+    let mut t = ReadPolicyByNameRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the ACL policy with the given ID.
+///
+/// The name of the policy is required when updating it.
+///
+/// See [UpdatePolicyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn update(
+    client: &impl Client,
+    id: &str,
+    name: &str,
+    opts: Option<&mut UpdatePolicyRequestBuilder>,
+) -> Result<ApiResponse<ACLPolicy>, ClientError> {
+    let mut t = UpdatePolicyRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).id(id).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
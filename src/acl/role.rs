@@ -0,0 +1,103 @@
+use crate::{
+    api::{
+        self,
+        acl::{
+            common::ACLRole,
+            requests::{
+                CreateRoleRequest, CreateRoleRequestBuilder, DeleteRoleRequest,
+                DeleteRoleRequestBuilder, ListRolesRequest, ListRolesRequestBuilder,
+                ReadRoleByNameRequest, ReadRoleByNameRequestBuilder, ReadRoleRequest,
+                ReadRoleRequestBuilder, UpdateRoleRequest, UpdateRoleRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Creates a new ACL role.
+///
+/// See [CreateRoleRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn create(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut CreateRoleRequestBuilder>,
+) -> Result<ApiResponse<ACLRole>, ClientError> {
+    let mut t = CreateRoleRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Deletes the ACL role with the given ID.
+///
+/// See [DeleteRoleRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete(
+    client: &impl Client,
+    id: &str,
+    opts: Option<&mut DeleteRoleRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = DeleteRoleRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).id(id).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists all ACL roles.
+///
+/// See [ListRolesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListRolesRequestBuilder>,
+) -> Result<ApiResponse<Vec<ACLRole>>, ClientError> {
+    let mut t = ListRolesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the ACL role with the given ID.
+///
+/// See [ReadRoleRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read(
+    client: &impl Client,
+    id: &str,
+    opts: Option<&mut ReadRoleRequestBuilder>,
+) -> Result<ApiResponse<ACLRole>, ClientError> {
+    let mut t = ReadRoleRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).id(id).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the ACL role with the given name.
+///
+/// See [ReadRoleByNameRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read_by_name(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut ReadRoleByNameRequestBuilder>,
+) -> Result<ApiResponse<ACLRole>, ClientError> {
+    let mut t = ReadRoleByNameRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the ACL role with the given ID.
+///
+/// The name of the role is required when updating it.
+///
+/// See [UpdateRoleRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn update(
+    client: &impl Client,
+    id: &str,
+    name: &str,
+    opts: Option<&mut UpdateRoleRequestBuilder>,
+) -> Result<ApiResponse<ACLRole>, ClientError> {
+    let mut t = UpdateRoleRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).id(id).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
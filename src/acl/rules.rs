@@ -0,0 +1,490 @@
+//! Builds and parses the rules of ACL policies.
+//!
+//! Consul stores the rules of a policy as an HCL (or JSON) document. [Rules]
+//! builds such a document from typed rules, formatting it as HCL with
+//! [ToString] or as JSON with [Rules::to_json], and parses existing documents
+//! in either format back into typed rules.
+//!
+//! # Example
+//!
+//! ```
+//! use consulrs::acl::rules::{Access, Rules};
+//!
+//! let rules = Rules::new()
+//!     .key_prefix("app/", Access::Write)
+//!     .service("web", Access::Read);
+//! assert_eq!(
+//!     rules.to_string(),
+//!     "key_prefix \"app/\" {\n  policy = \"write\"\n}\n\nservice \"web\" {\n  policy = \"read\"\n}\n"
+//! );
+//! assert_eq!(rules.to_string().parse::<Rules>().unwrap(), rules);
+//! ```
+//!
+//! The result is passed to [policy::create][crate::acl::policy::create] or
+//! [policy::update][crate::acl::policy::update] through the `rules` option
+//! of their request.
+use std::{fmt, str::FromStr};
+
+use serde_json::{Map, Value};
+
+use crate::{api::acl::common::ACLResource, error::ClientError};
+
+/// The disposition of a rule, i.e. whether it grants or denies access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Deny,
+    List,
+    Read,
+    Write,
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let access = match self {
+            Access::Deny => "deny",
+            Access::List => "list",
+            Access::Read => "read",
+            Access::Write => "write",
+        };
+        f.write_str(access)
+    }
+}
+
+impl FromStr for Access {
+    type Err = ClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "deny" => Ok(Access::Deny),
+            "list" => Ok(Access::List),
+            "read" => Ok(Access::Read),
+            "write" => Ok(Access::Write),
+            _ => Err(error(format!("unknown policy {:?}", s))),
+        }
+    }
+}
+
+/// A single rule of a policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rule {
+    /// The resource the rule applies to.
+    pub resource: ACLResource,
+    /// The name of the resource the rule matches, or [None] for resources
+    /// which don't have names, such as `operator`.
+    pub name: Option<String>,
+    /// Whether the name is matched as a prefix.
+    pub prefix: bool,
+    /// The access the rule grants.
+    pub policy: Access,
+    /// The access to intentions granted by a `service` rule.
+    pub intentions: Option<Access>,
+}
+
+impl Rule {
+    /// Returns the name of the rule in a policy, i.e. `key_prefix`.
+    fn kind(&self) -> String {
+        match self.prefix {
+            true => format!("{}_prefix", self.resource),
+            false => self.resource.to_string(),
+        }
+    }
+
+    /// Returns the key rules are ordered by, which puts rules applying to a
+    /// whole resource first.
+    fn order(&self) -> (bool, String, Option<&str>) {
+        (self.name.is_some(), self.kind(), self.name.as_deref())
+    }
+}
+
+/// The rules of an ACL policy.
+///
+/// The order of rules doesn't matter to Consul, so they're kept sorted: rules
+/// which apply to a whole resource, such as `operator`, come first, followed
+/// by the others ordered by their rule and name. Adding a rule for a name
+/// which already has one replaces it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rules {
+    rules: Vec<Rule>,
+}
+
+impl Rules {
+    /// Returns an empty set of rules.
+    pub fn new() -> Self {
+        Rules::default()
+    }
+
+    /// Returns the rules.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Adds the given rule.
+    pub fn rule(mut self, rule: Rule) -> Self {
+        match self
+            .rules
+            .binary_search_by(|r| r.order().cmp(&rule.order()))
+        {
+            Ok(i) => self.rules[i] = rule,
+            Err(i) => self.rules.insert(i, rule),
+        }
+        self
+    }
+
+    /// Sets the access to ACLs.
+    pub fn acl(self, policy: Access) -> Self {
+        self.global(ACLResource::Acl, policy)
+    }
+
+    /// Adds a rule for the agent on the given node.
+    pub fn agent(self, node: &str, policy: Access) -> Self {
+        self.named(ACLResource::Agent, node, false, policy)
+    }
+
+    /// Adds a rule for the agents on nodes starting with the given prefix.
+    pub fn agent_prefix(self, prefix: &str, policy: Access) -> Self {
+        self.named(ACLResource::Agent, prefix, true, policy)
+    }
+
+    /// Adds a rule for the given user event.
+    pub fn event(self, name: &str, policy: Access) -> Self {
+        self.named(ACLResource::Event, name, false, policy)
+    }
+
+    /// Adds a rule for user events starting with the given prefix.
+    pub fn event_prefix(self, prefix: &str, policy: Access) -> Self {
+        self.named(ACLResource::Event, prefix, true, policy)
+    }
+
+    /// Adds a rule for the given key.
+    pub fn key(self, key: &str, policy: Access) -> Self {
+        self.named(ACLResource::Key, key, false, policy)
+    }
+
+    /// Adds a rule for keys starting with the given prefix.
+    pub fn key_prefix(self, prefix: &str, policy: Access) -> Self {
+        self.named(ACLResource::Key, prefix, true, policy)
+    }
+
+    /// Sets the access to the keyring.
+    pub fn keyring(self, policy: Access) -> Self {
+        self.global(ACLResource::Keyring, policy)
+    }
+
+    /// Sets the access to the configuration of the service mesh.
+    pub fn mesh(self, policy: Access) -> Self {
+        self.global(ACLResource::Mesh, policy)
+    }
+
+    /// Adds a rule for the given node.
+    pub fn node(self, node: &str, policy: Access) -> Self {
+        self.named(ACLResource::Node, node, false, policy)
+    }
+
+    /// Adds a rule for nodes starting with the given prefix.
+    pub fn node_prefix(self, prefix: &str, policy: Access) -> Self {
+        self.named(ACLResource::Node, prefix, true, policy)
+    }
+
+    /// Sets the access to operator endpoints.
+    pub fn operator(self, policy: Access) -> Self {
+        self.global(ACLResource::Operator, policy)
+    }
+
+    /// Sets the access to cluster peerings.
+    pub fn peering(self, policy: Access) -> Self {
+        self.global(ACLResource::Peering, policy)
+    }
+
+    /// Adds a rule for the given prepared query.
+    pub fn query(self, name: &str, policy: Access) -> Self {
+        self.named(ACLResource::Query, name, false, policy)
+    }
+
+    /// Adds a rule for prepared queries starting with the given prefix.
+    pub fn query_prefix(self, prefix: &str, policy: Access) -> Self {
+        self.named(ACLResource::Query, prefix, true, policy)
+    }
+
+    /// Adds a rule for the given service.
+    pub fn service(self, service: &str, policy: Access) -> Self {
+        self.named(ACLResource::Service, service, false, policy)
+    }
+
+    /// Adds a rule for services starting with the given prefix.
+    pub fn service_prefix(self, prefix: &str, policy: Access) -> Self {
+        self.named(ACLResource::Service, prefix, true, policy)
+    }
+
+    /// Adds a rule for the sessions of the given node.
+    pub fn session(self, node: &str, policy: Access) -> Self {
+        self.named(ACLResource::Session, node, false, policy)
+    }
+
+    /// Adds a rule for the sessions of nodes starting with the given prefix.
+    pub fn session_prefix(self, prefix: &str, policy: Access) -> Self {
+        self.named(ACLResource::Session, prefix, true, policy)
+    }
+
+    /// Sets the access to the intentions of the given service, whose rule
+    /// must have been added already.
+    pub fn intentions(mut self, service: &str, policy: Access) -> Self {
+        let rule = self.rules.iter_mut().find(|r| {
+            r.resource == ACLResource::Service && !r.prefix && r.name.as_deref() == Some(service)
+        });
+        if let Some(rule) = rule {
+            rule.intentions = Some(policy);
+        }
+        self
+    }
+
+    /// Formats the rules as a JSON document.
+    pub fn to_json(&self) -> Result<String, ClientError> {
+        let mut doc = Map::new();
+        for rule in &self.rules {
+            let name = match &rule.name {
+                Some(name) => name,
+                None => {
+                    doc.insert(rule.kind(), Value::String(rule.policy.to_string()));
+                    continue;
+                }
+            };
+            let mut body = Map::new();
+            body.insert("policy".into(), Value::String(rule.policy.to_string()));
+            if let Some(intentions) = rule.intentions {
+                body.insert("intentions".into(), Value::String(intentions.to_string()));
+            }
+            doc.entry(rule.kind())
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .unwrap()
+                .insert(name.clone(), Value::Object(body));
+        }
+        serde_json::to_string_pretty(&doc)
+            .map_err(|e| ClientError::JsonSerializeError { source: e })
+    }
+
+    fn global(self, resource: ACLResource, policy: Access) -> Self {
+        self.rule(Rule {
+            resource,
+            name: None,
+            prefix: false,
+            policy,
+            intentions: None,
+        })
+    }
+
+    fn named(self, resource: ACLResource, name: &str, prefix: bool, policy: Access) -> Self {
+        self.rule(Rule {
+            resource,
+            name: Some(name.to_string()),
+            prefix,
+            policy,
+            intentions: None,
+        })
+    }
+}
+
+/// Formats the rules as an HCL document.
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, rule) in self.rules.iter().enumerate() {
+            let name = match &rule.name {
+                Some(name) => name,
+                None => {
+                    writeln!(f, "{} = \"{}\"", rule.kind(), rule.policy)?;
+                    continue;
+                }
+            };
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{} {} {{", rule.kind(), Value::String(name.clone()))?;
+            writeln!(f, "  policy = \"{}\"", rule.policy)?;
+            if let Some(intentions) = rule.intentions {
+                writeln!(f, "  intentions = \"{}\"", intentions)?;
+            }
+            writeln!(f, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the rules of a policy from an HCL or JSON document.
+impl FromStr for Rules {
+    type Err = ClientError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let doc = match s.trim_start().starts_with('{') {
+            true => serde_json::from_str(s).map_err(|e| error(e.to_string()))?,
+            false => parse_hcl(s)?,
+        };
+
+        let mut rules = Rules::new();
+        for (kind, value) in doc {
+            let (resource, prefix) = match kind.strip_suffix("_prefix") {
+                Some(resource) => (resource, true),
+                None => (kind.as_str(), false),
+            };
+            let resource: ACLResource = serde_json::from_value(Value::String(resource.into()))
+                .map_err(|_| error(format!("unknown rule {}", kind)))?;
+            match value {
+                Value::String(policy) => rules = rules.global(resource, policy.parse()?),
+                Value::Object(names) => {
+                    for (name, body) in names {
+                        let field = |field: &str| match body.get(field) {
+                            Some(Value::String(s)) => s.parse().map(Some),
+                            Some(_) => {
+                                Err(error(format!("invalid {} of {} {}", field, kind, name)))
+                            }
+                            None => Ok(None),
+                        };
+                        let policy = field("policy")?
+                            .ok_or_else(|| error(format!("missing policy of {} {}", kind, name)))?;
+                        let intentions = field("intentions")?;
+                        rules = rules.rule(Rule {
+                            resource,
+                            name: Some(name),
+                            prefix,
+                            policy,
+                            intentions,
+                        });
+                    }
+                }
+                _ => return Err(error(format!("invalid rule {}", kind))),
+            }
+        }
+        Ok(rules)
+    }
+}
+
+/// Parses the subset of HCL used by policies into the same structure as their
+/// JSON form, i.e. `key "a" { policy = "read" }` into
+/// `{"key": {"a": {"policy": "read"}}}`.
+fn parse_hcl(s: &str) -> Result<Map<String, Value>, ClientError> {
+    let mut tokens = tokenize(s)?.into_iter();
+    let mut doc = Map::new();
+    while let Some(token) = tokens.next() {
+        let kind = match token {
+            Token::Ident(kind) => kind,
+            t => return Err(error(format!("expected a rule, found {:?}", t))),
+        };
+        match tokens.next() {
+            Some(Token::Equals) => {
+                let policy = expect_string(&mut tokens)?;
+                doc.insert(kind, Value::String(policy));
+            }
+            Some(Token::String(name)) => {
+                expect(&mut tokens, Token::Open)?;
+                let mut body = Map::new();
+                loop {
+                    match tokens.next() {
+                        Some(Token::Close) => break,
+                        Some(Token::Ident(field)) => {
+                            expect(&mut tokens, Token::Equals)?;
+                            body.insert(field, Value::String(expect_string(&mut tokens)?));
+                        }
+                        t => return Err(error(format!("expected a field, found {:?}", t))),
+                    }
+                }
+                doc.entry(kind)
+                    .or_insert_with(|| Value::Object(Map::new()))
+                    .as_object_mut()
+                    .ok_or_else(|| error("a rule is both global and named".into()))?
+                    .insert(name, Value::Object(body));
+            }
+            t => return Err(error(format!("expected a name or =, found {:?}", t))),
+        }
+    }
+    Ok(doc)
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Close,
+    Equals,
+    Ident(String),
+    Open,
+    String(String),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, ClientError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() || c == ',' => {}
+            '#' => skip_line(&mut chars),
+            '/' if chars.peek() == Some(&'/') => skip_line(&mut chars),
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if last == '*' => break,
+                        Some(c) => last = c,
+                        None => return Err(error("unterminated comment".into())),
+                    }
+                }
+            }
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '=' => tokens.push(Token::Equals),
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => string.push('\n'),
+                            Some('t') => string.push('\t'),
+                            Some(c) => string.push(c),
+                            None => return Err(error("unterminated string".into())),
+                        },
+                        Some(c) => string.push(c),
+                        None => return Err(error("unterminated string".into())),
+                    }
+                }
+                tokens.push(Token::String(string));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut ident = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    ident.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            c => return Err(error(format!("unexpected character {:?}", c))),
+        }
+    }
+    Ok(tokens)
+}
+
+fn skip_line(chars: &mut impl Iterator<Item = char>) {
+    for c in chars {
+        if c == '\n' {
+            break;
+        }
+    }
+}
+
+fn expect(tokens: &mut impl Iterator<Item = Token>, expected: Token) -> Result<(), ClientError> {
+    match tokens.next() {
+        Some(t) if t == expected => Ok(()),
+        t => Err(error(format!("expected {:?}, found {:?}", expected, t))),
+    }
+}
+
+fn expect_string(tokens: &mut impl Iterator<Item = Token>) -> Result<String, ClientError> {
+    match tokens.next() {
+        Some(Token::String(s)) => Ok(s),
+        t => Err(error(format!("expected a string, found {:?}", t))),
+    }
+}
+
+fn error(message: String) -> ClientError {
+    ClientError::RuleParseError { message }
+}
@@ -0,0 +1,26 @@
+use crate::{
+    api::{
+        self,
+        acl::{
+            common::ACLToken,
+            requests::{ReadSelfTokenRequest, ReadSelfTokenRequestBuilder},
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Reads the ACL token configured on the client, i.e. to find out which
+/// policies and roles it's linked to.
+///
+/// See [ReadSelfTokenRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read_self(
+    client: &impl Client,
+    opts: Option<&mut ReadSelfTokenRequestBuilder>,
+) -> Result<ApiResponse<ACLToken>, ClientError> {
+    let mut t = ReadSelfTokenRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
@@ -0,0 +1,381 @@
+//! Reads information about and manages the local
+//! [agent](https://www.consul.io/api-docs/agent).
+use std::{pin::Pin, sync::Arc, time::Duration};
+
+use futures::{Stream, StreamExt};
+use tokio::task::JoinHandle;
+
+use crate::{
+    api::{
+        self,
+        agent::{
+            common::{AgentMember, AgentTokenKind, LogEntry},
+            requests::{
+                EnableMaintenanceRequest, EnableMaintenanceRequestBuilder, ForceLeaveRequest,
+                ForceLeaveRequestBuilder, LeaveRequest, LeaveRequestBuilder, ListMembersRequest,
+                ListMembersRequestBuilder, MonitorRequest, MonitorRequestBuilder, ReadHostRequest,
+                ReadHostRequestBuilder, ReadMetricsRequest, ReadMetricsRequestBuilder,
+                ReadSelfRequest, ReadSelfRequestBuilder, ReloadRequest, ReloadRequestBuilder,
+                UpdateTokenRequest, UpdateTokenRequestBuilder,
+            },
+            responses::{AgentHostResponse, AgentMetricsResponse, AgentSelfResponse},
+        },
+        service::requests::{
+            DeregisterServiceRequest, ReadServiceRequest, RegisterServiceRequest,
+            RegisterServiceRequestBuilder,
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+    lock,
+};
+
+/// The time waited before retrying after failing to check or restore the
+/// registration of a [ServiceHandle].
+pub const REREGISTER_RETRY_TIME: Duration = Duration::from_secs(5);
+
+/// A stream of log entries read from an agent.
+pub type LogStream = Pin<Box<dyn Stream<Item = Result<LogEntry, ClientError>> + Send>>;
+
+/// Forces a node into the `left` state.
+///
+/// Pass `prune` to remove the node from the member list entirely instead of
+/// waiting for it to be reaped.
+///
+/// See [ForceLeaveRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn force_leave(
+    client: &impl Client,
+    node: &str,
+    prune: bool,
+    opts: Option<&mut ForceLeaveRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = ForceLeaveRequest::builder();
+    let mut builder = opts.unwrap_or(&mut t).node(node);
+    if prune {
+        builder = builder.prune(true);
+    }
+    let endpoint = builder.build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Returns information about the host the local agent is running on.
+///
+/// See [ReadHostRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn host(
+    client: &impl Client,
+    opts: Option<&mut ReadHostRequestBuilder>,
+) -> Result<ApiResponse<AgentHostResponse>, ClientError> {
+    let mut t = ReadHostRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Gracefully leaves the cluster and shuts down the local agent.
+///
+/// See [LeaveRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn leave(
+    client: &impl Client,
+    opts: Option<&mut LeaveRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = LeaveRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Lists the members of the gossip pool seen by the local agent.
+///
+/// Pass `wan` to list the members of the WAN pool instead of the LAN pool.
+///
+/// See [ListMembersRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn members(
+    client: &impl Client,
+    wan: bool,
+    opts: Option<&mut ListMembersRequestBuilder>,
+) -> Result<ApiResponse<Vec<AgentMember>>, ClientError> {
+    let mut t = ListMembersRequest::builder();
+    let mut builder = opts.unwrap_or(&mut t);
+    if wan {
+        builder = builder.wan(true);
+    }
+    let endpoint = builder.build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Places the local agent in maintenance mode.
+///
+/// See [EnableMaintenanceRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn maintenance(
+    client: &impl Client,
+    enabled: bool,
+    opts: Option<&mut EnableMaintenanceRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = EnableMaintenanceRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).enable(enabled).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Returns the metrics of the local agent.
+///
+/// See [ReadMetricsRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn metrics(
+    client: &impl Client,
+    opts: Option<&mut ReadMetricsRequestBuilder>,
+) -> Result<ApiResponse<AgentMetricsResponse>, ClientError> {
+    let mut t = ReadMetricsRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Streams logs from the local agent until the stream is dropped.
+///
+/// Lines are parsed according to the `logjson` option, see [LogEntry] for
+/// what is available in each format.
+///
+/// See [MonitorRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn monitor(
+    client: &impl Client,
+    opts: Option<&mut MonitorRequestBuilder>,
+) -> Result<ApiResponse<LogStream>, ClientError> {
+    let mut t = MonitorRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    let json = endpoint.logjson.is_some();
+    let res = api::exec_with_lines(client, endpoint).await?;
+
+    Ok(res.map(|lines| {
+        let entries = lines
+            .filter(|line| futures::future::ready(!matches!(line, Ok(l) if l.is_empty())))
+            .map(move |line| {
+                let line = line?;
+                if json {
+                    serde_json::from_str(&line)
+                        .map_err(|e| ClientError::JsonDeserializeError { source: e })
+                } else {
+                    Ok(LogEntry::from_plain(&line))
+                }
+            });
+        Box::pin(entries) as LogStream
+    }))
+}
+
+/// Registers a service on the local agent for as long as the returned
+/// [ServiceHandle] is alive.
+///
+/// Pass `reregister` to watch the registration with a blocking query and
+/// register the service again whenever it goes missing, i.e. because the
+/// agent restarted without persisting it.
+///
+/// See [RegisterServiceRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn register_service<C: Client + 'static>(
+    client: Arc<C>,
+    name: &str,
+    opts: Option<&mut RegisterServiceRequestBuilder>,
+    reregister: bool,
+) -> Result<ServiceHandle<C>, ClientError> {
+    let mut t = RegisterServiceRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_empty(client.as_ref(), endpoint.clone()).await?;
+
+    let task = match reregister {
+        true => Some(tokio::spawn(keep_registered(
+            client.clone(),
+            endpoint.clone(),
+        ))),
+        false => None,
+    };
+    Ok(ServiceHandle {
+        client,
+        registration: Some(endpoint),
+        task,
+    })
+}
+
+/// Reloads the configuration of the local agent.
+///
+/// See [ReloadRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn reload(
+    client: &impl Client,
+    opts: Option<&mut ReloadRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = ReloadRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Returns the configuration and member information of the local agent.
+///
+/// See [ReadSelfRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn self_info(
+    client: &impl Client,
+    opts: Option<&mut ReadSelfRequestBuilder>,
+) -> Result<ApiResponse<AgentSelfResponse>, ClientError> {
+    let mut t = ReadSelfRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the ACL token of the given kind used by the local agent.
+///
+/// See [UpdateTokenRequest]
+#[instrument(skip(client, token, opts), err)]
+pub async fn update_token(
+    client: &impl Client,
+    kind: AgentTokenKind,
+    token: &str,
+    opts: Option<&mut UpdateTokenRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = UpdateTokenRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .kind(kind)
+        .token(token)
+        .build()
+        .unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// A service registered with [register_service].
+///
+/// The service, along with the checks registered with it, is deregistered
+/// when [ServiceHandle::shutdown] is called or, on a best-effort basis, when
+/// the handle is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+/// use consulrs::{agent, api::service::requests::RegisterServiceRequest};
+/// use std::sync::Arc;
+///
+/// # tokio_test::block_on(async {
+/// # let client = ConsulClient::new(
+/// #     ConsulClientSettingsBuilder::default()
+/// #         .address("https://127.0.0.1:8200")
+/// #         .build()
+/// #         .unwrap()
+/// # ).unwrap();
+/// let handle = agent::register_service(
+///     Arc::new(client),
+///     "billing",
+///     Some(RegisterServiceRequest::builder().port(8080u64)),
+///     true,
+/// )
+/// .await
+/// .unwrap();
+/// // Serve requests while registered
+/// handle.shutdown().await.unwrap();
+/// # })
+/// ```
+pub struct ServiceHandle<C: Client + 'static> {
+    client: Arc<C>,
+    registration: Option<RegisterServiceRequest>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl<C: Client + 'static> ServiceHandle<C> {
+    /// Returns the ID of the registered service.
+    pub fn id(&self) -> &str {
+        service_id(self.registration.as_ref().unwrap())
+    }
+
+    /// Deregisters the service.
+    #[instrument(skip(self), fields(id = %self.id()), err)]
+    pub async fn shutdown(mut self) -> Result<(), ClientError> {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        let registration = self.registration.take().unwrap();
+        deregister(self.client.as_ref(), &registration).await
+    }
+}
+
+impl<C: Client + 'static> Drop for ServiceHandle<C> {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        if let Some(registration) = self.registration.take() {
+            let client = self.client.clone();
+            lock::spawn_release(
+                format!("service {}", service_id(&registration)),
+                async move { deregister(client.as_ref(), &registration).await },
+            );
+        }
+    }
+}
+
+/// Deregisters the service described by the given registration.
+async fn deregister(
+    client: &impl Client,
+    registration: &RegisterServiceRequest,
+) -> Result<(), ClientError> {
+    let endpoint = DeregisterServiceRequest {
+        features: None,
+        id: service_id(registration).to_string(),
+        ns: registration.ns.clone(),
+        partition: registration.partition.clone(),
+    };
+    api::exec_with_empty(client, endpoint).await?;
+    Ok(())
+}
+
+/// Watches the given registration on the local agent, registering it again
+/// whenever it goes missing.
+async fn keep_registered<C: Client>(client: Arc<C>, registration: RegisterServiceRequest) {
+    let id = service_id(&registration).to_string();
+    let mut hash = None;
+    loop {
+        let endpoint = ReadServiceRequest {
+            features: None,
+            name: id.clone(),
+            hash: hash.take(),
+            ns: registration.ns.clone(),
+            partition: registration.partition.clone(),
+            wait: Some("5m".into()),
+        };
+
+        match api::exec_with_result(client.as_ref(), endpoint).await {
+            Ok(res) => {
+                hash = res.content_hash;
+                // Without a hash the next read can't block
+                if hash.is_none() {
+                    tokio::time::sleep(REREGISTER_RETRY_TIME).await;
+                }
+            }
+            Err(e) if e.is_not_found() => {
+                warn!(
+                    "Service {} is no longer registered, registering it again",
+                    id
+                );
+                if let Err(e) = api::exec_with_empty(client.as_ref(), registration.clone()).await {
+                    warn!("Failed registering service {}: {}", id, e);
+                    tokio::time::sleep(REREGISTER_RETRY_TIME).await;
+                }
+            }
+            Err(e) => {
+                warn!("Failed reading service {}: {}", id, e);
+                tokio::time::sleep(REREGISTER_RETRY_TIME).await;
+            }
+        }
+    }
+}
+
+/// Returns the ID of the service described by the given registration, which
+/// defaults to its name.
+fn service_id(registration: &RegisterServiceRequest) -> &str {
+    registration
+        .id
+        .as_deref()
+        .or(registration.name.as_deref())
+        .unwrap_or_default()
+}
@@ -1,33 +1,82 @@
-use std::str::FromStr;
+use std::{
+    convert::TryFrom,
+    future::Future,
+    pin::Pin,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use crate::api::features::FeaturedEndpoint;
-use crate::client::Client;
+use crate::client::{
+    unix, Client, ErrorClass, Interceptor, QueryOptions, RequestMetrics, RetryPolicy,
+    REQUEST_ID_HEADER,
+};
 use crate::error::ClientError;
+use async_trait::async_trait;
+use bytes::Bytes;
 use derive_builder::Builder;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use rustify::clients::reqwest::Client as HTTPClient;
 use rustify::endpoint::{Endpoint, EndpointResult, MiddleWare};
+use rustify::enums::RequestMethod;
 use rustify::errors::ClientError as RestClientError;
 use serde::de::DeserializeOwned;
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+use tracing::Instrument;
 
 pub use crate::api::features::Features;
 
+pub mod acl;
+pub mod agent;
+pub mod blocking;
 pub mod catalog;
 pub mod check;
+pub mod config;
 pub mod connect;
+pub mod coordinate;
+pub mod discovery_chain;
+pub mod event;
 pub mod features;
+pub mod health;
+#[cfg(feature = "unstable-internal")]
+pub mod internal;
 pub mod kv;
+pub mod namespace;
+pub mod operator;
+pub mod partition;
+pub mod peering;
+pub mod query;
 pub mod service;
 pub mod session;
 pub mod snapshot;
+pub mod status;
+pub mod txn;
+pub mod watch;
+
+/// A stream of chunks read from a response body.
+pub type BodyStream = Pin<Box<dyn Stream<Item = Result<Bytes, ClientError>> + Send>>;
+
+/// A stream of newline delimited lines read from a response body.
+pub type LineStream = Pin<Box<dyn Stream<Item = Result<String, ClientError>> + Send>>;
+
+/// A stream of chunks sent as a request body.
+type ReaderBody = Pin<Box<dyn Stream<Item = std::io::Result<Bytes>> + Send + Sync>>;
 
 #[derive(Builder, Debug)]
 #[builder(pattern = "owned")]
 pub struct ApiResponse<T> {
+    #[builder(setter(into, strip_option), default)]
+    pub age: Option<String>,
     #[builder(setter(into, strip_option), default)]
     pub cache: Option<String>,
     #[builder(setter(into, strip_option), default)]
     pub content_hash: Option<String>,
     #[builder(setter(into, strip_option), default)]
     pub default_acl_policy: Option<String>,
+    #[builder(default)]
+    pub headers: http::HeaderMap,
     #[builder(setter(into, strip_option), default)]
     pub index: Option<String>,
     #[builder(setter(into, strip_option), default)]
@@ -43,6 +92,74 @@ impl<T> ApiResponse<T> {
     pub fn builder() -> ApiResponseBuilder<T> {
         ApiResponseBuilder::default()
     }
+
+    /// Returns the same response with its contents transformed by the given
+    /// function, keeping every header.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ApiResponse<U> {
+        ApiResponse {
+            age: self.age,
+            cache: self.cache,
+            content_hash: self.content_hash,
+            default_acl_policy: self.default_acl_policy,
+            headers: self.headers,
+            index: self.index,
+            known_leader: self.known_leader,
+            last_contact: self.last_contact,
+            query_backend: self.query_backend,
+            response: f(self.response),
+        }
+    }
+
+    /// Returns how long ago the agent cached the result, if it was served from
+    /// the agent cache.
+    pub fn cache_age(&self) -> Option<Duration> {
+        self.age
+            .as_deref()
+            .and_then(|a| a.parse().ok())
+            .map(Duration::from_secs)
+    }
+
+    /// Returns whether the result was served from the agent cache, if the
+    /// request was a cached read.
+    pub fn cache_hit(&self) -> Option<bool> {
+        self.cache.as_deref().map(|c| c == "HIT")
+    }
+
+    /// Returns the value of the given response header, if it was sent and is
+    /// valid UTF-8.
+    ///
+    /// Every header sent by Consul is kept in `headers`, including those which
+    /// have no dedicated field or accessor.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    /// Returns whether results the token isn't allowed to read were left out
+    /// of the response, if reported.
+    pub fn filtered_by_acls(&self) -> Option<bool> {
+        self.header("X-Consul-Results-Filtered-By-ACLs")
+            .and_then(|f| f.parse().ok())
+    }
+
+    /// Returns whether the server which answered the request knew of a
+    /// leader, if reported.
+    pub fn has_leader(&self) -> Option<bool> {
+        self.known_leader.as_deref().and_then(|l| l.parse().ok())
+    }
+
+    /// Returns the time since the server which answered the request last
+    /// contacted the leader, if reported.
+    ///
+    /// This is always zero when the leader answered the request and is mostly
+    /// useful for bounding how stale a result read with
+    /// [ConsistencyMode::STALE][crate::api::features::ConsistencyMode::STALE]
+    /// may be.
+    pub fn staleness(&self) -> Option<Duration> {
+        self.last_contact
+            .as_deref()
+            .and_then(|c| c.parse().ok())
+            .map(Duration::from_millis)
+    }
 }
 
 /// A [MiddleWare] for adding version and token information to all requests.
@@ -50,14 +167,31 @@ impl<T> ApiResponse<T> {
 /// Implements [MiddleWare] to provide support for prepending API version
 /// information to all requests and adding an ACL token to the header of all
 /// requests. Additionally, any API features specified in the endpoint are
-/// appended to the request. This is passed by the API functions when an
+/// appended to the request, followed by any default [QueryOptions] which the
+/// request didn't set itself. This is passed by the API functions when an
 /// endpoint is executed.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct EndpointMiddleware {
+    pub defaults: QueryOptions,
     pub features: Option<Features>,
+    /// The header and value of the request ID sent with the request, if any.
+    pub request_id: Option<(String, String)>,
     pub token: Option<String>,
     pub version: String,
 }
+
+// The token is redacted so that the middleware can be logged safely
+impl std::fmt::Debug for EndpointMiddleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EndpointMiddleware")
+            .field("defaults", &self.defaults)
+            .field("features", &self.features)
+            .field("request_id", &self.request_id)
+            .field("token", &crate::client::redacted(&self.token))
+            .field("version", &self.version)
+            .finish()
+    }
+}
 impl MiddleWare for EndpointMiddleware {
     #[instrument(skip(self, req), err)]
     fn request<E: Endpoint>(
@@ -87,11 +221,45 @@ impl MiddleWare for EndpointMiddleware {
             );
         }
 
+        // Add the request ID to correlate the request with server logs
+        if let Some((header, id)) = &self.request_id {
+            debug!("Middleware: adding request ID {}", id);
+            if let (Ok(header), Ok(id)) = (
+                http::header::HeaderName::from_bytes(header.as_bytes()),
+                http::HeaderValue::from_str(id),
+            ) {
+                req.headers_mut().insert(header, id);
+            }
+        }
+
         // Add optional API features
         if let Some(f) = &self.features {
             f.process(req);
         }
 
+        // Add any default query options the request didn't set itself
+        let url = url::Url::parse(req.uri().to_string().as_str()).unwrap();
+        let keys: Vec<String> = url.query_pairs().map(|(k, _)| k.into_owned()).collect();
+        let mut defaults = vec![
+            ("dc", &self.defaults.dc),
+            ("ns", &self.defaults.ns),
+            ("partition", &self.defaults.partition),
+        ];
+        if keys.iter().any(|k| k == "index") {
+            defaults.push(("wait", &self.defaults.wait));
+        }
+        let missing: Vec<(&str, &String)> = defaults
+            .into_iter()
+            .filter(|(k, _)| !keys.iter().any(|key| key == k))
+            .filter_map(|(k, v)| v.as_ref().map(|v| (k, v)))
+            .collect();
+        if !missing.is_empty() {
+            let mut url_c = url.clone();
+            url_c.query_pairs_mut().extend_pairs(missing);
+            debug!("Middleware: adding default query options");
+            *req.uri_mut() = http::Uri::from_str(url_c.as_str()).unwrap();
+        }
+
         Ok(())
     }
 
@@ -116,18 +284,13 @@ where
     E: Endpoint<Response = ()> + FeaturedEndpoint,
 {
     info!("Executing {} and expecting no response", endpoint.path());
-    let features = endpoint.features();
-    endpoint
-        .with_middleware(&client.middle(features))
-        .exec(client.http())
-        .await
-        .map_err(parse_err)
-        .map(parse_empty)?
+    exec_with_retry(client, endpoint).await.map(parse_empty)?
 }
 
 /// Executes an [Endpoint] and returns the raw response body.
 ///
-/// Any errors which occur in execution are wrapped in a
+/// The entire body is buffered in memory, see [exec_with_stream] for large
+/// responses. Any errors which occur in execution are wrapped in a
 /// [ClientError::RestClientError] and propagated.
 pub async fn exec_with_raw<E>(
     client: &impl Client,
@@ -137,13 +300,7 @@ where
     E: Endpoint + FeaturedEndpoint,
 {
     info!("Executing {} and expecting a response", endpoint.path());
-    let features = endpoint.features();
-    endpoint
-        .with_middleware(&client.middle(features))
-        .exec(client.http())
-        .await
-        .map_err(parse_err)
-        .map(parse_raw)?
+    exec_with_retry(client, endpoint).await.map(parse_raw)?
 }
 
 /// Executes an [Endpoint] and returns the result.
@@ -162,13 +319,794 @@ where
     E: Endpoint + FeaturedEndpoint,
 {
     info!("Executing {} and expecting a response", endpoint.path());
+    exec_with_retry(client, endpoint).await.map(parse)?
+}
+
+/// Executes an [Endpoint], streaming the contents of the given reader as the
+/// request body.
+///
+/// Unlike the other executors the request body is never buffered in memory,
+/// which makes this suitable for large uploads such as snapshots. Any raw data
+/// set on the endpoint itself is replaced by the reader.
+pub async fn exec_with_reader<E, R>(
+    client: &impl Client,
+    endpoint: E,
+    reader: R,
+) -> Result<ApiResponse<()>, ClientError>
+where
+    E: Endpoint<Response = ()> + FeaturedEndpoint,
+    R: AsyncRead + Send + Sync + 'static,
+{
+    info!("Executing {} with a streamed body", endpoint.path());
+    let span = request_span(client, &endpoint);
+    let measured = Measured::new(&endpoint);
+    let timeout = timeout(client, &endpoint);
+    let res = async {
+        let _permit = limit(client).await;
+        let req = build_request(client, endpoint).await?;
+        let request_id = request_id(client, &req, &span);
+        with_timeout(
+            timeout,
+            send(client, req, Some(Box::pin(ReaderStream::new(reader)))),
+        )
+        .await
+        .map_err(|e| with_request_id(e, request_id.as_deref()))
+    }
+    .instrument(span.clone())
+    .await;
+    #[cfg(feature = "otel")]
+    crate::otel::record(&span, res.as_ref().map(|r| (r.status(), r.headers())), 1);
+    measured.finish(client, res.as_ref().err(), 1);
+    let res = res?;
+    Ok(parse_headers(res.headers()).response(()).build().unwrap())
+}
+
+/// Executes an [Endpoint] and returns the response body as a [BodyStream].
+///
+/// Unlike [exec_with_raw] the response body is never buffered in memory,
+/// which makes this suitable for large downloads such as snapshots.
+pub async fn exec_with_stream<E>(
+    client: &impl Client,
+    endpoint: E,
+) -> Result<ApiResponse<BodyStream>, ClientError>
+where
+    E: Endpoint + FeaturedEndpoint,
+{
+    info!(
+        "Executing {} and expecting a streamed response",
+        endpoint.path()
+    );
+    let span = request_span(client, &endpoint);
+    let measured = Measured::new(&endpoint);
+    let timeout = timeout(client, &endpoint);
+    let res = async {
+        let _permit = limit(client).await;
+        let req = build_request(client, endpoint).await?;
+        let request_id = request_id(client, &req, &span);
+        with_timeout(timeout, send(client, req, None))
+            .await
+            .map_err(|e| with_request_id(e, request_id.as_deref()))
+    }
+    .instrument(span.clone())
+    .await;
+    #[cfg(feature = "otel")]
+    crate::otel::record(&span, res.as_ref().map(|r| (r.status(), r.headers())), 1);
+    measured.finish(client, res.as_ref().err(), 1);
+    let res = res?;
+
+    let builder = parse_headers(res.headers());
+    let stream = res
+        .bytes_stream()
+        .map_err(|e| ClientError::StreamError { source: e });
+    Ok(builder
+        .response(Box::pin(stream) as BodyStream)
+        .build()
+        .unwrap())
+}
+
+/// Executes an [Endpoint] and returns the response body as a [LineStream].
+///
+/// This is suited to long-lived responses which write one record per line,
+/// such as agent logs. Trailing carriage returns are removed and a final line
+/// without a newline is still yielded once the body ends.
+pub async fn exec_with_lines<E>(
+    client: &impl Client,
+    endpoint: E,
+) -> Result<ApiResponse<LineStream>, ClientError>
+where
+    E: Endpoint + FeaturedEndpoint,
+{
+    let res = exec_with_stream(client, endpoint).await?;
+    Ok(res.map(|body| {
+        let lines = stream::unfold(
+            (body, Vec::new(), false),
+            |(mut body, mut buf, mut done)| async move {
+                loop {
+                    if let Some(pos) = buf.iter().position(|b| *b == b'\n') {
+                        let line: Vec<u8> = buf.drain(..=pos).collect();
+                        return Some((parse_line(&line), (body, buf, done)));
+                    }
+                    if done {
+                        if buf.is_empty() {
+                            return None;
+                        }
+                        let line = std::mem::take(&mut buf);
+                        return Some((parse_line(&line), (body, buf, done)));
+                    }
+                    match body.next().await {
+                        Some(Ok(chunk)) => buf.extend_from_slice(&chunk),
+                        Some(Err(e)) => return Some((Err(e), (body, buf, true))),
+                        None => done = true,
+                    }
+                }
+            },
+        );
+
+        Box::pin(lines) as LineStream
+    }))
+}
+
+/// Decodes a single line, removing any trailing line ending.
+fn parse_line(line: &[u8]) -> Result<String, ClientError> {
+    let line = std::str::from_utf8(line).map_err(|e| ClientError::Utf8DecodeError { source: e })?;
+    Ok(line.trim_end_matches(&['\r', '\n'][..]).to_string())
+}
+
+/// Executes an [Endpoint], retrying failures according to the [RetryPolicy]
+/// of the client.
+async fn exec_with_retry<E>(
+    client: &impl Client,
+    endpoint: E,
+) -> Result<EndpointResult<E::Response>, ClientError>
+where
+    E: Endpoint + FeaturedEndpoint,
+{
+    let policy = &client.settings().retry;
+    let idempotent = is_idempotent(&endpoint, policy);
+    let span = request_span(client, &endpoint);
+    let measured = Measured::new(&endpoint);
+    let timeout = timeout(client, &endpoint);
+
+    let mut attempt = 1;
+    let res = async {
+        let middleware = middleware(client, endpoint.features()).await?;
+        let request_id = middleware.request_id.as_ref().map(|(_, id)| id.clone());
+        if let Some(id) = &request_id {
+            span.record("consul.request_id", id.as_str());
+        }
+        let middleware = RefreshableMiddleware(Mutex::new(middleware));
+        let endpoint = endpoint.with_middleware(&middleware);
+
+        let mut refreshed = false;
+        loop {
+            let res = {
+                let _permit = limit(client).await;
+                with_timeout(timeout, exec_with_failover(client, &endpoint))
+                    .instrument(span.clone())
+                    .await
+            };
+            match res {
+                // Rejected requests are never applied, so it's safe to send
+                // them again with a new token
+                Err(ClientError::APIError { code: 403, .. })
+                    if !refreshed && client.settings().token_source.is_some() =>
+                {
+                    warn!("Request was rejected, refreshing the token");
+                    let source = client.settings().token_source.as_ref().unwrap();
+                    let token = source.refresh().await?;
+                    middleware.0.lock().unwrap().token = Some(token);
+                    refreshed = true;
+                }
+                // Rate limited requests weren't handled, so they're retried
+                // even when they aren't idempotent
+                Err(e)
+                    if (idempotent || e.is_rate_limited())
+                        && attempt < policy.max_attempts
+                        && is_retryable(&e) =>
+                {
+                    let backoff = policy
+                        .backoff(attempt)
+                        .max(e.retry_after().unwrap_or_default());
+                    warn!(
+                        "Attempt {} of {} failed, retrying in {:?}: {}",
+                        attempt, policy.max_attempts, backoff, e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                res => return res.map_err(|e| with_request_id(e, request_id.as_deref())),
+            }
+        }
+    }
+    .await;
+
+    #[cfg(feature = "otel")]
+    crate::otel::record(
+        &span,
+        res.as_ref()
+            .map(|r| (r.response.status(), r.response.headers())),
+        attempt,
+    );
+    measured.finish(client, res.as_ref().err(), attempt);
+    res
+}
+
+/// Returns the timeout of each attempt at sending a request for an
+/// [Endpoint], preferring the timeout configured on the request itself.
+fn timeout<E>(client: &impl Client, endpoint: &E) -> Option<Duration>
+where
+    E: Endpoint + FeaturedEndpoint,
+{
     let features = endpoint.features();
-    endpoint
-        .with_middleware(&client.middle(features))
-        .exec(client.http())
+    if let Some(timeout) = features.as_ref().and_then(|f| f.timeout) {
+        return Some(timeout);
+    }
+
+    let blocking = features.map(|f| f.blocking.is_some()).unwrap_or(false)
+        || endpoint
+            .query()
+            .ok()
+            .flatten()
+            .map(|q| url::form_urlencoded::parse(q.as_bytes()).any(|(k, _)| k == "index"))
+            .unwrap_or(false);
+    match blocking {
+        true => client.settings().blocking_timeout,
+        false => client.settings().timeout,
+    }
+}
+
+/// Runs a request, failing with a [ClientError::TimeoutError] if it doesn't
+/// finish within the given timeout.
+async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    request: impl Future<Output = Result<T, ClientError>>,
+) -> Result<T, ClientError> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, request)
+            .await
+            .unwrap_or(Err(ClientError::TimeoutError { timeout })),
+        None => request.await,
+    }
+}
+
+/// Waits until the [Limiter][crate::client::Limiter] of a client, if any,
+/// allows sending a request.
+async fn limit(client: &impl Client) -> Option<tokio::sync::SemaphorePermit<'_>> {
+    match client.limiter() {
+        Some(limiter) => limiter.acquire().await,
+        None => None,
+    }
+}
+
+/// The details of a request reported to the
+/// [MetricsRecorder][crate::client::MetricsRecorder] of a client once it
+/// finishes.
+struct Measured {
+    endpoint: &'static str,
+    method: RequestMethod,
+    path: String,
+    start: Instant,
+}
+
+impl Measured {
+    /// Starts measuring a request for the given [Endpoint].
+    fn new<E: Endpoint>(endpoint: &E) -> Measured {
+        let name = std::any::type_name::<E>();
+        Measured {
+            endpoint: name.rsplit("::").next().unwrap_or(name),
+            method: endpoint.method(),
+            path: endpoint.path(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Reports the finished request to the recorder of the client, if any.
+    fn finish(self, client: &impl Client, error: Option<&ClientError>, attempts: u32) {
+        if let Some(recorder) = &client.settings().metrics {
+            recorder.record(&RequestMetrics {
+                attempts,
+                endpoint: self.endpoint,
+                error: error.map(ErrorClass::of),
+                latency: self.start.elapsed(),
+                method: format!("{:?}", self.method),
+                path: self.path,
+            });
+        }
+    }
+}
+
+/// Returns the span a request for an [Endpoint] is executed in, which is only
+/// recorded with the `otel` feature enabled.
+fn request_span<E: Endpoint>(client: &impl Client, endpoint: &E) -> tracing::Span {
+    #[cfg(feature = "otel")]
+    let span = crate::otel::request_span(client, &endpoint.method(), &endpoint.path());
+    #[cfg(not(feature = "otel"))]
+    let span = {
+        let _ = (client, endpoint);
+        tracing::Span::none()
+    };
+    span
+}
+
+/// Returns the ID a request was built with, if any, and records it on the
+/// span of the request.
+fn request_id(
+    client: &impl Client,
+    req: &http::Request<Vec<u8>>,
+    span: &tracing::Span,
+) -> Option<String> {
+    let header = client.settings().request_id_header.as_deref();
+    let id = req
+        .headers()
+        .get(header.unwrap_or(REQUEST_ID_HEADER))
+        .and_then(|id| id.to_str().ok())?;
+    span.record("consul.request_id", id);
+    Some(id.to_string())
+}
+
+/// Adds the ID of the request to an error returned by the server.
+fn with_request_id(e: ClientError, id: Option<&str>) -> ClientError {
+    match e {
+        ClientError::APIError {
+            code,
+            index,
+            message,
+            request_id: None,
+            retry_after,
+        } => ClientError::APIError {
+            code,
+            index,
+            message,
+            request_id: id.map(String::from),
+            retry_after,
+        },
+        e => e,
+    }
+}
+
+/// Returns the middleware of a client, with a token fetched from its
+/// [TokenSource][crate::client::TokenSource] if it has one.
+async fn middleware(
+    client: &impl Client,
+    features: Option<Features>,
+) -> Result<EndpointMiddleware, ClientError> {
+    let mut middleware = client.middle(features);
+    if let Some(source) = &client.settings().token_source {
+        middleware.token = Some(source.token().await?);
+    }
+    Ok(middleware)
+}
+
+/// An [EndpointMiddleware] whose token can still be replaced after an
+/// endpoint was mutated with it.
+struct RefreshableMiddleware(Mutex<EndpointMiddleware>);
+
+impl MiddleWare for RefreshableMiddleware {
+    fn request<E: Endpoint>(
+        &self,
+        endpoint: &E,
+        req: &mut http::Request<Vec<u8>>,
+    ) -> Result<(), RestClientError> {
+        self.0.lock().unwrap().request(endpoint, req)
+    }
+
+    fn response<E: Endpoint>(
+        &self,
+        endpoint: &E,
+        resp: &mut http::Response<Vec<u8>>,
+    ) -> Result<(), RestClientError> {
+        self.0.lock().unwrap().response(endpoint, resp)
+    }
+}
+
+/// Executes an [Endpoint] against the servers of the client's
+/// [ServerPool][crate::client::ServerPool],
+/// moving on to the next server whenever one can't be reached.
+async fn exec_with_failover<E>(
+    client: &impl Client,
+    endpoint: &E,
+) -> Result<EndpointResult<E::Response>, ClientError>
+where
+    E: Endpoint,
+{
+    let pool = match client.pool() {
+        Some(pool) => pool,
+        None => {
+            return endpoint
+                .exec(&intercepted(client, client.http()))
+                .await
+                .map_err(parse_err)
+        }
+    };
+
+    let mut last = None;
+    for http in pool.order() {
+        match endpoint
+            .exec(&intercepted(client, http))
+            .await
+            .map_err(parse_err)
+        {
+            Err(e) if is_connection_error(&e) => {
+                warn!(
+                    "Failed connecting to {}, failing over: {}",
+                    unix::address(&http.base),
+                    e
+                );
+                pool.mark_failed(http);
+                last = Some(e);
+            }
+            res => {
+                pool.mark_healthy(http);
+                return res;
+            }
+        }
+    }
+    match last {
+        Some(e) => Err(e),
+        None => endpoint
+            .exec(&intercepted(client, client.http()))
+            .await
+            .map_err(parse_err),
+    }
+}
+
+/// An [HTTPClient] which passes requests and responses through the
+/// [Interceptors][Interceptor] of a client.
+pub(crate) struct Intercepted<'a> {
+    http: &'a HTTPClient,
+    interceptors: &'a [Arc<dyn Interceptor>],
+    #[cfg(feature = "mock")]
+    mock: Option<&'a crate::mock::MockServer>,
+    #[cfg(feature = "replay")]
+    cassette: Option<&'a crate::replay::Cassette>,
+    enforce_token_header: bool,
+}
+
+impl Intercepted<'_> {
+    /// Sends a buffered request to wherever the client points at, or replays
+    /// its response from the cassette of the client.
+    async fn transmit(
+        &self,
+        req: http::Request<Vec<u8>>,
+    ) -> Result<http::Response<Vec<u8>>, RestClientError> {
+        #[cfg(feature = "replay")]
+        if let Some(cassette) = self.cassette {
+            if cassette.mode() == crate::replay::Mode::Replay {
+                return Ok(cassette.play(&req));
+            }
+            let (method, uri) = (req.method().clone(), req.uri().clone());
+            let res = self.forward(req).await?;
+            cassette.record_response(&method, &uri, &res);
+            return Ok(res);
+        }
+
+        self.forward(req).await
+    }
+
+    /// Sends a buffered request to wherever the client points at.
+    async fn forward(
+        &self,
+        req: http::Request<Vec<u8>>,
+    ) -> Result<http::Response<Vec<u8>>, RestClientError> {
+        #[cfg(feature = "mock")]
+        if let Some(mock) = self.mock {
+            return Ok(mock.handle(req).await);
+        }
+
+        match unix::socket_path(req.uri()) {
+            Some(path) => send_unix(&path, req).await,
+            None => rustify::client::Client::send(self.http, req).await,
+        }
+    }
+}
+
+#[async_trait]
+impl rustify::client::Client for Intercepted<'_> {
+    async fn send(
+        &self,
+        mut req: http::Request<Vec<u8>>,
+    ) -> Result<http::Response<Vec<u8>>, RestClientError> {
+        for i in self.interceptors {
+            i.on_request(&mut req);
+        }
+        header_token(&mut req, self.enforce_token_header)
+            .map_err(|e| RestClientError::GenericError { source: e.into() })?;
+        let res = self.transmit(req).await?;
+        for i in self.interceptors {
+            i.on_response(&res);
+        }
+
+        // rustify drops the headers of unsuccessful responses, so errors
+        // which ask to be retried later or carry an index are raised here
+        // instead
+        if !res.status().is_success() {
+            let index = index(res.headers());
+            let retry_after = retry_after(res.headers());
+            if index.is_some() || retry_after.is_some() {
+                let code = res.status().as_u16();
+                let message = String::from_utf8(res.into_body())
+                    .ok()
+                    .filter(|m| !m.is_empty());
+                return Err(RestClientError::GenericError {
+                    source: ClientError::APIError {
+                        code,
+                        index,
+                        message,
+                        request_id: None,
+                        retry_after,
+                    }
+                    .into(),
+                });
+            }
+        }
+        Ok(res)
+    }
+
+    fn base(&self) -> &str {
+        self.http.base()
+    }
+}
+
+/// Sends a buffered request over a unix domain socket, mirroring how
+/// [HTTPClient] reports errors so that failing over and retrying still apply.
+async fn send_unix(
+    path: &str,
+    req: http::Request<Vec<u8>>,
+) -> Result<http::Response<Vec<u8>>, RestClientError> {
+    let url = req.uri().to_string();
+    let method = req.method().to_string();
+    let res = unix::send(path, req.map(hyper::Body::from))
         .await
-        .map_err(parse_err)
-        .map(parse)?
+        .map_err(|e| RestClientError::RequestError {
+            source: e.into(),
+            url,
+            method,
+        })?;
+
+    let (parts, body) = res.into_parts();
+    let body = hyper::body::to_bytes(body)
+        .await
+        .map_err(|e| RestClientError::ResponseError { source: e.into() })?;
+    Ok(http::Response::from_parts(parts, body.to_vec()))
+}
+
+/// Wraps an [HTTPClient] of a client so that it runs the client's
+/// [Interceptors][Interceptor].
+pub(crate) fn intercepted<'a>(client: &'a impl Client, http: &'a HTTPClient) -> Intercepted<'a> {
+    Intercepted {
+        http,
+        interceptors: &client.settings().interceptors,
+        #[cfg(feature = "mock")]
+        mock: client.mock(),
+        #[cfg(feature = "replay")]
+        cassette: client.settings().cassette.as_deref(),
+        enforce_token_header: client.settings().enforce_token_header,
+    }
+}
+
+/// Moves an ACL token passed in the deprecated `token` query parameter into
+/// the `X-Consul-Token` header, unless the header is already set, or rejects
+/// the request with a [ClientError::TokenInQueryError] when `enforce` is set.
+fn header_token(req: &mut http::Request<Vec<u8>>, enforce: bool) -> Result<(), ClientError> {
+    let url = match url::Url::parse(req.uri().to_string().as_str()) {
+        Ok(url) => url,
+        Err(_) => return Ok(()),
+    };
+    let (mut tokens, rest): (Vec<_>, Vec<_>) = url
+        .query_pairs()
+        .into_owned()
+        .partition(|(k, _)| k == "token");
+    if tokens.is_empty() {
+        return Ok(());
+    }
+    if enforce {
+        return Err(ClientError::TokenInQueryError);
+    }
+
+    warn!("Moving ACL token from the query string into the X-Consul-Token header");
+    let mut url_c = url.clone();
+    if rest.is_empty() {
+        url_c.set_query(None);
+    } else {
+        url_c.query_pairs_mut().clear().extend_pairs(rest);
+    }
+    *req.uri_mut() = http::Uri::from_str(url_c.as_str()).unwrap();
+    if !req.headers().contains_key("X-Consul-Token") {
+        if let Some(Ok(token)) = tokens.pop().map(|(_, t)| http::HeaderValue::from_str(&t)) {
+            req.headers_mut().insert("X-Consul-Token", token);
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether an [Endpoint] may be safely retried under a [RetryPolicy].
+fn is_idempotent<E: Endpoint>(endpoint: &E, policy: &RetryPolicy) -> bool {
+    match endpoint.method() {
+        RequestMethod::GET | RequestMethod::HEAD => true,
+        _ if !policy.retry_writes => false,
+        _ => {
+            let cas = endpoint
+                .query()
+                .ok()
+                .flatten()
+                .map(|q| url::form_urlencoded::parse(q.as_bytes()).any(|(k, _)| k == "cas"))
+                .unwrap_or(false);
+            !cas && endpoint.path() != "txn"
+        }
+    }
+}
+
+/// Returns whether an error is transient and the request should be retried.
+fn is_retryable(e: &ClientError) -> bool {
+    match e {
+        ClientError::APIError { code, .. } => *code >= 500 || *code == 429 || e.is_no_leader(),
+        ClientError::TimeoutError { .. } => true,
+        e => is_connection_error(e),
+    }
+}
+
+/// Returns the index of a response from its `X-Consul-Index` header.
+fn index(headers: &http::HeaderMap) -> Option<u64> {
+    headers.get("X-Consul-Index")?.to_str().ok()?.parse().ok()
+}
+
+/// Returns how long a response asks to wait before retrying, from its
+/// `Retry-After` header holding either a number of seconds or a date.
+fn retry_after(headers: &http::HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(http::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    match value.parse::<u64>() {
+        Ok(secs) => Some(Duration::from_secs(secs)),
+        Err(_) => {
+            let date = httpdate::parse_http_date(value).ok()?;
+            Some(
+                date.duration_since(std::time::SystemTime::now())
+                    .unwrap_or_default(),
+            )
+        }
+    }
+}
+
+/// Returns whether an error was caused by failing to reach the server.
+fn is_connection_error(e: &ClientError) -> bool {
+    matches!(
+        e,
+        ClientError::RestClientError {
+            source: RestClientError::RequestError { .. },
+        }
+    )
+}
+
+/// Builds the request for an [Endpoint] using the middleware of the client,
+/// targeting the preferred server of its [ServerPool][crate::client::ServerPool].
+async fn build_request<E>(
+    client: &impl Client,
+    endpoint: E,
+) -> Result<http::Request<Vec<u8>>, ClientError>
+where
+    E: Endpoint + FeaturedEndpoint,
+{
+    let base = client
+        .pool()
+        .and_then(|p| p.order().first().map(|h| h.base.clone()))
+        .unwrap_or_else(|| client.http().base.clone());
+    let features = endpoint.features();
+    let mut req = endpoint
+        .with_middleware(&middleware(client, features).await?)
+        .request(&base)
+        .map_err(ClientError::from)?;
+    for i in &client.settings().interceptors {
+        i.on_request(&mut req);
+    }
+    header_token(&mut req, client.settings().enforce_token_header)?;
+    Ok(req)
+}
+
+/// Sends a request, optionally replacing its body with the given stream, and
+/// converts unsuccessful responses into a [ClientError::APIError].
+async fn send(
+    client: &impl Client,
+    req: http::Request<Vec<u8>>,
+    body: Option<ReaderBody>,
+) -> Result<reqwest::Response, ClientError> {
+    let res = transmit(client, req, body).await?;
+
+    let interceptors = &client.settings().interceptors;
+    if !interceptors.is_empty() {
+        let mut intercepted = http::Response::new(Vec::new());
+        *intercepted.status_mut() = res.status();
+        *intercepted.headers_mut() = res.headers().clone();
+        for i in interceptors {
+            i.on_response(&intercepted);
+        }
+    }
+
+    if !res.status().is_success() {
+        let code = res.status().as_u16();
+        let index = index(res.headers());
+        let retry_after = retry_after(res.headers());
+        let message = res.text().await.ok().filter(|m| !m.is_empty());
+        return Err(ClientError::APIError {
+            code,
+            index,
+            message,
+            request_id: None,
+            retry_after,
+        });
+    }
+    Ok(res)
+}
+
+/// Sends a request to wherever the client points at, optionally replacing its
+/// body with the given stream, or replays its response from the cassette of
+/// the client.
+async fn transmit(
+    client: &impl Client,
+    req: http::Request<Vec<u8>>,
+    body: Option<ReaderBody>,
+) -> Result<reqwest::Response, ClientError> {
+    #[cfg(feature = "replay")]
+    if let Some(cassette) = client.settings().cassette.as_deref() {
+        if cassette.mode() == crate::replay::Mode::Replay {
+            return Ok(reqwest::Response::from(cassette.play(&req)));
+        }
+        let (method, uri) = (req.method().clone(), req.uri().clone());
+        let res = forward(client, req, body).await?;
+        let mut buffered = http::Response::new(Vec::new());
+        *buffered.status_mut() = res.status();
+        *buffered.headers_mut() = res.headers().clone();
+        *buffered.body_mut() = res
+            .bytes()
+            .await
+            .map_err(|e| ClientError::StreamError { source: e })?
+            .to_vec();
+        cassette.record_response(&method, &uri, &buffered);
+        return Ok(reqwest::Response::from(buffered));
+    }
+
+    forward(client, req, body).await
+}
+
+/// Sends a request to wherever the client points at, optionally replacing its
+/// body with the given stream.
+async fn forward(
+    client: &impl Client,
+    req: http::Request<Vec<u8>>,
+    body: Option<ReaderBody>,
+) -> Result<reqwest::Response, ClientError> {
+    #[cfg(feature = "mock")]
+    if let Some(mock) = client.mock() {
+        return Ok(reqwest::Response::from(mock.handle(req).await));
+    }
+
+    match unix::socket_path(req.uri()) {
+        Some(path) => {
+            let req = match body {
+                Some(body) => req.map(|_| hyper::Body::wrap_stream(body)),
+                None => req.map(hyper::Body::from),
+            };
+            let res = unix::send(&path, req)
+                .await
+                .map_err(|e| ClientError::UnixSocketError { source: e, path })?;
+            Ok(reqwest::Response::from(res))
+        }
+        None => {
+            let mut req = reqwest::Request::try_from(req)
+                .map_err(|e| ClientError::StreamError { source: e })?;
+            if let Some(body) = body {
+                *req.body_mut() = Some(reqwest::Body::wrap_stream(body));
+            }
+            client
+                .http()
+                .http
+                .execute(req)
+                .await
+                .map_err(|e| ClientError::StreamError { source: e })
+        }
+    }
 }
 
 /// Parses an [EndpointResult], turning it into an [ApiResponse].
@@ -184,7 +1122,7 @@ where
 }
 
 /// Parses an [EndpointResult], turning it into an [ApiResponse].
-fn parse_empty(result: EndpointResult<()>) -> Result<ApiResponse<()>, ClientError> {
+pub(crate) fn parse_empty(result: EndpointResult<()>) -> Result<ApiResponse<()>, ClientError> {
     let mut builder = parse_headers(result.response.headers());
 
     builder = builder.response(());
@@ -204,12 +1142,12 @@ where
 }
 
 /// Parses commonly found header fields out of response headers.
-fn parse_headers<T>(headers: &http::HeaderMap) -> ApiResponseBuilder<T>
-where
-    T: DeserializeOwned + Send + Sync,
-{
-    let mut builder = ApiResponse::builder();
+fn parse_headers<T>(headers: &http::HeaderMap) -> ApiResponseBuilder<T> {
+    let mut builder = ApiResponse::builder().headers(headers.clone());
 
+    if headers.contains_key("Age") {
+        builder = builder.age(headers["Age"].to_str().unwrap());
+    }
     if headers.contains_key("X-Cache") {
         builder = builder.cache(headers["X-Cache"].to_str().unwrap());
     }
@@ -236,12 +1174,33 @@ where
     builder
 }
 
+/// Turns a [ClientError::APIError] with a 404 status code into [None], for
+/// reads where a missing entity is an expected outcome.
+pub(crate) fn optional<T>(result: Result<T, ClientError>) -> Result<Option<T>, ClientError> {
+    match result {
+        Ok(res) => Ok(Some(res)),
+        Err(e) if e.is_not_found() => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
 /// Extracts any API errors found and converts them to [ClientError::APIError].
-fn parse_err(e: RestClientError) -> ClientError {
+pub(crate) fn parse_err(e: RestClientError) -> ClientError {
+    // Errors raised by the client while sending are passed through rustify
+    let e = match e {
+        RestClientError::GenericError { source } => match source.downcast::<ClientError>() {
+            Ok(e) => return e,
+            Err(source) => RestClientError::GenericError { source },
+        },
+        e => e,
+    };
     if let RestClientError::ServerResponseError { code, content } = &e {
         ClientError::APIError {
             code: *code,
+            index: None,
             message: content.clone(),
+            request_id: None,
+            retry_after: None,
         }
     } else {
         ClientError::from(e)
@@ -0,0 +1,250 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+};
+
+/// The level of access to an ACL resource.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ACLAccess {
+    List,
+    #[default]
+    Read,
+    Write,
+}
+
+impl fmt::Display for ACLAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let access = match self {
+            ACLAccess::List => "list",
+            ACLAccess::Read => "read",
+            ACLAccess::Write => "write",
+        };
+        f.write_str(access)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ACLAuthMethod {
+    pub config: Option<HashMap<String, serde_json::Value>>,
+    pub create_index: Option<u64>,
+    pub description: Option<String>,
+    pub display_name: Option<String>,
+    #[serde(rename = "MaxTokenTTL")]
+    pub max_token_ttl: Option<String>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub token_locality: Option<String>,
+    #[serde(rename = "Type")]
+    pub ty: String,
+}
+
+/// A permission checked with [acl::authorize][crate::acl::authorize].
+///
+/// The segment names the resource, i.e. the key or service name, and is left
+/// out for resources which don't have any, such as `operator`. Consul sets
+/// `allow` in the response.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ACLAuthorization {
+    pub access: ACLAccess,
+    pub allow: Option<bool>,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub resource: ACLResource,
+    pub segment: Option<String>,
+}
+
+impl fmt::Display for ACLAuthorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.segment {
+            Some(segment) => write!(f, "{} {} \"{}\"", self.access, self.resource, segment),
+            None => write!(f, "{} {}", self.access, self.resource),
+        }
+    }
+}
+
+/// The kind of identity a binding rule grants to tokens created by logging in.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ACLBindType {
+    Node,
+    Role,
+    #[default]
+    Service,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ACLBindingRule {
+    pub auth_method: String,
+    pub bind_name: String,
+    pub bind_type: ACLBindType,
+    pub create_index: Option<u64>,
+    pub description: Option<String>,
+    #[serde(rename = "ID")]
+    pub id: String,
+    pub modify_index: Option<u64>,
+    pub namespace: Option<String>,
+    pub selector: Option<String>,
+}
+
+/// A reference to another ACL object by either its ID or name.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ACLLink {
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    pub name: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ACLNodeIdentity {
+    pub datacenter: String,
+    pub node_name: String,
+}
+
+/// An ACL policy. The rules of a policy are omitted when it's listed.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ACLPolicy {
+    pub create_index: Option<u64>,
+    pub datacenters: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub hash: Option<String>,
+    #[serde(rename = "ID")]
+    pub id: String,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub rules: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ACLReplicationStatus {
+    pub enabled: bool,
+    pub last_error: Option<String>,
+    pub last_error_message: Option<String>,
+    pub last_success: Option<String>,
+    pub replicated_index: Option<u64>,
+    pub replicated_role_index: Option<u64>,
+    pub replicated_token_index: Option<u64>,
+    pub replication_type: Option<String>,
+    pub running: bool,
+    pub source_datacenter: Option<String>,
+}
+
+/// The resources ACL rules grant access to.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ACLResource {
+    Acl,
+    Agent,
+    Event,
+    Intention,
+    #[default]
+    Key,
+    Keyring,
+    Mesh,
+    Node,
+    Operator,
+    Peering,
+    Query,
+    Service,
+    Session,
+}
+
+impl fmt::Display for ACLResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let resource = match self {
+            ACLResource::Acl => "acl",
+            ACLResource::Agent => "agent",
+            ACLResource::Event => "event",
+            ACLResource::Intention => "intention",
+            ACLResource::Key => "key",
+            ACLResource::Keyring => "keyring",
+            ACLResource::Mesh => "mesh",
+            ACLResource::Node => "node",
+            ACLResource::Operator => "operator",
+            ACLResource::Peering => "peering",
+            ACLResource::Query => "query",
+            ACLResource::Service => "service",
+            ACLResource::Session => "session",
+        };
+        f.write_str(resource)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ACLRole {
+    pub create_index: Option<u64>,
+    pub description: Option<String>,
+    pub hash: Option<String>,
+    #[serde(rename = "ID")]
+    pub id: String,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub node_identities: Option<Vec<ACLNodeIdentity>>,
+    pub policies: Option<Vec<ACLLink>>,
+    pub service_identities: Option<Vec<ACLServiceIdentity>>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ACLServiceIdentity {
+    pub datacenters: Option<Vec<String>>,
+    pub service_name: String,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ACLToken {
+    #[serde(rename = "AccessorID")]
+    pub accessor_id: String,
+    pub auth_method: Option<String>,
+    pub create_index: Option<u64>,
+    pub create_time: Option<String>,
+    pub description: Option<String>,
+    pub expiration_time: Option<String>,
+    #[serde(rename = "ExpirationTTL")]
+    pub expiration_ttl: Option<String>,
+    pub hash: Option<String>,
+    pub local: Option<bool>,
+    pub modify_index: Option<u64>,
+    pub namespace: Option<String>,
+    pub node_identities: Option<Vec<ACLNodeIdentity>>,
+    pub policies: Option<Vec<ACLLink>>,
+    pub roles: Option<Vec<ACLLink>>,
+    #[serde(rename = "SecretID")]
+    pub secret_id: Option<String>,
+    pub service_identities: Option<Vec<ACLServiceIdentity>>,
+}
@@ -0,0 +1,774 @@
+use super::common::{
+    ACLAuthMethod, ACLAuthorization, ACLBindType, ACLBindingRule, ACLLink, ACLNodeIdentity,
+    ACLPolicy, ACLReplicationStatus, ACLRole, ACLServiceIdentity, ACLToken,
+};
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::{collections::HashMap, fmt::Debug};
+
+/// ## Bootstrap ACLs
+/// This endpoint does a special one-time bootstrap of the ACL system, making
+/// the first management token.
+///
+/// * Path: acl/bootstrap
+/// * Method: PUT
+/// * Response: [ACLToken]
+/// * Reference: https://www.consul.io/api-docs/acl#bootstrap-acls
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "acl/bootstrap",
+    method = "PUT",
+    response = "ACLToken",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct BootstrapRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub bootstrap_secret: Option<String>,
+}
+
+/// ## Check ACL Replication
+/// This endpoint returns the status of the ACL replication processes in the
+/// datacenter.
+///
+/// * Path: acl/replication
+/// * Method: GET
+/// * Response: [ACLReplicationStatus]
+/// * Reference: https://www.consul.io/api-docs/acl#check-acl-replication
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/replication",
+    response = "ACLReplicationStatus",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReplicationStatusRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
+
+/// ## Create Policy
+/// This endpoint creates a new ACL policy.
+///
+/// * Path: acl/policy
+/// * Method: PUT
+/// * Response: [ACLPolicy]
+/// * Reference: https://www.consul.io/api-docs/acl/policies#create-a-policy
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "acl/policy",
+    method = "PUT",
+    response = "ACLPolicy",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CreatePolicyRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub name: String,
+    pub datacenters: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub namespace: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub rules: Option<String>,
+}
+
+/// ## Read Policy
+/// This endpoint reads an ACL policy with the given ID.
+///
+/// * Path: acl/policy/{self.id}
+/// * Method: GET
+/// * Response: [ACLPolicy]
+/// * Reference: https://www.consul.io/api-docs/acl/policies#read-a-policy
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/policy/{self.id}",
+    response = "ACLPolicy",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadPolicyRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub id: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Read Policy by Name
+/// This endpoint reads an ACL policy with the given name.
+///
+/// * Path: acl/policy/name/{self.name}
+/// * Method: GET
+/// * Response: [ACLPolicy]
+/// * Reference: https://www.consul.io/api-docs/acl/policies#read-a-policy-by-name
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/policy/name/{self.name}",
+    response = "ACLPolicy",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadPolicyByNameRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Update Policy
+/// This endpoint updates an existing ACL policy.
+///
+/// * Path: acl/policy/{self.id}
+/// * Method: PUT
+/// * Response: [ACLPolicy]
+/// * Reference: https://www.consul.io/api-docs/acl/policies#update-a-policy
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "acl/policy/{self.id}",
+    method = "PUT",
+    response = "ACLPolicy",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdatePolicyRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub datacenters: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub namespace: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub rules: Option<String>,
+}
+
+/// ## Delete Policy
+/// This endpoint deletes an ACL policy.
+///
+/// * Path: acl/policy/{self.id}
+/// * Method: DELETE
+/// * Response: [bool]
+/// * Reference: https://www.consul.io/api-docs/acl/policies#delete-a-policy
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/policy/{self.id}",
+    method = "DELETE",
+    response = "bool",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct DeletePolicyRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub id: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## List Policies
+/// This endpoint lists all the ACL policies.
+///
+/// * Path: acl/policies
+/// * Method: GET
+/// * Response: [Vec<ACLPolicy>]
+/// * Reference: https://www.consul.io/api-docs/acl/policies#list-policies
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "acl/policies", response = "Vec<ACLPolicy>", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ListPoliciesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Create Role
+/// This endpoint creates a new ACL role.
+///
+/// * Path: acl/role
+/// * Method: PUT
+/// * Response: [ACLRole]
+/// * Reference: https://www.consul.io/api-docs/acl/roles#create-a-role
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "acl/role",
+    method = "PUT",
+    response = "ACLRole",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CreateRoleRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub name: String,
+    pub description: Option<String>,
+    pub namespace: Option<String>,
+    pub node_identities: Option<Vec<ACLNodeIdentity>>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub policies: Option<Vec<ACLLink>>,
+    pub service_identities: Option<Vec<ACLServiceIdentity>>,
+}
+
+/// ## Read Role
+/// This endpoint reads an ACL role with the given ID.
+///
+/// * Path: acl/role/{self.id}
+/// * Method: GET
+/// * Response: [ACLRole]
+/// * Reference: https://www.consul.io/api-docs/acl/roles#read-a-role
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "acl/role/{self.id}", response = "ACLRole", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadRoleRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub id: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Read Role by Name
+/// This endpoint reads an ACL role with the given name.
+///
+/// * Path: acl/role/name/{self.name}
+/// * Method: GET
+/// * Response: [ACLRole]
+/// * Reference: https://www.consul.io/api-docs/acl/roles#read-a-role-by-name
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/role/name/{self.name}",
+    response = "ACLRole",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadRoleByNameRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Update Role
+/// This endpoint updates an existing ACL role.
+///
+/// * Path: acl/role/{self.id}
+/// * Method: PUT
+/// * Response: [ACLRole]
+/// * Reference: https://www.consul.io/api-docs/acl/roles#update-a-role
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "acl/role/{self.id}",
+    method = "PUT",
+    response = "ACLRole",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdateRoleRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub namespace: Option<String>,
+    pub node_identities: Option<Vec<ACLNodeIdentity>>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub policies: Option<Vec<ACLLink>>,
+    pub service_identities: Option<Vec<ACLServiceIdentity>>,
+}
+
+/// ## Delete Role
+/// This endpoint deletes an ACL role.
+///
+/// * Path: acl/role/{self.id}
+/// * Method: DELETE
+/// * Response: [bool]
+/// * Reference: https://www.consul.io/api-docs/acl/roles#delete-a-role
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/role/{self.id}",
+    method = "DELETE",
+    response = "bool",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct DeleteRoleRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub id: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## List Roles
+/// This endpoint lists all the ACL roles.
+///
+/// * Path: acl/roles
+/// * Method: GET
+/// * Response: [Vec<ACLRole>]
+/// * Reference: https://www.consul.io/api-docs/acl/roles#list-roles
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "acl/roles", response = "Vec<ACLRole>", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ListRolesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub policy: Option<String>,
+}
+
+/// ## Create Auth Method
+/// This endpoint creates a new ACL auth method.
+///
+/// * Path: acl/auth-method
+/// * Method: PUT
+/// * Response: [ACLAuthMethod]
+/// * Reference: https://www.consul.io/api-docs/acl/auth-methods#create-an-auth-method
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "acl/auth-method",
+    method = "PUT",
+    response = "ACLAuthMethod",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CreateAuthMethodRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub name: String,
+    pub config: Option<HashMap<String, serde_json::Value>>,
+    pub description: Option<String>,
+    pub display_name: Option<String>,
+    #[serde(rename = "MaxTokenTTL")]
+    pub max_token_ttl: Option<String>,
+    pub namespace: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub token_locality: Option<String>,
+    #[serde(rename = "Type")]
+    pub ty: String,
+}
+
+/// ## Read Auth Method
+/// This endpoint reads an ACL auth method with the given name.
+///
+/// * Path: acl/auth-method/{self.name}
+/// * Method: GET
+/// * Response: [ACLAuthMethod]
+/// * Reference: https://www.consul.io/api-docs/acl/auth-methods#read-an-auth-method
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/auth-method/{self.name}",
+    response = "ACLAuthMethod",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadAuthMethodRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Update Auth Method
+/// This endpoint updates an existing ACL auth method.
+///
+/// * Path: acl/auth-method/{self.name}
+/// * Method: PUT
+/// * Response: [ACLAuthMethod]
+/// * Reference: https://www.consul.io/api-docs/acl/auth-methods#update-an-auth-method
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "acl/auth-method/{self.name}",
+    method = "PUT",
+    response = "ACLAuthMethod",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdateAuthMethodRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+    pub config: Option<HashMap<String, serde_json::Value>>,
+    pub description: Option<String>,
+    pub display_name: Option<String>,
+    #[serde(rename = "MaxTokenTTL")]
+    pub max_token_ttl: Option<String>,
+    pub namespace: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub token_locality: Option<String>,
+    #[serde(rename = "Type")]
+    pub ty: String,
+}
+
+/// ## Delete Auth Method
+/// This endpoint deletes an ACL auth method.
+///
+/// * Path: acl/auth-method/{self.name}
+/// * Method: DELETE
+/// * Response: [bool]
+/// * Reference: https://www.consul.io/api-docs/acl/auth-methods#delete-an-auth-method
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/auth-method/{self.name}",
+    method = "DELETE",
+    response = "bool",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct DeleteAuthMethodRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## List Auth Methods
+/// This endpoint lists all the ACL auth methods.
+///
+/// * Path: acl/auth-methods
+/// * Method: GET
+/// * Response: [Vec<ACLAuthMethod>]
+/// * Reference: https://www.consul.io/api-docs/acl/auth-methods#list-auth-methods
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/auth-methods",
+    response = "Vec<ACLAuthMethod>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListAuthMethodsRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Create Binding Rule
+/// This endpoint creates a new ACL binding rule.
+///
+/// * Path: acl/binding-rule
+/// * Method: PUT
+/// * Response: [ACLBindingRule]
+/// * Reference: https://www.consul.io/api-docs/acl/binding-rules#create-a-binding-rule
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "acl/binding-rule",
+    method = "PUT",
+    response = "ACLBindingRule",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CreateBindingRuleRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub auth_method: String,
+    pub bind_name: String,
+    pub bind_type: ACLBindType,
+    pub description: Option<String>,
+    pub namespace: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub selector: Option<String>,
+}
+
+/// ## Read Binding Rule
+/// This endpoint reads an ACL binding rule with the given ID.
+///
+/// * Path: acl/binding-rule/{self.id}
+/// * Method: GET
+/// * Response: [ACLBindingRule]
+/// * Reference: https://www.consul.io/api-docs/acl/binding-rules#read-a-binding-rule
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/binding-rule/{self.id}",
+    response = "ACLBindingRule",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadBindingRuleRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub id: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Update Binding Rule
+/// This endpoint updates an existing ACL binding rule.
+///
+/// * Path: acl/binding-rule/{self.id}
+/// * Method: PUT
+/// * Response: [ACLBindingRule]
+/// * Reference: https://www.consul.io/api-docs/acl/binding-rules#update-a-binding-rule
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "acl/binding-rule/{self.id}",
+    method = "PUT",
+    response = "ACLBindingRule",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdateBindingRuleRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub id: String,
+    pub auth_method: String,
+    pub bind_name: String,
+    pub bind_type: ACLBindType,
+    pub description: Option<String>,
+    pub namespace: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub selector: Option<String>,
+}
+
+/// ## Delete Binding Rule
+/// This endpoint deletes an ACL binding rule.
+///
+/// * Path: acl/binding-rule/{self.id}
+/// * Method: DELETE
+/// * Response: [bool]
+/// * Reference: https://www.consul.io/api-docs/acl/binding-rules#delete-a-binding-rule
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/binding-rule/{self.id}",
+    method = "DELETE",
+    response = "bool",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct DeleteBindingRuleRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub id: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## List Binding Rules
+/// This endpoint lists all the ACL binding rules.
+///
+/// * Path: acl/binding-rules
+/// * Method: GET
+/// * Response: [Vec<ACLBindingRule>]
+/// * Reference: https://www.consul.io/api-docs/acl/binding-rules#list-binding-rules
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "acl/binding-rules",
+    response = "Vec<ACLBindingRule>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListBindingRulesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub authmethod: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Login to Auth Method
+/// This endpoint exchanges an auth method bearer token for a newly created
+/// Consul ACL token.
+///
+/// * Path: acl/login
+/// * Method: POST
+/// * Response: [ACLToken]
+/// * Reference: https://www.consul.io/api-docs/acl#login-to-auth-method
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "acl/login",
+    method = "POST",
+    response = "ACLToken",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct LoginRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub auth_method: String,
+    pub bearer_token: String,
+    pub meta: Option<HashMap<String, String>>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+}
+
+/// ## Logout from Auth Method
+/// This endpoint destroys the token passed in the X-Consul-Token header,
+/// which must have been created by logging in to an auth method.
+///
+/// * Path: acl/logout
+/// * Method: POST
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/acl#logout-from-auth-method
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "acl/logout", method = "POST", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct LogoutRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Read Self Token
+/// This endpoint returns the ACL token sent with the request.
+///
+/// * Path: acl/token/self
+/// * Method: GET
+/// * Response: [ACLToken]
+/// * Reference: https://www.consul.io/api-docs/acl/tokens#read-self-token
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "acl/token/self", response = "ACLToken", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadSelfTokenRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+}
+
+/// ## Authorize
+/// This endpoint checks whether the ACL token sent with the request grants
+/// each of the given permissions. It's an internal endpoint used by the UI.
+///
+/// * Path: internal/acl/authorize
+/// * Method: POST
+/// * Response: [Vec<ACLAuthorization>]
+/// * Reference: https://github.com/hashicorp/consul/blob/main/agent/acl_endpoint.go
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "internal/acl/authorize",
+    method = "POST",
+    response = "Vec<ACLAuthorization>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct AuthorizeRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(raw)]
+    pub data: Vec<u8>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
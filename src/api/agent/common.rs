@@ -0,0 +1,244 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{collections::HashMap, fmt, fmt::Debug};
+
+/// The configuration of the local agent as reported by [AgentSelfResponse][crate::api::agent::responses::AgentSelfResponse].
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct AgentConfig {
+    pub datacenter: String,
+    #[serde(rename = "NodeID")]
+    pub node_id: Option<String>,
+    pub node_name: String,
+    pub primary_datacenter: Option<String>,
+    pub revision: Option<String>,
+    pub server: bool,
+    pub version: String,
+}
+
+/// A member of the LAN or WAN gossip pool.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct AgentMember {
+    pub addr: String,
+    pub delegate_cur: Option<u8>,
+    pub delegate_max: Option<u8>,
+    pub delegate_min: Option<u8>,
+    pub name: String,
+    pub port: u16,
+    pub protocol_cur: Option<u8>,
+    pub protocol_max: Option<u8>,
+    pub protocol_min: Option<u8>,
+    pub status: AgentMemberStatus,
+    pub tags: Option<HashMap<String, String>>,
+}
+
+/// The Serf status of a gossip pool member.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum AgentMemberStatus {
+    #[default]
+    None,
+    Alive,
+    Leaving,
+    Left,
+    Failed,
+}
+
+impl From<u8> for AgentMemberStatus {
+    fn from(status: u8) -> Self {
+        match status {
+            1 => AgentMemberStatus::Alive,
+            2 => AgentMemberStatus::Leaving,
+            3 => AgentMemberStatus::Left,
+            4 => AgentMemberStatus::Failed,
+            _ => AgentMemberStatus::None,
+        }
+    }
+}
+
+impl From<AgentMemberStatus> for u8 {
+    fn from(status: AgentMemberStatus) -> Self {
+        status as u8
+    }
+}
+
+/// The kinds of ACL tokens which can be set on an agent.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentTokenKind {
+    Agent,
+    AgentRecovery,
+    ConfigFileServiceRegistration,
+    #[default]
+    Default,
+    Replication,
+}
+
+impl fmt::Display for AgentTokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            AgentTokenKind::Agent => "agent",
+            AgentTokenKind::AgentRecovery => "agent_recovery",
+            AgentTokenKind::ConfigFileServiceRegistration => "config_file_service_registration",
+            AgentTokenKind::Default => "default",
+            AgentTokenKind::Replication => "replication",
+        };
+        f.write_str(kind)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct GaugeValue {
+    pub labels: Option<HashMap<String, String>>,
+    pub name: String,
+    pub value: f64,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct HostCPU {
+    pub cache_size: Option<u64>,
+    pub cores: Option<u64>,
+    pub cpu: Option<u64>,
+    pub family: Option<String>,
+    pub flags: Option<Vec<String>>,
+    pub mhz: Option<f64>,
+    pub model: Option<String>,
+    pub model_name: Option<String>,
+    pub vendor_id: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct HostDisk {
+    pub free: Option<u64>,
+    pub fstype: Option<String>,
+    pub path: Option<String>,
+    pub total: Option<u64>,
+    pub used: Option<u64>,
+    pub used_percent: Option<f64>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct HostOS {
+    pub boot_time: Option<u64>,
+    #[serde(rename = "hostid")]
+    pub host_id: Option<String>,
+    pub hostname: Option<String>,
+    pub kernel_arch: Option<String>,
+    pub kernel_version: Option<String>,
+    pub os: Option<String>,
+    pub platform: Option<String>,
+    pub platform_family: Option<String>,
+    pub platform_version: Option<String>,
+    pub procs: Option<u64>,
+    pub uptime: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct HostMemory {
+    pub available: Option<u64>,
+    pub free: Option<u64>,
+    pub total: Option<u64>,
+    pub used: Option<u64>,
+    pub used_percent: Option<f64>,
+}
+
+/// A single log line streamed from the agent.
+///
+/// Lines in the plain format only have their timestamp and level parsed out,
+/// with the remainder of the line kept as the message. Lines in the JSON
+/// format additionally carry the module and any extra fields.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LogEntry {
+    #[serde(rename = "@level")]
+    pub level: Option<LogLevel>,
+    #[serde(rename = "@message")]
+    pub message: String,
+    #[serde(rename = "@module")]
+    pub module: Option<String>,
+    #[serde(rename = "@timestamp")]
+    pub timestamp: Option<String>,
+    #[serde(flatten)]
+    pub fields: HashMap<String, serde_json::Value>,
+}
+
+impl LogEntry {
+    /// Parses a line in the plain log format, i.e.
+    /// `2021-01-01T00:00:00.000Z [INFO]  agent: message`.
+    pub fn from_plain(line: &str) -> Self {
+        let parsed = line.split_once(" [").and_then(|(timestamp, rest)| {
+            let (level, message) = rest.split_once(']')?;
+            let level = serde_json::from_value(serde_json::Value::String(level.to_lowercase()));
+            Some((timestamp, level.ok()?, message.trim_start()))
+        });
+
+        match parsed {
+            Some((timestamp, level, message)) => LogEntry {
+                level: Some(level),
+                message: message.to_string(),
+                timestamp: Some(timestamp.to_string()),
+                ..Default::default()
+            },
+            None => LogEntry {
+                message: line.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// The levels at which an agent can log.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct PointValue {
+    pub name: String,
+    pub points: Vec<f64>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct SampledValue {
+    pub count: u64,
+    pub labels: Option<HashMap<String, String>>,
+    pub max: f64,
+    pub mean: f64,
+    pub min: f64,
+    pub name: String,
+    pub rate: f64,
+    pub stddev: f64,
+    pub sum: f64,
+}
@@ -0,0 +1,213 @@
+use super::{
+    common::{AgentMember, AgentTokenKind, LogLevel},
+    responses::{AgentHostResponse, AgentMetricsResponse, AgentSelfResponse},
+};
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// ## Read Configuration
+/// This endpoint returns the configuration and member information of the local
+/// agent.
+///
+/// * Path: agent/self
+/// * Method: GET
+/// * Response: [AgentSelfResponse]
+/// * Reference: https://www.consul.io/api-docs/agent#read-configuration
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "agent/self", response = "AgentSelfResponse", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadSelfRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+}
+
+/// ## View Metrics
+/// This endpoint returns the configured metrics for the local agent.
+///
+/// * Path: agent/metrics
+/// * Method: GET
+/// * Response: [AgentMetricsResponse]
+/// * Reference: https://www.consul.io/api-docs/agent#view-metrics
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "agent/metrics",
+    response = "AgentMetricsResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadMetricsRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+}
+
+/// ## Retrieve Host Information
+/// This endpoint returns information about the host the agent is running on.
+///
+/// * Path: agent/host
+/// * Method: GET
+/// * Response: [AgentHostResponse]
+/// * Reference: https://www.consul.io/api-docs/agent#retrieve-host-information
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "agent/host", response = "AgentHostResponse", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadHostRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+}
+
+/// ## List Members
+/// This endpoint returns the members the agent sees in the cluster gossip pool.
+///
+/// Consul only checks for the presence of `wan`, so setting it to `false`
+/// still lists WAN members.
+///
+/// * Path: agent/members
+/// * Method: GET
+/// * Response: [Vec<AgentMember>]
+/// * Reference: https://www.consul.io/api-docs/agent#list-members
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "agent/members",
+    response = "Vec<AgentMember>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListMembersRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub segment: Option<String>,
+    #[endpoint(query)]
+    pub wan: Option<bool>,
+}
+
+/// ## Stream Logs
+/// This endpoint streams logs from the local agent until the connection is
+/// closed.
+///
+/// Consul only checks for the presence of `logjson`, so setting it to `false`
+/// still streams JSON.
+///
+/// * Path: agent/monitor
+/// * Method: GET
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/agent#stream-logs
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(path = "agent/monitor", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct MonitorRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub logjson: Option<bool>,
+    #[endpoint(query)]
+    pub loglevel: Option<LogLevel>,
+}
+
+/// ## Enable Maintenance Mode
+/// This endpoint places the agent into "maintenance mode".
+///
+/// * Path: agent/maintenance
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/agent#enable-maintenance-mode
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "agent/maintenance", method = "PUT", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct EnableMaintenanceRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub enable: bool,
+    #[endpoint(query)]
+    pub reason: Option<String>,
+}
+
+/// ## Force Leave and Shutdown
+/// This endpoint instructs the agent to force a node into the `left` state.
+///
+/// Consul only checks for the presence of `prune` and `wan`, so setting
+/// either to `false` still enables it.
+///
+/// * Path: agent/force-leave/{self.node}
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/agent#force-leave-and-shutdown
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "agent/force-leave/{self.node}",
+    method = "PUT",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ForceLeaveRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub node: String,
+    #[endpoint(query)]
+    pub prune: Option<bool>,
+    #[endpoint(query)]
+    pub wan: Option<bool>,
+}
+
+/// ## Graceful Leave and Shutdown
+/// This endpoint triggers a graceful leave and shutdown of the agent.
+///
+/// * Path: agent/leave
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/agent#graceful-leave-and-shutdown
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "agent/leave", method = "PUT", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct LeaveRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+}
+
+/// ## Reload Agent
+/// This endpoint instructs the agent to reload its configuration.
+///
+/// * Path: agent/reload
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/agent#reload-agent
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "agent/reload", method = "PUT", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ReloadRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+}
+
+/// ## Update ACL Tokens
+/// This endpoint updates the ACL token of the given kind used by the agent.
+///
+/// The `agent_recovery` kind requires Consul 1.11 and the
+/// `config_file_service_registration` kind requires Consul 1.15.
+///
+/// * Path: agent/token/{self.kind}
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/agent#update-acl-tokens
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(path = "agent/token/{self.kind}", method = "PUT", builder = "true")]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdateTokenRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub kind: AgentTokenKind,
+    pub token: String,
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::common::{
+    AgentConfig, AgentMember, GaugeValue, HostCPU, HostDisk, HostMemory, HostOS, PointValue,
+    SampledValue,
+};
+use crate::api::coordinate::common::Coordinate;
+
+/// Response from executing
+/// [ReadHostRequest][crate::api::agent::requests::ReadHostRequest]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AgentHostResponse {
+    #[serde(rename = "CollectionTime")]
+    pub collection_time: i64,
+    #[serde(rename = "CPU", default)]
+    pub cpu: Vec<HostCPU>,
+    #[serde(rename = "Disk")]
+    pub disk: Option<HostDisk>,
+    #[serde(rename = "Host")]
+    pub host: Option<HostOS>,
+    #[serde(rename = "Memory")]
+    pub memory: Option<HostMemory>,
+}
+
+/// Response from executing
+/// [ReadMetricsRequest][crate::api::agent::requests::ReadMetricsRequest]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AgentMetricsResponse {
+    pub counters: Vec<SampledValue>,
+    pub gauges: Vec<GaugeValue>,
+    pub points: Vec<PointValue>,
+    pub samples: Vec<SampledValue>,
+    pub timestamp: String,
+}
+
+/// Response from executing
+/// [ReadSelfRequest][crate::api::agent::requests::ReadSelfRequest]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AgentSelfResponse {
+    pub config: AgentConfig,
+    pub coord: Option<Coordinate>,
+    pub debug_config: Option<serde_json::Value>,
+    pub member: AgentMember,
+    pub meta: Option<HashMap<String, String>>,
+    pub stats: Option<HashMap<String, HashMap<String, String>>>,
+}
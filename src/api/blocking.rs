@@ -0,0 +1,129 @@
+use futures::stream::{self, Stream};
+use rustify::endpoint::Endpoint;
+
+use crate::{
+    api::{
+        self,
+        features::{Blocking, FeaturedEndpoint},
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// A long-poll loop over an [Endpoint] which supports
+/// [Blocking Queries](https://www.consul.io/api-docs/features/blocking).
+///
+/// Each request made by the query is configured with the index returned in the
+/// `X-Consul-Index` header of the previous response, causing Consul to hold
+/// the request open until the result changes or the wait time is reached. The
+/// first request is made with the configured starting index (zero by default)
+/// and therefore returns immediately.
+///
+/// Any other [Features][crate::api::Features] already configured on the endpoint (i.e. a consistency
+/// mode or filter) are preserved on every request.
+///
+/// # Example
+///
+/// ```no_run
+/// # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+/// use consulrs::api::blocking::BlockingQuery;
+/// use consulrs::api::kv::requests::ReadKeyRequest;
+/// use futures::StreamExt;
+///
+/// # let client = ConsulClient::new(
+/// #     ConsulClientSettingsBuilder::default()
+/// #         .address("https://127.0.0.1:8200")
+/// #         .build()
+/// #         .unwrap()
+/// # ).unwrap();
+/// # tokio_test::block_on(async {
+/// let endpoint = ReadKeyRequest::builder().key("mykey").build().unwrap();
+/// let stream = BlockingQuery::new(&client, endpoint).wait("30s").stream();
+/// futures::pin_mut!(stream);
+///
+/// while let Some(res) = stream.next().await {
+///     println!("{:?}", res.unwrap().response);
+/// }
+/// # })
+/// ```
+pub struct BlockingQuery<'a, C: Client, E: Endpoint + FeaturedEndpoint + Clone> {
+    client: &'a C,
+    endpoint: E,
+    index: u64,
+    wait: Option<String>,
+}
+
+impl<'a, C, E> BlockingQuery<'a, C, E>
+where
+    C: Client,
+    E: Endpoint + FeaturedEndpoint + Clone,
+{
+    /// Returns a new [BlockingQuery] which executes the given [Endpoint].
+    pub fn new(client: &'a C, endpoint: E) -> Self {
+        BlockingQuery {
+            client,
+            endpoint,
+            index: 0,
+            wait: None,
+        }
+    }
+
+    /// Sets the index the first request is made with.
+    pub fn index(mut self, index: u64) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Sets the maximum duration each request is allowed to block for (i.e.
+    /// "5m"). Consul uses its own default of five minutes when not set.
+    pub fn wait(mut self, wait: &str) -> Self {
+        self.wait = Some(wait.into());
+        self
+    }
+
+    /// Executes the next request using the given index, returning the
+    /// response along with the index to use for the request after it.
+    #[instrument(skip(self), err)]
+    pub async fn next(&self, index: u64) -> Result<(ApiResponse<E::Response>, u64), ClientError> {
+        let mut endpoint = self.endpoint.clone();
+        let mut features = endpoint.features().unwrap_or_default();
+        features.blocking = Some(Blocking {
+            index,
+            wait: self.wait.clone(),
+        });
+        endpoint.set_features(Some(features));
+
+        let res = api::exec_with_result(self.client, endpoint).await?;
+        let next = parse_index(&res)?;
+        Ok((res, next))
+    }
+
+    /// Returns a [Stream] which yields the response of each request.
+    ///
+    /// The stream never ends on its own - it ends only after yielding the
+    /// first error encountered.
+    pub fn stream(self) -> impl Stream<Item = Result<ApiResponse<E::Response>, ClientError>> + 'a
+    where
+        E: 'a,
+    {
+        let index = self.index;
+        stream::try_unfold((self, index), |(query, index)| async move {
+            let (res, next) = query.next(index).await?;
+            Ok(Some((res, (query, next))))
+        })
+    }
+}
+
+/// Parses the `X-Consul-Index` header value out of an [ApiResponse].
+///
+/// Returns a [ClientError::InvalidIndexError] if the response did not contain
+/// a valid index, which usually means the endpoint does not support blocking.
+pub fn parse_index<T>(res: &ApiResponse<T>) -> Result<u64, ClientError> {
+    res.index
+        .as_ref()
+        .and_then(|i| i.parse::<u64>().ok())
+        .ok_or_else(|| ClientError::InvalidIndexError {
+            index: res.index.clone(),
+        })
+}
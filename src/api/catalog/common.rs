@@ -2,7 +2,10 @@ use crate::api::service::common::{AgentServiceConnect, AgentServiceConnectProxy,
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+};
 
 #[skip_serializing_none]
 #[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
@@ -34,6 +37,63 @@ pub struct CatalogService {
     pub tagged_addresses: Option<HashMap<String, String>>,
 }
 
+/// The name of a service qualified by its namespace and partition.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CompoundServiceName {
+    pub name: String,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+}
+
+/// The kinds of gateways which services can be linked to.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GatewayKind {
+    ApiGateway,
+    IngressGateway,
+    #[default]
+    TerminatingGateway,
+}
+
+impl fmt::Display for GatewayKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            GatewayKind::ApiGateway => "api-gateway",
+            GatewayKind::IngressGateway => "ingress-gateway",
+            GatewayKind::TerminatingGateway => "terminating-gateway",
+        };
+        f.write_str(kind)
+    }
+}
+
+/// A service linked to a gateway, along with the settings the gateway uses to
+/// reach it.
+///
+/// The TLS settings are only set for terminating gateways, while the port,
+/// protocol and hosts are only set for ingress gateways.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct GatewayService {
+    #[serde(rename = "CAFile")]
+    pub ca_file: Option<String>,
+    pub cert_file: Option<String>,
+    pub from_wildcard: Option<bool>,
+    pub gateway: CompoundServiceName,
+    pub gateway_kind: GatewayKind,
+    pub hosts: Option<Vec<String>>,
+    pub key_file: Option<String>,
+    pub port: Option<u64>,
+    pub protocol: Option<String>,
+    pub service: CompoundServiceName,
+    #[serde(rename = "SNI")]
+    pub sni: Option<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -1,6 +1,6 @@
 use super::{
-    common::{CatalogService, Node},
-    responses::{GatewayServiceResponse, ListNodeServicesResponse, ListNodesForServiceResponse},
+    common::{CatalogService, GatewayService, Node},
+    responses::{ListNodeServicesResponse, ListNodesForServiceResponse},
 };
 use crate::api::{check::common::AgentCheck, service::common::AgentService, Features};
 use consulrs_derive::QueryEndpoint;
@@ -17,7 +17,7 @@ use std::{collections::HashMap, fmt::Debug};
 /// * Method: PUT
 /// * Response: [bool]
 /// * Reference: https://www.consul.io/api-docs/catalog#register-entity
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
 #[endpoint(
     path = "catalog/register",
     method = "PUT",
@@ -35,9 +35,17 @@ pub struct RegisterEntityRequest {
     pub check: Option<AgentCheck>,
     pub checks: Option<Vec<AgentCheck>>,
     pub datacenter: Option<String>,
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
     pub tagged_addresses: Option<HashMap<String, String>>,
+    pub namespace: Option<String>,
     pub node_meta: Option<HashMap<String, String>>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
     pub service: Option<AgentService>,
     pub skip_node_update: Option<bool>,
 }
@@ -50,7 +58,7 @@ pub struct RegisterEntityRequest {
 /// * Method: PUT
 /// * Response: [bool]
 /// * Reference: https://www.consul.io/api-docs/catalog#deregister-entity
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
 #[endpoint(
     path = "catalog/deregister",
     method = "PUT",
@@ -68,6 +76,12 @@ pub struct DeregisterEntityRequest {
     pub check_id: Option<String>,
     pub datacenter: Option<String>,
     pub namespace: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
     #[serde(rename = "ServiceID")]
     pub service_id: Option<String>,
 }
@@ -79,7 +93,7 @@ pub struct DeregisterEntityRequest {
 /// * Method: GET
 /// * Response: [Vec<String>]
 /// * Reference: https://www.consul.io/api-docs/catalog#list-datacenters
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "catalog/datacenters",
     response = "Vec<String>",
@@ -98,7 +112,7 @@ pub struct ListDatacentersRequest {
 /// * Method: GET
 /// * Response: [Vec<Node>]
 /// * Reference: https://www.consul.io/api-docs/catalog#list-nodes
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(path = "catalog/nodes", response = "Vec<Node>", builder = "true")]
 #[builder(setter(into, strip_option), default)]
 pub struct ListNodesRequest {
@@ -108,6 +122,10 @@ pub struct ListNodesRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub near: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub peer: Option<String>,
 }
 
 /// ## List Services
@@ -117,7 +135,7 @@ pub struct ListNodesRequest {
 /// * Method: GET
 /// * Response: [HashMap<String, Vec<String>>]
 /// * Reference: https://www.consul.io/api-docs/catalog#list-services
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "catalog/services",
     response = "HashMap<String, Vec<String>>",
@@ -131,6 +149,10 @@ pub struct ListServicesRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub peer: Option<String>,
 }
 
 /// ## List Nodes for Service
@@ -140,7 +162,7 @@ pub struct ListServicesRequest {
 /// * Method: GET
 /// * Response: [Vec<CatalogService>]
 /// * Reference: https://www.consul.io/api-docs/catalog#list-nodes-for-service
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "catalog/service/{self.service}",
     response = "Vec<CatalogService>",
@@ -156,6 +178,10 @@ pub struct ListNodesForServiceRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub peer: Option<String>,
 }
 
 /// ## List Nodes for Connect-capable Service
@@ -166,7 +192,7 @@ pub struct ListNodesForServiceRequest {
 /// * Method: GET
 /// * Response: [Vec<ListNodesForServiceResponse>]
 /// * Reference: https://www.consul.io/api-docs/catalog#list-nodes-for-connect-capable-service
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "catalog/connect/{self.service}",
     response = "Vec<ListNodesForServiceResponse>",
@@ -182,16 +208,21 @@ pub struct ListNodesForConnectServiceRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## List Services for Node
 /// This endpoint returns the node's registered services.
 ///
+/// The services can be narrowed down with a filter on their fields, such as
+/// `Filter::selector("Service").eq("web")`.
+///
 /// * Path: catalog/node-services/{self.node}
 /// * Method: GET
 /// * Response: [ListNodeServicesResponse]
 /// * Reference: https://www.consul.io/api-docs/catalog#list-services-for-node
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "catalog/node-services/{self.node}",
     response = "ListNodeServicesResponse",
@@ -207,6 +238,10 @@ pub struct ListNodeServicesRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub peer: Option<String>,
 }
 
 /// ## List Services for Gateway
@@ -215,12 +250,12 @@ pub struct ListNodeServicesRequest {
 ///
 /// * Path: catalog/gateway-services/{self.gateway}
 /// * Method: GET
-/// * Response: [Option<Vec<GatewayServiceResponse>>]
+/// * Response: [Option<Vec<GatewayService>>]
 /// * Reference: https://www.consul.io/api-docs/catalog#list-services-for-gateway
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "catalog/gateway-services/{self.gateway}",
-    response = "Option<Vec<GatewayServiceResponse>>",
+    response = "Option<Vec<GatewayService>>",
     builder = "true"
 )]
 #[builder(setter(into, strip_option), default)]
@@ -233,4 +268,6 @@ pub struct ListGatewayServicesRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
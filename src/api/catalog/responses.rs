@@ -45,29 +45,3 @@ pub struct ListNodeServicesResponse {
     pub node: Node,
     pub services: Vec<AgentService>,
 }
-
-/// Response from executing
-/// [ListNodeServicesRequest][crate::api::catalog::requests::ListNodeServicesRequest]
-#[derive(Deserialize, Debug)]
-pub struct GatewayServiceResponse {
-    #[serde(rename = "CAFile")]
-    pub ca_file: Option<String>,
-    pub cert_file: Option<String>,
-    pub from_wildcard: Option<bool>,
-    pub gateway: CompoundServiceNameResponse,
-    pub gateway_kind: String,
-    pub hosts: Option<Vec<String>>,
-    pub key_file: Option<String>,
-    pub port: Option<u64>,
-    pub protocol: Option<String>,
-    pub service: CompoundServiceNameResponse,
-    #[serde(rename = "SNI")]
-    pub sni: Option<String>,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-pub struct CompoundServiceNameResponse {
-    pub name: String,
-    pub namespace: Option<String>,
-}
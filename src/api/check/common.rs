@@ -67,6 +67,53 @@ pub struct AgentServiceCheck {
     pub ttl: Option<String>,
 }
 
+/// The kind of a check registered with
+/// [RegisterCheckRequest][crate::api::check::requests::RegisterCheckRequest],
+/// along with the fields each kind requires.
+///
+/// Options which only apply to some kinds, such as the headers of an HTTP
+/// check, are set on the request builder and validated against the kind when
+/// it's built.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CheckKind {
+    /// Passing while the given node is healthy.
+    AliasNode { node: String },
+    /// Passing while the given service, on the given node or the local agent,
+    /// is healthy.
+    AliasService {
+        service: String,
+        node: Option<String>,
+    },
+    /// Executes the given command in a Docker container.
+    Docker {
+        container: String,
+        args: Vec<String>,
+        interval: String,
+    },
+    /// Queries the standard gRPC health checking protocol of the given address.
+    Grpc {
+        address: String,
+        interval: String,
+        use_tls: bool,
+    },
+    /// Sends an HTTP/2 ping to the given address.
+    H2Ping {
+        address: String,
+        interval: String,
+        use_tls: bool,
+    },
+    /// Makes a request to the given URL.
+    Http { url: String, interval: String },
+    /// Checks that the given operating system service is running.
+    OsService { service: String, interval: String },
+    /// Executes the given command.
+    Script { args: Vec<String>, interval: String },
+    /// Opens a TCP connection to the given address.
+    Tcp { address: String, interval: String },
+    /// Must be updated within the given TTL to remain passing.
+    Ttl { ttl: String },
+}
+
 #[skip_serializing_none]
 #[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -97,14 +144,17 @@ pub struct HealthCheck {
 #[builder(setter(into, strip_option), default)]
 pub struct HealthCheckDefinition {
     pub body: Option<String>,
+    pub deregister_critical_service_after: Option<String>,
     pub deregister_critical_service_after_duration: Option<String>,
     pub header: Option<HashMap<String, String>>,
     #[serde(rename = "HTTP")]
     pub http: Option<String>,
+    pub interval: Option<String>,
     pub interval_duration: Option<String>,
     pub method: Option<String>,
     #[serde(rename = "TCP")]
     pub tcp: Option<String>,
+    pub timeout: Option<String>,
     pub timeout_duration: Option<String>,
     #[serde(rename = "TLSServerName")]
     pub tls_server_name: Option<String>,
@@ -1,6 +1,6 @@
 use crate::api::Features;
 
-use super::common::AgentCheck;
+use super::common::{AgentCheck, CheckKind};
 use consulrs_derive::QueryEndpoint;
 use derive_builder::Builder;
 use rustify_derive::Endpoint;
@@ -14,7 +14,7 @@ use std::{collections::HashMap, fmt::Debug};
 /// * Method: GET
 /// * Response: [HashMap<String, AgentCheck>]
 /// * Reference: https://www.consul.io/api-docs/catalog#list-services-for-node
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/checks",
     response = "HashMap<String, AgentCheck>",
@@ -26,6 +26,8 @@ pub struct ListChecksRequest {
     pub features: Option<Features>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## Register Check
@@ -36,10 +38,19 @@ pub struct ListChecksRequest {
 /// * Method: PUT
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/agent/check#register-check
+///
+/// The kind of check is determined by which of its defining fields is set
+/// (i.e. `ttl` for a TTL check or `http` for an HTTP check). Building the
+/// request fails if no kind, or more than one kind, is configured, if the
+/// configured kind is missing a required field or if an option which doesn't
+/// apply to the configured kind is set. The constructors on this type (i.e.
+/// [RegisterCheckRequest::http]) and [RegisterCheckRequestBuilder::kind]
+/// return a builder with the fields required by each kind already set.
 #[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
 #[endpoint(path = "agent/check/register", method = "PUT", builder = "true")]
 #[serde(rename_all = "PascalCase")]
 #[builder(setter(into, strip_option), default)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct RegisterCheckRequest {
     #[endpoint(skip)]
     #[serde(skip)]
@@ -50,6 +61,7 @@ pub struct RegisterCheckRequest {
     pub args: Option<Vec<String>>,
     pub body: Option<String>,
     pub deregister_critical_service_after: Option<String>,
+    #[serde(rename = "DockerContainerID")]
     pub docker_container_id: Option<String>,
     pub failures_before_critical: Option<u64>,
     #[serde(rename = "GRPC")]
@@ -58,6 +70,8 @@ pub struct RegisterCheckRequest {
     pub grpc_use_tls: Option<bool>,
     #[serde(rename = "H2PING")]
     pub h2_ping: Option<String>,
+    #[serde(rename = "H2PingUseTLS")]
+    pub h2_ping_use_tls: Option<bool>,
     pub header: Option<HashMap<String, String>>,
     #[serde(rename = "HTTP")]
     pub http: Option<String>,
@@ -66,9 +80,19 @@ pub struct RegisterCheckRequest {
     pub interval: Option<String>,
     pub method: Option<String>,
     pub namespace: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
     pub notes: Option<String>,
+    #[serde(rename = "OSService")]
+    pub os_service: Option<String>,
     pub output_max_size: Option<u64>,
+    #[serde(rename = "ServiceID")]
     pub service_id: Option<String>,
+    pub shell: Option<String>,
     pub status: Option<String>,
     pub success_before_passing: Option<u64>,
     #[serde(rename = "TCP")]
@@ -77,11 +101,216 @@ pub struct RegisterCheckRequest {
     #[serde(rename = "TLSServerName")]
     pub tls_server_name: Option<String>,
     #[serde(rename = "TLSSkipVerify")]
-    pub tls_skip_verify: Option<String>,
+    pub tls_skip_verify: Option<bool>,
     #[serde(rename = "TTL")]
     pub ttl: Option<String>,
 }
 
+impl RegisterCheckRequest {
+    /// Returns a builder for a check which is alive as long as the given
+    /// service is healthy.
+    pub fn alias(service: &str) -> RegisterCheckRequestBuilder {
+        let mut builder = RegisterCheckRequest::builder();
+        builder.alias_service(service);
+        builder
+    }
+
+    /// Returns a builder for a check which is alive as long as the given node
+    /// is healthy.
+    pub fn alias_node(node: &str) -> RegisterCheckRequestBuilder {
+        let mut builder = RegisterCheckRequest::builder();
+        builder.alias_node(node);
+        builder
+    }
+
+    /// Returns a builder for a check which executes the given command in a
+    /// Docker container at the given interval.
+    pub fn docker(
+        container: &str,
+        args: Vec<String>,
+        interval: &str,
+    ) -> RegisterCheckRequestBuilder {
+        let mut builder = RegisterCheckRequest::builder();
+        builder
+            .docker_container_id(container)
+            .args(args)
+            .interval(interval);
+        builder
+    }
+
+    /// Returns a builder for a check which queries the given gRPC endpoint at
+    /// the given interval.
+    pub fn grpc(address: &str, interval: &str) -> RegisterCheckRequestBuilder {
+        let mut builder = RegisterCheckRequest::builder();
+        builder.grpc(address).interval(interval);
+        builder
+    }
+
+    /// Returns a builder for a check which sends an HTTP/2 ping to the given
+    /// address at the given interval.
+    pub fn h2_ping(address: &str, interval: &str) -> RegisterCheckRequestBuilder {
+        let mut builder = RegisterCheckRequest::builder();
+        builder.h2_ping(address).interval(interval);
+        builder
+    }
+
+    /// Returns a builder for a check which makes a request to the given URL at
+    /// the given interval.
+    pub fn http(url: &str, interval: &str) -> RegisterCheckRequestBuilder {
+        let mut builder = RegisterCheckRequest::builder();
+        builder.http(url).interval(interval);
+        builder
+    }
+
+    /// Returns a builder for a check which checks that the given operating
+    /// system service is running at the given interval.
+    pub fn os_service(service: &str, interval: &str) -> RegisterCheckRequestBuilder {
+        let mut builder = RegisterCheckRequest::builder();
+        builder.os_service(service).interval(interval);
+        builder
+    }
+
+    /// Returns a builder for a check which executes the given command at the
+    /// given interval.
+    pub fn script(args: Vec<String>, interval: &str) -> RegisterCheckRequestBuilder {
+        let mut builder = RegisterCheckRequest::builder();
+        builder.args(args).interval(interval);
+        builder
+    }
+
+    /// Returns a builder for a check which opens a TCP connection to the given
+    /// address at the given interval.
+    pub fn tcp(address: &str, interval: &str) -> RegisterCheckRequestBuilder {
+        let mut builder = RegisterCheckRequest::builder();
+        builder.tcp(address).interval(interval);
+        builder
+    }
+
+    /// Returns a builder for a check which must be updated within the given
+    /// TTL to remain passing.
+    pub fn ttl(ttl: &str) -> RegisterCheckRequestBuilder {
+        let mut builder = RegisterCheckRequest::builder();
+        builder.ttl(ttl);
+        builder
+    }
+}
+
+impl RegisterCheckRequestBuilder {
+    /// Sets the fields of the given kind of check.
+    pub fn kind(&mut self, kind: CheckKind) -> &mut Self {
+        match kind {
+            CheckKind::AliasNode { node } => self.alias_node(node),
+            CheckKind::AliasService { service, node } => {
+                self.alias_service(service);
+                match node {
+                    Some(node) => self.alias_node(node),
+                    None => self,
+                }
+            }
+            CheckKind::Docker {
+                container,
+                args,
+                interval,
+            } => self
+                .docker_container_id(container)
+                .args(args)
+                .interval(interval),
+            CheckKind::Grpc {
+                address,
+                interval,
+                use_tls,
+            } => self.grpc(address).grpc_use_tls(use_tls).interval(interval),
+            CheckKind::H2Ping {
+                address,
+                interval,
+                use_tls,
+            } => self
+                .h2_ping(address)
+                .h2_ping_use_tls(use_tls)
+                .interval(interval),
+            CheckKind::Http { url, interval } => self.http(url).interval(interval),
+            CheckKind::OsService { service, interval } => {
+                self.os_service(service).interval(interval)
+            }
+            CheckKind::Script { args, interval } => self.args(args).interval(interval),
+            CheckKind::Tcp { address, interval } => self.tcp(address).interval(interval),
+            CheckKind::Ttl { ttl } => self.ttl(ttl),
+        }
+    }
+
+    /// Validates that exactly one kind of check is configured along with the
+    /// fields that kind requires, and that no options for other kinds are set.
+    fn validate(&self) -> Result<(), String> {
+        let docker = is_set(&self.docker_container_id);
+        let kinds = [
+            (
+                "alias",
+                is_set(&self.alias_service) || is_set(&self.alias_node),
+            ),
+            ("docker", docker),
+            ("gRPC", is_set(&self.grpc)),
+            ("H2 ping", is_set(&self.h2_ping)),
+            ("HTTP", is_set(&self.http)),
+            ("OS service", is_set(&self.os_service)),
+            ("script", is_set(&self.args) && !docker),
+            ("TCP", is_set(&self.tcp)),
+            ("TTL", is_set(&self.ttl)),
+        ];
+        let configured: Vec<&str> = kinds
+            .iter()
+            .filter(|(_, set)| *set)
+            .map(|(kind, _)| *kind)
+            .collect();
+
+        let kind = match configured.as_slice() {
+            [] => return Err("No kind of check is configured".into()),
+            [kind] => *kind,
+            kinds => {
+                return Err(format!(
+                    "Multiple kinds of check are configured: {:?}",
+                    kinds
+                ))
+            }
+        };
+        match kind {
+            "alias" | "TTL" => {}
+            _ if !is_set(&self.interval) => {
+                return Err(format!("{} checks require an interval", kind));
+            }
+            "docker" if !is_set(&self.args) && !is_set(&self.shell) => {
+                return Err("Docker checks require args or a shell".into());
+            }
+            _ => {}
+        }
+
+        let tls = ["gRPC", "H2 ping", "HTTP", "TCP"];
+        let options = [
+            ("body", is_set(&self.body), &["HTTP"][..]),
+            ("gRPC TLS", is_set(&self.grpc_use_tls), &["gRPC"]),
+            ("H2 ping TLS", is_set(&self.h2_ping_use_tls), &["H2 ping"]),
+            ("header", is_set(&self.header), &["HTTP"]),
+            ("method", is_set(&self.method), &["HTTP"]),
+            ("shell", is_set(&self.shell), &["docker"]),
+            ("TLS server name", is_set(&self.tls_server_name), &tls),
+            ("TLS skip verify", is_set(&self.tls_skip_verify), &tls),
+        ];
+        for (option, set, kinds) in options {
+            if set && !kinds.contains(&kind) {
+                return Err(format!(
+                    "{} checks don't support the {} option",
+                    kind, option
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether an optional builder field has been set to a value.
+fn is_set<T>(field: &Option<Option<T>>) -> bool {
+    matches!(field, Some(Some(_)))
+}
+
 /// ## Deregister Check
 /// This endpoint remove a check from the local agent.
 ///
@@ -89,7 +318,7 @@ pub struct RegisterCheckRequest {
 /// * Method: PUT
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/agent/check#deregister-check
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/check/deregister/{self.check}",
     method = "PUT",
@@ -103,6 +332,8 @@ pub struct DeregisterCheckRequest {
     pub check: String,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## TTL Check Pass
@@ -113,7 +344,7 @@ pub struct DeregisterCheckRequest {
 /// * Method: PUT
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/agent/check#ttl-check-pass
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/check/pass/{self.check}",
     method = "PUT",
@@ -125,9 +356,12 @@ pub struct TtlCheckPassRequest {
     pub features: Option<Features>,
     #[endpoint(skip)]
     pub check: String,
+    #[endpoint(query)]
     pub note: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## TTL Check Warn
@@ -138,7 +372,7 @@ pub struct TtlCheckPassRequest {
 /// * Method: PUT
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/agent/check#ttl-check-warn
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/check/warn/{self.check}",
     method = "PUT",
@@ -150,9 +384,12 @@ pub struct TtlCheckWarnRequest {
     pub features: Option<Features>,
     #[endpoint(skip)]
     pub check: String,
+    #[endpoint(query)]
     pub note: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## TTL Check Fail
@@ -163,7 +400,7 @@ pub struct TtlCheckWarnRequest {
 /// * Method: PUT
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/agent/check#ttl-check-fail
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/check/fail/{self.check}",
     method = "PUT",
@@ -175,9 +412,12 @@ pub struct TtlCheckFailRequest {
     pub features: Option<Features>,
     #[endpoint(skip)]
     pub check: String,
+    #[endpoint(query)]
     pub note: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## TTL Check Update
@@ -188,7 +428,7 @@ pub struct TtlCheckFailRequest {
 /// * Method: PUT
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/agent/check#ttl-check-update
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
 #[endpoint(
     path = "agent/check/update/{self.check}",
     method = "PUT",
@@ -203,6 +443,8 @@ pub struct TtlCheckUpdateRequest {
     pub check: String,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
     #[serde(rename = "Output")]
     pub output: Option<String>,
     #[serde(rename = "Status")]
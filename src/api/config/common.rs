@@ -0,0 +1,490 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{collections::HashMap, fmt, fmt::Debug};
+
+use crate::api::connect::common::{ExposeConfig, MeshGatewayConfig, TransparentProxyConfig};
+
+/// A configuration entry, tagged by its kind.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "Kind")]
+pub enum ConfigEntry {
+    #[serde(rename = "ingress-gateway")]
+    IngressGateway(IngressGatewayConfigEntry),
+    #[serde(rename = "mesh")]
+    Mesh(MeshConfigEntry),
+    #[serde(rename = "proxy-defaults")]
+    ProxyDefaults(ProxyConfigEntry),
+    #[serde(rename = "service-defaults")]
+    ServiceDefaults(ServiceConfigEntry),
+    #[serde(rename = "service-resolver")]
+    ServiceResolver(ServiceResolverConfigEntry),
+    #[serde(rename = "service-router")]
+    ServiceRouter(ServiceRouterConfigEntry),
+    #[serde(rename = "service-splitter")]
+    ServiceSplitter(ServiceSplitterConfigEntry),
+    #[serde(rename = "terminating-gateway")]
+    TerminatingGateway(TerminatingGatewayConfigEntry),
+}
+
+impl ConfigEntry {
+    /// Returns the kind of this entry.
+    pub fn kind(&self) -> ConfigEntryKind {
+        match self {
+            ConfigEntry::IngressGateway(_) => ConfigEntryKind::IngressGateway,
+            ConfigEntry::Mesh(_) => ConfigEntryKind::Mesh,
+            ConfigEntry::ProxyDefaults(_) => ConfigEntryKind::ProxyDefaults,
+            ConfigEntry::ServiceDefaults(_) => ConfigEntryKind::ServiceDefaults,
+            ConfigEntry::ServiceResolver(_) => ConfigEntryKind::ServiceResolver,
+            ConfigEntry::ServiceRouter(_) => ConfigEntryKind::ServiceRouter,
+            ConfigEntry::ServiceSplitter(_) => ConfigEntryKind::ServiceSplitter,
+            ConfigEntry::TerminatingGateway(_) => ConfigEntryKind::TerminatingGateway,
+        }
+    }
+}
+
+impl Default for ConfigEntry {
+    fn default() -> Self {
+        ConfigEntry::ServiceDefaults(ServiceConfigEntry::default())
+    }
+}
+
+/// The kinds of configuration entries.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfigEntryKind {
+    IngressGateway,
+    Mesh,
+    ProxyDefaults,
+    #[default]
+    ServiceDefaults,
+    ServiceResolver,
+    ServiceRouter,
+    ServiceSplitter,
+    TerminatingGateway,
+}
+
+impl fmt::Display for ConfigEntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            ConfigEntryKind::IngressGateway => "ingress-gateway",
+            ConfigEntryKind::Mesh => "mesh",
+            ConfigEntryKind::ProxyDefaults => "proxy-defaults",
+            ConfigEntryKind::ServiceDefaults => "service-defaults",
+            ConfigEntryKind::ServiceResolver => "service-resolver",
+            ConfigEntryKind::ServiceRouter => "service-router",
+            ConfigEntryKind::ServiceSplitter => "service-splitter",
+            ConfigEntryKind::TerminatingGateway => "terminating-gateway",
+        };
+        f.write_str(kind)
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CookieConfig {
+    pub path: Option<String>,
+    pub session: Option<bool>,
+    #[serde(rename = "TTL")]
+    pub ttl: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct GatewayTLSConfig {
+    pub cipher_suites: Option<Vec<String>>,
+    pub enabled: Option<bool>,
+    #[serde(rename = "TLSMaxVersion")]
+    pub tls_max_version: Option<String>,
+    #[serde(rename = "TLSMinVersion")]
+    pub tls_min_version: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct HashPolicy {
+    pub cookie_config: Option<CookieConfig>,
+    pub field: Option<String>,
+    pub field_value: Option<String>,
+    #[serde(rename = "SourceIP")]
+    pub source_ip: Option<bool>,
+    pub terminal: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct IngressGatewayConfigEntry {
+    pub create_index: Option<u64>,
+    pub listeners: Option<Vec<IngressListener>>,
+    pub meta: Option<HashMap<String, String>>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    #[serde(rename = "TLS")]
+    pub tls: Option<GatewayTLSConfig>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct IngressListener {
+    pub port: u64,
+    pub protocol: Option<String>,
+    pub services: Option<Vec<IngressService>>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct IngressService {
+    pub hosts: Option<Vec<String>>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct LeastRequestConfig {
+    pub choice_count: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct LinkedService {
+    #[serde(rename = "CAFile")]
+    pub ca_file: Option<String>,
+    pub cert_file: Option<String>,
+    pub key_file: Option<String>,
+    pub name: String,
+    pub namespace: Option<String>,
+    #[serde(rename = "SNI")]
+    pub sni: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct LoadBalancer {
+    pub hash_policies: Option<Vec<HashPolicy>>,
+    pub least_request_config: Option<LeastRequestConfig>,
+    pub policy: Option<String>,
+    pub ring_hash_config: Option<RingHashConfig>,
+}
+
+/// The mesh entry is a singleton, so it has no name.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct MeshConfigEntry {
+    pub create_index: Option<u64>,
+    pub meta: Option<HashMap<String, String>>,
+    pub modify_index: Option<u64>,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub transparent_proxy: Option<MeshTransparentProxyConfig>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct MeshTransparentProxyConfig {
+    pub mesh_destinations_only: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct PassiveHealthCheck {
+    pub enforcing_consecutive_5xx: Option<u64>,
+    pub interval: Option<String>,
+    pub max_failures: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ProxyConfigEntry {
+    pub config: Option<HashMap<String, serde_json::Value>>,
+    pub create_index: Option<u64>,
+    pub expose: Option<ExposeConfig>,
+    pub mesh_gateway: Option<MeshGatewayConfig>,
+    pub meta: Option<HashMap<String, String>>,
+    pub mode: Option<String>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub transparent_proxy: Option<TransparentProxyConfig>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct RingHashConfig {
+    pub maximum_ring_size: Option<u64>,
+    pub minimum_ring_size: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceConfigEntry {
+    pub balance_inbound_connections: Option<String>,
+    pub create_index: Option<u64>,
+    pub expose: Option<ExposeConfig>,
+    #[serde(rename = "ExternalSNI")]
+    pub external_sni: Option<String>,
+    pub local_connect_timeout_ms: Option<u64>,
+    pub local_request_timeout_ms: Option<u64>,
+    pub max_inbound_connections: Option<u64>,
+    pub mesh_gateway: Option<MeshGatewayConfig>,
+    pub meta: Option<HashMap<String, String>>,
+    pub mode: Option<String>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub protocol: Option<String>,
+    pub transparent_proxy: Option<TransparentProxyConfig>,
+    pub upstream_config: Option<UpstreamConfiguration>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceResolverConfigEntry {
+    pub connect_timeout: Option<String>,
+    pub create_index: Option<u64>,
+    pub default_subset: Option<String>,
+    pub failover: Option<HashMap<String, ServiceResolverFailover>>,
+    pub load_balancer: Option<LoadBalancer>,
+    pub meta: Option<HashMap<String, String>>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub redirect: Option<ServiceResolverRedirect>,
+    pub subsets: Option<HashMap<String, ServiceResolverSubset>>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceResolverFailover {
+    pub datacenters: Option<Vec<String>>,
+    pub namespace: Option<String>,
+    pub service: Option<String>,
+    pub service_subset: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceResolverRedirect {
+    pub datacenter: Option<String>,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub service: Option<String>,
+    pub service_subset: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceResolverSubset {
+    pub filter: Option<String>,
+    pub only_passing: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceRoute {
+    pub destination: Option<ServiceRouteDestination>,
+    #[serde(rename = "Match")]
+    pub route_match: Option<ServiceRouteMatch>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceRouteDestination {
+    pub namespace: Option<String>,
+    pub num_retries: Option<u64>,
+    pub partition: Option<String>,
+    pub prefix_rewrite: Option<String>,
+    pub request_timeout: Option<String>,
+    pub retry_on_connect_failure: Option<bool>,
+    pub retry_on_status_codes: Option<Vec<u64>>,
+    pub service: Option<String>,
+    pub service_subset: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceRouteHTTPMatch {
+    pub header: Option<Vec<ServiceRouteHTTPMatchHeader>>,
+    pub methods: Option<Vec<String>>,
+    pub path_exact: Option<String>,
+    pub path_prefix: Option<String>,
+    pub path_regex: Option<String>,
+    pub query_param: Option<Vec<ServiceRouteHTTPMatchQueryParam>>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceRouteHTTPMatchHeader {
+    pub exact: Option<String>,
+    pub invert: Option<bool>,
+    pub name: String,
+    pub prefix: Option<String>,
+    pub present: Option<bool>,
+    pub regex: Option<String>,
+    pub suffix: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceRouteHTTPMatchQueryParam {
+    pub exact: Option<String>,
+    pub name: String,
+    pub present: Option<bool>,
+    pub regex: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceRouteMatch {
+    #[serde(rename = "HTTP")]
+    pub http: Option<ServiceRouteHTTPMatch>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceRouterConfigEntry {
+    pub create_index: Option<u64>,
+    pub meta: Option<HashMap<String, String>>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub routes: Option<Vec<ServiceRoute>>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceSplit {
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub service: Option<String>,
+    pub service_subset: Option<String>,
+    pub weight: f32,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceSplitterConfigEntry {
+    pub create_index: Option<u64>,
+    pub meta: Option<HashMap<String, String>>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub splits: Option<Vec<ServiceSplit>>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct TerminatingGatewayConfigEntry {
+    pub create_index: Option<u64>,
+    pub meta: Option<HashMap<String, String>>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub services: Option<Vec<LinkedService>>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpstreamConfig {
+    pub balance_outbound_connections: Option<String>,
+    pub connect_timeout_ms: Option<u64>,
+    #[serde(rename = "EnvoyClusterJSON")]
+    pub envoy_cluster_json: Option<String>,
+    #[serde(rename = "EnvoyListenerJSON")]
+    pub envoy_listener_json: Option<String>,
+    pub limits: Option<UpstreamLimits>,
+    pub mesh_gateway: Option<MeshGatewayConfig>,
+    pub name: Option<String>,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub passive_health_check: Option<PassiveHealthCheck>,
+    pub protocol: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpstreamConfiguration {
+    pub defaults: Option<UpstreamConfig>,
+    pub overrides: Option<Vec<UpstreamConfig>>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpstreamLimits {
+    pub max_concurrent_requests: Option<u64>,
+    pub max_connections: Option<u64>,
+    pub max_pending_requests: Option<u64>,
+}
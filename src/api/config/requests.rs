@@ -0,0 +1,118 @@
+use super::common::{ConfigEntry, ConfigEntryKind};
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// ## Apply Configuration
+/// This endpoint creates or updates the given config entry.
+///
+/// * Path: config
+/// * Method: PUT
+/// * Response: [bool]
+/// * Reference: https://www.consul.io/api-docs/config#apply-configuration
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(path = "config", method = "PUT", response = "bool", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ApplyConfigRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[serde(flatten)]
+    pub entry: ConfigEntry,
+    #[endpoint(query)]
+    pub cas: Option<u64>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Delete Configuration
+/// This endpoint deletes the given config entry.
+///
+/// * Path: config/{self.kind}/{self.name}
+/// * Method: DELETE
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/config#delete-configuration
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "config/{self.kind}/{self.name}",
+    method = "DELETE",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct DeleteConfigRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub kind: ConfigEntryKind,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Get Configuration
+/// This endpoint returns the config entry with the given kind and name.
+///
+/// * Path: config/{self.kind}/{self.name}
+/// * Method: GET
+/// * Response: [ConfigEntry]
+/// * Reference: https://www.consul.io/api-docs/config#get-configuration
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "config/{self.kind}/{self.name}",
+    response = "ConfigEntry",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct GetConfigRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub kind: ConfigEntryKind,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## List Configurations
+/// This endpoint returns all config entries of the given kind.
+///
+/// * Path: config/{self.kind}
+/// * Method: GET
+/// * Response: [Vec<ConfigEntry>]
+/// * Reference: https://www.consul.io/api-docs/config#list-configurations
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "config/{self.kind}",
+    response = "Vec<ConfigEntry>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListConfigsRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub kind: ConfigEntryKind,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
@@ -3,6 +3,59 @@ use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use std::{collections::HashMap, fmt::Debug};
 
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CAConfig {
+    pub config: Option<HashMap<String, serde_json::Value>>,
+    pub create_index: Option<u64>,
+    pub force_without_cross_signing: Option<bool>,
+    pub modify_index: Option<u64>,
+    pub provider: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CARoot {
+    pub active: Option<bool>,
+    pub create_index: Option<u64>,
+    pub external_trust_domain: Option<String>,
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    pub intermediate_certs: Option<Vec<String>>,
+    pub modify_index: Option<u64>,
+    pub name: Option<String>,
+    pub not_after: Option<String>,
+    pub not_before: Option<String>,
+    pub private_key_bits: Option<u64>,
+    pub private_key_type: Option<String>,
+    pub root_cert: Option<String>,
+    pub serial_number: Option<u64>,
+    #[serde(rename = "SigningKeyID")]
+    pub signing_key_id: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CARootList {
+    #[serde(rename = "ActiveRootID")]
+    pub active_root_id: Option<String>,
+    pub roots: Option<Vec<CARoot>>,
+    pub trust_domain: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ConnectAuthorization {
+    pub authorized: bool,
+    pub reason: String,
+}
+
 #[skip_serializing_none]
 #[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -24,6 +77,110 @@ pub struct ExposePath {
     pub protocol: Option<String>,
 }
 
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct Intention {
+    pub action: Option<IntentionAction>,
+    pub create_index: Option<u64>,
+    pub created_at: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "DestinationNS")]
+    pub destination_ns: Option<String>,
+    pub destination_name: Option<String>,
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    pub meta: Option<HashMap<String, String>>,
+    pub modify_index: Option<u64>,
+    pub permissions: Option<Vec<IntentionPermission>>,
+    pub precedence: Option<u64>,
+    #[serde(rename = "SourceNS")]
+    pub source_ns: Option<String>,
+    pub source_name: Option<String>,
+    pub source_type: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// Whether an intention allows or denies matching traffic.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntentionAction {
+    Allow,
+    Deny,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct IntentionCheck {
+    pub allowed: bool,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct IntentionHTTPHeaderPermission {
+    pub exact: Option<String>,
+    pub invert: Option<bool>,
+    pub name: String,
+    pub prefix: Option<String>,
+    pub present: Option<bool>,
+    pub regex: Option<String>,
+    pub suffix: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct IntentionHTTPPermission {
+    pub header: Option<Vec<IntentionHTTPHeaderPermission>>,
+    pub methods: Option<Vec<String>>,
+    pub path_exact: Option<String>,
+    pub path_prefix: Option<String>,
+    pub path_regex: Option<String>,
+}
+
+/// Which side of an intention to match a service name against.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntentionMatchType {
+    #[default]
+    Destination,
+    Source,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option))]
+pub struct IntentionPermission {
+    pub action: IntentionAction,
+    #[serde(rename = "HTTP")]
+    #[builder(default)]
+    pub http: Option<IntentionHTTPPermission>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct LeafCert {
+    #[serde(rename = "CertPEM")]
+    pub cert_pem: Option<String>,
+    pub create_index: Option<u64>,
+    pub modify_index: Option<u64>,
+    #[serde(rename = "PrivateKeyPEM")]
+    pub private_key_pem: Option<String>,
+    pub serial_number: Option<String>,
+    pub service: Option<String>,
+    #[serde(rename = "ServiceURI")]
+    pub service_uri: Option<String>,
+    pub valid_after: Option<String>,
+    pub valid_before: Option<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
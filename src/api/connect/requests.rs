@@ -0,0 +1,299 @@
+use super::common::{
+    CAConfig, CARootList, ConnectAuthorization, Intention, IntentionAction, IntentionCheck,
+    IntentionMatchType, IntentionPermission, LeafCert,
+};
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::{collections::HashMap, fmt::Debug};
+
+/// ## Authorize
+/// This endpoint tests whether a connection attempt is authorized between two
+/// services. It is used by native Connect integrations to authorize inbound
+/// connections without a proxy.
+///
+/// * Path: agent/connect/authorize
+/// * Method: POST
+/// * Response: [ConnectAuthorization]
+/// * Reference: https://www.consul.io/api-docs/agent/connect#authorize
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "agent/connect/authorize",
+    method = "POST",
+    response = "ConnectAuthorization",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct AuthorizeRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub client_cert_serial: String,
+    #[serde(rename = "ClientCertURI")]
+    pub client_cert_uri: String,
+    pub namespace: Option<String>,
+    pub target: String,
+}
+
+/// ## Check Intention Result
+/// This endpoint evaluates the intentions for a specific source and
+/// destination and returns whether the connection would be authorized.
+///
+/// * Path: connect/intentions/check
+/// * Method: GET
+/// * Response: [IntentionCheck]
+/// * Reference: https://www.consul.io/api-docs/connect/intentions#check-intention-result
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "connect/intentions/check",
+    response = "IntentionCheck",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct CheckIntentionRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub destination: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub source: String,
+}
+
+/// ## Delete Intention by Name
+/// This endpoint deletes a specific intention by its unique source and
+/// destination.
+///
+/// * Path: connect/intentions/exact
+/// * Method: DELETE
+/// * Response: [bool]
+/// * Reference: https://www.consul.io/api-docs/connect/intentions#delete-intention-by-name
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "connect/intentions/exact",
+    method = "DELETE",
+    response = "bool",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct DeleteIntentionRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub destination: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub source: String,
+}
+
+/// ## List Intentions
+/// This endpoint lists all intentions.
+///
+/// * Path: connect/intentions
+/// * Method: GET
+/// * Response: [Vec<Intention>]
+/// * Reference: https://www.consul.io/api-docs/connect/intentions#list-intentions
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "connect/intentions",
+    response = "Vec<Intention>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListIntentionsRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## List CA Root Certificates
+/// This endpoint returns the current list of trusted CA root certificates in
+/// the cluster.
+///
+/// * Path: connect/ca/roots
+/// * Method: GET
+/// * Response: [CARootList]
+/// * Reference: https://www.consul.io/api-docs/connect/ca#list-ca-root-certificates
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "connect/ca/roots", response = "CARootList", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ListRootsRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+}
+
+/// ## List Matching Intentions
+/// This endpoint lists the intentions that match a given source or
+/// destination, ordered by precedence.
+///
+/// * Path: connect/intentions/match
+/// * Method: GET
+/// * Response: [HashMap<String, Vec<Intention>>]
+/// * Reference: https://www.consul.io/api-docs/connect/intentions#list-matching-intentions
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "connect/intentions/match",
+    response = "HashMap<String, Vec<Intention>>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct MatchIntentionsRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub by: IntentionMatchType,
+    #[endpoint(query)]
+    pub name: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Get CA Configuration
+/// This endpoint returns the current CA configuration.
+///
+/// * Path: connect/ca/configuration
+/// * Method: GET
+/// * Response: [CAConfig]
+/// * Reference: https://www.consul.io/api-docs/connect/ca#get-ca-configuration
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "connect/ca/configuration",
+    response = "CAConfig",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadConfigRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+}
+
+/// ## Read Specific Intention by Name
+/// This endpoint reads a specific intention by its unique source and
+/// destination.
+///
+/// * Path: connect/intentions/exact
+/// * Method: GET
+/// * Response: [Intention]
+/// * Reference: https://www.consul.io/api-docs/connect/intentions#read-specific-intention-by-name
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "connect/intentions/exact",
+    response = "Intention",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadIntentionRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub destination: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub source: String,
+}
+
+/// ## Update CA Configuration
+/// This endpoint updates the configuration for the CA. If this results in a
+/// new root certificate being used, the root rotation process will be
+/// triggered.
+///
+/// * Path: connect/ca/configuration
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/connect/ca#update-ca-configuration
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(path = "connect/ca/configuration", method = "PUT", builder = "true")]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdateConfigRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub config: Option<HashMap<String, serde_json::Value>>,
+    pub force_without_cross_signing: Option<bool>,
+    pub provider: String,
+}
+
+/// ## Service Leaf Certificate
+/// This endpoint returns the leaf certificate representing a single service.
+///
+/// * Path: agent/connect/ca/leaf/{self.service}
+/// * Method: GET
+/// * Response: [LeafCert]
+/// * Reference: https://www.consul.io/api-docs/agent/connect#service-leaf-certificate
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "agent/connect/ca/leaf/{self.service}",
+    response = "LeafCert",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadLeafRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub service: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Upsert Intention by Name
+/// This endpoint creates a new intention or updates an existing one with the
+/// given source and destination.
+///
+/// Either an action or a set of L7 permissions must be specified.
+///
+/// * Path: connect/intentions/exact
+/// * Method: PUT
+/// * Response: [bool]
+/// * Reference: https://www.consul.io/api-docs/connect/intentions#upsert-intention-by-name
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "connect/intentions/exact",
+    method = "PUT",
+    response = "bool",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpsertIntentionRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub action: Option<IntentionAction>,
+    pub description: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "destination")]
+    pub destination: String,
+    pub meta: Option<HashMap<String, String>>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub permissions: Option<Vec<IntentionPermission>>,
+    #[endpoint(query)]
+    #[serde(rename = "source")]
+    pub source: String,
+    pub source_type: Option<String>,
+}
@@ -0,0 +1,61 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{fmt::Debug, time::Duration};
+
+/// A network coordinate in the Vivaldi system used by Consul to estimate the
+/// round trip time between nodes.
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into), default)]
+pub struct Coordinate {
+    pub adjustment: f64,
+    pub error: f64,
+    pub height: f64,
+    pub vec: Vec<f64>,
+}
+
+impl Coordinate {
+    /// Returns the estimated round trip time between this coordinate and the
+    /// given coordinate, or [None] if their dimensions don't match.
+    pub fn distance_to(&self, other: &Coordinate) -> Option<Duration> {
+        if self.vec.len() != other.vec.len() {
+            return None;
+        }
+
+        let magnitude = self
+            .vec
+            .iter()
+            .zip(other.vec.iter())
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f64>()
+            .sqrt();
+        let dist = magnitude + self.height + other.height;
+
+        // Only apply the adjustments if the result stays positive
+        let adjusted = dist + self.adjustment + other.adjustment;
+        let dist = if adjusted > 0.0 { adjusted } else { dist };
+        Some(Duration::from_secs_f64(dist.max(0.0)))
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CoordinateEntry {
+    pub coord: Coordinate,
+    pub node: String,
+    pub segment: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct DatacenterCoordinates {
+    #[serde(rename = "AreaID")]
+    pub area_id: Option<String>,
+    pub coordinates: Vec<CoordinateEntry>,
+    pub datacenter: String,
+}
@@ -0,0 +1,107 @@
+use super::common::{Coordinate, CoordinateEntry, DatacenterCoordinates};
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// ## Read WAN Coordinates
+/// This endpoint returns the WAN network coordinates for all Consul servers,
+/// organized by datacenter.
+///
+/// * Path: coordinate/datacenters
+/// * Method: GET
+/// * Response: [Vec<DatacenterCoordinates>]
+/// * Reference: https://www.consul.io/api-docs/coordinate#read-wan-coordinates
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "coordinate/datacenters",
+    response = "Vec<DatacenterCoordinates>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListDatacenterCoordinatesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+}
+
+/// ## Read LAN Coordinates for all nodes
+/// This endpoint returns the LAN network coordinates for all nodes in a given
+/// datacenter.
+///
+/// * Path: coordinate/nodes
+/// * Method: GET
+/// * Response: [Vec<CoordinateEntry>]
+/// * Reference: https://www.consul.io/api-docs/coordinate#read-lan-coordinates-for-all-nodes
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "coordinate/nodes",
+    response = "Vec<CoordinateEntry>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListNodeCoordinatesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub segment: Option<String>,
+}
+
+/// ## Read LAN Coordinates for a node
+/// This endpoint returns the LAN network coordinates for the given node.
+///
+/// * Path: coordinate/node/{self.node}
+/// * Method: GET
+/// * Response: [Vec<CoordinateEntry>]
+/// * Reference: https://www.consul.io/api-docs/coordinate#read-lan-coordinates-for-a-node
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "coordinate/node/{self.node}",
+    response = "Vec<CoordinateEntry>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadNodeCoordinatesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub node: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub segment: Option<String>,
+}
+
+/// ## Update LAN Coordinates for a node
+/// This endpoint updates the LAN network coordinates for a node in a given
+/// datacenter.
+///
+/// * Path: coordinate/update
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/coordinate#update-lan-coordinates-for-a-node
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(path = "coordinate/update", method = "PUT", builder = "true")]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdateNodeCoordinatesRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    #[serde(rename = "dc")]
+    pub dc: Option<String>,
+    pub coord: Coordinate,
+    pub node: String,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub segment: Option<String>,
+}
@@ -0,0 +1,114 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{collections::HashMap, fmt::Debug};
+
+use crate::api::{
+    config::common::{LoadBalancer, ServiceResolverSubset, ServiceRoute, ServiceSplit},
+    connect::common::MeshGatewayConfig,
+};
+
+/// A discovery chain compiled from the service's config entries.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CompiledDiscoveryChain {
+    pub customization_hash: Option<String>,
+    pub datacenter: String,
+    pub default: Option<bool>,
+    pub namespace: Option<String>,
+    pub nodes: Option<HashMap<String, DiscoveryGraphNode>>,
+    pub partition: Option<String>,
+    pub protocol: String,
+    pub service_name: String,
+    pub start_node: Option<String>,
+    pub targets: Option<HashMap<String, DiscoveryTarget>>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct DiscoveryFailover {
+    pub targets: Vec<String>,
+}
+
+/// A single node in the compiled chain, keyed by its ID in
+/// [CompiledDiscoveryChain::nodes].
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct DiscoveryGraphNode {
+    pub load_balancer: Option<LoadBalancer>,
+    pub name: String,
+    pub resolver: Option<DiscoveryResolver>,
+    pub routes: Option<Vec<DiscoveryRoute>>,
+    pub splits: Option<Vec<DiscoverySplit>>,
+    #[serde(rename = "Type")]
+    pub node_type: DiscoveryGraphNodeType,
+}
+
+/// The types of nodes found in a compiled discovery chain.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscoveryGraphNodeType {
+    #[default]
+    Resolver,
+    Router,
+    Splitter,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct DiscoveryResolver {
+    pub connect_timeout: Option<String>,
+    pub default: Option<bool>,
+    pub failover: Option<DiscoveryFailover>,
+    pub target: String,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct DiscoveryRoute {
+    pub definition: Option<ServiceRoute>,
+    pub next_node: String,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct DiscoverySplit {
+    pub definition: Option<ServiceSplit>,
+    pub next_node: String,
+    pub weight: f32,
+}
+
+/// A concrete upstream cluster a chain resolves to, keyed by its ID in
+/// [CompiledDiscoveryChain::targets].
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct DiscoveryTarget {
+    pub connect_timeout: Option<String>,
+    pub datacenter: String,
+    pub external: Option<bool>,
+    #[serde(rename = "ID")]
+    pub id: String,
+    pub mesh_gateway: Option<MeshGatewayConfig>,
+    pub name: Option<String>,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+    pub service: String,
+    pub service_subset: Option<String>,
+    #[serde(rename = "SNI")]
+    pub sni: Option<String>,
+    pub subset: Option<ServiceResolverSubset>,
+}
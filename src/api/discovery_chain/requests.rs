@@ -0,0 +1,37 @@
+use super::responses::DiscoveryChainResponse;
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// ## Read Compiled Discovery Chain
+/// This endpoint returns the compiled discovery chain for a service.
+///
+/// * Path: discovery-chain/{self.service}
+/// * Method: GET
+/// * Response: [DiscoveryChainResponse]
+/// * Reference: https://www.consul.io/api-docs/discovery-chain#read-compiled-discovery-chain
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "discovery-chain/{self.service}",
+    response = "DiscoveryChainResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct GetDiscoveryChainRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub service: String,
+    #[endpoint(query)]
+    #[serde(rename = "compile-dc")]
+    pub compile_dc: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
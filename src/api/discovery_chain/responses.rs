@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::CompiledDiscoveryChain;
+
+/// Response from executing
+/// [GetDiscoveryChainRequest][crate::api::discovery_chain::requests::GetDiscoveryChainRequest]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DiscoveryChainResponse {
+    pub chain: CompiledDiscoveryChain,
+}
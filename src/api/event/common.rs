@@ -0,0 +1,23 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::fmt::Debug;
+
+use crate::api::kv::common::Base64String;
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UserEvent {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "LTime")]
+    pub ltime: u64,
+    pub name: String,
+    pub node_filter: Option<String>,
+    pub payload: Option<Base64String>,
+    pub service_filter: Option<String>,
+    pub tag_filter: Option<String>,
+    pub version: Option<u64>,
+}
@@ -0,0 +1,61 @@
+use super::common::UserEvent;
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use std::fmt::Debug;
+
+/// ## Fire Event
+/// This endpoint triggers a new user event.
+///
+/// * Path: event/fire/{self.name}
+/// * Method: PUT
+/// * Response: [UserEvent]
+/// * Reference: https://www.consul.io/api-docs/event#fire-event
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "event/fire/{self.name}",
+    method = "PUT",
+    response = "UserEvent",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct FireEventRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(raw)]
+    pub payload: Vec<u8>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub node: Option<String>,
+    #[endpoint(query)]
+    pub service: Option<String>,
+    #[endpoint(query)]
+    pub tag: Option<String>,
+}
+
+/// ## List Events
+/// This endpoint returns the most recent events known by the agent.
+///
+/// * Path: event/list
+/// * Method: GET
+/// * Response: [Vec<UserEvent>]
+/// * Reference: https://www.consul.io/api-docs/event#list-events
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "event/list", response = "Vec<UserEvent>", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ListEventsRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub name: Option<String>,
+    #[endpoint(query)]
+    pub node: Option<String>,
+    #[endpoint(query)]
+    pub service: Option<String>,
+    #[endpoint(query)]
+    pub tag: Option<String>,
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, str::FromStr, time::Duration};
 
 use derive_builder::Builder;
 use http::{HeaderValue, Request};
@@ -14,6 +14,9 @@ use http::{HeaderValue, Request};
 /// error which will eventually make it back to the end-user.
 pub trait FeaturedEndpoint {
     fn features(&self) -> Option<Features>;
+
+    /// Replaces the [Features] configured on this endpoint.
+    fn set_features(&mut self, features: Option<Features>);
 }
 
 /// A set of features which can be applied to an endpoint request.
@@ -25,6 +28,10 @@ pub trait FeaturedEndpoint {
 ///   * [Filtering](https://www.consul.io/api-docs/features/filtering)
 ///   * [Caching](https://www.consul.io/api-docs/features/caching)
 ///
+/// Additionally, a `timeout`, set through the `request_timeout` method of a
+/// request builder, overrides the timeouts configured on the client for a
+/// single request, see [ConsulClientSettings][crate::client::ConsulClientSettings].
+///
 /// By default, all features are optional and must be individually configured
 /// in order for them to be applied to a request. Note that not all endpoints
 /// support all features or combination of features - this crate performs no
@@ -38,6 +45,8 @@ pub struct Features {
     pub cached: Option<String>,
     pub filter: Option<String>,
     pub mode: Option<ConsistencyMode>,
+    pub request_id: Option<String>,
+    pub timeout: Option<Duration>,
 }
 
 impl Features {
@@ -75,13 +84,10 @@ impl Features {
         }
 
         // Consistency Modes
-        if let Some(m) = &self.mode {
-            let mode = match m {
-                ConsistencyMode::CONSISTENT => "consistent",
-                ConsistencyMode::STALE => "stale",
-            };
-
-            keys.push(mode.into())
+        match &self.mode {
+            Some(ConsistencyMode::CONSISTENT) => keys.push("consistent".into()),
+            Some(ConsistencyMode::STALE) => keys.push("stale".into()),
+            Some(ConsistencyMode::DEFAULT) | None => {}
         }
 
         let mut url = url::Url::parse(request.uri().to_string().as_str()).unwrap();
@@ -113,9 +119,217 @@ pub struct Blocking {
     pub wait: Option<String>,
 }
 
+/// Configuration options for the Caching feature.
+///
+/// Setting these on a request through the `cached` method of its builder
+/// serves it from the agent cache. The options are sent as the
+/// `Cache-Control` header and are all optional, in which case the agent uses
+/// its defaults.
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(setter(strip_option, into), default)]
+pub struct CacheOptions {
+    /// The maximum age of a cached result before it's fetched again.
+    pub max_age: Option<Duration>,
+    /// Whether a cached result older than `max_age` must be fetched again
+    /// before being returned, even if fetching it fails.
+    pub must_revalidate: bool,
+    /// How long past `max_age` a cached result may still be returned when
+    /// fetching it again fails.
+    pub stale_if_error: Option<Duration>,
+}
+
+impl CacheOptions {
+    /// Returns a default instance of [CacheOptionsBuilder] for configuring
+    /// caching.
+    pub fn builder() -> CacheOptionsBuilder {
+        CacheOptionsBuilder::default()
+    }
+
+    /// Returns the value of the `Cache-Control` header for these options.
+    pub fn header(&self) -> String {
+        let mut directives = Vec::new();
+        if let Some(max_age) = self.max_age {
+            directives.push(format!("max-age={}", max_age.as_secs()));
+        }
+        if self.must_revalidate {
+            directives.push("must-revalidate".to_string());
+        }
+        if let Some(stale_if_error) = self.stale_if_error {
+            directives.push(format!("stale-if-error={}", stale_if_error.as_secs()));
+        }
+        directives.join(", ")
+    }
+}
+
 /// Configuration options for the Consistency Mode feature.
-#[derive(Debug, Clone)]
+///
+/// [ConsistencyMode::DEFAULT] adds nothing to the request, which makes it
+/// useful for overriding a client-wide mode set through
+/// [ConsulClientSettings][crate::client::ConsulClientSettings].
+#[derive(Debug, Clone, PartialEq)]
 pub enum ConsistencyMode {
     CONSISTENT,
+    DEFAULT,
     STALE,
 }
+
+/// A boolean expression for the Filtering feature.
+///
+/// Expressions are built from a [Selector] and combined with [Filter::and],
+/// [Filter::or] and [Filter::not], taking care of quoting values and
+/// grouping sub-expressions. A filter is set on a request through the
+/// `filter` method of its builder.
+///
+/// ```
+/// use consulrs::api::features::Filter;
+///
+/// let filter = Filter::selector("Meta.env")
+///     .eq("prod")
+///     .and(Filter::selector("Service.Tags").contains("v1").not());
+/// assert_eq!(
+///     filter.to_string(),
+///     r#"Meta.env == "prod" and not Service.Tags contains "v1""#
+/// );
+/// ```
+///
+/// See the [filtering](https://www.consul.io/api-docs/features/filtering)
+/// documentation for the selectors supported by each endpoint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    expression: String,
+    precedence: Precedence,
+}
+
+impl Filter {
+    /// Starts an expression matching on the given selector, e.g.
+    /// `Meta.env` or `Service.Tags`.
+    pub fn selector(selector: impl Into<String>) -> Selector {
+        Selector(selector.into())
+    }
+
+    /// Matches when both this and the given expression match.
+    pub fn and(self, other: Filter) -> Filter {
+        Filter::binary(self, "and", other, Precedence::And)
+    }
+
+    /// Matches when this expression doesn't match.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Filter {
+        Filter {
+            expression: format!("not {}", self.group(Precedence::Not)),
+            precedence: Precedence::Not,
+        }
+    }
+
+    /// Matches when either this or the given expression matches.
+    pub fn or(self, other: Filter) -> Filter {
+        Filter::binary(self, "or", other, Precedence::Or)
+    }
+
+    fn binary(left: Filter, op: &str, right: Filter, precedence: Precedence) -> Filter {
+        Filter {
+            expression: format!(
+                "{} {} {}",
+                left.group(precedence),
+                op,
+                right.group(precedence)
+            ),
+            precedence,
+        }
+    }
+
+    /// Returns the expression, in parentheses if it binds looser than an
+    /// operator of the given precedence.
+    fn group(self, precedence: Precedence) -> String {
+        if self.precedence < precedence {
+            format!("({})", self.expression)
+        } else {
+            self.expression
+        }
+    }
+}
+
+impl std::fmt::Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl From<Filter> for String {
+    fn from(filter: Filter) -> Self {
+        filter.expression
+    }
+}
+
+/// How tightly an expression binds, from loosest to tightest.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+enum Precedence {
+    Or,
+    And,
+    Not,
+    Match,
+}
+
+/// The selector of a [Filter] expression which is completed by one of its
+/// match operators.
+#[derive(Clone, Debug)]
+pub struct Selector(String);
+
+impl Selector {
+    /// Matches when the selected collection contains the given value.
+    pub fn contains(self, value: &str) -> Filter {
+        self.matching("contains", Some(value))
+    }
+
+    /// Matches when the selected value equals the given value.
+    pub fn eq(self, value: &str) -> Filter {
+        self.matching("==", Some(value))
+    }
+
+    /// Matches when the selected value or collection is empty.
+    pub fn is_empty(self) -> Filter {
+        self.matching("is empty", None)
+    }
+
+    /// Matches when the selected value or collection isn't empty.
+    pub fn is_not_empty(self) -> Filter {
+        self.matching("is not empty", None)
+    }
+
+    /// Matches when the selected value matches the given regular expression.
+    pub fn matches(self, regex: &str) -> Filter {
+        self.matching("matches", Some(regex))
+    }
+
+    /// Matches when the selected value doesn't equal the given value.
+    pub fn ne(self, value: &str) -> Filter {
+        self.matching("!=", Some(value))
+    }
+
+    /// Matches when the selected collection doesn't contain the given value.
+    pub fn not_contains(self, value: &str) -> Filter {
+        self.matching("not contains", Some(value))
+    }
+
+    /// Matches when the selected value doesn't match the given regular
+    /// expression.
+    pub fn not_matches(self, regex: &str) -> Filter {
+        self.matching("not matches", Some(regex))
+    }
+
+    fn matching(self, op: &str, value: Option<&str>) -> Filter {
+        let expression = match value {
+            Some(v) => format!("{} {} {}", self.0, op, quote(v)),
+            None => format!("{} {}", self.0, op),
+        };
+        Filter {
+            expression,
+            precedence: Precedence::Match,
+        }
+    }
+}
+
+/// Quotes a value for use in a filter expression.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
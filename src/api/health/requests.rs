@@ -0,0 +1,170 @@
+use super::common::ServiceEntry;
+use crate::api::{check::common::HealthCheck, Features};
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use std::fmt::Debug;
+
+/// ## List Checks for Node
+/// This endpoint returns the checks specific to the node provided on the path.
+///
+/// * Path: health/node/{self.node}
+/// * Method: GET
+/// * Response: [Vec<HealthCheck>]
+/// * Reference: https://www.consul.io/api-docs/health#list-checks-for-node
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "health/node/{self.node}",
+    response = "Vec<HealthCheck>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListNodeChecksRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub node: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub peer: Option<String>,
+}
+
+/// ## List Checks for Service
+/// This endpoint returns the checks associated with the service provided on
+/// the path.
+///
+/// * Path: health/checks/{self.service}
+/// * Method: GET
+/// * Response: [Vec<HealthCheck>]
+/// * Reference: https://www.consul.io/api-docs/health#list-checks-for-service
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "health/checks/{self.service}",
+    response = "Vec<HealthCheck>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListServiceChecksRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub service: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub near: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub peer: Option<String>,
+}
+
+/// ## List Nodes for Service
+/// This endpoint returns the service instances providing the service
+/// indicated on the path along with their node and health checks.
+///
+/// * Path: health/service/{self.service}
+/// * Method: GET
+/// * Response: [Vec<ServiceEntry>]
+/// * Reference: https://www.consul.io/api-docs/health#list-nodes-for-service
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "health/service/{self.service}",
+    response = "Vec<ServiceEntry>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListServiceNodesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub service: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub near: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub passing: Option<bool>,
+    #[endpoint(query)]
+    pub peer: Option<String>,
+    #[endpoint(query)]
+    pub tag: Option<String>,
+}
+
+/// ## List Service Instances for Connect
+/// This endpoint returns the Connect-capable instances of the service
+/// indicated on the path, i.e. its Connect-native instances and the sidecar
+/// proxies of its other instances, along with their node and health checks.
+///
+/// * Path: health/connect/{self.service}
+/// * Method: GET
+/// * Response: [Vec<ServiceEntry>]
+/// * Reference: https://www.consul.io/api-docs/health#list-service-instances-for-connect-enabled-service
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "health/connect/{self.service}",
+    response = "Vec<ServiceEntry>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListConnectNodesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub service: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub near: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub passing: Option<bool>,
+    #[endpoint(query)]
+    pub peer: Option<String>,
+    #[endpoint(query)]
+    pub tag: Option<String>,
+}
+
+/// ## List Checks in State
+/// This endpoint returns the checks in the state provided on the path. The
+/// state may be one of "any", "passing", "warning", or "critical".
+///
+/// * Path: health/state/{self.state}
+/// * Method: GET
+/// * Response: [Vec<HealthCheck>]
+/// * Reference: https://www.consul.io/api-docs/health#list-checks-in-state
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "health/state/{self.state}",
+    response = "Vec<HealthCheck>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListChecksInStateRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub state: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub near: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
@@ -0,0 +1,106 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{collections::HashMap, fmt::Debug};
+
+use crate::api::{check::common::HealthCheck, service::common::AgentService};
+
+/// The addresses and linked services of a gateway.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct GatewaySummary {
+    pub addresses: Option<Vec<String>>,
+    pub associated_service_count: Option<u64>,
+}
+
+/// Whether the intentions allow traffic between two services in a topology.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct IntentionDecision {
+    pub allowed: Option<bool>,
+    pub default_allow: Option<bool>,
+    pub external_source: Option<String>,
+    pub has_exact: Option<bool>,
+    pub has_permissions: Option<bool>,
+}
+
+/// A node along with its services and checks, as listed in the UI.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct NodeSummary {
+    pub address: Option<String>,
+    pub checks: Option<Vec<HealthCheck>>,
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    pub meta: Option<HashMap<String, String>>,
+    pub node: String,
+    pub partition: Option<String>,
+    pub peer_name: Option<String>,
+    pub services: Option<Vec<AgentService>>,
+    pub tagged_addresses: Option<HashMap<String, String>>,
+}
+
+/// A service aggregated over all of its instances, as listed in the UI.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceSummary {
+    pub checks_critical: Option<u64>,
+    pub checks_passing: Option<u64>,
+    pub checks_warning: Option<u64>,
+    pub connect_native: Option<bool>,
+    pub connected_with_gateway: Option<bool>,
+    pub connected_with_proxy: Option<bool>,
+    pub datacenter: Option<String>,
+    pub external_sources: Option<Vec<String>>,
+    pub gateway_config: Option<GatewaySummary>,
+    pub instance_count: Option<u64>,
+    pub kind: Option<String>,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub nodes: Option<Vec<String>>,
+    pub partition: Option<String>,
+    pub peer_name: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub transparent_proxy: Option<bool>,
+}
+
+/// The upstreams and downstreams of a service in the service mesh.
+///
+/// The sources map the names of the upstreams and downstreams to where their
+/// relationship with the service comes from, such as `specific-intention` or
+/// `routing-config`.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceTopology {
+    pub downstream_sources: Option<HashMap<String, String>>,
+    pub downstreams: Option<Vec<TopologySummary>>,
+    #[serde(rename = "FilteredByACLs")]
+    pub filtered_by_acls: Option<bool>,
+    pub protocol: Option<String>,
+    pub transparent_proxy: Option<bool>,
+    pub upstream_sources: Option<HashMap<String, String>>,
+    pub upstreams: Option<Vec<TopologySummary>>,
+}
+
+/// An upstream or downstream of a service along with the intentions between
+/// the two.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct TopologySummary {
+    pub intention: Option<IntentionDecision>,
+    pub source: Option<String>,
+    #[serde(flatten)]
+    pub summary: ServiceSummary,
+}
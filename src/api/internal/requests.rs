@@ -0,0 +1,96 @@
+use super::common::{NodeSummary, ServiceSummary, ServiceTopology};
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use std::fmt::Debug;
+
+/// ## List Nodes for the UI
+/// This endpoint returns the nodes in a datacenter along with their services
+/// and checks. It is an internal endpoint used by the Consul UI and may change
+/// between releases.
+///
+/// * Path: internal/ui/nodes
+/// * Method: GET
+/// * Response: [Vec<NodeSummary>]
+/// * Reference: https://github.com/hashicorp/consul/blob/main/agent/ui_endpoint.go
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "internal/ui/nodes",
+    response = "Vec<NodeSummary>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListUiNodesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub peer: Option<String>,
+}
+
+/// ## List Services for the UI
+/// This endpoint returns a summary of each service in a datacenter, aggregated
+/// over its instances. It is an internal endpoint used by the Consul UI and may
+/// change between releases.
+///
+/// * Path: internal/ui/services
+/// * Method: GET
+/// * Response: [Vec<ServiceSummary>]
+/// * Reference: https://github.com/hashicorp/consul/blob/main/agent/ui_endpoint.go
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "internal/ui/services",
+    response = "Vec<ServiceSummary>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListUiServicesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub peer: Option<String>,
+}
+
+/// ## Read Service Topology
+/// This endpoint returns the upstreams and downstreams of a service in the
+/// service mesh. It is an internal endpoint used by the Consul UI and may
+/// change between releases.
+///
+/// The kind is empty for regular services and `ingress-gateway` for ingress
+/// gateways, and is always sent as the endpoint requires it.
+///
+/// * Path: internal/ui/service-topology/{self.service}
+/// * Method: GET
+/// * Response: [ServiceTopology]
+/// * Reference: https://github.com/hashicorp/consul/blob/main/agent/ui_endpoint.go
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "internal/ui/service-topology/{self.service}",
+    response = "ServiceTopology",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadServiceTopologyRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub service: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub kind: String,
+    #[endpoint(query)]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
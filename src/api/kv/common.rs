@@ -10,6 +10,12 @@ use crate::error::ClientError;
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Base64String(String);
 
+impl From<&[u8]> for Base64String {
+    fn from(bytes: &[u8]) -> Self {
+        Base64String(base64::encode(bytes))
+    }
+}
+
 impl TryInto<Vec<u8>> for Base64String {
     type Error = ClientError;
 
@@ -29,6 +35,111 @@ impl TryInto<String> for Base64String {
     }
 }
 
+/// The flag value marking a value as compressed with gzip.
+pub const GZIP_FLAG_VALUE: u64 = 0x1f8b_0800_6b76_677a;
+
+/// The flag value marking a value as compressed with zstd.
+pub const ZSTD_FLAG_VALUE: u64 = 0x28b5_2ffd_6b76_7a73;
+
+/// The algorithm used to compress a value before storing it.
+///
+/// A compressed value is marked by setting the flags of its key to the flag
+/// value of its algorithm, the same way the lock recipes mark their keys, so
+/// it can't carry flags of its own. Each algorithm is only available when its
+/// feature of the same name is enabled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Compression {
+    #[cfg(feature = "gzip")]
+    Gzip,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Compression {
+    /// The algorithms which are enabled.
+    const ENABLED: &'static [Compression] = &[
+        #[cfg(feature = "gzip")]
+        Compression::Gzip,
+        #[cfg(feature = "zstd")]
+        Compression::Zstd,
+    ];
+
+    /// Returns the enabled compression marked by the given flags, if any.
+    pub fn from_flags(flags: u64) -> Option<Compression> {
+        Compression::ENABLED
+            .iter()
+            .copied()
+            .find(|c| c.flags() == flags)
+    }
+
+    /// Returns the flag value marking a value as compressed with this
+    /// algorithm.
+    pub fn flags(self) -> u64 {
+        match self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => GZIP_FLAG_VALUE,
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => ZSTD_FLAG_VALUE,
+        }
+    }
+
+    /// Compresses the given bytes.
+    #[cfg_attr(not(any(feature = "gzip", feature = "zstd")), allow(unused_variables))]
+    pub fn compress(self, bytes: &[u8]) -> Result<Vec<u8>, ClientError> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                use std::io::Write;
+
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder
+                    .write_all(bytes)
+                    .and_then(|_| encoder.finish())
+                    .map_err(|e| ClientError::CompressionError { source: e })
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                zstd::encode_all(bytes, 0).map_err(|e| ClientError::CompressionError { source: e })
+            }
+        }
+    }
+
+    /// Decompresses the given bytes.
+    #[cfg_attr(not(any(feature = "gzip", feature = "zstd")), allow(unused_variables))]
+    pub fn decompress(self, bytes: &[u8]) -> Result<Vec<u8>, ClientError> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                use std::io::Read;
+
+                let mut decompressed = Vec::new();
+                flate2::read::GzDecoder::new(bytes)
+                    .read_to_end(&mut decompressed)
+                    .map(|_| decompressed)
+                    .map_err(|e| ClientError::CompressionError { source: e })
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                zstd::decode_all(bytes).map_err(|e| ClientError::CompressionError { source: e })
+            }
+        }
+    }
+}
+
+/// A typed view of the opaque 64-bit flags stored alongside every key.
+///
+/// Consul never interprets the flags, so applications are free to use them for
+/// their own metadata, such as a schema version or a content type marker. This
+/// trait is implemented for every type which converts to and from a [u64].
+///
+/// Note that keys written with a [Compression] have their flags set to
+/// [GZIP_FLAG_VALUE] or [ZSTD_FLAG_VALUE], so those two values should not be
+/// used as application flags.
+pub trait KvFlags: From<u64> + Into<u64> {}
+
+impl<T: From<u64> + Into<u64>> KvFlags for T {}
+
 #[skip_serializing_none]
 #[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -44,6 +155,55 @@ pub struct KVPair {
     pub value: Option<Base64String>,
 }
 
+impl KVPair {
+    /// Returns the flags of this key decoded into the given type.
+    pub fn flags_as<F: KvFlags>(&self) -> F {
+        F::from(self.flags)
+    }
+}
+
+/// The manifest stored at the key of a value which is split into chunks.
+///
+/// The chunks are stored under `<key>/<checksum>/<index>`, where the checksum
+/// is the hex encoded SHA-256 digest of the whole value.
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[builder(setter(into, strip_option), default)]
+pub struct LargeValueManifest {
+    pub checksum: String,
+    pub chunks: Vec<String>,
+    pub size: u64,
+}
+
+/// The keys changed by [kv::sync][crate::kv::sync], in sorted order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KVDiff {
+    pub created: Vec<String>,
+    pub deleted: Vec<String>,
+    pub updated: Vec<String>,
+}
+
+impl KVDiff {
+    /// Returns whether no keys were changed.
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.deleted.is_empty() && self.updated.is_empty()
+    }
+}
+
+/// A single key as written by `consul kv export` and read by `consul kv import`.
+///
+/// The fields are serialized in the same order as the CLI writes them.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[builder(setter(into, strip_option), default)]
+pub struct KVExportEntry {
+    pub key: String,
+    pub flags: u64,
+    pub value: Base64String,
+    pub namespace: Option<String>,
+    pub partition: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct GenericKVPair<T: DeserializeOwned> {
     pub create_index: u64,
@@ -55,3 +215,10 @@ pub struct GenericKVPair<T: DeserializeOwned> {
     pub session: Option<String>,
     pub value: T,
 }
+
+impl<T: DeserializeOwned> GenericKVPair<T> {
+    /// Returns the flags of this key decoded into the given type.
+    pub fn flags_as<F: KvFlags>(&self) -> F {
+        F::from(self.flags)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::api::Features;
 
-use super::common::KVPair;
+use super::common::{Compression, KVPair};
 use consulrs_derive::QueryEndpoint;
 use derive_builder::Builder;
 use rustify_derive::Endpoint;
@@ -14,7 +14,7 @@ use std::fmt::Debug;
 /// * Method: GET
 /// * Response: [ReadKeyResponse]
 /// * Reference: https://www.consul.io/api-docs/kv#read-key
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(path = "kv/{self.key}", response = "Vec<KVPair>", builder = "true")]
 #[builder(setter(into, strip_option), default)]
 pub struct ReadKeyRequest {
@@ -27,6 +27,8 @@ pub struct ReadKeyRequest {
     #[endpoint(query)]
     pub ns: Option<String>,
     #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
     pub recurse: Option<bool>,
 }
 
@@ -37,7 +39,7 @@ pub struct ReadKeyRequest {
 /// * Method: GET
 /// * Response: [ReadKeyResponse]
 /// * Reference: https://www.consul.io/api-docs/kv#read-key
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(path = "kv/{self.key}", response = "Vec<u8>", builder = "true")]
 #[builder(setter(into, strip_option), default)]
 pub struct ReadRawKeyRequest {
@@ -50,6 +52,8 @@ pub struct ReadRawKeyRequest {
     #[endpoint(query)]
     pub ns: Option<String>,
     #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
     #[builder(setter(skip), default = "true")]
     pub raw: bool,
 }
@@ -61,7 +65,7 @@ pub struct ReadRawKeyRequest {
 /// * Method: GET
 /// * Response: [Vec<String>]
 /// * Reference: https://www.consul.io/api-docs/kv#read-key
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(path = "kv/{self.key}", response = "Vec<String>", builder = "true")]
 #[builder(setter(into, strip_option), default)]
 pub struct ReadKeysRequest {
@@ -77,6 +81,8 @@ pub struct ReadKeysRequest {
     #[endpoint(query)]
     pub ns: Option<String>,
     #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
     pub raw: Option<bool>,
     #[endpoint(query)]
     pub recurse: Option<bool>,
@@ -91,7 +97,7 @@ pub struct ReadKeysRequest {
 /// * Method: PUT
 /// * Response: [bool]
 /// * Reference: https://www.consul.io/api-docs/kv#create-update-key
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "kv/{self.key}",
     method = "PUT",
@@ -100,6 +106,8 @@ pub struct ReadKeysRequest {
 )]
 #[builder(setter(into, strip_option), default)]
 pub struct SetKeyRequest {
+    #[endpoint(skip)]
+    pub compression: Option<Compression>,
     #[endpoint(skip)]
     pub features: Option<Features>,
     #[endpoint(skip)]
@@ -117,6 +125,8 @@ pub struct SetKeyRequest {
     #[endpoint(query)]
     pub ns: Option<String>,
     #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
     pub release: Option<String>,
 }
 
@@ -127,7 +137,7 @@ pub struct SetKeyRequest {
 /// * Method: DELETE
 /// * Response: [bool]
 /// * Reference: https://www.consul.io/api-docs/kv#delete-key
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "kv/{self.key}",
     method = "DELETE",
@@ -147,5 +157,7 @@ pub struct DeleteKeyRequest {
     #[endpoint(query)]
     pub ns: Option<String>,
     #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
     pub recurse: Option<bool>,
 }
@@ -0,0 +1,31 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{collections::HashMap, fmt::Debug};
+
+use crate::api::acl::common::ACLLink;
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct Namespace {
+    #[serde(rename = "ACLs")]
+    pub acls: Option<NamespaceACLConfig>,
+    pub create_index: Option<u64>,
+    pub deleted_at: Option<String>,
+    pub description: Option<String>,
+    pub meta: Option<HashMap<String, String>>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+}
+
+/// The policies and roles applied to every token created in a namespace.
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct NamespaceACLConfig {
+    pub policy_defaults: Option<Vec<ACLLink>>,
+    pub role_defaults: Option<Vec<ACLLink>>,
+}
@@ -0,0 +1,128 @@
+use super::common::{Namespace, NamespaceACLConfig};
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::{collections::HashMap, fmt::Debug};
+
+/// ## Create a Namespace
+/// This endpoint creates a new namespace.
+///
+/// * Path: namespace
+/// * Method: PUT
+/// * Response: [Namespace]
+/// * Reference: https://www.consul.io/api-docs/namespaces#create-a-namespace
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "namespace",
+    method = "PUT",
+    response = "Namespace",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CreateNamespaceRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[serde(rename = "ACLs")]
+    pub acls: Option<NamespaceACLConfig>,
+    pub description: Option<String>,
+    pub meta: Option<HashMap<String, String>>,
+    pub name: String,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+}
+
+/// ## Delete a Namespace
+/// This endpoint marks a namespace for deletion. Once marked, Consul will
+/// delete all the associated resources in the background.
+///
+/// * Path: namespace/{self.name}
+/// * Method: DELETE
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/namespaces#delete-a-namespace
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "namespace/{self.name}", method = "DELETE", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct DeleteNamespaceRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## List all Namespaces
+/// This endpoint lists all the namespaces which the token used for the
+/// request has access to.
+///
+/// * Path: namespaces
+/// * Method: GET
+/// * Response: [Vec<Namespace>]
+/// * Reference: https://www.consul.io/api-docs/namespaces#list-all-namespaces
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "namespaces", response = "Vec<Namespace>", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ListNamespacesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Read a Namespace
+/// This endpoint reads a namespace with the given name.
+///
+/// * Path: namespace/{self.name}
+/// * Method: GET
+/// * Response: [Namespace]
+/// * Reference: https://www.consul.io/api-docs/namespaces#read-a-namespace
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "namespace/{self.name}",
+    response = "Namespace",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadNamespaceRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Update a Namespace
+/// This endpoint updates an existing namespace, replacing its definition.
+///
+/// * Path: namespace/{self.name}
+/// * Method: PUT
+/// * Response: [Namespace]
+/// * Reference: https://www.consul.io/api-docs/namespaces#update-a-namespace
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "namespace/{self.name}",
+    method = "PUT",
+    response = "Namespace",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdateNamespaceRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[serde(rename = "ACLs")]
+    pub acls: Option<NamespaceACLConfig>,
+    pub description: Option<String>,
+    pub meta: Option<HashMap<String, String>>,
+    pub name: String,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+}
@@ -0,0 +1,213 @@
+use super::responses::{KeyringResponse, LicenseResponse, TransferLeaderResponse, UsageResponse};
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// ## Change Primary Gossip Encryption Key
+/// This endpoint changes the primary gossip encryption key. The key must
+/// already be installed before this operation can succeed.
+///
+/// * Path: operator/keyring
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/operator/keyring#change-primary-gossip-encryption-key
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(path = "operator/keyring", method = "PUT", builder = "true")]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ChangeKeyRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub key: String,
+    #[endpoint(query)]
+    #[serde(rename = "relay-factor")]
+    pub relay_factor: Option<u8>,
+}
+
+/// ## Getting the Consul License
+/// This endpoint gets information about the current license. This is only
+/// supported by Consul Enterprise.
+///
+/// * Path: operator/license
+/// * Method: GET
+/// * Response: [LicenseResponse]
+/// * Reference: https://www.consul.io/api-docs/operator/license#getting-the-consul-license
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "operator/license",
+    response = "LicenseResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct GetLicenseRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
+
+/// ## Getting Consul Usage Information
+/// This endpoint returns information about the number of services in the
+/// cluster.
+///
+/// * Path: operator/usage
+/// * Method: GET
+/// * Response: [UsageResponse]
+/// * Reference: https://www.consul.io/api-docs/operator/usage#getting-usage-information
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "operator/usage", response = "UsageResponse", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct GetUsageRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub global: Option<bool>,
+}
+
+/// ## Delete a Gossip Encryption Key
+/// This endpoint removes a gossip encryption key from the cluster. This
+/// operation may only be performed on keys which are not currently the
+/// primary key.
+///
+/// * Path: operator/keyring
+/// * Method: DELETE
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/operator/keyring#delete-a-gossip-encryption-key
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(path = "operator/keyring", method = "DELETE", builder = "true")]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct DeleteKeyRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub key: String,
+    #[endpoint(query)]
+    #[serde(rename = "relay-factor")]
+    pub relay_factor: Option<u8>,
+}
+
+/// ## Add a New Gossip Encryption Key
+/// This endpoint installs a new gossip encryption key into the cluster.
+///
+/// * Path: operator/keyring
+/// * Method: POST
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/operator/keyring#add-a-new-gossip-encryption-key
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(path = "operator/keyring", method = "POST", builder = "true")]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct InstallKeyRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub key: String,
+    #[endpoint(query)]
+    #[serde(rename = "relay-factor")]
+    pub relay_factor: Option<u8>,
+}
+
+/// ## Updating the Consul License
+/// This endpoint updates the license used by the cluster. This is only
+/// supported by Consul Enterprise.
+///
+/// * Path: operator/license
+/// * Method: PUT
+/// * Response: [LicenseResponse]
+/// * Reference: https://www.consul.io/api-docs/operator/license#updating-the-consul-license
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "operator/license",
+    method = "PUT",
+    response = "LicenseResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct PutLicenseRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(raw)]
+    pub license: Vec<u8>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
+
+/// ## Resetting the Consul License
+/// This endpoint resets the license to the license the cluster was started
+/// with. This is only supported by Consul Enterprise.
+///
+/// * Path: operator/license
+/// * Method: DELETE
+/// * Response: [LicenseResponse]
+/// * Reference: https://www.consul.io/api-docs/operator/license#resetting-the-consul-license
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "operator/license",
+    method = "DELETE",
+    response = "LicenseResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ResetLicenseRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
+
+/// ## List Gossip Encryption Keys
+/// This endpoint lists the gossip encryption keys installed on both the WAN
+/// and LAN rings of every known datacenter.
+///
+/// * Path: operator/keyring
+/// * Method: GET
+/// * Response: [Vec<KeyringResponse>]
+/// * Reference: https://www.consul.io/api-docs/operator/keyring#list-gossip-encryption-keys
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "operator/keyring",
+    response = "Vec<KeyringResponse>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListKeysRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    #[serde(rename = "local-only")]
+    pub local_only: Option<bool>,
+    #[endpoint(query)]
+    #[serde(rename = "relay-factor")]
+    pub relay_factor: Option<u8>,
+}
+
+/// ## Transfer Raft Leadership
+/// This endpoint transfers Raft leadership from the current leader to another
+/// server, picked by Raft unless an ID is given.
+///
+/// * Path: operator/raft/transfer-leader
+/// * Method: POST
+/// * Response: [TransferLeaderResponse]
+/// * Reference: https://www.consul.io/api-docs/operator/raft#transfer-raft-leadership
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "operator/raft/transfer-leader",
+    method = "POST",
+    response = "TransferLeaderResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct TransferLeaderRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub id: Option<String>,
+}
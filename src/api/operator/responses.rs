@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The license fields use snake case, unlike the rest of the Consul API.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct License {
+    pub customer_id: String,
+    pub expiration_time: String,
+    pub features: Vec<String>,
+    pub flags: HashMap<String, serde_json::Value>,
+    pub installation_id: String,
+    pub issue_time: String,
+    pub license_id: String,
+    pub modules: Vec<String>,
+    pub product: String,
+    pub start_time: String,
+    pub termination_time: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct KeyringResponse {
+    pub datacenter: String,
+    pub keys: HashMap<String, u64>,
+    pub messages: Option<HashMap<String, String>>,
+    pub num_nodes: u64,
+    pub primary_keys: Option<HashMap<String, u64>>,
+    pub segment: Option<String>,
+    #[serde(rename = "WAN")]
+    pub wan: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LicenseResponse {
+    pub license: License,
+    pub valid: bool,
+    pub warnings: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ServiceUsage {
+    pub billable_service_instances: Option<u64>,
+    pub connect_service_instances: Option<HashMap<String, u64>>,
+    pub service_instances: u64,
+    pub services: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TransferLeaderResponse {
+    pub success: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UsageResponse {
+    pub usage: HashMap<String, ServiceUsage>,
+}
@@ -0,0 +1,16 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::fmt::Debug;
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct Partition {
+    pub create_index: Option<u64>,
+    pub deleted_at: Option<String>,
+    pub description: Option<String>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+}
@@ -0,0 +1,109 @@
+use super::common::Partition;
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// ## Create a Partition
+/// This endpoint creates a new partition.
+///
+/// * Path: partition
+/// * Method: PUT
+/// * Response: [Partition]
+/// * Reference: https://www.consul.io/api-docs/admin-partitions#create-a-partition
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "partition",
+    method = "PUT",
+    response = "Partition",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CreatePartitionRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub description: Option<String>,
+    pub name: String,
+}
+
+/// ## Delete a Partition
+/// This endpoint marks a partition for deletion. Once marked, Consul will
+/// delete all the associated resources in the background.
+///
+/// * Path: partition/{self.name}
+/// * Method: DELETE
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/admin-partitions#delete-a-partition
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "partition/{self.name}", method = "DELETE", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct DeletePartitionRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+}
+
+/// ## List all Partitions
+/// This endpoint lists all the partitions.
+///
+/// * Path: partitions
+/// * Method: GET
+/// * Response: [Vec<Partition>]
+/// * Reference: https://www.consul.io/api-docs/admin-partitions#list-all-partitions
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "partitions", response = "Vec<Partition>", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ListPartitionsRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+}
+
+/// ## Read a Partition
+/// This endpoint reads a partition with the given name.
+///
+/// * Path: partition/{self.name}
+/// * Method: GET
+/// * Response: [Partition]
+/// * Reference: https://www.consul.io/api-docs/admin-partitions#read-a-partition
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "partition/{self.name}",
+    response = "Partition",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadPartitionRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+}
+
+/// ## Update a Partition
+/// This endpoint updates the description of an existing partition.
+///
+/// * Path: partition/{self.name}
+/// * Method: PUT
+/// * Response: [Partition]
+/// * Reference: https://www.consul.io/api-docs/admin-partitions#update-a-partition
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "partition/{self.name}",
+    method = "PUT",
+    response = "Partition",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdatePartitionRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub description: Option<String>,
+    pub name: String,
+}
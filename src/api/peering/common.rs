@@ -0,0 +1,63 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{collections::HashMap, fmt::Debug};
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct Peering {
+    pub create_index: Option<u64>,
+    pub deleted_at: Option<String>,
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    pub meta: Option<HashMap<String, String>>,
+    pub modify_index: Option<u64>,
+    pub name: String,
+    pub partition: Option<String>,
+    #[serde(rename = "PeerCAPems")]
+    pub peer_ca_pems: Option<Vec<String>>,
+    #[serde(rename = "PeerID")]
+    pub peer_id: Option<String>,
+    pub peer_server_addresses: Option<Vec<String>>,
+    pub peer_server_name: Option<String>,
+    pub remote: Option<PeeringRemoteInfo>,
+    pub state: Option<PeeringState>,
+    pub stream_status: Option<PeeringStreamStatus>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct PeeringRemoteInfo {
+    pub datacenter: Option<String>,
+    pub partition: Option<String>,
+}
+
+/// The state of a peering connection.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PeeringState {
+    Active,
+    Deleting,
+    Establishing,
+    Failing,
+    Pending,
+    Terminated,
+    #[serde(other)]
+    Undefined,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct PeeringStreamStatus {
+    pub exported_services: Option<Vec<String>>,
+    pub imported_services: Option<Vec<String>>,
+    pub last_heartbeat: Option<String>,
+    pub last_receive: Option<String>,
+    pub last_send: Option<String>,
+}
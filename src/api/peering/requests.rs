@@ -0,0 +1,112 @@
+use super::{common::Peering, responses::GenerateTokenResponse};
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::{collections::HashMap, fmt::Debug};
+
+/// ## Delete a Peering Connection
+/// This endpoint marks a peering connection for deletion. Once marked,
+/// Consul will delete all the data imported from the peer in the background.
+///
+/// * Path: peering/{self.name}
+/// * Method: DELETE
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/peering#delete-a-peering-connection
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "peering/{self.name}", method = "DELETE", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct DeletePeeringRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Establish a Peering Connection
+/// This endpoint establishes a peering connection with a peer cluster using
+/// a token generated by that cluster.
+///
+/// * Path: peering/establish
+/// * Method: POST
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/peering#establish-a-peering-connection
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(path = "peering/establish", method = "POST", builder = "true")]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct EstablishPeeringRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub meta: Option<HashMap<String, String>>,
+    pub partition: Option<String>,
+    pub peer_name: String,
+    pub peering_token: String,
+}
+
+/// ## Generate a Peering Token
+/// This endpoint generates a peering token which a peer cluster can use to
+/// establish a peering connection.
+///
+/// * Path: peering/token
+/// * Method: POST
+/// * Response: [GenerateTokenResponse]
+/// * Reference: https://www.consul.io/api-docs/peering#generate-a-peering-token
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "peering/token",
+    method = "POST",
+    response = "GenerateTokenResponse",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct GenerateTokenRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    pub meta: Option<HashMap<String, String>>,
+    pub partition: Option<String>,
+    pub peer_name: String,
+    pub server_external_addresses: Option<Vec<String>>,
+}
+
+/// ## List all Peerings
+/// This endpoint lists all the peering connections.
+///
+/// * Path: peerings
+/// * Method: GET
+/// * Response: [Vec<Peering>]
+/// * Reference: https://www.consul.io/api-docs/peering#list-all-peerings
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "peerings", response = "Vec<Peering>", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ListPeeringsRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
+
+/// ## Read a Peering Connection
+/// This endpoint reads a peering connection with the given name.
+///
+/// * Path: peering/{self.name}
+/// * Method: GET
+/// * Response: [Peering]
+/// * Reference: https://www.consul.io/api-docs/peering#read-a-peering-connection
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "peering/{self.name}", response = "Peering", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadPeeringRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub name: String,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+}
@@ -0,0 +1,67 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::{collections::HashMap, fmt::Debug};
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct PreparedQueryDefinition {
+    #[serde(rename = "DNS")]
+    pub dns: Option<QueryDNSOptions>,
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub service: Option<ServiceQuery>,
+    pub session: Option<String>,
+    pub template: Option<QueryTemplateOptions>,
+    pub token: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct QueryDNSOptions {
+    #[serde(rename = "TTL")]
+    pub ttl: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct QueryFailoverOptions {
+    pub datacenters: Option<Vec<String>>,
+    pub nearest_n: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct QueryTemplateOptions {
+    pub regexp: Option<String>,
+    pub remove_empty_tags: Option<bool>,
+    #[serde(rename = "Type")]
+    pub ty: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceQuery {
+    pub connect: Option<bool>,
+    pub failover: Option<QueryFailoverOptions>,
+    #[serde(rename = "IgnoreCheckIDs")]
+    pub ignore_check_ids: Option<Vec<String>>,
+    pub namespace: Option<String>,
+    pub near: Option<String>,
+    pub node_meta: Option<HashMap<String, String>>,
+    pub only_passing: Option<bool>,
+    pub service: String,
+    pub service_meta: Option<HashMap<String, String>>,
+    pub tags: Option<Vec<String>>,
+}
@@ -0,0 +1,190 @@
+use super::{
+    common::{PreparedQueryDefinition, QueryDNSOptions, QueryTemplateOptions, ServiceQuery},
+    responses::{CreateQueryResponse, ExecuteQueryResponse, ExplainQueryResponse},
+};
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// ## Create Prepared Query
+/// This endpoint creates a new prepared query.
+///
+/// * Path: query
+/// * Method: POST
+/// * Response: [CreateQueryResponse]
+/// * Reference: https://www.consul.io/api-docs/query#create-prepared-query
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(
+    path = "query",
+    method = "POST",
+    response = "CreateQueryResponse",
+    builder = "true"
+)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CreateQueryRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    #[serde(rename = "dc")]
+    pub dc: Option<String>,
+    #[serde(rename = "DNS")]
+    pub dns: Option<QueryDNSOptions>,
+    pub name: Option<String>,
+    pub service: ServiceQuery,
+    pub session: Option<String>,
+    pub template: Option<QueryTemplateOptions>,
+    pub token: Option<String>,
+}
+
+/// ## Delete Prepared Query
+/// This endpoint deletes an existing prepared query.
+///
+/// * Path: query/{self.uuid}
+/// * Method: DELETE
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/query#delete-prepared-query
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "query/{self.uuid}", method = "DELETE", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct DeleteQueryRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub uuid: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
+
+/// ## Execute Prepared Query
+/// This endpoint executes an existing prepared query. The query may be
+/// referenced by its ID or name.
+///
+/// * Path: query/{self.query}/execute
+/// * Method: GET
+/// * Response: [ExecuteQueryResponse]
+/// * Reference: https://www.consul.io/api-docs/query#execute-prepared-query
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "query/{self.query}/execute",
+    response = "ExecuteQueryResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ExecuteQueryRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub query: String,
+    #[endpoint(query)]
+    pub connect: Option<bool>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    pub limit: Option<u64>,
+    #[endpoint(query)]
+    pub near: Option<String>,
+}
+
+/// ## Explain Prepared Query
+/// This endpoint generates a fully-rendered query for the given ID or name,
+/// which is useful for debugging templates.
+///
+/// * Path: query/{self.query}/explain
+/// * Method: GET
+/// * Response: [ExplainQueryResponse]
+/// * Reference: https://www.consul.io/api-docs/query#explain-prepared-query
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "query/{self.query}/explain",
+    response = "ExplainQueryResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ExplainQueryRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub query: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
+
+/// ## List Prepared Queries
+/// This endpoint returns a list of all prepared queries.
+///
+/// * Path: query
+/// * Method: GET
+/// * Response: [Vec<PreparedQueryDefinition>]
+/// * Reference: https://www.consul.io/api-docs/query#read-prepared-query-1
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "query",
+    response = "Vec<PreparedQueryDefinition>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListQueriesRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
+
+/// ## Read Prepared Query
+/// This endpoint reads a specific prepared query.
+///
+/// * Path: query/{self.uuid}
+/// * Method: GET
+/// * Response: [Vec<PreparedQueryDefinition>]
+/// * Reference: https://www.consul.io/api-docs/query#read-prepared-query
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "query/{self.uuid}",
+    response = "Vec<PreparedQueryDefinition>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadQueryRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    pub uuid: String,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
+
+/// ## Update Prepared Query
+/// This endpoint updates an existing prepared query, replacing its entire
+/// definition.
+///
+/// * Path: query/{self.uuid}
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: https://www.consul.io/api-docs/query#update-prepared-query
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[endpoint(path = "query/{self.uuid}", method = "PUT", builder = "true")]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct UpdateQueryRequest {
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub features: Option<Features>,
+    #[endpoint(skip)]
+    #[serde(skip)]
+    pub uuid: String,
+    #[endpoint(query)]
+    #[serde(rename = "dc")]
+    pub dc: Option<String>,
+    #[serde(rename = "DNS")]
+    pub dns: Option<QueryDNSOptions>,
+    pub name: Option<String>,
+    pub service: ServiceQuery,
+    pub session: Option<String>,
+    pub template: Option<QueryTemplateOptions>,
+    pub token: Option<String>,
+}
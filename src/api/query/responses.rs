@@ -0,0 +1,27 @@
+use super::common::{PreparedQueryDefinition, QueryDNSOptions};
+use crate::api::health::common::ServiceEntry;
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CreateQueryResponse {
+    #[serde(rename = "ID")]
+    pub id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ExecuteQueryResponse {
+    pub datacenter: String,
+    #[serde(rename = "DNS")]
+    pub dns: QueryDNSOptions,
+    pub failovers: u64,
+    pub namespace: Option<String>,
+    pub nodes: Vec<ServiceEntry>,
+    pub service: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ExplainQueryResponse {
+    pub query: PreparedQueryDefinition,
+}
@@ -97,10 +97,11 @@ pub struct AgentServiceRegistration {
     pub kind: Option<String>,
     pub meta: Option<HashMap<String, String>>,
     pub name: Option<String>,
-    pub ns: Option<String>,
+    pub namespace: Option<String>,
     pub port: Option<u64>,
     pub proxy: Option<AgentServiceConnectProxy>,
-    pub tagged_addresses: Option<HashMap<String, String>>,
+    pub socket_path: Option<String>,
+    pub tagged_addresses: Option<HashMap<String, AgentServiceAddress>>,
     pub tags: Option<Vec<String>>,
     pub weights: Option<AgentWeights>,
 }
@@ -1,6 +1,6 @@
 use super::common::{
     AgentService, AgentServiceAddress, AgentServiceChecksInfo, AgentServiceConnect,
-    AgentServiceConnectProxy, AgentWeights,
+    AgentServiceConnectProxy, AgentServiceRegistration, AgentWeights,
 };
 use crate::api::{check::common::AgentServiceCheck, Features};
 use consulrs_derive::QueryEndpoint;
@@ -17,7 +17,7 @@ use std::{collections::HashMap, fmt::Debug};
 /// * Method: GET
 /// * Response: [HashMap<String, AgentService>]
 /// * Reference: https://www.consul.io/api-docs/agent/service#list-services
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/services",
     response = "HashMap<String, AgentService>",
@@ -29,17 +29,21 @@ pub struct ListServicesRequest {
     pub features: Option<Features>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## Get Service Configuration
 /// This endpoint returns the full service definition for a single service
-/// instance registered on the local agent.
+/// instance registered on the local agent. Setting `hash` to the content hash
+/// of a previous response blocks until the definition changes or `wait` is
+/// reached.
 ///
 /// * Path: agent/service/{self.name}
 /// * Method: GET
 /// * Response: [AgentService]
 /// * Reference: https://www.consul.io/api-docs/agent/service#get-service-configuration
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/service/{self.name}",
     response = "AgentService",
@@ -52,7 +56,13 @@ pub struct ReadServiceRequest {
     #[endpoint(skip)]
     pub name: String,
     #[endpoint(query)]
+    pub hash: Option<String>,
+    #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub wait: Option<String>,
 }
 
 /// ## Get local service health
@@ -62,7 +72,7 @@ pub struct ReadServiceRequest {
 /// * Method: GET
 /// * Response: [Vec<AgentServiceChecksInfo>]
 /// * Reference: https://www.consul.io/api-docs/agent/service#get-local-service-health
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/health/service/name/{self.name}",
     response = "Vec<AgentServiceChecksInfo>",
@@ -76,6 +86,8 @@ pub struct ServiceHealthRequest {
     pub name: String,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## Get local service health by ID
@@ -85,7 +97,7 @@ pub struct ServiceHealthRequest {
 /// * Method: GET
 /// * Response: [Vec<AgentServiceChecksInfo>]
 /// * Reference: https://www.consul.io/api-docs/agent/service#get-local-service-health-by-its-id
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/health/service/id/{self.id}",
     response = "Vec<AgentServiceChecksInfo>",
@@ -99,6 +111,8 @@ pub struct ServiceHealthByIdRequest {
     pub id: String,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## Register Service
@@ -109,6 +123,9 @@ pub struct ServiceHealthByIdRequest {
 /// * Method: PUT
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/agent/service#register-service
+///
+/// A builder can be created from a complete [AgentServiceRegistration] (i.e.
+/// one deserialized from a service definition file) using [From].
 #[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
 #[endpoint(path = "agent/service/register", method = "PUT", builder = "true")]
 #[serde(rename_all = "PascalCase")]
@@ -127,14 +144,51 @@ pub struct RegisterServiceRequest {
     pub kind: Option<String>,
     pub meta: Option<HashMap<String, String>>,
     pub name: Option<String>,
+    pub namespace: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
     pub port: Option<u64>,
     pub proxy: Option<AgentServiceConnectProxy>,
+    #[endpoint(query)]
+    #[serde(rename = "replace-existing-checks")]
+    pub replace_existing_checks: Option<bool>,
+    pub socket_path: Option<String>,
     pub tagged_addresses: Option<HashMap<String, AgentServiceAddress>>,
     pub tags: Option<Vec<String>>,
     pub weights: Option<AgentWeights>,
 }
 
+impl From<AgentServiceRegistration> for RegisterServiceRequestBuilder {
+    fn from(registration: AgentServiceRegistration) -> Self {
+        RegisterServiceRequestBuilder {
+            features: None,
+            address: Some(registration.address),
+            check: Some(registration.check),
+            checks: Some(registration.checks),
+            connect: Some(registration.connect),
+            enable_tag_override: Some(registration.enable_tag_override),
+            id: Some(registration.id),
+            kind: Some(registration.kind),
+            meta: Some(registration.meta),
+            name: Some(registration.name),
+            namespace: Some(registration.namespace),
+            ns: None,
+            partition: None,
+            port: Some(registration.port),
+            proxy: Some(registration.proxy),
+            replace_existing_checks: None,
+            socket_path: Some(registration.socket_path),
+            tagged_addresses: Some(registration.tagged_addresses),
+            tags: Some(registration.tags),
+            weights: Some(registration.weights),
+        }
+    }
+}
+
 /// ## Deregister Service
 /// This endpoint removes a service from the local agent.
 ///
@@ -142,7 +196,7 @@ pub struct RegisterServiceRequest {
 /// * Method: PUT
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/agent/service#deregister-service
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/service/deregister/{self.id}",
     method = "PUT",
@@ -156,6 +210,8 @@ pub struct DeregisterServiceRequest {
     pub id: String,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## Enable Maintenance Mode
@@ -165,7 +221,7 @@ pub struct DeregisterServiceRequest {
 /// * Method: PUT
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/agent/service#enable-maintenance-mode
-#[derive(Builder, Debug, Default, Endpoint, QueryEndpoint)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(
     path = "agent/service/maintenance/{self.id}",
     method = "PUT",
@@ -181,4 +237,8 @@ pub struct EnableMaintenanceRequest {
     pub enable: bool,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
+    #[endpoint(query)]
+    pub reason: Option<String>,
 }
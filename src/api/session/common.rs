@@ -17,7 +17,7 @@ pub struct ServiceCheck {
 #[serde(rename_all = "PascalCase")]
 #[builder(setter(into, strip_option), default)]
 pub struct SessionEntry {
-    pub behavior: Option<String>,
+    pub behavior: Option<SessionBehavior>,
     pub create_index: Option<u64>,
     #[serde(rename = "ID")]
     pub id: Option<String>,
@@ -30,3 +30,11 @@ pub struct SessionEntry {
     #[serde(rename = "TTL")]
     pub ttl: Option<String>,
 }
+
+/// The action taken on locks held by a session when it's invalidated.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionBehavior {
+    Delete,
+    Release,
+}
@@ -1,5 +1,5 @@
 use super::{
-    common::{ServiceCheck, SessionEntry},
+    common::{ServiceCheck, SessionBehavior, SessionEntry},
     responses::CreateSessionResponse,
 };
 use crate::api::Features;
@@ -29,7 +29,16 @@ pub struct CreateSessionRequest {
     #[endpoint(skip)]
     #[serde(skip)]
     pub features: Option<Features>,
-    pub behavior: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "dc")]
+    pub dc: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "ns")]
+    pub ns: Option<String>,
+    #[endpoint(query)]
+    #[serde(rename = "partition")]
+    pub partition: Option<String>,
+    pub behavior: Option<SessionBehavior>,
     pub create_index: Option<u64>,
     #[serde(rename = "ID")]
     pub id: Option<String>,
@@ -52,7 +61,6 @@ pub struct CreateSessionRequest {
 /// * Reference: https://www.consul.io/api-docs/session#delete-session
 #[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
 #[endpoint(path = "session/destroy/{self.uuid}", method = "PUT", builder = "true")]
-#[serde(rename_all = "PascalCase")]
 #[builder(setter(into, strip_option), default)]
 pub struct DeleteSessionRequest {
     #[endpoint(skip)]
@@ -64,6 +72,8 @@ pub struct DeleteSessionRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## Read Session
@@ -79,7 +89,6 @@ pub struct DeleteSessionRequest {
     response = "Vec<SessionEntry>",
     builder = "true"
 )]
-#[serde(rename_all = "PascalCase")]
 #[builder(setter(into, strip_option), default)]
 pub struct ReadSessionRequest {
     #[endpoint(skip)]
@@ -91,6 +100,8 @@ pub struct ReadSessionRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## List Sessions for Node
@@ -106,7 +117,6 @@ pub struct ReadSessionRequest {
     response = "Vec<SessionEntry>",
     builder = "true"
 )]
-#[serde(rename_all = "PascalCase")]
 #[builder(setter(into, strip_option), default)]
 pub struct ListNodeSessionsRequest {
     #[endpoint(skip)]
@@ -118,6 +128,8 @@ pub struct ListNodeSessionsRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## List Sessions
@@ -133,7 +145,6 @@ pub struct ListNodeSessionsRequest {
     response = "Vec<SessionEntry>",
     builder = "true"
 )]
-#[serde(rename_all = "PascalCase")]
 #[builder(setter(into, strip_option), default)]
 pub struct ListSessionsRequest {
     #[endpoint(skip)]
@@ -143,6 +154,8 @@ pub struct ListSessionsRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
 
 /// ## Renew Session
@@ -159,7 +172,6 @@ pub struct ListSessionsRequest {
     response = "Vec<SessionEntry>",
     builder = "true"
 )]
-#[serde(rename_all = "PascalCase")]
 #[builder(setter(into, strip_option), default)]
 pub struct RenewSessionRequest {
     #[endpoint(skip)]
@@ -171,4 +183,6 @@ pub struct RenewSessionRequest {
     pub dc: Option<String>,
     #[endpoint(query)]
     pub ns: Option<String>,
+    #[endpoint(query)]
+    pub partition: Option<String>,
 }
@@ -2,7 +2,6 @@ use crate::api::Features;
 use consulrs_derive::QueryEndpoint;
 use derive_builder::Builder;
 use rustify_derive::Endpoint;
-use serde::Serialize;
 use std::fmt::Debug;
 
 /// ## Generate Snapshot
@@ -13,13 +12,11 @@ use std::fmt::Debug;
 /// * Method: GET
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/snapshot#generate-snapshot
-#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(path = "snapshot", builder = "true")]
-#[serde(rename_all = "PascalCase")]
 #[builder(setter(into, strip_option), default)]
 pub struct GenerateSnapshotRequest {
     #[endpoint(skip)]
-    #[serde(skip)]
     pub features: Option<Features>,
     #[endpoint(query)]
     pub dc: Option<String>,
@@ -34,13 +31,11 @@ pub struct GenerateSnapshotRequest {
 /// * Method: PUT
 /// * Response: N/A
 /// * Reference: https://www.consul.io/api-docs/snapshot#restore-snapshot
-#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint, Serialize)]
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
 #[endpoint(path = "snapshot", method = "PUT", builder = "true")]
-#[serde(rename_all = "PascalCase")]
 #[builder(setter(into, strip_option), default)]
 pub struct RestoreSnapshotRequest {
     #[endpoint(skip)]
-    #[serde(skip)]
     pub features: Option<Features>,
     #[endpoint(raw)]
     pub data: Vec<u8>,
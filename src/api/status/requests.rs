@@ -0,0 +1,41 @@
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use std::fmt::Debug;
+
+/// ## Get Raft Leader
+/// This endpoint returns the Raft leader for the datacenter in which the
+/// agent is running.
+///
+/// * Path: status/leader
+/// * Method: GET
+/// * Response: [String]
+/// * Reference: https://www.consul.io/api-docs/status#get-raft-leader
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "status/leader", response = "String", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct GetLeaderRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
+
+/// ## List Raft Peers
+/// This endpoint retrieves the Raft peers for the datacenter in which the
+/// agent is running.
+///
+/// * Path: status/peers
+/// * Method: GET
+/// * Response: [Vec<String>]
+/// * Reference: https://www.consul.io/api-docs/status#list-raft-peers
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(path = "status/peers", response = "Vec<String>", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct ListPeersRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
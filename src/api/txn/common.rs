@@ -0,0 +1,109 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::api::{
+    catalog::common::Node,
+    check::common::HealthCheck,
+    kv::common::{Base64String, KVPair},
+    service::common::AgentService,
+};
+
+/// A single operation within a transaction.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum TxnOp {
+    #[serde(rename = "KV")]
+    KV(KVTxnOp),
+    Node(NodeTxnOp),
+    Service(Box<ServiceTxnOp>),
+    Check(Box<CheckTxnOp>),
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct KVTxnOp {
+    pub verb: KVTxnVerb,
+    pub key: String,
+    pub flags: Option<u64>,
+    pub index: Option<u64>,
+    pub namespace: Option<String>,
+    pub session: Option<String>,
+    pub value: Option<Base64String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KVTxnVerb {
+    Set,
+    Cas,
+    Lock,
+    Unlock,
+    #[default]
+    Get,
+    GetTree,
+    CheckIndex,
+    CheckSession,
+    CheckNotExists,
+    Delete,
+    DeleteTree,
+    DeleteCas,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct NodeTxnOp {
+    pub verb: TxnVerb,
+    pub node: Node,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct ServiceTxnOp {
+    pub verb: TxnVerb,
+    pub node: String,
+    pub service: AgentService,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[builder(setter(into, strip_option), default)]
+pub struct CheckTxnOp {
+    pub verb: TxnVerb,
+    pub check: HealthCheck,
+}
+
+/// The verbs available to node, service, and check operations.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TxnVerb {
+    Set,
+    Cas,
+    #[default]
+    Get,
+    Delete,
+    DeleteCas,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TxnError {
+    pub op_index: u64,
+    pub what: String,
+}
+
+/// The result of a single operation within a transaction.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum TxnResult {
+    #[serde(rename = "KV")]
+    KV(KVPair),
+    Node(Node),
+    Service(Box<AgentService>),
+    Check(Box<HealthCheck>),
+}
@@ -0,0 +1,31 @@
+use super::responses::TransactionResponse;
+use crate::api::Features;
+use consulrs_derive::QueryEndpoint;
+use derive_builder::Builder;
+use rustify_derive::Endpoint;
+use std::fmt::Debug;
+
+/// ## Create Transaction
+/// This endpoint permits submitting a list of operations to apply to Consul
+/// inside of a transaction.
+///
+/// * Path: txn
+/// * Method: PUT
+/// * Response: [TransactionResponse]
+/// * Reference: https://www.consul.io/api-docs/txn#create-transaction
+#[derive(Builder, Clone, Debug, Default, Endpoint, QueryEndpoint)]
+#[endpoint(
+    path = "txn",
+    method = "PUT",
+    response = "TransactionResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct TransactionRequest {
+    #[endpoint(skip)]
+    pub features: Option<Features>,
+    #[endpoint(raw)]
+    pub data: Vec<u8>,
+    #[endpoint(query)]
+    pub dc: Option<String>,
+}
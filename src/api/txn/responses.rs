@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+use super::common::{TxnError, TxnResult};
+
+/// Response from executing
+/// [TransactionRequest][crate::api::txn::requests::TransactionRequest]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct TransactionResponse {
+    pub errors: Option<Vec<TxnError>>,
+    pub results: Option<Vec<TxnResult>>,
+}
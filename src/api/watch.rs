@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use futures::stream::{self, Stream};
+use rustify::endpoint::Endpoint;
+use serde::Serialize;
+
+use crate::{
+    api::{blocking::BlockingQuery, features::FeaturedEndpoint, ApiResponse},
+    client::Client,
+    error::ClientError,
+};
+
+/// The default delay applied after the first failed request.
+pub const DEFAULT_MIN_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The default upper bound on the delay between failed requests.
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A typed watcher built on top of a [BlockingQuery].
+///
+/// Where a [BlockingQuery] yields every response it receives, a [Watcher]
+/// only yields a response when the watched result has actually changed. It
+/// follows the guidance given in the
+/// [Consul documentation](https://www.consul.io/api-docs/features/blocking#implementation-details):
+///
+/// * Responses with an unchanged index (i.e. the wait time was reached) are
+///   skipped.
+/// * If the index goes backwards (i.e. after a snapshot restore) it is reset
+///   to zero.
+/// * Responses whose content is identical to the last yielded response are
+///   skipped.
+/// * A 404 (i.e. a missing key or an empty prefix) is an empty result, which
+///   is blocked on using the index Consul returns with it like any other
+///   result. A key created later is yielded as soon as it exists.
+///
+/// Failed requests are yielded as errors and then retried with an exponential
+/// backoff between the configured minimum and maximum delays, which makes the
+/// stream suitable for long-running watches.
+pub struct Watcher<'a, C: Client, E: Endpoint + FeaturedEndpoint + Clone> {
+    query: BlockingQuery<'a, C, E>,
+    max_backoff: Duration,
+    min_backoff: Duration,
+}
+
+impl<'a, C, E> Watcher<'a, C, E>
+where
+    C: Client,
+    E: Endpoint + FeaturedEndpoint + Clone,
+    E::Response: Default + Serialize,
+{
+    /// Returns a new [Watcher] which watches the given [Endpoint].
+    pub fn new(client: &'a C, endpoint: E) -> Self {
+        Watcher {
+            query: BlockingQuery::new(client, endpoint),
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            min_backoff: DEFAULT_MIN_BACKOFF,
+        }
+    }
+
+    /// Sets the minimum and maximum delays used when backing off after a
+    /// failed request.
+    pub fn backoff(mut self, min: Duration, max: Duration) -> Self {
+        self.min_backoff = min;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Sets the maximum duration each underlying request is allowed to block
+    /// for (i.e. "5m").
+    pub fn wait(mut self, wait: &str) -> Self {
+        self.query = self.query.wait(wait);
+        self
+    }
+
+    /// Returns a [Stream] which yields the watched result each time it
+    /// changes. The first result is always yielded.
+    pub fn stream(self) -> impl Stream<Item = Result<ApiResponse<E::Response>, ClientError>> + 'a
+    where
+        E: 'a,
+    {
+        let state = WatchState {
+            watcher: self,
+            failures: 0,
+            index: 0,
+            last: None,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if state.failures > 0 {
+                    let delay = state.watcher.delay(state.failures);
+                    debug!("Backing off for {:?} before retrying watch", delay);
+                    tokio::time::sleep(delay).await;
+                }
+
+                let (res, next) = match state.watcher.query.next(state.index).await {
+                    Ok(r) => r,
+                    Err(e) if e.is_not_found() && e.index().is_some() => {
+                        let index = e.index().unwrap();
+                        let res = ApiResponse::builder()
+                            .index(index.to_string())
+                            .response(E::Response::default())
+                            .build()
+                            .unwrap();
+                        (res, index)
+                    }
+                    Err(e) => {
+                        state.failures += 1;
+                        return Some((Err(e), state));
+                    }
+                };
+                state.failures = 0;
+
+                // Skip responses which were the result of the wait time being
+                // reached.
+                let last = state.index;
+                if state.last.is_some() && next == last {
+                    continue;
+                }
+
+                // Reset the index if it goes backwards
+                state.index = if next < last {
+                    info!("Index went backwards ({} < {}), resetting", next, last);
+                    0
+                } else {
+                    next
+                };
+
+                // Skip responses which have changed index but not content
+                let content = serde_json::to_vec(&res.response)
+                    .map_err(|e| ClientError::JsonSerializeError { source: e });
+                let content = match content {
+                    Ok(c) => c,
+                    Err(e) => return Some((Err(e), state)),
+                };
+                if state.last.as_ref() == Some(&content) {
+                    continue;
+                }
+                state.last = Some(content);
+
+                return Some((Ok(res), state));
+            }
+        })
+    }
+
+    /// Returns the delay to wait after the given number of failed requests.
+    fn delay(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+        std::cmp::min(self.min_backoff.saturating_mul(factor), self.max_backoff)
+    }
+}
+
+/// The internal state threaded through a [Watcher] stream.
+struct WatchState<'a, C: Client, E: Endpoint + FeaturedEndpoint + Clone> {
+    watcher: Watcher<'a, C, E>,
+    failures: u32,
+    index: u64,
+    last: Option<Vec<u8>>,
+}
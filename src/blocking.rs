@@ -0,0 +1,1124 @@
+//! Synchronous wrappers around the asynchronous API functions.
+//!
+//! Each module mirrors its asynchronous counterpart at the crate root (i.e.
+//! [blocking::kv::read][crate::blocking::kv::read] wraps [kv::read][crate::kv::read])
+//! and takes a blocking [ConsulClient] in place of a [Client][crate::client::Client].
+//! Functions which return a stream or a long-running handle, such as
+//! [kv::watch][crate::kv::watch] or [lock::Lock][crate::lock::Lock], have no
+//! blocking version.
+//!
+//! This module is only available with the `blocking` feature enabled.
+//!
+//! ```no_run
+//! use consulrs::blocking::{self, ConsulClient};
+//! use consulrs::client::ConsulClientSettingsBuilder;
+//!
+//! let client = ConsulClient::new(
+//!     ConsulClientSettingsBuilder::default()
+//!         .address("http://127.0.0.1:8500")
+//!         .build()
+//!         .unwrap(),
+//! )
+//! .unwrap();
+//!
+//! blocking::kv::set(&client, "mykey", b"myvalue", None).unwrap();
+//! let res = blocking::kv::read_raw(&client, "mykey", None).unwrap();
+//! assert_eq!(res.response, b"myvalue");
+//! ```
+
+use std::future::Future;
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{
+    client::{self, ConsulClientSettings},
+    error::ClientError,
+};
+
+/// A client which executes requests synchronously.
+///
+/// Wraps an asynchronous [ConsulClient][client::ConsulClient] along with a
+/// single-threaded runtime used to drive its requests to completion. The
+/// functions in this module must not be called from within an asynchronous
+/// context, as blocking on the runtime from inside another one panics.
+pub struct ConsulClient {
+    client: client::ConsulClient,
+    runtime: Runtime,
+}
+
+impl ConsulClient {
+    /// Creates a new [ConsulClient] using the given [ConsulClientSettings].
+    pub fn new(settings: ConsulClientSettings) -> Result<ConsulClient, ClientError> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| ClientError::RuntimeBuildError { source: e })?;
+        let client = client::ConsulClient::new(settings)?;
+
+        Ok(ConsulClient { client, runtime })
+    }
+
+    /// Returns the underlying asynchronous client.
+    pub fn inner(&self) -> &client::ConsulClient {
+        &self.client
+    }
+
+    /// Runs the given future to completion on this client's runtime.
+    ///
+    /// This can be used to call asynchronous functions which have no blocking
+    /// version, passing [inner][ConsulClient::inner] as their client.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+/// Generates blocking versions of the given functions from the module imported
+/// as `inner`, each taking a blocking [ConsulClient] followed by the original
+/// arguments.
+macro_rules! blocking {
+    ($($name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty;)*) => {
+        $(
+            #[doc = concat!("Blocking version of [", stringify!($name), "][inner::", stringify!($name), "].")]
+            pub fn $name(
+                client: &crate::blocking::ConsulClient,
+                $($arg: $ty),*
+            ) -> Result<crate::api::ApiResponse<$ret>, crate::error::ClientError> {
+                client.block_on(inner::$name(client.inner(), $($arg),*))
+            }
+        )*
+    };
+}
+
+pub mod acl {
+    //! Blocking versions of the functions for ACLs.
+
+    use crate::acl as inner;
+    use crate::api::acl::{
+        common::{ACLReplicationStatus, ACLToken},
+        requests::{
+            BootstrapRequestBuilder, LoginRequestBuilder, LogoutRequestBuilder,
+            ReplicationStatusRequestBuilder,
+        },
+    };
+
+    blocking! {
+        bootstrap(opts: Option<&mut BootstrapRequestBuilder>) -> ACLToken;
+        login(
+            auth_method: &str,
+            bearer_token: &str,
+            opts: Option<&mut LoginRequestBuilder>,
+        ) -> ACLToken;
+        logout(token: &str, opts: Option<&mut LogoutRequestBuilder>) -> ();
+        replication_status(
+            opts: Option<&mut ReplicationStatusRequestBuilder>,
+        ) -> ACLReplicationStatus;
+    }
+
+    pub mod auth_method {
+        //! Blocking versions of the functions for ACL auth methods.
+
+        use crate::acl::auth_method as inner;
+        use crate::api::acl::{
+            common::ACLAuthMethod,
+            requests::{
+                CreateAuthMethodRequestBuilder, DeleteAuthMethodRequestBuilder,
+                ListAuthMethodsRequestBuilder, ReadAuthMethodRequestBuilder,
+                UpdateAuthMethodRequestBuilder,
+            },
+        };
+
+        blocking! {
+            create(
+                name: &str,
+                ty: &str,
+                opts: Option<&mut CreateAuthMethodRequestBuilder>,
+            ) -> ACLAuthMethod;
+            delete(name: &str, opts: Option<&mut DeleteAuthMethodRequestBuilder>) -> bool;
+            list(opts: Option<&mut ListAuthMethodsRequestBuilder>) -> Vec<ACLAuthMethod>;
+            read(name: &str, opts: Option<&mut ReadAuthMethodRequestBuilder>) -> ACLAuthMethod;
+            update(
+                name: &str,
+                ty: &str,
+                opts: Option<&mut UpdateAuthMethodRequestBuilder>,
+            ) -> ACLAuthMethod;
+        }
+    }
+
+    pub mod binding_rule {
+        //! Blocking versions of the functions for ACL binding rules.
+
+        use crate::acl::binding_rule as inner;
+        use crate::api::acl::{
+            common::{ACLBindType, ACLBindingRule},
+            requests::{
+                CreateBindingRuleRequestBuilder, DeleteBindingRuleRequestBuilder,
+                ListBindingRulesRequestBuilder, ReadBindingRuleRequestBuilder,
+                UpdateBindingRuleRequestBuilder,
+            },
+        };
+
+        blocking! {
+            create(
+                auth_method: &str,
+                bind_type: ACLBindType,
+                bind_name: &str,
+                opts: Option<&mut CreateBindingRuleRequestBuilder>,
+            ) -> ACLBindingRule;
+            delete(id: &str, opts: Option<&mut DeleteBindingRuleRequestBuilder>) -> bool;
+            list(opts: Option<&mut ListBindingRulesRequestBuilder>) -> Vec<ACLBindingRule>;
+            read(id: &str, opts: Option<&mut ReadBindingRuleRequestBuilder>) -> ACLBindingRule;
+            update(
+                id: &str,
+                auth_method: &str,
+                bind_type: ACLBindType,
+                bind_name: &str,
+                opts: Option<&mut UpdateBindingRuleRequestBuilder>,
+            ) -> ACLBindingRule;
+        }
+    }
+
+    pub mod policy {
+        //! Blocking versions of the functions for ACL policies.
+
+        use crate::acl::policy as inner;
+        use crate::api::acl::{
+            common::ACLPolicy,
+            requests::{
+                CreatePolicyRequestBuilder, DeletePolicyRequestBuilder, ListPoliciesRequestBuilder,
+                ReadPolicyByNameRequestBuilder, ReadPolicyRequestBuilder,
+                UpdatePolicyRequestBuilder,
+            },
+        };
+
+        blocking! {
+            create(name: &str, opts: Option<&mut CreatePolicyRequestBuilder>) -> ACLPolicy;
+            delete(id: &str, opts: Option<&mut DeletePolicyRequestBuilder>) -> bool;
+            list(opts: Option<&mut ListPoliciesRequestBuilder>) -> Vec<ACLPolicy>;
+            read(id: &str, opts: Option<&mut ReadPolicyRequestBuilder>) -> ACLPolicy;
+            read_by_name(
+                name: &str,
+                opts: Option<&mut ReadPolicyByNameRequestBuilder>,
+            ) -> ACLPolicy;
+            update(
+                id: &str,
+                name: &str,
+                opts: Option<&mut UpdatePolicyRequestBuilder>,
+            ) -> ACLPolicy;
+        }
+    }
+
+    pub mod role {
+        //! Blocking versions of the functions for ACL roles.
+
+        use crate::acl::role as inner;
+        use crate::api::acl::{
+            common::ACLRole,
+            requests::{
+                CreateRoleRequestBuilder, DeleteRoleRequestBuilder, ListRolesRequestBuilder,
+                ReadRoleByNameRequestBuilder, ReadRoleRequestBuilder, UpdateRoleRequestBuilder,
+            },
+        };
+
+        blocking! {
+            create(name: &str, opts: Option<&mut CreateRoleRequestBuilder>) -> ACLRole;
+            delete(id: &str, opts: Option<&mut DeleteRoleRequestBuilder>) -> bool;
+            list(opts: Option<&mut ListRolesRequestBuilder>) -> Vec<ACLRole>;
+            read(id: &str, opts: Option<&mut ReadRoleRequestBuilder>) -> ACLRole;
+            read_by_name(name: &str, opts: Option<&mut ReadRoleByNameRequestBuilder>) -> ACLRole;
+            update(id: &str, name: &str, opts: Option<&mut UpdateRoleRequestBuilder>) -> ACLRole;
+        }
+    }
+}
+
+pub mod agent {
+    //! Blocking versions of the functions for the local agent.
+
+    use crate::agent as inner;
+    use crate::api::agent::{
+        common::{AgentMember, AgentTokenKind},
+        requests::{
+            EnableMaintenanceRequestBuilder, ForceLeaveRequestBuilder, LeaveRequestBuilder,
+            ListMembersRequestBuilder, ReadHostRequestBuilder, ReadMetricsRequestBuilder,
+            ReadSelfRequestBuilder, ReloadRequestBuilder, UpdateTokenRequestBuilder,
+        },
+        responses::{AgentHostResponse, AgentMetricsResponse, AgentSelfResponse},
+    };
+
+    blocking! {
+        force_leave(node: &str, prune: bool, opts: Option<&mut ForceLeaveRequestBuilder>) -> ();
+        host(opts: Option<&mut ReadHostRequestBuilder>) -> AgentHostResponse;
+        leave(opts: Option<&mut LeaveRequestBuilder>) -> ();
+        members(wan: bool, opts: Option<&mut ListMembersRequestBuilder>) -> Vec<AgentMember>;
+        maintenance(enabled: bool, opts: Option<&mut EnableMaintenanceRequestBuilder>) -> ();
+        metrics(opts: Option<&mut ReadMetricsRequestBuilder>) -> AgentMetricsResponse;
+        reload(opts: Option<&mut ReloadRequestBuilder>) -> ();
+        self_info(opts: Option<&mut ReadSelfRequestBuilder>) -> AgentSelfResponse;
+        update_token(
+            kind: AgentTokenKind,
+            token: &str,
+            opts: Option<&mut UpdateTokenRequestBuilder>,
+        ) -> ();
+    }
+}
+
+pub mod catalog {
+    //! Blocking versions of the functions for the catalog.
+
+    use std::collections::HashMap;
+
+    use crate::api::catalog::{
+        common::{CatalogService, GatewayService, Node},
+        requests::{
+            DeregisterEntityRequestBuilder, ListDatacentersRequestBuilder,
+            ListGatewayServicesRequestBuilder, ListNodeServicesRequestBuilder,
+            ListNodesForConnectServiceRequestBuilder, ListNodesForServiceRequestBuilder,
+            ListNodesRequestBuilder, ListServicesRequestBuilder, RegisterEntityRequestBuilder,
+        },
+        responses::{ListNodeServicesResponse, ListNodesForServiceResponse},
+    };
+    use crate::catalog as inner;
+
+    blocking! {
+        datacenters(opts: Option<&mut ListDatacentersRequestBuilder>) -> Vec<String>;
+        deregister(node: &str, opts: Option<&mut DeregisterEntityRequestBuilder>) -> bool;
+        gateway(
+            gateway: &str,
+            opts: Option<&mut ListGatewayServicesRequestBuilder>,
+        ) -> Option<Vec<GatewayService>>;
+        node(
+            node: &str,
+            opts: Option<&mut ListNodeServicesRequestBuilder>,
+        ) -> ListNodeServicesResponse;
+        nodes(opts: Option<&mut ListNodesRequestBuilder>) -> Vec<Node>;
+        nodes_with_service(
+            service: &str,
+            opts: Option<&mut ListNodesForServiceRequestBuilder>,
+        ) -> Vec<CatalogService>;
+        nodes_with_connect_service(
+            service: &str,
+            opts: Option<&mut ListNodesForConnectServiceRequestBuilder>,
+        ) -> Vec<ListNodesForServiceResponse>;
+        register(
+            node: &str,
+            address: &str,
+            opts: Option<&mut RegisterEntityRequestBuilder>,
+        ) -> bool;
+        services(opts: Option<&mut ListServicesRequestBuilder>) -> HashMap<String, Vec<String>>;
+    }
+}
+
+pub mod check {
+    //! Blocking versions of the functions for checks.
+
+    use std::collections::HashMap;
+
+    use crate::api::check::{
+        common::AgentCheck,
+        requests::{
+            DeregisterCheckRequestBuilder, ListChecksRequestBuilder, RegisterCheckRequestBuilder,
+            TtlCheckFailRequestBuilder, TtlCheckPassRequestBuilder, TtlCheckUpdateRequestBuilder,
+            TtlCheckWarnRequestBuilder,
+        },
+    };
+    use crate::check as inner;
+
+    blocking! {
+        deregister(name: &str, opts: Option<&mut DeregisterCheckRequestBuilder>) -> ();
+        fail(name: &str, opts: Option<&mut TtlCheckFailRequestBuilder>) -> ();
+        list(opts: Option<&mut ListChecksRequestBuilder>) -> HashMap<String, AgentCheck>;
+        pass(name: &str, opts: Option<&mut TtlCheckPassRequestBuilder>) -> ();
+        register(name: &str, opts: Option<&mut RegisterCheckRequestBuilder>) -> ();
+        set_status(name: &str, status: &str, opts: Option<&mut TtlCheckUpdateRequestBuilder>) -> ();
+        warn(name: &str, opts: Option<&mut TtlCheckWarnRequestBuilder>) -> ();
+    }
+}
+
+pub mod config {
+    //! Blocking versions of the functions for config entries.
+
+    use crate::api::config::{
+        common::{ConfigEntry, ConfigEntryKind},
+        requests::{
+            ApplyConfigRequestBuilder, DeleteConfigRequestBuilder, GetConfigRequestBuilder,
+            ListConfigsRequestBuilder,
+        },
+    };
+    use crate::config as inner;
+
+    blocking! {
+        apply(entry: ConfigEntry, opts: Option<&mut ApplyConfigRequestBuilder>) -> bool;
+        delete(
+            kind: ConfigEntryKind,
+            name: &str,
+            opts: Option<&mut DeleteConfigRequestBuilder>,
+        ) -> ();
+        get(
+            kind: ConfigEntryKind,
+            name: &str,
+            opts: Option<&mut GetConfigRequestBuilder>,
+        ) -> ConfigEntry;
+        list(
+            kind: ConfigEntryKind,
+            opts: Option<&mut ListConfigsRequestBuilder>,
+        ) -> Vec<ConfigEntry>;
+    }
+}
+
+pub mod connect {
+    //! Blocking versions of the functions for Connect.
+
+    use crate::api::connect::{common::ConnectAuthorization, requests::AuthorizeRequestBuilder};
+    use crate::connect::{self as inner, Upstream};
+    use crate::error::ClientError;
+
+    blocking! {
+        authorize(
+            target: &str,
+            client_cert_uri: &str,
+            client_cert_serial: &str,
+            opts: Option<&mut AuthorizeRequestBuilder>,
+        ) -> ConnectAuthorization;
+    }
+
+    /// Blocking version of [upstream_address][inner::upstream_address].
+    pub fn upstream_address(
+        client: &crate::blocking::ConsulClient,
+        service: &str,
+    ) -> Result<Option<Upstream>, ClientError> {
+        client.block_on(inner::upstream_address(client.inner(), service))
+    }
+
+    pub mod ca {
+        //! Blocking versions of the functions for the Connect CA.
+
+        use crate::api::connect::{
+            common::{CAConfig, CARootList, LeafCert},
+            requests::{
+                ListRootsRequestBuilder, ReadConfigRequestBuilder, ReadLeafRequestBuilder,
+                UpdateConfigRequestBuilder,
+            },
+        };
+        use crate::connect::ca as inner;
+
+        blocking! {
+            config(opts: Option<&mut ReadConfigRequestBuilder>) -> CAConfig;
+            leaf(service: &str, opts: Option<&mut ReadLeafRequestBuilder>) -> LeafCert;
+            roots(opts: Option<&mut ListRootsRequestBuilder>) -> CARootList;
+            update_config(provider: &str, opts: Option<&mut UpdateConfigRequestBuilder>) -> ();
+        }
+    }
+
+    pub mod intention {
+        //! Blocking versions of the functions for Connect intentions.
+
+        use std::collections::HashMap;
+
+        use crate::api::connect::{
+            common::{Intention, IntentionCheck, IntentionMatchType},
+            requests::{
+                CheckIntentionRequestBuilder, DeleteIntentionRequestBuilder,
+                ListIntentionsRequestBuilder, MatchIntentionsRequestBuilder,
+                ReadIntentionRequestBuilder, UpsertIntentionRequestBuilder,
+            },
+        };
+        use crate::connect::intention::{self as inner, IntentionGraph};
+        use crate::error::ClientError;
+
+        blocking! {
+            check(
+                source: &str,
+                destination: &str,
+                opts: Option<&mut CheckIntentionRequestBuilder>,
+            ) -> IntentionCheck;
+            delete(
+                source: &str,
+                destination: &str,
+                opts: Option<&mut DeleteIntentionRequestBuilder>,
+            ) -> bool;
+            list(opts: Option<&mut ListIntentionsRequestBuilder>) -> Vec<Intention>;
+            matches(
+                by: IntentionMatchType,
+                name: &str,
+                opts: Option<&mut MatchIntentionsRequestBuilder>,
+            ) -> HashMap<String, Vec<Intention>>;
+            read(
+                source: &str,
+                destination: &str,
+                opts: Option<&mut ReadIntentionRequestBuilder>,
+            ) -> Intention;
+            upsert(
+                source: &str,
+                destination: &str,
+                opts: Option<&mut UpsertIntentionRequestBuilder>,
+            ) -> bool;
+        }
+
+        /// Blocking version of [graph][inner::graph].
+        pub fn graph(
+            client: &crate::blocking::ConsulClient,
+            opts: Option<&mut ListIntentionsRequestBuilder>,
+        ) -> Result<IntentionGraph, ClientError> {
+            client.block_on(inner::graph(client.inner(), opts))
+        }
+    }
+
+    pub mod proxy {
+        //! Blocking versions of the functions for Connect proxies.
+
+        use serde_json::Value;
+
+        use crate::connect::proxy::{self as inner, BootstrapOptions};
+        use crate::error::ClientError;
+
+        /// Blocking version of [bootstrap][inner::bootstrap].
+        pub fn bootstrap(
+            client: &crate::blocking::ConsulClient,
+            proxy_id: &str,
+            opts: &BootstrapOptions,
+        ) -> Result<Value, ClientError> {
+            client.block_on(inner::bootstrap(client.inner(), proxy_id, opts))
+        }
+    }
+}
+
+pub mod coordinate {
+    //! Blocking versions of the functions for network coordinates.
+
+    use crate::api::coordinate::{
+        common::{Coordinate, CoordinateEntry, DatacenterCoordinates},
+        requests::{
+            ListDatacenterCoordinatesRequestBuilder, ListNodeCoordinatesRequestBuilder,
+            ReadNodeCoordinatesRequestBuilder, UpdateNodeCoordinatesRequestBuilder,
+        },
+    };
+    use crate::coordinate as inner;
+
+    blocking! {
+        datacenters(
+            opts: Option<&mut ListDatacenterCoordinatesRequestBuilder>,
+        ) -> Vec<DatacenterCoordinates>;
+        node(
+            node: &str,
+            opts: Option<&mut ReadNodeCoordinatesRequestBuilder>,
+        ) -> Vec<CoordinateEntry>;
+        nodes(opts: Option<&mut ListNodeCoordinatesRequestBuilder>) -> Vec<CoordinateEntry>;
+        update(
+            node: &str,
+            coord: Coordinate,
+            opts: Option<&mut UpdateNodeCoordinatesRequestBuilder>,
+        ) -> ();
+    }
+}
+
+pub mod discovery {
+    //! Blocking versions of the functions for service discovery.
+
+    use crate::discovery as inner;
+    use crate::error::ClientError;
+    use std::net::SocketAddr;
+
+    /// Blocking version of [resolve][inner::resolve].
+    pub fn resolve(
+        client: &crate::blocking::ConsulClient,
+        service: &str,
+        tag: Option<&str>,
+        passing: bool,
+    ) -> Result<Vec<SocketAddr>, ClientError> {
+        client.block_on(inner::resolve(client.inner(), service, tag, passing))
+    }
+}
+
+pub mod discovery_chain {
+    //! Blocking versions of the functions for discovery chains.
+
+    use crate::api::discovery_chain::{
+        requests::GetDiscoveryChainRequestBuilder, responses::DiscoveryChainResponse,
+    };
+    use crate::discovery_chain as inner;
+
+    blocking! {
+        get(
+            service: &str,
+            opts: Option<&mut GetDiscoveryChainRequestBuilder>,
+        ) -> DiscoveryChainResponse;
+    }
+}
+
+pub mod event {
+    //! Blocking versions of the functions for user events.
+
+    use crate::api::event::{
+        common::UserEvent,
+        requests::{FireEventRequestBuilder, ListEventsRequestBuilder},
+    };
+    use crate::event as inner;
+
+    blocking! {
+        fire(name: &str, payload: &[u8], opts: Option<&mut FireEventRequestBuilder>) -> UserEvent;
+        list(opts: Option<&mut ListEventsRequestBuilder>) -> Vec<UserEvent>;
+    }
+}
+
+pub mod health {
+    //! Blocking versions of the functions for health checks.
+
+    use crate::api::check::common::HealthCheck;
+    use crate::api::health::{
+        common::ServiceEntry,
+        requests::{
+            ListChecksInStateRequestBuilder, ListConnectNodesRequestBuilder,
+            ListNodeChecksRequestBuilder, ListServiceChecksRequestBuilder,
+            ListServiceNodesRequestBuilder,
+        },
+    };
+    use crate::health as inner;
+
+    blocking! {
+        checks(
+            service: &str,
+            opts: Option<&mut ListServiceChecksRequestBuilder>,
+        ) -> Vec<HealthCheck>;
+        connect(
+            service: &str,
+            opts: Option<&mut ListConnectNodesRequestBuilder>,
+        ) -> Vec<ServiceEntry>;
+        node(node: &str, opts: Option<&mut ListNodeChecksRequestBuilder>) -> Vec<HealthCheck>;
+        service(
+            service: &str,
+            opts: Option<&mut ListServiceNodesRequestBuilder>,
+        ) -> Vec<ServiceEntry>;
+        state(state: &str, opts: Option<&mut ListChecksInStateRequestBuilder>) -> Vec<HealthCheck>;
+    }
+}
+
+#[cfg(feature = "unstable-internal")]
+pub mod internal {
+    //! Blocking versions of the functions for the internal UI endpoints.
+
+    use crate::api::internal::{
+        common::{NodeSummary, ServiceSummary, ServiceTopology},
+        requests::{
+            ListUiNodesRequestBuilder, ListUiServicesRequestBuilder,
+            ReadServiceTopologyRequestBuilder,
+        },
+    };
+    use crate::internal as inner;
+
+    blocking! {
+        nodes(opts: Option<&mut ListUiNodesRequestBuilder>) -> Vec<NodeSummary>;
+        services(opts: Option<&mut ListUiServicesRequestBuilder>) -> Vec<ServiceSummary>;
+        topology(
+            service: &str,
+            opts: Option<&mut ReadServiceTopologyRequestBuilder>,
+        ) -> ServiceTopology;
+    }
+}
+
+pub mod kv {
+    //! Blocking versions of the functions for the KV store.
+
+    use crate::api::kv::{
+        common::{GenericKVPair, KVExportEntry, KVPair, KvFlags},
+        requests::{
+            DeleteKeyRequestBuilder, ReadKeyRequestBuilder, ReadKeysRequestBuilder,
+            ReadRawKeyRequestBuilder, SetKeyRequestBuilder,
+        },
+    };
+    use crate::kv as inner;
+    use crate::{api::ApiResponse, error::ClientError};
+    use serde::{de::DeserializeOwned, Serialize};
+
+    blocking! {
+        acquire(key: &str, session: &str, opts: Option<&mut SetKeyRequestBuilder>) -> bool;
+        cas_set(
+            key: &str,
+            value: impl Into<Vec<u8>>,
+            modify_index: u64,
+            opts: Option<&mut SetKeyRequestBuilder>,
+        ) -> bool;
+        delete(key: &str, opts: Option<&mut DeleteKeyRequestBuilder>) -> bool;
+        delete_cas(
+            key: &str,
+            modify_index: u64,
+            opts: Option<&mut DeleteKeyRequestBuilder>,
+        ) -> bool;
+        delete_tree(prefix: &str, opts: Option<&mut DeleteKeyRequestBuilder>) -> bool;
+        ephemeral_set(
+            key: &str,
+            value: impl Into<Vec<u8>>,
+            session: &str,
+            opts: Option<&mut SetKeyRequestBuilder>,
+        ) -> bool;
+        export(prefix: &str, opts: Option<&mut ReadKeyRequestBuilder>) -> Vec<KVExportEntry>;
+        keys(path: &str, opts: Option<&mut ReadKeysRequestBuilder>) -> Vec<String>;
+        read_large(key: &str, opts: Option<&mut ReadKeyRequestBuilder>) -> Vec<u8>;
+        read_raw(key: &str, opts: Option<&mut ReadRawKeyRequestBuilder>) -> Vec<u8>;
+        read(key: &str, opts: Option<&mut ReadKeyRequestBuilder>) -> Vec<KVPair>;
+        release(key: &str, session: &str, opts: Option<&mut SetKeyRequestBuilder>) -> bool;
+        set(
+            key: &str,
+            value: impl Into<Vec<u8>>,
+            opts: Option<&mut SetKeyRequestBuilder>,
+        ) -> bool;
+        set_if_absent(
+            key: &str,
+            value: impl Into<Vec<u8>>,
+            opts: Option<&mut SetKeyRequestBuilder>,
+        ) -> bool;
+        set_with_flags(
+            key: &str,
+            value: impl Into<Vec<u8>>,
+            flags: impl KvFlags,
+            opts: Option<&mut SetKeyRequestBuilder>,
+        ) -> bool;
+    }
+
+    /// Blocking version of [import][inner::import].
+    pub fn import(
+        client: &crate::blocking::ConsulClient,
+        entries: &[KVExportEntry],
+        batch_size: Option<usize>,
+    ) -> Result<(), ClientError> {
+        client.block_on(inner::import(client.inner(), entries, batch_size))
+    }
+
+    /// Blocking version of [read_by_flags][inner::read_by_flags].
+    pub fn read_by_flags<F: KvFlags>(
+        client: &crate::blocking::ConsulClient,
+        prefix: &str,
+        predicate: impl Fn(&F) -> bool,
+        opts: Option<&mut ReadKeyRequestBuilder>,
+    ) -> Result<ApiResponse<Vec<KVPair>>, ClientError> {
+        client.block_on(inner::read_by_flags(
+            client.inner(),
+            prefix,
+            predicate,
+            opts,
+        ))
+    }
+
+    /// Blocking version of [read_cbor][inner::read_cbor].
+    #[cfg(feature = "cbor")]
+    pub fn read_cbor<T: DeserializeOwned>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        opts: Option<&mut ReadKeyRequestBuilder>,
+    ) -> Result<ApiResponse<GenericKVPair<T>>, ClientError> {
+        client.block_on(inner::read_cbor(client.inner(), key, opts))
+    }
+
+    /// Blocking version of [read_json][inner::read_json].
+    pub fn read_json<T: DeserializeOwned>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        opts: Option<&mut ReadKeyRequestBuilder>,
+    ) -> Result<ApiResponse<GenericKVPair<T>>, ClientError> {
+        client.block_on(inner::read_json(client.inner(), key, opts))
+    }
+
+    /// Blocking version of [read_json_raw][inner::read_json_raw].
+    pub fn read_json_raw<T: DeserializeOwned>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        opts: Option<&mut ReadRawKeyRequestBuilder>,
+    ) -> Result<ApiResponse<T>, ClientError> {
+        client.block_on(inner::read_json_raw(client.inner(), key, opts))
+    }
+
+    /// Blocking version of [read_json_recurse][inner::read_json_recurse].
+    pub fn read_json_recurse<T: DeserializeOwned>(
+        client: &crate::blocking::ConsulClient,
+        prefix: &str,
+        opts: Option<&mut ReadKeyRequestBuilder>,
+    ) -> Result<ApiResponse<Vec<GenericKVPair<T>>>, ClientError> {
+        client.block_on(inner::read_json_recurse(client.inner(), prefix, opts))
+    }
+
+    /// Blocking version of [read_msgpack][inner::read_msgpack].
+    #[cfg(feature = "msgpack")]
+    pub fn read_msgpack<T: DeserializeOwned>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        opts: Option<&mut ReadKeyRequestBuilder>,
+    ) -> Result<ApiResponse<GenericKVPair<T>>, ClientError> {
+        client.block_on(inner::read_msgpack(client.inner(), key, opts))
+    }
+
+    /// Blocking version of [read_yaml][inner::read_yaml].
+    #[cfg(feature = "yaml")]
+    pub fn read_yaml<T: DeserializeOwned>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        opts: Option<&mut ReadKeyRequestBuilder>,
+    ) -> Result<ApiResponse<GenericKVPair<T>>, ClientError> {
+        client.block_on(inner::read_yaml(client.inner(), key, opts))
+    }
+
+    /// Blocking version of [set_cbor][inner::set_cbor].
+    #[cfg(feature = "cbor")]
+    pub fn set_cbor<T: Serialize>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        value: &T,
+        opts: Option<&mut SetKeyRequestBuilder>,
+    ) -> Result<ApiResponse<bool>, ClientError> {
+        client.block_on(inner::set_cbor(client.inner(), key, value, opts))
+    }
+
+    /// Blocking version of [set_json][inner::set_json].
+    pub fn set_json<T: Serialize>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        value: &T,
+        opts: Option<&mut SetKeyRequestBuilder>,
+    ) -> Result<ApiResponse<bool>, ClientError> {
+        client.block_on(inner::set_json(client.inner(), key, value, opts))
+    }
+
+    /// Blocking version of [set_large][inner::set_large].
+    pub fn set_large(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        value: impl Into<Vec<u8>>,
+    ) -> Result<(), ClientError> {
+        client.block_on(inner::set_large(client.inner(), key, value))
+    }
+
+    /// Blocking version of [set_msgpack][inner::set_msgpack].
+    #[cfg(feature = "msgpack")]
+    pub fn set_msgpack<T: Serialize>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        value: &T,
+        opts: Option<&mut SetKeyRequestBuilder>,
+    ) -> Result<ApiResponse<bool>, ClientError> {
+        client.block_on(inner::set_msgpack(client.inner(), key, value, opts))
+    }
+
+    /// Blocking version of [set_yaml][inner::set_yaml].
+    #[cfg(feature = "yaml")]
+    pub fn set_yaml<T: Serialize>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        value: &T,
+        opts: Option<&mut SetKeyRequestBuilder>,
+    ) -> Result<ApiResponse<bool>, ClientError> {
+        client.block_on(inner::set_yaml(client.inner(), key, value, opts))
+    }
+
+    /// Blocking version of [try_read][inner::try_read].
+    pub fn try_read(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        opts: Option<&mut ReadKeyRequestBuilder>,
+    ) -> Result<Option<ApiResponse<Vec<KVPair>>>, ClientError> {
+        client.block_on(inner::try_read(client.inner(), key, opts))
+    }
+
+    /// Blocking version of [try_read_json][inner::try_read_json].
+    pub fn try_read_json<T: DeserializeOwned>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        opts: Option<&mut ReadKeyRequestBuilder>,
+    ) -> Result<Option<ApiResponse<GenericKVPair<T>>>, ClientError> {
+        client.block_on(inner::try_read_json(client.inner(), key, opts))
+    }
+
+    /// Blocking version of [try_read_raw][inner::try_read_raw].
+    pub fn try_read_raw(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        opts: Option<&mut ReadRawKeyRequestBuilder>,
+    ) -> Result<Option<ApiResponse<Vec<u8>>>, ClientError> {
+        client.block_on(inner::try_read_raw(client.inner(), key, opts))
+    }
+
+    /// Blocking version of [update][inner::update].
+    pub fn update<F>(
+        client: &crate::blocking::ConsulClient,
+        key: &str,
+        f: F,
+    ) -> Result<ApiResponse<Vec<u8>>, ClientError>
+    where
+        F: FnMut(Option<Vec<u8>>) -> Vec<u8>,
+    {
+        client.block_on(inner::update(client.inner(), key, f))
+    }
+}
+
+pub mod namespace {
+    //! Blocking versions of the functions for namespaces.
+
+    use crate::api::namespace::{
+        common::Namespace,
+        requests::{
+            CreateNamespaceRequestBuilder, DeleteNamespaceRequestBuilder,
+            ListNamespacesRequestBuilder, ReadNamespaceRequestBuilder,
+            UpdateNamespaceRequestBuilder,
+        },
+    };
+    use crate::namespace as inner;
+
+    blocking! {
+        create(name: &str, opts: Option<&mut CreateNamespaceRequestBuilder>) -> Namespace;
+        delete(name: &str, opts: Option<&mut DeleteNamespaceRequestBuilder>) -> ();
+        list(opts: Option<&mut ListNamespacesRequestBuilder>) -> Vec<Namespace>;
+        read(name: &str, opts: Option<&mut ReadNamespaceRequestBuilder>) -> Namespace;
+        update(name: &str, opts: Option<&mut UpdateNamespaceRequestBuilder>) -> Namespace;
+    }
+}
+
+pub mod operator {
+    //! Blocking versions of the functions for operator endpoints.
+
+    use crate::api::operator::{requests::GetUsageRequestBuilder, responses::UsageResponse};
+    use crate::operator as inner;
+
+    blocking! {
+        usage(opts: Option<&mut GetUsageRequestBuilder>) -> UsageResponse;
+    }
+
+    pub mod keyring {
+        //! Blocking versions of the functions for the gossip keyring.
+
+        use crate::api::operator::{
+            requests::{
+                ChangeKeyRequestBuilder, DeleteKeyRequestBuilder, InstallKeyRequestBuilder,
+                ListKeysRequestBuilder,
+            },
+            responses::KeyringResponse,
+        };
+        use crate::operator::keyring as inner;
+
+        blocking! {
+            install(key: &str, opts: Option<&mut InstallKeyRequestBuilder>) -> ();
+            list(opts: Option<&mut ListKeysRequestBuilder>) -> Vec<KeyringResponse>;
+            remove(key: &str, opts: Option<&mut DeleteKeyRequestBuilder>) -> ();
+            use_key(key: &str, opts: Option<&mut ChangeKeyRequestBuilder>) -> ();
+        }
+    }
+
+    pub mod license {
+        //! Blocking versions of the functions for the Enterprise license.
+
+        use crate::api::operator::{
+            requests::{
+                GetLicenseRequestBuilder, PutLicenseRequestBuilder, ResetLicenseRequestBuilder,
+            },
+            responses::LicenseResponse,
+        };
+        use crate::operator::license as inner;
+
+        blocking! {
+            get(opts: Option<&mut GetLicenseRequestBuilder>) -> LicenseResponse;
+            put(license: &str, opts: Option<&mut PutLicenseRequestBuilder>) -> LicenseResponse;
+            reset(opts: Option<&mut ResetLicenseRequestBuilder>) -> LicenseResponse;
+        }
+    }
+
+    pub mod raft {
+        //! Blocking versions of the functions for the Raft subsystem.
+
+        use crate::api::operator::{
+            requests::TransferLeaderRequestBuilder, responses::TransferLeaderResponse,
+        };
+        use crate::operator::raft as inner;
+
+        blocking! {
+            transfer_leader(
+                id: &str,
+                opts: Option<&mut TransferLeaderRequestBuilder>,
+            ) -> TransferLeaderResponse;
+        }
+    }
+}
+
+pub mod partition {
+    //! Blocking versions of the functions for admin partitions.
+
+    use crate::api::partition::{
+        common::Partition,
+        requests::{
+            CreatePartitionRequestBuilder, DeletePartitionRequestBuilder,
+            ListPartitionsRequestBuilder, ReadPartitionRequestBuilder,
+            UpdatePartitionRequestBuilder,
+        },
+    };
+    use crate::partition as inner;
+
+    blocking! {
+        create(name: &str, opts: Option<&mut CreatePartitionRequestBuilder>) -> Partition;
+        delete(name: &str, opts: Option<&mut DeletePartitionRequestBuilder>) -> ();
+        list(opts: Option<&mut ListPartitionsRequestBuilder>) -> Vec<Partition>;
+        read(name: &str, opts: Option<&mut ReadPartitionRequestBuilder>) -> Partition;
+        update(name: &str, opts: Option<&mut UpdatePartitionRequestBuilder>) -> Partition;
+    }
+}
+
+pub mod peering {
+    //! Blocking versions of the functions for cluster peering.
+
+    use crate::api::peering::{
+        common::Peering,
+        requests::{
+            DeletePeeringRequestBuilder, EstablishPeeringRequestBuilder,
+            GenerateTokenRequestBuilder, ListPeeringsRequestBuilder, ReadPeeringRequestBuilder,
+        },
+        responses::GenerateTokenResponse,
+    };
+    use crate::peering as inner;
+
+    blocking! {
+        delete(name: &str, opts: Option<&mut DeletePeeringRequestBuilder>) -> ();
+        establish(name: &str, token: &str, opts: Option<&mut EstablishPeeringRequestBuilder>) -> ();
+        list(opts: Option<&mut ListPeeringsRequestBuilder>) -> Vec<Peering>;
+        read(name: &str, opts: Option<&mut ReadPeeringRequestBuilder>) -> Peering;
+        token(name: &str, opts: Option<&mut GenerateTokenRequestBuilder>) -> GenerateTokenResponse;
+    }
+}
+
+pub mod query {
+    //! Blocking versions of the functions for prepared queries.
+
+    use crate::api::query::{
+        common::{PreparedQueryDefinition, ServiceQuery},
+        requests::{
+            CreateQueryRequestBuilder, DeleteQueryRequestBuilder, ExecuteQueryRequestBuilder,
+            ExplainQueryRequestBuilder, ListQueriesRequestBuilder, ReadQueryRequestBuilder,
+            UpdateQueryRequestBuilder,
+        },
+        responses::{CreateQueryResponse, ExecuteQueryResponse, ExplainQueryResponse},
+    };
+    use crate::query as inner;
+
+    blocking! {
+        create(
+            service: ServiceQuery,
+            opts: Option<&mut CreateQueryRequestBuilder>,
+        ) -> CreateQueryResponse;
+        delete(uuid: &str, opts: Option<&mut DeleteQueryRequestBuilder>) -> ();
+        execute(query: &str, opts: Option<&mut ExecuteQueryRequestBuilder>) -> ExecuteQueryResponse;
+        explain(query: &str, opts: Option<&mut ExplainQueryRequestBuilder>) -> ExplainQueryResponse;
+        list(opts: Option<&mut ListQueriesRequestBuilder>) -> Vec<PreparedQueryDefinition>;
+        read(
+            uuid: &str,
+            opts: Option<&mut ReadQueryRequestBuilder>,
+        ) -> Vec<PreparedQueryDefinition>;
+        update(
+            uuid: &str,
+            service: ServiceQuery,
+            opts: Option<&mut UpdateQueryRequestBuilder>,
+        ) -> ();
+    }
+}
+
+pub mod service {
+    //! Blocking versions of the functions for services.
+
+    use std::collections::HashMap;
+
+    use crate::api::service::{
+        common::{AgentService, AgentServiceChecksInfo},
+        requests::{
+            DeregisterServiceRequestBuilder, EnableMaintenanceRequestBuilder,
+            ListServicesRequestBuilder, ReadServiceRequestBuilder, RegisterServiceRequestBuilder,
+            ServiceHealthByIdRequestBuilder, ServiceHealthRequestBuilder,
+        },
+    };
+    use crate::service as inner;
+    use crate::{api::ApiResponse, error::ClientError};
+
+    blocking! {
+        deregister(id: &str, opts: Option<&mut DeregisterServiceRequestBuilder>) -> ();
+        health(
+            name: &str,
+            opts: Option<&mut ServiceHealthRequestBuilder>,
+        ) -> Vec<AgentServiceChecksInfo>;
+        health_by_id(
+            id: &str,
+            opts: Option<&mut ServiceHealthByIdRequestBuilder>,
+        ) -> Vec<AgentServiceChecksInfo>;
+        list(opts: Option<&mut ListServicesRequestBuilder>) -> HashMap<String, AgentService>;
+        maintenance(
+            id: &str,
+            enabled: bool,
+            opts: Option<&mut EnableMaintenanceRequestBuilder>,
+        ) -> ();
+        read(name: &str, opts: Option<&mut ReadServiceRequestBuilder>) -> AgentService;
+        register(name: &str, opts: Option<&mut RegisterServiceRequestBuilder>) -> ();
+    }
+
+    /// Blocking version of [try_read][inner::try_read].
+    pub fn try_read(
+        client: &crate::blocking::ConsulClient,
+        name: &str,
+        opts: Option<&mut ReadServiceRequestBuilder>,
+    ) -> Result<Option<ApiResponse<AgentService>>, ClientError> {
+        client.block_on(inner::try_read(client.inner(), name, opts))
+    }
+}
+
+pub mod session {
+    //! Blocking versions of the functions for sessions.
+
+    use crate::api::session::{
+        common::SessionEntry,
+        requests::{
+            CreateSessionRequestBuilder, DeleteSessionRequestBuilder,
+            ListNodeSessionsRequestBuilder, ListSessionsRequestBuilder, ReadSessionRequestBuilder,
+            RenewSessionRequestBuilder,
+        },
+        responses::CreateSessionResponse,
+    };
+    use crate::session as inner;
+
+    blocking! {
+        create(opts: Option<&mut CreateSessionRequestBuilder>) -> CreateSessionResponse;
+        delete(uuid: &str, opts: Option<&mut DeleteSessionRequestBuilder>) -> ();
+        list(opts: Option<&mut ListSessionsRequestBuilder>) -> Vec<SessionEntry>;
+        list_by_node(
+            node: &str,
+            opts: Option<&mut ListNodeSessionsRequestBuilder>,
+        ) -> Vec<SessionEntry>;
+        read(uuid: &str, opts: Option<&mut ReadSessionRequestBuilder>) -> Vec<SessionEntry>;
+        renew(uuid: &str, opts: Option<&mut RenewSessionRequestBuilder>) -> Vec<SessionEntry>;
+    }
+}
+
+pub mod snapshot {
+    //! Blocking versions of the functions for snapshots.
+
+    use crate::api::snapshot::requests::GenerateSnapshotRequestBuilder;
+    use crate::snapshot as inner;
+
+    blocking! {
+        backup(opts: Option<&mut GenerateSnapshotRequestBuilder>) -> Vec<u8>;
+    }
+}
+
+pub mod status {
+    //! Blocking versions of the functions for the Raft status.
+
+    use crate::api::status::requests::{GetLeaderRequestBuilder, ListPeersRequestBuilder};
+    use crate::status as inner;
+
+    blocking! {
+        leader(opts: Option<&mut GetLeaderRequestBuilder>) -> String;
+        peers(opts: Option<&mut ListPeersRequestBuilder>) -> Vec<String>;
+    }
+}
+
+pub mod txn {
+    //! Blocking versions of the functions for transactions.
+
+    use crate::api::txn::{requests::TransactionRequestBuilder, responses::TransactionResponse};
+    use crate::txn as inner;
+    use crate::txn::Transaction;
+
+    blocking! {
+        execute(
+            txn: &Transaction,
+            opts: Option<&mut TransactionRequestBuilder>,
+        ) -> TransactionResponse;
+    }
+}
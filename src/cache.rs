@@ -0,0 +1,546 @@
+//! In-process caches for KV and health reads.
+//!
+//! A [CachedKv] or [CachedHealth] reads through to Consul the first time a key
+//! or service is requested, then keeps the result up to date with a blocking
+//! query in the background and serves later reads from memory. Entries which
+//! aren't read for `idle_timeout` are evicted, which stops their query.
+//!
+//! Reads never return a result older than `max_stale`: background queries
+//! wait for at most half of it, so a healthy entry is confirmed at least that
+//! often, and an entry whose query keeps failing is read through again. Keys
+//! which don't exist are polled at the same interval, since Consul can't block
+//! on them. How effective a cache is can be checked with its [CacheStats].
+//!
+//! With a [CacheConfig::snapshot] file, the last known results are also
+//! persisted to disk and served whenever Consul can't be reached, including
+//! by a freshly started process which hasn't been able to read anything yet.
+//! Changes are written to it in the background, batched over
+//! [SNAPSHOT_DELAY], and can be written right away with `flush`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::cache::{CacheConfig, CachedKv};
+//! use std::{sync::Arc, time::Duration};
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let config = CacheConfig::builder()
+//!     .max_stale(Duration::from_secs(5))
+//!     .build()
+//!     .unwrap();
+//! let cache = CachedKv::new(Arc::new(client), config);
+//! for _ in 0..1000 {
+//!     let flag = cache.get("features/new-ui").await.unwrap();
+//! }
+//! assert_eq!(cache.stats().misses, 1);
+//! # })
+//! ```
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryInto,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use derive_builder::Builder;
+use rustify::endpoint::Endpoint;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    api::{
+        self,
+        blocking::{parse_index, BlockingQuery},
+        features::FeaturedEndpoint,
+        health::{common::ServiceEntry, requests::ListServiceNodesRequest},
+        kv::{common::KVPair, requests::ReadKeyRequest},
+        watch::{DEFAULT_MAX_BACKOFF, DEFAULT_MIN_BACKOFF},
+        ApiResponse,
+    },
+    client::{Client, ErrorClass},
+    error::ClientError,
+    kv,
+};
+
+/// How long changes are collected for before a snapshot is rewritten.
+pub const SNAPSHOT_DELAY: Duration = Duration::from_secs(1);
+
+/// Configuration for a [CachedKv] or [CachedHealth].
+///
+/// Results are never older than `max_stale` when read, and entries are
+/// evicted once they haven't been read for `idle_timeout`.
+///
+/// With a `snapshot` file configured, the last known result of every key or
+/// service is saved to it as JSON and loaded again when the cache is created.
+/// Reads which can't reach Consul, because it can't be connected to, times
+/// out, or fails with a 5xx status code, are then served the last known
+/// result instead of failing, regardless of `max_stale`, so applications can
+/// start and keep running while the cluster is unreachable.
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into), default)]
+pub struct CacheConfig {
+    pub idle_timeout: Duration,
+    pub max_stale: Duration,
+    #[builder(setter(into, strip_option))]
+    pub snapshot: Option<PathBuf>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            idle_timeout: Duration::from_secs(5 * 60),
+            max_stale: Duration::from_secs(10),
+            snapshot: None,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Returns a default instance of [CacheConfigBuilder] for configuring a
+    /// cache.
+    pub fn builder() -> CacheConfigBuilder {
+        CacheConfigBuilder::default()
+    }
+
+    /// Returns how long background queries block for.
+    fn wait(&self) -> Duration {
+        std::cmp::max(self.max_stale / 2, Duration::from_millis(1))
+    }
+}
+
+/// Counters describing how reads were served by a cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The number of entries currently cached.
+    pub entries: usize,
+    /// The number of reads served from the snapshot because Consul couldn't
+    /// be reached.
+    pub fallback: u64,
+    /// The number of reads served from memory.
+    pub hits: u64,
+    /// The number of reads of entries which weren't cached.
+    pub misses: u64,
+    /// The number of reads of entries older than `max_stale`, which were read
+    /// through to Consul.
+    pub stale: u64,
+}
+
+/// A cache of the values of keys in the KV store.
+pub struct CachedKv<C: Client + 'static> {
+    cache: Cache<C, ReadKeyRequest, Option<KVPair>>,
+}
+
+impl<C: Client + 'static> CachedKv<C> {
+    /// Returns a new, empty [CachedKv].
+    pub fn new(client: Arc<C>, config: CacheConfig) -> Self {
+        CachedKv {
+            cache: Cache::new(client, config, kv_endpoint, kv_parse),
+        }
+    }
+
+    /// Returns the given key, or [None] if it doesn't exist.
+    ///
+    /// See [kv::read]
+    pub async fn get(&self, key: &str) -> Result<Option<KVPair>, ClientError> {
+        let client = self.cache.inner.client.as_ref();
+        self.cache
+            .get(key)
+            .await?
+            .map(|kv| kv::decompress(client, kv))
+            .transpose()
+    }
+
+    /// Returns the raw value of the given key, or [None] if it doesn't exist.
+    pub async fn get_raw(&self, key: &str) -> Result<Option<Vec<u8>>, ClientError> {
+        match self.get(key).await? {
+            Some(KVPair { value: Some(v), .. }) => v.try_into().map(Some),
+            Some(_) => Ok(Some(Vec::new())),
+            None => Ok(None),
+        }
+    }
+
+    /// Writes any results which haven't been saved to the snapshot yet.
+    pub async fn flush(&self) {
+        self.cache.flush().await
+    }
+
+    /// Returns the counters of this cache.
+    pub fn stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+}
+
+/// A cache of the healthy instances of services.
+pub struct CachedHealth<C: Client + 'static> {
+    cache: Cache<C, ListServiceNodesRequest, Vec<ServiceEntry>>,
+}
+
+impl<C: Client + 'static> CachedHealth<C> {
+    /// Returns a new, empty [CachedHealth].
+    pub fn new(client: Arc<C>, config: CacheConfig) -> Self {
+        CachedHealth {
+            cache: Cache::new(client, config, health_endpoint, |res| {
+                res.map(|r| r.response)
+            }),
+        }
+    }
+
+    /// Returns the instances of the given service which pass their health
+    /// checks.
+    ///
+    /// See [health::service][crate::health::service]
+    pub async fn service(&self, service: &str) -> Result<Vec<ServiceEntry>, ClientError> {
+        self.cache.get(service).await
+    }
+
+    /// Writes any results which haven't been saved to the snapshot yet.
+    pub async fn flush(&self) {
+        self.cache.flush().await
+    }
+
+    /// Returns the counters of this cache.
+    pub fn stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+}
+
+fn kv_endpoint(key: &str) -> ReadKeyRequest {
+    ReadKeyRequest::builder().key(key).build().unwrap()
+}
+
+fn kv_parse(
+    res: Result<ApiResponse<Vec<KVPair>>, ClientError>,
+) -> Result<Option<KVPair>, ClientError> {
+    match res {
+        Ok(res) => Ok(res.response.into_iter().next()),
+        Err(e) if e.is_not_found() => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn health_endpoint(service: &str) -> ListServiceNodesRequest {
+    ListServiceNodesRequest::builder()
+        .service(service)
+        .passing(true)
+        .build()
+        .unwrap()
+}
+
+type Entries<T> = Mutex<HashMap<String, (Arc<Mutex<Entry<T>>>, tokio::task::JoinHandle<()>)>>;
+type Parse<R, T> = fn(Result<ApiResponse<R>, ClientError>) -> Result<T, ClientError>;
+
+/// A cached result.
+struct Entry<T> {
+    read: Instant,
+    updated: Instant,
+    value: T,
+}
+
+/// The entries of a cache, each kept up to date by a background task.
+struct Cache<C: Client + 'static, E: Endpoint, T> {
+    fallback: AtomicU64,
+    hits: AtomicU64,
+    inner: Arc<Inner<C, E, T>>,
+    misses: AtomicU64,
+    stale: AtomicU64,
+}
+
+/// The parts of a cache shared with its background tasks.
+struct Inner<C: Client + 'static, E: Endpoint, T> {
+    client: Arc<C>,
+    config: CacheConfig,
+    endpoint: fn(&str) -> E,
+    entries: Entries<T>,
+    parse: Parse<E::Response, T>,
+    snapshot: Option<Arc<Snapshot<T>>>,
+}
+
+impl<C, E, T> Cache<C, E, T>
+where
+    C: Client + 'static,
+    E: Endpoint + FeaturedEndpoint + Clone + Send + Sync + 'static,
+    E::Response: Send,
+    T: Clone + DeserializeOwned + Serialize + Send + 'static,
+{
+    fn new(
+        client: Arc<C>,
+        config: CacheConfig,
+        endpoint: fn(&str) -> E,
+        parse: Parse<E::Response, T>,
+    ) -> Self {
+        let snapshot = config.snapshot.clone().map(|p| Arc::new(Snapshot::load(p)));
+        Cache {
+            fallback: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            inner: Arc::new(Inner {
+                client,
+                config,
+                endpoint,
+                entries: Mutex::new(HashMap::new()),
+                parse,
+                snapshot,
+            }),
+            misses: AtomicU64::new(0),
+            stale: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the cached result for the given name if it's fresh, and reads
+    /// it through otherwise.
+    async fn get(&self, name: &str) -> Result<T, ClientError> {
+        let inner = &self.inner;
+        let cached = inner
+            .entries
+            .lock()
+            .unwrap()
+            .get(name)
+            .map(|(e, _)| e.clone());
+        match cached {
+            Some(entry) => {
+                let mut entry = entry.lock().unwrap();
+                entry.read = Instant::now();
+                if entry.updated.elapsed() <= inner.config.max_stale {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return Ok(entry.value.clone());
+                }
+                self.stale.fetch_add(1, Ordering::Relaxed);
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let endpoint = (inner.endpoint)(name);
+        let res = api::exec_with_result(inner.client.as_ref(), endpoint).await;
+        let index = res.as_ref().ok().and_then(|r| parse_index(r).ok());
+        let value = match (inner.parse)(res) {
+            Ok(value) => value,
+            Err(e) => return self.fall_back(name, e),
+        };
+        if let Some(snapshot) = &inner.snapshot {
+            snapshot.save(name, &value);
+        }
+
+        let mut entries = inner.entries.lock().unwrap();
+        match entries.get(name) {
+            Some((entry, _)) => {
+                let mut entry = entry.lock().unwrap();
+                entry.updated = Instant::now();
+                entry.value = value.clone();
+            }
+            None => {
+                debug!("Caching {}", name);
+                let entry = Arc::new(Mutex::new(Entry {
+                    read: Instant::now(),
+                    updated: Instant::now(),
+                    value: value.clone(),
+                }));
+                let task = tokio::spawn(refresh(
+                    inner.clone(),
+                    name.to_string(),
+                    index.unwrap_or(0),
+                    entry.clone(),
+                ));
+                entries.insert(name.to_string(), (entry, task));
+            }
+        }
+        Ok(value)
+    }
+
+    /// Returns the last known result for the given name from the snapshot
+    /// if Consul couldn't be reached, and the error otherwise.
+    fn fall_back(&self, name: &str, e: ClientError) -> Result<T, ClientError> {
+        let unreachable = matches!(
+            ErrorClass::of(&e),
+            ErrorClass::Connection | ErrorClass::Server | ErrorClass::Timeout
+        );
+        let known = match &self.inner.snapshot {
+            Some(snapshot) if unreachable => snapshot.get(name),
+            _ => None,
+        };
+        match known {
+            Some(value) => {
+                warn!(
+                    "Serving last known {} as Consul is unreachable: {}",
+                    name, e
+                );
+                self.fallback.fetch_add(1, Ordering::Relaxed);
+                Ok(value)
+            }
+            None => Err(e),
+        }
+    }
+
+    async fn flush(&self) {
+        if let Some(snapshot) = &self.inner.snapshot {
+            snapshot.flush().await;
+        }
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.inner.entries.lock().unwrap().len(),
+            fallback: self.fallback.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            stale: self.stale.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl<C: Client + 'static, E: Endpoint, T> Drop for Cache<C, E, T> {
+    fn drop(&mut self) {
+        for (_, task) in self.inner.entries.lock().unwrap().values() {
+            task.abort();
+        }
+    }
+}
+
+/// Keeps an entry up to date until it's evicted for being idle.
+///
+/// Without an index to block on, i.e. for a key which doesn't exist, the
+/// result is polled every `wait` instead.
+async fn refresh<C, E, T>(
+    inner: Arc<Inner<C, E, T>>,
+    name: String,
+    mut index: u64,
+    entry: Arc<Mutex<Entry<T>>>,
+) where
+    C: Client,
+    E: Endpoint + FeaturedEndpoint + Clone,
+    T: Clone + DeserializeOwned + Serialize + Send + 'static,
+{
+    let wait = inner.config.wait();
+    let query = BlockingQuery::new(inner.client.as_ref(), (inner.endpoint)(&name))
+        .wait(&format!("{}ms", wait.as_millis()));
+    let mut failures = 0;
+    loop {
+        if failures > 0 {
+            let factor = 2u32.saturating_pow(failures - 1);
+            let delay = DEFAULT_MIN_BACKOFF.saturating_mul(factor);
+            tokio::time::sleep(std::cmp::min(delay, DEFAULT_MAX_BACKOFF)).await;
+        } else if index == 0 {
+            tokio::time::sleep(wait).await;
+        }
+
+        let (res, next) = match query.next(index).await {
+            Ok((res, next)) => (Ok(res), next),
+            Err(e) => (Err(e), 0),
+        };
+        match (inner.parse)(res) {
+            Ok(value) => {
+                failures = 0;
+                // Only results with a new index are worth saving
+                if let Some(snapshot) = inner.snapshot.as_ref().filter(|_| next != index) {
+                    snapshot.save(&name, &value);
+                }
+                index = if next < index { 0 } else { next };
+                let mut entry = entry.lock().unwrap();
+                entry.updated = Instant::now();
+                entry.value = value;
+            }
+            Err(e) => {
+                warn!("Failed refreshing cached {}: {}", name, e);
+                failures += 1;
+            }
+        }
+
+        // Locked in the same order as when reading
+        let mut entries = inner.entries.lock().unwrap();
+        if entry.lock().unwrap().read.elapsed() > inner.config.idle_timeout {
+            debug!("Evicting cached {}", name);
+            entries.remove(&name);
+            return;
+        }
+    }
+}
+
+/// The last known results of a cache, saved to a JSON file.
+struct Snapshot<T> {
+    path: PathBuf,
+    pending: AtomicBool,
+    values: Mutex<BTreeMap<String, T>>,
+    writing: tokio::sync::Mutex<()>,
+}
+
+impl<T: Clone + DeserializeOwned + Serialize + Send + 'static> Snapshot<T> {
+    /// Loads the results saved at the given path, starting without any if
+    /// there aren't any or they can't be read.
+    fn load(path: PathBuf) -> Self {
+        let values = match std::fs::read(&path) {
+            Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|e| {
+                warn!("Ignoring invalid cache snapshot {}: {}", path.display(), e);
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+        Snapshot {
+            path,
+            pending: AtomicBool::new(false),
+            values: Mutex::new(values),
+            writing: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<T> {
+        self.values.lock().unwrap().get(name).cloned()
+    }
+
+    /// Saves a result, rewriting the file after [SNAPSHOT_DELAY] unless a
+    /// rewrite is already pending, which then includes it.
+    fn save(self: &Arc<Self>, name: &str, value: &T) {
+        self.values
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), value.clone());
+        if !self.pending.swap(true, Ordering::AcqRel) {
+            let snapshot = self.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(SNAPSHOT_DELAY).await;
+                snapshot.flush().await;
+            });
+        }
+    }
+
+    /// Rewrites the file if any results were saved since it was last written.
+    async fn flush(&self) {
+        // Held until written so an older rewrite can't replace a newer one
+        let _writing = self.writing.lock().await;
+        if !self.pending.swap(false, Ordering::AcqRel) {
+            return;
+        }
+        let content = serde_json::to_vec(&*self.values.lock().unwrap());
+
+        // Written next to the file and renamed so a crash never leaves a
+        // partially written snapshot behind
+        let path = self.path.clone();
+        let written = match content {
+            Ok(content) => tokio::task::spawn_blocking(move || {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                let tmp = path.with_file_name(format!(".{}.tmp", name));
+                std::fs::write(&tmp, content).and_then(|_| std::fs::rename(&tmp, &path))
+            })
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.map_err(|e| e.to_string())),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = written {
+            warn!(
+                "Failed saving cache snapshot {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
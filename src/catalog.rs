@@ -4,7 +4,7 @@ use crate::{
     api::{
         self,
         catalog::{
-            common::{CatalogService, Node},
+            common::{CatalogService, GatewayService, Node},
             requests::{
                 DeregisterEntityRequest, DeregisterEntityRequestBuilder, ListDatacentersRequest,
                 ListDatacentersRequestBuilder, ListGatewayServicesRequest,
@@ -15,15 +15,15 @@ use crate::{
                 ListServicesRequest, ListServicesRequestBuilder, RegisterEntityRequest,
                 RegisterEntityRequestBuilder,
             },
-            responses::{
-                GatewayServiceResponse, ListNodeServicesResponse, ListNodesForServiceResponse,
-            },
+            responses::{ListNodeServicesResponse, ListNodesForServiceResponse},
         },
+        watch::Watcher,
         ApiResponse,
     },
     client::Client,
     error::ClientError,
 };
+use futures::Stream;
 
 /// Lists all known datacenters.
 ///
@@ -52,7 +52,7 @@ pub async fn deregister(
     api::exec_with_result(client, endpoint).await
 }
 
-/// Lists all services on a gateway.
+/// Lists all services linked to an ingress or terminating gateway.
 ///
 /// See [ListGatewayServicesRequest]
 #[instrument(skip(client, opts), err)]
@@ -60,7 +60,7 @@ pub async fn gateway(
     client: &impl Client,
     gateway: &str,
     opts: Option<&mut ListGatewayServicesRequestBuilder>,
-) -> Result<ApiResponse<Option<Vec<GatewayServiceResponse>>>, ClientError> {
+) -> Result<ApiResponse<Option<Vec<GatewayService>>>, ClientError> {
     let mut t = ListGatewayServicesRequest::builder();
     let endpoint = opts.unwrap_or(&mut t).gateway(gateway).build().unwrap();
     api::exec_with_result(client, endpoint).await
@@ -68,6 +68,9 @@ pub async fn gateway(
 
 /// Lists all services on a node.
 ///
+/// Only the services matching the filter of the options are returned when one
+/// is set.
+///
 /// See [ListNodeServicesRequest]
 #[instrument(skip(client, opts), err)]
 pub async fn node(
@@ -153,3 +156,20 @@ pub async fn services(
     let endpoint = opts.unwrap_or(&mut t).build().unwrap();
     api::exec_with_result(client, endpoint).await
 }
+
+/// Watches the nodes providing the given service for changes.
+///
+/// The returned stream yields the current list of nodes and then yields again
+/// each time the list changes.
+///
+/// See [Watcher] and [ListNodesForServiceRequest]
+#[instrument(skip(client, opts))]
+pub fn watch<'a, C: Client>(
+    client: &'a C,
+    service: &str,
+    opts: Option<&mut ListNodesForServiceRequestBuilder>,
+) -> impl Stream<Item = Result<ApiResponse<Vec<CatalogService>>, ClientError>> + 'a {
+    let mut t = ListNodesForServiceRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).service(service).build().unwrap();
+    Watcher::new(client, endpoint).stream()
+}
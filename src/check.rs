@@ -1,4 +1,9 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use tokio::{
+    sync::{mpsc, watch},
+    task::JoinHandle,
+};
 
 use crate::{
     api::{
@@ -76,6 +81,9 @@ pub async fn pass(
 
 /// Registers a new check on an agent.
 ///
+/// Returns a [ClientError::InvalidCheckError] if the check is not configured
+/// correctly for its kind.
+///
 /// See [RegisterCheckRequest]
 #[instrument(skip(client, opts), err)]
 pub async fn register(
@@ -84,7 +92,13 @@ pub async fn register(
     opts: Option<&mut RegisterCheckRequestBuilder>,
 ) -> Result<ApiResponse<()>, ClientError> {
     let mut t = RegisterCheckRequest::builder();
-    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    let endpoint =
+        opts.unwrap_or(&mut t)
+            .name(name)
+            .build()
+            .map_err(|e| ClientError::InvalidCheckError {
+                message: e.to_string(),
+            })?;
     api::exec_with_empty(client, endpoint).await
 }
 
@@ -121,3 +135,114 @@ pub async fn warn(
     let endpoint = opts.unwrap_or(&mut t).check(name).build().unwrap();
     api::exec_with_empty(client, endpoint).await
 }
+
+/// A background task which keeps a TTL check passing.
+///
+/// The check is marked as passing immediately and then at half of its TTL,
+/// which gives a failed request time to be retried before the check expires.
+/// Failed requests are reported through [CheckKeepAlive::failures] and do not
+/// stop the task. The task stops when [CheckKeepAlive::stop] is called or the
+/// [CheckKeepAlive] is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+/// use consulrs::check::CheckKeepAlive;
+/// use std::{sync::Arc, time::Duration};
+///
+/// # tokio_test::block_on(async {
+/// # let client = ConsulClient::new(
+/// #     ConsulClientSettingsBuilder::default()
+/// #         .address("https://127.0.0.1:8200")
+/// #         .build()
+/// #         .unwrap()
+/// # ).unwrap();
+/// let mut keep_alive = CheckKeepAlive::start(Arc::new(client), "my-check", Duration::from_secs(10));
+/// while let Some(e) = keep_alive.failures().recv().await {
+///     println!("Failed updating check: {}", e);
+/// }
+/// # })
+/// ```
+pub struct CheckKeepAlive {
+    failures: mpsc::UnboundedReceiver<ClientError>,
+    paused: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl CheckKeepAlive {
+    /// Starts keeping the given TTL check alive.
+    pub fn start<C: Client + 'static>(client: Arc<C>, check: &str, ttl: Duration) -> Self {
+        let (failures_tx, failures) = mpsc::unbounded_channel();
+        let (paused, paused_rx) = watch::channel(false);
+        let task = tokio::spawn(keep_alive(
+            client,
+            check.to_string(),
+            ttl / 2,
+            paused_rx,
+            failures_tx,
+        ));
+
+        CheckKeepAlive {
+            failures,
+            paused,
+            task,
+        }
+    }
+
+    /// Returns a receiver which yields each error encountered while updating
+    /// the check.
+    pub fn failures(&mut self) -> &mut mpsc::UnboundedReceiver<ClientError> {
+        &mut self.failures
+    }
+
+    /// Returns whether updating the check is paused.
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Pauses updating the check, allowing it to expire.
+    pub fn pause(&self) {
+        let _ = self.paused.send(true);
+    }
+
+    /// Resumes updating the check, immediately marking it as passing.
+    pub fn resume(&self) {
+        let _ = self.paused.send(false);
+    }
+
+    /// Stops updating the check, which is equivalent to dropping it.
+    pub fn stop(self) {}
+}
+
+impl Drop for CheckKeepAlive {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Marks the given check as passing at the given interval while not paused.
+async fn keep_alive<C: Client>(
+    client: Arc<C>,
+    check: String,
+    interval: Duration,
+    mut paused: watch::Receiver<bool>,
+    failures: mpsc::UnboundedSender<ClientError>,
+) {
+    loop {
+        while *paused.borrow() {
+            if paused.changed().await.is_err() {
+                return;
+            }
+        }
+
+        match pass(client.as_ref(), &check, None).await {
+            Ok(_) => debug!("Marked check {} as passing", check),
+            Err(e) => {
+                warn!("Failed marking check {} as passing: {}", check, e);
+                let _ = failures.send(e);
+            }
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
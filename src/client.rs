@@ -1,13 +1,25 @@
 use async_trait::async_trait;
 use derive_builder::Builder;
 use rustify::clients::reqwest::Client as HTTPClient;
-use std::{env, fs};
+use std::{
+    collections::hash_map::RandomState,
+    env, fmt, fs,
+    hash::{BuildHasher, Hasher},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::sync::{Semaphore, SemaphorePermit};
 
 use crate::{
-    api::{EndpointMiddleware, Features},
+    api::{features::ConsistencyMode, EndpointMiddleware, Features},
     error::ClientError,
 };
 
+pub(crate) mod unix;
+
 /// The client interface capabale of interacting with API functions
 #[async_trait]
 pub trait Client: Send + Sync + Sized {
@@ -19,16 +31,38 @@ pub trait Client: Send + Sync + Sized {
 
     /// Returns the settings used to configure this client
     fn settings(&self) -> &ConsulClientSettings;
+
+    /// Returns the pool of servers requests may fail over between, if any
+    fn pool(&self) -> Option<&ServerPool> {
+        None
+    }
+
+    /// Returns the limiter requests wait on before being sent, if any
+    fn limiter(&self) -> Option<&Limiter> {
+        None
+    }
+
+    /// Returns the in-memory server which handles requests in place of
+    /// Consul, if any
+    #[cfg(feature = "mock")]
+    fn mock(&self) -> Option<&crate::mock::MockServer> {
+        None
+    }
 }
 
 /// A client which can be used to execute calls against a Consul server.
 ///
 /// A consul client is configured using [ConsulClientSettings] and will
 /// automatically configure a backing instance of a [HTTPClient] which is
-/// used for executing [Endpoints][rustify::endpoint::Endpoint].
+/// used for executing [Endpoints][rustify::endpoint::Endpoint]. When
+/// additional addresses are configured, requests fail over between them
+/// using a [ServerPool].
 pub struct ConsulClient {
     pub http: HTTPClient,
+    pub limiter: Limiter,
+    pub pool: ServerPool,
     pub settings: ConsulClientSettings,
+    pub token_file: Option<TokenFile>,
 }
 
 #[async_trait]
@@ -38,23 +72,49 @@ impl Client for ConsulClient {
     }
 
     fn middle(&self, features: Option<Features>) -> EndpointMiddleware {
-        let version_str = format!("v{}", self.settings.version);
-        EndpointMiddleware {
-            features,
-            token: self.settings.token.clone(),
-            version: version_str,
-        }
+        // A token read from a file takes precedence over a static one
+        let token = match &self.token_file {
+            Some(file) => file.token().or_else(|| self.settings.token.clone()),
+            None => self.settings.token.clone(),
+        };
+
+        self.settings.middleware(features, token)
     }
 
     fn settings(&self) -> &ConsulClientSettings {
         &self.settings
     }
+
+    fn pool(&self) -> Option<&ServerPool> {
+        Some(&self.pool)
+    }
+
+    fn limiter(&self) -> Option<&Limiter> {
+        Some(&self.limiter)
+    }
 }
 
 impl ConsulClient {
     /// Creates a new [ConsulClient] using the given [ConsulClientSettings].
     #[instrument(skip(settings), err)]
     pub fn new(settings: ConsulClientSettings) -> Result<ConsulClient, ClientError> {
+        // Rejects limits which would never let a request through
+        if let Some(limit) = &settings.rate_limit {
+            if limit.per_second.is_nan() || limit.per_second <= 0.0 {
+                return Err(ClientError::InvalidSettingsError {
+                    message: format!(
+                        "rate_limit.per_second must be positive, got {}",
+                        limit.per_second
+                    ),
+                });
+            }
+        }
+        if settings.max_in_flight == Some(0) {
+            return Err(ClientError::InvalidSettingsError {
+                message: "max_in_flight must be at least 1".to_string(),
+            });
+        }
+
         let mut http_client = reqwest::ClientBuilder::new();
 
         // Disable TLS checks if specified
@@ -63,6 +123,24 @@ impl ConsulClient {
         }
         http_client = http_client.danger_accept_invalid_certs(!settings.verify);
 
+        if let Some(timeout) = settings.connect_timeout {
+            http_client = http_client.connect_timeout(timeout);
+        }
+
+        // Tunes the connection pool, leaving reqwest's defaults otherwise
+        if let Some(max) = settings.pool_max_idle_per_host {
+            http_client = http_client.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = settings.pool_idle_timeout {
+            http_client = http_client.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = settings.tcp_keepalive {
+            http_client = http_client.tcp_keepalive(interval);
+        }
+        if !settings.http2 {
+            http_client = http_client.http1_only();
+        }
+
         // Adds CA certificates
         for path in &settings.ca_certs {
             let content = std::fs::read(&path).map_err(|e| ClientError::FileReadError {
@@ -110,8 +188,138 @@ impl ConsulClient {
         let http_client = http_client
             .build()
             .map_err(|e| ClientError::RestClientBuildError { source: e })?;
-        let http = HTTPClient::new(settings.address.as_str(), http_client);
-        Ok(ConsulClient { settings, http })
+        let pool = ServerPool::new(
+            std::iter::once(&settings.address)
+                .chain(&settings.addresses)
+                .map(|a| HTTPClient::new(&unix::base(a), http_client.clone()))
+                .collect(),
+            settings.selection.clone(),
+            settings.failover_cooldown,
+        );
+        let http = HTTPClient::new(&unix::base(&settings.address), http_client);
+        let token_file = settings
+            .token_file
+            .as_deref()
+            .map(TokenFile::new)
+            .transpose()?;
+        let limiter = Limiter::new(settings.rate_limit.clone(), settings.max_in_flight);
+        Ok(ConsulClient {
+            settings,
+            http,
+            limiter,
+            pool,
+            token_file,
+        })
+    }
+}
+
+/// A [Client] which sends every request to a fixed datacenter, namespace, and
+/// admin partition.
+///
+/// Wraps a shared client and replaces the `dc`, `ns`, and `partition` of its
+/// [QueryOptions] with those configured in the [Scope], so that a library can
+/// be handed a client scoped to its tenant without passing options to every
+/// call. As with other default query options, a request which sets one of
+/// them itself still takes precedence. Connections, retries, and limits are
+/// shared with the wrapped client.
+///
+/// ```
+/// use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder, Scope, ScopedClient};
+/// use std::sync::Arc;
+///
+/// let client = Arc::new(ConsulClient::new(
+///     ConsulClientSettingsBuilder::default()
+///         .address("https://127.0.0.1:8200")
+///         .build()
+///         .unwrap()
+/// ).unwrap());
+/// let tenant = ScopedClient::new(client, Scope::builder().ns("team-a").build().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct ScopedClient<C: Client> {
+    inner: Arc<C>,
+    scope: Scope,
+    settings: ConsulClientSettings,
+}
+
+#[async_trait]
+impl<C: Client> Client for ScopedClient<C> {
+    fn http(&self) -> &HTTPClient {
+        self.inner.http()
+    }
+
+    fn middle(&self, features: Option<Features>) -> EndpointMiddleware {
+        let mut middleware = self.inner.middle(features);
+        middleware.defaults = self.settings.default_query_options.clone();
+        middleware
+    }
+
+    fn settings(&self) -> &ConsulClientSettings {
+        &self.settings
+    }
+
+    fn pool(&self) -> Option<&ServerPool> {
+        self.inner.pool()
+    }
+
+    fn limiter(&self) -> Option<&Limiter> {
+        self.inner.limiter()
+    }
+
+    #[cfg(feature = "mock")]
+    fn mock(&self) -> Option<&crate::mock::MockServer> {
+        self.inner.mock()
+    }
+}
+
+impl<C: Client> ScopedClient<C> {
+    /// Creates a new [ScopedClient] sending requests through the given client
+    /// to the given [Scope].
+    pub fn new(inner: Arc<C>, scope: Scope) -> Self {
+        let mut settings = inner.settings().clone();
+        let defaults = &mut settings.default_query_options;
+        if scope.dc.is_some() {
+            defaults.dc = scope.dc.clone();
+        }
+        if scope.ns.is_some() {
+            defaults.ns = scope.ns.clone();
+        }
+        if scope.partition.is_some() {
+            defaults.partition = scope.partition.clone();
+        }
+        ScopedClient {
+            inner,
+            scope,
+            settings,
+        }
+    }
+
+    /// Returns the wrapped client.
+    pub fn inner(&self) -> &Arc<C> {
+        &self.inner
+    }
+
+    /// Returns the [Scope] requests are sent to.
+    pub fn scope(&self) -> &Scope {
+        &self.scope
+    }
+}
+
+/// The datacenter, namespace, and admin partition a [ScopedClient] sends
+/// requests to. Any of them which isn't set falls back to the default query
+/// options of the wrapped client.
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(setter(into, strip_option), default)]
+pub struct Scope {
+    pub dc: Option<String>,
+    pub ns: Option<String>,
+    pub partition: Option<String>,
+}
+
+impl Scope {
+    /// Returns a default instance of [ScopeBuilder] for configuring a scope.
+    pub fn builder() -> ScopeBuilder {
+        ScopeBuilder::default()
     }
 }
 
@@ -127,28 +335,329 @@ impl ConsulClient {
 /// * `token`: CONSUL_HTTP_TOKEN
 /// * `verify`: CONSUL_HTTP_SSL_VERIFY
 ///
+/// See [ConsulClientSettings::from_env] for configuring every setting the same
+/// way the Consul CLI does instead.
+///
 /// Note that the client key must be in an RSA or PKCS#8 format, otherwise the
 /// client will fail to be created with a "key not found" error.
-#[derive(Builder, Clone, Debug)]
+#[derive(Builder, Clone)]
 #[builder(setter(into, strip_option))]
 pub struct ConsulClientSettings {
+    /// The address requests are sent to, which may also point at the unix
+    /// domain socket of a local agent, i.e. `unix:///var/run/consul.sock`.
     #[builder(default = "self.default_address()")]
     pub address: String,
+    /// Addresses failed over to when `address` can't be reached, see
+    /// [ServerPool].
+    #[builder(default)]
+    pub addresses: Vec<String>,
+    /// Replaces `timeout` for blocking queries, and should be longer than the
+    /// `wait` time of the query.
+    #[builder(default)]
+    pub blocking_timeout: Option<Duration>,
     #[builder(default = "self.default_ca_certs()")]
     pub ca_certs: Vec<String>,
+    /// Records responses to or replays them from a cassette, see
+    /// `replay::Cassette`.
+    #[cfg(feature = "replay")]
+    #[builder(setter(custom), default)]
+    pub cassette: Option<Arc<crate::replay::Cassette>>,
     #[builder(default = "self.default_client_cert()")]
     pub client_cert: Option<String>,
     #[builder(default = "self.default_client_key()")]
     pub client_key: Option<String>,
+    /// Limits how long connecting over TCP may take.
+    #[builder(default)]
+    pub connect_timeout: Option<Duration>,
+    /// Applied to every request which doesn't configure them itself, see
+    /// [QueryOptions].
+    #[builder(default)]
+    pub default_query_options: QueryOptions,
+    /// Decompresses values read from the KV store whose flags mark them as
+    /// compressed with an enabled algorithm, see
+    /// [Compression][crate::api::kv::common::Compression]. Off by default,
+    /// since other clients may give the same flags a meaning of their own.
+    #[builder(default)]
+    pub decompress: bool,
+    /// The ACL token is only ever sent in the `X-Consul-Token` header and is
+    /// redacted from debug output. A `token` query parameter added to a
+    /// request, such as by an [Interceptor], is moved into the header, unless
+    /// this is set, in which case the request fails with a
+    /// [ClientError::TokenInQueryError] instead.
+    #[builder(default)]
+    pub enforce_token_header: bool,
+    #[builder(default = "Duration::from_secs(30)")]
+    pub failover_cooldown: Duration,
+    /// Negotiates HTTP/2 with servers serving TLS. Doesn't apply to unix
+    /// domain sockets.
+    #[builder(default = "true")]
+    pub http2: bool,
+    /// Every request and response passes through these, see [Interceptor].
+    #[builder(setter(custom), default)]
+    pub interceptors: Vec<Arc<dyn Interceptor>>,
+    /// The most requests sent at once, see [Limiter].
+    #[builder(default)]
+    pub max_in_flight: Option<usize>,
+    /// Every finished request is reported to this recorder, see
+    /// [MetricsRecorder].
+    #[builder(setter(custom), default)]
+    pub metrics: Option<Arc<dyn MetricsRecorder>>,
+    /// How long idle connections are kept alive to be reused. Doesn't apply
+    /// to unix domain sockets.
+    #[builder(default)]
+    pub pool_idle_timeout: Option<Duration>,
+    /// The most idle connections kept to each server, which applications
+    /// holding many concurrent blocking queries may want to raise. Doesn't
+    /// apply to unix domain sockets.
+    #[builder(default)]
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Requests are sent no faster than this, see [Limiter].
+    #[builder(default)]
+    pub rate_limit: Option<RateLimit>,
+    /// Every request is sent with a randomly generated ID in this header, such
+    /// as `X-Request-Id`, which is recorded on its span with the `otel`
+    /// feature enabled and included in any [ClientError::APIError] so failed
+    /// requests can be found in the logs of Consul or a proxy in front of it.
+    /// A request can also set its own ID through the `request_id` method of
+    /// its builder, which is sent in the [REQUEST_ID_HEADER] when no header
+    /// is configured.
+    #[builder(default)]
+    pub request_id_header: Option<String>,
+    /// Decides which failed requests are retried, including timed out reads.
+    /// Never retries by default, see [RetryPolicy].
+    #[builder(default)]
+    pub retry: RetryPolicy,
+    #[builder(setter(custom), default = "Arc::new(Priority)")]
+    pub selection: Arc<dyn SelectionPolicy>,
+    /// How often TCP keepalives are sent. Doesn't apply to unix domain
+    /// sockets.
+    #[builder(default)]
+    pub tcp_keepalive: Option<Duration>,
+    /// Fails each attempt at sending a request with a
+    /// [ClientError::TimeoutError] once it takes longer than this. Can be
+    /// overridden on a single request through the `request_timeout` method of
+    /// its builder.
+    #[builder(default)]
+    pub timeout: Option<Duration>,
     #[builder(setter(into), default = "self.default_token()")]
     pub token: Option<String>,
+    /// Takes precedence over `token` and is re-read whenever it changes, see
+    /// [TokenFile].
+    #[builder(default)]
+    pub token_file: Option<String>,
+    /// Takes precedence over `token` and `token_file`, see [TokenSource].
+    #[builder(setter(custom), default)]
+    pub token_source: Option<Arc<dyn TokenSource>>,
     #[builder(default = "self.default_verify()")]
     pub verify: bool,
     #[builder(setter(into, strip_option), default = "1")]
     pub version: u8,
 }
 
+// The token is redacted so that settings can be logged safely
+impl fmt::Debug for ConsulClientSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("ConsulClientSettings");
+        s.field("address", &self.address)
+            .field("addresses", &self.addresses)
+            .field("blocking_timeout", &self.blocking_timeout)
+            .field("ca_certs", &self.ca_certs);
+        #[cfg(feature = "replay")]
+        s.field("cassette", &self.cassette);
+        s.field("client_cert", &self.client_cert)
+            .field("client_key", &self.client_key)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("default_query_options", &self.default_query_options)
+            .field("decompress", &self.decompress)
+            .field("enforce_token_header", &self.enforce_token_header)
+            .field("failover_cooldown", &self.failover_cooldown)
+            .field("http2", &self.http2)
+            .field("interceptors", &self.interceptors)
+            .field("max_in_flight", &self.max_in_flight)
+            .field("metrics", &self.metrics)
+            .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("rate_limit", &self.rate_limit)
+            .field("request_id_header", &self.request_id_header)
+            .field("retry", &self.retry)
+            .field("selection", &self.selection)
+            .field("tcp_keepalive", &self.tcp_keepalive)
+            .field("timeout", &self.timeout)
+            .field("token", &redacted(&self.token))
+            .field("token_file", &self.token_file)
+            .field("token_source", &self.token_source)
+            .field("verify", &self.verify)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl ConsulClientSettings {
+    /// Returns the middleware for executing an endpoint with the given
+    /// features and ACL token under these settings.
+    pub(crate) fn middleware(
+        &self,
+        features: Option<Features>,
+        token: Option<String>,
+    ) -> EndpointMiddleware {
+        // Fall back to the client-wide consistency mode if one is set
+        let features = match &self.default_query_options.consistency {
+            Some(mode) => {
+                let mut features = features.unwrap_or_default();
+                features.mode = features.mode.or_else(|| Some(mode.clone()));
+                Some(features)
+            }
+            None => features,
+        };
+
+        // A request's own ID is always sent, others only when enabled
+        let request_id = features
+            .as_ref()
+            .and_then(|f| f.request_id.clone())
+            .or_else(|| self.request_id_header.as_ref().map(|_| new_request_id()))
+            .map(|id| {
+                let header = self.request_id_header.as_deref();
+                (header.unwrap_or(REQUEST_ID_HEADER).to_string(), id)
+            });
+
+        EndpointMiddleware {
+            defaults: self.default_query_options.clone(),
+            features,
+            request_id,
+            token,
+            version: format!("v{}", self.version),
+        }
+    }
+
+    /// Creates [ConsulClientSettings] from the same environment variables, and
+    /// with the same precedence, as the official Consul CLI:
+    ///
+    /// * `CONSUL_HTTP_ADDR`: the address, which defaults to the http scheme
+    ///   when none is given
+    /// * `CONSUL_HTTP_TOKEN` / `CONSUL_HTTP_TOKEN_FILE`: the ACL token, where
+    ///   the contents of the file take precedence and are re-read whenever it
+    ///   changes
+    /// * `CONSUL_CACERT` / `CONSUL_CAPATH`: the CA certificates
+    /// * `CONSUL_CLIENT_CERT` / `CONSUL_CLIENT_KEY`: the client certificate
+    /// * `CONSUL_HTTP_SSL`: whether to use the https scheme
+    /// * `CONSUL_HTTP_SSL_VERIFY`: whether to verify TLS certificates
+    /// * `CONSUL_NAMESPACE`: the default namespace of every request
+    ///
+    /// Boolean variables accept the same values as the CLI, invalid values
+    /// are logged and ignored.
+    pub fn from_env() -> Result<ConsulClientSettings, ClientError> {
+        let mut builder = ConsulClientSettingsBuilder::default();
+
+        let ssl = env_bool("CONSUL_HTTP_SSL").unwrap_or(false);
+        let address =
+            env::var("CONSUL_HTTP_ADDR").unwrap_or_else(|_| String::from("127.0.0.1:8500"));
+        let address = match address.split_once("://") {
+            // Never revert to http if TLS was explicitly requested
+            Some(("http", rest)) if ssl => format!("https://{}", rest),
+            Some(_) => address,
+            None if ssl => format!("https://{}", address),
+            None => format!("http://{}", address),
+        };
+        info!("Using consul address {}", address);
+        builder.address(address);
+
+        if let Ok(path) = env::var("CONSUL_HTTP_TOKEN_FILE") {
+            info!("Using consul ACL token file from $CONSUL_HTTP_TOKEN_FILE");
+            builder.token_file(path);
+        }
+
+        if let Some(verify) = env_bool("CONSUL_HTTP_SSL_VERIFY") {
+            builder.verify(verify);
+        }
+
+        if let Ok(ns) = env::var("CONSUL_NAMESPACE") {
+            info!("Using consul namespace from $CONSUL_NAMESPACE");
+            builder.default_query_options(QueryOptions {
+                ns: Some(ns),
+                ..Default::default()
+            });
+        }
+
+        // Every setting has a default, so building can't fail
+        Ok(builder.build().unwrap())
+    }
+}
+
+/// Parses a boolean environment variable the same way the Consul CLI does.
+fn env_bool(name: &str) -> Option<bool> {
+    let value = env::var(name).ok()?;
+    match value.as_str() {
+        "1" | "t" | "T" | "true" | "TRUE" | "True" => Some(true),
+        "0" | "f" | "F" | "false" | "FALSE" | "False" => Some(false),
+        _ => {
+            warn!("Ignoring invalid boolean in ${}: {}", name, value);
+            None
+        }
+    }
+}
+
+/// The header request IDs are sent in unless another is configured.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Returns a new random request ID formatted as a version 4 UUID.
+fn new_request_id() -> String {
+    // A fresh RandomState is randomly seeded, which is enough for IDs which
+    // only need to be unique across logs
+    let high = RandomState::new().build_hasher().finish();
+    let low = RandomState::new().build_hasher().finish();
+    let random = ((high as u128) << 64) | low as u128;
+    // Set the version to 4 and the variant to RFC 4122
+    let uuid = (random & !((0xf000 << 64) | (0xc << 60))) | (0x4000 << 64) | (0x8 << 60);
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        uuid >> 96,
+        (uuid >> 80) & 0xffff,
+        (uuid >> 64) & 0xffff,
+        (uuid >> 48) & 0xffff,
+        uuid & 0xffff_ffff_ffff
+    )
+}
+
+/// Replaces an ACL token with a placeholder for logging.
+pub(crate) fn redacted(token: &Option<String>) -> Option<&'static str> {
+    token.as_ref().map(|_| "<redacted>")
+}
+
 impl ConsulClientSettingsBuilder {
+    /// Sets the [Cassette][crate::replay::Cassette] responses are recorded to
+    /// or replayed from.
+    #[cfg(feature = "replay")]
+    pub fn cassette(&mut self, cassette: crate::replay::Cassette) -> &mut Self {
+        self.cassette = Some(Some(Arc::new(cassette)));
+        self
+    }
+
+    /// Adds an [Interceptor], which is called after any previously added ones.
+    pub fn interceptor(&mut self, interceptor: impl Interceptor + 'static) -> &mut Self {
+        self.interceptors
+            .get_or_insert_with(Vec::new)
+            .push(Arc::new(interceptor));
+        self
+    }
+
+    /// Sets the [MetricsRecorder] every finished request is reported to.
+    pub fn metrics(&mut self, recorder: impl MetricsRecorder + 'static) -> &mut Self {
+        self.metrics = Some(Some(Arc::new(recorder)));
+        self
+    }
+
+    /// Sets the [SelectionPolicy] used to order servers when failing over.
+    pub fn selection(&mut self, policy: impl SelectionPolicy + 'static) -> &mut Self {
+        self.selection = Some(Arc::new(policy));
+        self
+    }
+
+    /// Sets the [TokenSource] used to fetch the token sent with requests.
+    pub fn token_source(&mut self, source: impl TokenSource + 'static) -> &mut Self {
+        self.token_source = Some(Some(Arc::new(source)));
+        self
+    }
+
     fn default_address(&self) -> String {
         match env::var("CONSUL_HTTP_ADDR") {
             Ok(s) => {
@@ -231,3 +740,509 @@ impl ConsulClientSettingsBuilder {
         }
     }
 }
+
+/// An ACL token read from a file, such as one rendered by a Vault agent
+/// template.
+///
+/// The modification time of the file is checked before every request and the
+/// token is re-read whenever it changes, so rotating the token doesn't require
+/// restarting the application. Surrounding whitespace is removed and an empty
+/// file means there's no token.
+pub struct TokenFile {
+    path: String,
+    state: Mutex<(Option<SystemTime>, Option<String>)>,
+}
+
+// The token is left out so that clients can be logged safely
+impl fmt::Debug for TokenFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenFile")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TokenFile {
+    /// Creates a new [TokenFile], reading the initial token from the file at
+    /// the given path.
+    pub fn new(path: &str) -> Result<TokenFile, ClientError> {
+        let file = TokenFile {
+            path: path.to_string(),
+            state: Mutex::new((None, None)),
+        };
+        file.reload()?;
+        Ok(file)
+    }
+
+    /// Returns the current token, re-reading the file first if it changed.
+    ///
+    /// If the file can no longer be read the last token read is returned.
+    pub fn token(&self) -> Option<String> {
+        if let Err(e) = self.reload() {
+            warn!("Failed reloading ACL token from {}: {}", self.path, e);
+        }
+        self.state.lock().unwrap().1.clone()
+    }
+
+    fn reload(&self) -> Result<(), ClientError> {
+        let err = |e| ClientError::FileReadError {
+            source: e,
+            path: self.path.clone(),
+        };
+        let modified = fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .map_err(err)?;
+
+        let mut state = self.state.lock().unwrap();
+        if state.0 == Some(modified) {
+            return Ok(());
+        }
+
+        info!("Reading consul ACL token from {}", self.path);
+        let token = fs::read_to_string(&self.path).map_err(err)?;
+        let token = token.trim();
+        *state = (
+            Some(modified),
+            Some(token.to_string()).filter(|t| !t.is_empty()),
+        );
+        Ok(())
+    }
+}
+
+/// Provides the ACL tokens sent with requests, in place of a static token.
+///
+/// This allows plugging in any token rotation scheme, such as fetching tokens
+/// from the Consul secrets engine of Vault or logging in with an ACL auth
+/// method. A token is fetched before every request, so sources should cache
+/// tokens themselves. When Consul rejects a request with a 403 the client
+/// calls [refresh][TokenSource::refresh] and retries the request once with the
+/// new token. Streamed requests are never retried.
+#[async_trait]
+pub trait TokenSource: fmt::Debug + Send + Sync {
+    /// Returns the token to send with the next request.
+    async fn token(&self) -> Result<String, ClientError>;
+
+    /// Returns a new token after Consul rejected the last one.
+    ///
+    /// Defaults to calling [token][TokenSource::token] again.
+    async fn refresh(&self) -> Result<String, ClientError> {
+        self.token().await
+    }
+}
+
+/// Hooks into the requests sent and responses received by a [ConsulClient].
+///
+/// Interceptors can be used to add custom headers, sign requests, or record
+/// metrics and audit logs. Requests are passed after all other processing,
+/// just before they're sent, and are seen again whenever a request is retried
+/// or fails over to another server. All responses are passed, including error
+/// responses, though streamed responses are passed with an empty body.
+pub trait Interceptor: fmt::Debug + Send + Sync {
+    /// Called with each request just before it's sent.
+    fn on_request(&self, _req: &mut http::Request<Vec<u8>>) {}
+
+    /// Called with each response as soon as it's received.
+    fn on_response(&self, _res: &http::Response<Vec<u8>>) {}
+}
+
+/// Records metrics about the requests made by a [ConsulClient].
+///
+/// The recorder is called once for every API call after it finishes,
+/// successfully or not, with its endpoint, latency and outcome. Retries and
+/// failing over to other servers happen within a single call, so the latency
+/// includes any time spent waiting between attempts. Streamed calls finish
+/// once the response headers are received.
+///
+/// Enabling the `metrics` feature adds `metrics::Facade`, an implementation
+/// which records through the `metrics` crate.
+pub trait MetricsRecorder: fmt::Debug + Send + Sync {
+    /// Called with each finished request.
+    fn record(&self, request: &RequestMetrics);
+}
+
+/// A finished request, as passed to a [MetricsRecorder].
+#[derive(Clone, Debug)]
+pub struct RequestMetrics {
+    /// The number of attempts made according to the [RetryPolicy].
+    pub attempts: u32,
+    /// The name of the request type, e.g. `ReadKeyRequest`, which is suitable
+    /// as a metric label.
+    pub endpoint: &'static str,
+    /// The class of error the request failed with, if any.
+    pub error: Option<ErrorClass>,
+    /// The time taken from the first attempt until the last one finished.
+    pub latency: Duration,
+    /// The HTTP method, e.g. `GET`.
+    pub method: String,
+    /// The path of the request, without the API version or query string.
+    ///
+    /// Paths contain keys and names, so they shouldn't be used as metric
+    /// labels.
+    pub path: String,
+}
+
+/// A coarse classification of the errors a request can fail with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// Consul rejected the ACL token (HTTP 403).
+    AclDenied,
+    /// Consul rejected the request as invalid, i.e. any other 4xx status.
+    BadRequest,
+    /// No server could be reached.
+    Connection,
+    /// The requested entity doesn't exist (HTTP 404).
+    NotFound,
+    /// Any error which happened before sending the request or after receiving
+    /// a response, such as failing to parse the response.
+    Other,
+    /// Consul rejected the request because a rate limit was exceeded (HTTP
+    /// 429).
+    RateLimited,
+    /// Consul failed to handle the request, i.e. any 5xx status.
+    Server,
+    /// The request timed out.
+    Timeout,
+}
+
+impl ErrorClass {
+    /// Classifies the given error.
+    pub fn of(e: &ClientError) -> ErrorClass {
+        match e {
+            e if e.is_not_found() => ErrorClass::NotFound,
+            e if e.is_acl_denied() => ErrorClass::AclDenied,
+            e if e.is_rate_limited() => ErrorClass::RateLimited,
+            ClientError::APIError { code, .. } if *code >= 500 => ErrorClass::Server,
+            ClientError::APIError { .. } => ErrorClass::BadRequest,
+            ClientError::RestClientError {
+                source: rustify::errors::ClientError::RequestError { .. },
+            }
+            | ClientError::StreamError { .. }
+            | ClientError::UnixSocketError { .. } => ErrorClass::Connection,
+            ClientError::TimeoutError { .. } => ErrorClass::Timeout,
+            _ => ErrorClass::Other,
+        }
+    }
+
+    /// Returns the class as a lowercase string, e.g. `not_found`, which is
+    /// suitable as a metric label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorClass::AclDenied => "acl_denied",
+            ErrorClass::BadRequest => "bad_request",
+            ErrorClass::Connection => "connection",
+            ErrorClass::NotFound => "not_found",
+            ErrorClass::Other => "other",
+            ErrorClass::RateLimited => "rate_limited",
+            ErrorClass::Server => "server",
+            ErrorClass::Timeout => "timeout",
+        }
+    }
+}
+
+impl fmt::Display for ErrorClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Query options applied to every request made by a [ConsulClient] unless the
+/// request configures them itself.
+///
+/// Options are only added to the query string of a request when it doesn't
+/// already contain them, so setting an option on a request builder always
+/// takes precedence. The `wait` option is only added to blocking queries and
+/// [ConsistencyMode::DEFAULT] can be used to opt a single request out of the
+/// default `consistency` mode. The ACL token is configured separately through
+/// [ConsulClientSettings::token].
+///
+/// Consul ignores these options on endpoints which don't support them, with
+/// the exception of non-default namespaces and partitions which are rejected
+/// by Consul OSS.
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(setter(into, strip_option), default)]
+pub struct QueryOptions {
+    pub consistency: Option<ConsistencyMode>,
+    pub dc: Option<String>,
+    pub ns: Option<String>,
+    pub partition: Option<String>,
+    pub wait: Option<String>,
+}
+
+impl QueryOptions {
+    /// Returns a default instance of [QueryOptionsBuilder] for configuring
+    /// options.
+    pub fn builder() -> QueryOptionsBuilder {
+        QueryOptionsBuilder::default()
+    }
+}
+
+/// Configures how a [ConsulClient] retries failed requests.
+///
+/// A request is retried when Consul responds with a 5xx status code (which
+/// includes "No cluster leader" errors), when it's rejected by the rate limits
+/// of Consul with a 429 status code, or when the connection to Consul fails.
+/// When Consul sends a `Retry-After` header the retry waits at least that
+/// long. Each retry waits twice as long as the previous one, up to
+/// `max_backoff`, with up to half of the delay randomized when `jitter` is
+/// enabled.
+///
+/// Only `GET` requests are retried by default. Setting `retry_writes` also
+/// retries other requests, with the exception of check-and-set writes and
+/// transactions which are never retried. Requests rejected by rate limits are
+/// retried regardless, since Consul didn't handle them. Streamed requests, such
+/// as snapshots and agent logs, are never retried.
+///
+/// The default policy makes a single attempt, i.e. it never retries.
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into), default)]
+pub struct RetryPolicy {
+    pub initial_backoff: Duration,
+    pub jitter: bool,
+    pub max_attempts: u32,
+    pub max_backoff: Duration,
+    pub retry_writes: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            jitter: true,
+            max_attempts: 1,
+            max_backoff: Duration::from_secs(5),
+            retry_writes: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns a default instance of [RetryPolicyBuilder] for configuring a
+    /// policy.
+    pub fn builder() -> RetryPolicyBuilder {
+        RetryPolicyBuilder::default()
+    }
+
+    /// Returns how long to wait before retrying after the given attempt,
+    /// starting from 1.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        if !self.jitter {
+            return backoff;
+        }
+
+        // A fresh RandomState is randomly seeded, which is enough for jitter
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        let half = backoff / 2;
+        half + half.mul_f64(random)
+    }
+}
+
+/// Configures a token bucket which limits the rate at which a [ConsulClient]
+/// sends requests.
+///
+/// The bucket starts out full, holding `burst` tokens, and is refilled at
+/// `per_second` tokens per second. Every attempt at sending a request takes a
+/// token, waiting for one to become available when the bucket is empty, which
+/// spreads out bursts of requests such as many watches restarting at once.
+/// `per_second` must be positive, [ConsulClient::new] fails with a
+/// [ClientError::InvalidSettingsError] otherwise.
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into), default)]
+pub struct RateLimit {
+    pub burst: u32,
+    pub per_second: f64,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        RateLimit {
+            burst: 100,
+            per_second: 100.0,
+        }
+    }
+}
+
+impl RateLimit {
+    /// Returns a default instance of [RateLimitBuilder] for configuring a
+    /// rate limit.
+    pub fn builder() -> RateLimitBuilder {
+        RateLimitBuilder::default()
+    }
+}
+
+/// Limits the rate and concurrency of the requests sent by a [ConsulClient].
+///
+/// Every attempt at sending a request first waits for one of the
+/// `max_in_flight` permits and then for a token of the [RateLimit], if either
+/// is configured. The permit is held until the response is received, so
+/// blocking queries hold theirs for as long as they block and the limit
+/// should leave room for the number of concurrent watches. Streamed responses
+/// release their permit once the response headers are received.
+#[derive(Debug)]
+pub struct Limiter {
+    bucket: Option<(RateLimit, Mutex<(f64, Instant)>)>,
+    in_flight: Option<Semaphore>,
+}
+
+impl Limiter {
+    /// Creates a new [Limiter] from the given rate limit and maximum number
+    /// of requests in flight.
+    pub fn new(rate_limit: Option<RateLimit>, max_in_flight: Option<usize>) -> Self {
+        Limiter {
+            bucket: rate_limit.map(|r| {
+                let burst = f64::from(r.burst.max(1));
+                (r, Mutex::new((burst, Instant::now())))
+            }),
+            in_flight: max_in_flight.map(|max| Semaphore::new(max.max(1))),
+        }
+    }
+
+    /// Waits until a request may be sent, returning a permit to hold until
+    /// its response is received.
+    pub(crate) async fn acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let permit = match &self.in_flight {
+            // The semaphore is never closed
+            Some(semaphore) => Some(semaphore.acquire().await.unwrap()),
+            None => None,
+        };
+
+        if let Some((limit, state)) = &self.bucket {
+            loop {
+                let wait = {
+                    let mut state = state.lock().unwrap();
+                    let (tokens, refilled) = &mut *state;
+                    let now = Instant::now();
+                    *tokens = (*tokens
+                        + now.duration_since(*refilled).as_secs_f64() * limit.per_second)
+                        .min(f64::from(limit.burst.max(1)));
+                    *refilled = now;
+                    if *tokens >= 1.0 {
+                        *tokens -= 1.0;
+                        break;
+                    }
+                    Duration::from_secs_f64((1.0 - *tokens) / limit.per_second)
+                };
+                tokio::time::sleep(wait).await;
+            }
+        }
+
+        permit
+    }
+}
+
+/// The health of a single server in a [ServerPool].
+#[derive(Clone, Debug)]
+pub struct ServerStatus {
+    pub address: String,
+    pub healthy: bool,
+}
+
+/// Decides the order in which the servers of a [ServerPool] are tried.
+pub trait SelectionPolicy: fmt::Debug + Send + Sync {
+    /// Returns the indexes of the given servers in the order they should be
+    /// tried. Servers which are left out are not tried at all.
+    fn order(&self, servers: &[ServerStatus]) -> Vec<usize>;
+}
+
+/// Tries healthy servers in the order they were configured, followed by
+/// unhealthy servers as a last resort.
+#[derive(Clone, Debug, Default)]
+pub struct Priority;
+
+impl SelectionPolicy for Priority {
+    fn order(&self, servers: &[ServerStatus]) -> Vec<usize> {
+        let (healthy, unhealthy): (Vec<usize>, Vec<usize>) =
+            (0..servers.len()).partition(|i| servers[*i].healthy);
+        healthy.into_iter().chain(unhealthy).collect()
+    }
+}
+
+/// Spreads requests across healthy servers, followed by unhealthy servers as
+/// a last resort.
+#[derive(Debug, Default)]
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl SelectionPolicy for RoundRobin {
+    fn order(&self, servers: &[ServerStatus]) -> Vec<usize> {
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) =
+            (0..servers.len()).partition(|i| servers[*i].healthy);
+        if !healthy.is_empty() {
+            let start = self.next.fetch_add(1, Ordering::Relaxed) % healthy.len();
+            healthy.rotate_left(start);
+        }
+        healthy.into_iter().chain(unhealthy).collect()
+    }
+}
+
+/// A set of Consul servers which requests fail over between.
+///
+/// A server which can't be reached is marked unhealthy for the configured
+/// cooldown, after which it's considered healthy again. Servers are tried in
+/// the order decided by the [SelectionPolicy] of the pool until one responds.
+/// Failing over only happens on connection errors; any response from a server,
+/// including an error response, is returned as is. Failing over happens within
+/// a single attempt of the [RetryPolicy]. Streamed requests always
+/// go to the first server chosen, without failing over.
+pub struct ServerPool {
+    cooldown: Duration,
+    policy: Arc<dyn SelectionPolicy>,
+    servers: Vec<(HTTPClient, Mutex<Option<Instant>>)>,
+}
+
+impl ServerPool {
+    /// Creates a new [ServerPool] from the given clients, one per server.
+    pub fn new(
+        servers: Vec<HTTPClient>,
+        policy: Arc<dyn SelectionPolicy>,
+        cooldown: Duration,
+    ) -> Self {
+        ServerPool {
+            cooldown,
+            policy,
+            servers: servers.into_iter().map(|s| (s, Mutex::new(None))).collect(),
+        }
+    }
+
+    /// Returns the current health of each server in the pool.
+    pub fn statuses(&self) -> Vec<ServerStatus> {
+        self.servers
+            .iter()
+            .map(|(http, failed)| ServerStatus {
+                address: unix::address(&http.base),
+                healthy: failed
+                    .lock()
+                    .unwrap()
+                    .is_none_or(|t| t.elapsed() >= self.cooldown),
+            })
+            .collect()
+    }
+
+    /// Returns the clients of the servers in the order they should be tried.
+    pub fn order(&self) -> Vec<&HTTPClient> {
+        self.policy
+            .order(&self.statuses())
+            .into_iter()
+            .filter_map(|i| self.servers.get(i).map(|(http, _)| http))
+            .collect()
+    }
+
+    /// Marks the server with the given client as unreachable.
+    pub fn mark_failed(&self, http: &HTTPClient) {
+        if let Some((_, failed)) = self.servers.iter().find(|(h, _)| h.base == http.base) {
+            *failed.lock().unwrap() = Some(Instant::now());
+        }
+    }
+
+    /// Marks the server with the given client as reachable.
+    pub fn mark_healthy(&self, http: &HTTPClient) {
+        if let Some((_, failed)) = self.servers.iter().find(|(h, _)| h.base == http.base) {
+            *failed.lock().unwrap() = None;
+        }
+    }
+}
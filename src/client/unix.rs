@@ -0,0 +1,102 @@
+//! Support for reaching a Consul agent listening on a unix domain socket.
+//!
+//! The underlying [HTTPClient][rustify::clients::reqwest::Client] can only
+//! speak TCP, so a `unix://` address is turned into a base URL with the `unix`
+//! scheme which carries the hex encoded socket path as its host. Requests
+//! targeting such a URL are sent over the socket instead, with a `localhost`
+//! host, the same as the Go client does.
+
+use std::io;
+
+/// The prefix of an address which points at a unix domain socket.
+const PREFIX: &str = "unix://";
+
+/// The scheme of a base URL which points at a unix domain socket.
+const SCHEME: &str = "unix";
+
+/// Returns the base URL used for requests to the given address.
+///
+/// Addresses which don't point at a unix domain socket are returned as is.
+pub(crate) fn base(address: &str) -> String {
+    match address.strip_prefix(PREFIX) {
+        Some(path) => {
+            let host: String = path.bytes().map(|b| format!("{:02x}", b)).collect();
+            format!("{}://{}", SCHEME, host)
+        }
+        None => address.to_string(),
+    }
+}
+
+/// Returns the address a base URL was created from, see [base].
+pub(crate) fn address(base: &str) -> String {
+    base.parse::<http::Uri>()
+        .ok()
+        .as_ref()
+        .and_then(socket_path)
+        .map(|path| format!("{}{}", PREFIX, path))
+        .unwrap_or_else(|| base.to_string())
+}
+
+/// Returns the path of the socket a request should be sent over, if any.
+pub(crate) fn socket_path(uri: &http::Uri) -> Option<String> {
+    if uri.scheme_str() != Some(SCHEME) {
+        return None;
+    }
+    let host = uri.host()?;
+    let bytes = (0..host.len())
+        .step_by(2)
+        .map(|i| {
+            host.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Sends a request over the unix domain socket at the given path.
+///
+/// A new connection is made for every request.
+#[cfg(unix)]
+pub(crate) async fn send(
+    path: &str,
+    mut req: http::Request<hyper::Body>,
+) -> io::Result<http::Response<hyper::Body>> {
+    let stream = tokio::net::UnixStream::connect(path).await?;
+    let (mut sender, conn) = hyper::client::conn::handshake(stream)
+        .await
+        .map_err(io::Error::other)?;
+    tokio::spawn(async move {
+        if let Err(e) = conn.await {
+            warn!("Connection to unix socket failed: {}", e);
+        }
+    });
+
+    let target = req
+        .uri()
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/")
+        .parse()
+        .map_err(io::Error::other)?;
+    *req.uri_mut() = target;
+    req.headers_mut().insert(
+        http::header::HOST,
+        http::HeaderValue::from_static("localhost"),
+    );
+
+    sender.send_request(req).await.map_err(io::Error::other)
+}
+
+/// Sends a request over the unix domain socket at the given path.
+///
+/// Unix domain sockets are only supported on unix platforms.
+#[cfg(not(unix))]
+pub(crate) async fn send(
+    path: &str,
+    _: http::Request<hyper::Body>,
+) -> io::Result<http::Response<hyper::Body>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("Unix domain sockets are not supported: {}", path),
+    ))
+}
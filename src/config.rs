@@ -0,0 +1,89 @@
+//! Manages Consul [configuration entries](https://www.consul.io/api-docs/config).
+use crate::{
+    api::{
+        self,
+        config::{
+            common::{ConfigEntry, ConfigEntryKind},
+            requests::{
+                ApplyConfigRequest, ApplyConfigRequestBuilder, DeleteConfigRequest,
+                DeleteConfigRequestBuilder, GetConfigRequest, GetConfigRequestBuilder,
+                ListConfigsRequest, ListConfigsRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Creates or updates the given config entry.
+///
+/// When the `cas` option is set the entry is only written if its modify
+/// index matches, in which case the response indicates whether the write
+/// succeeded.
+///
+/// See [ApplyConfigRequest]
+#[instrument(skip(client, entry, opts), err)]
+pub async fn apply(
+    client: &impl Client,
+    entry: ConfigEntry,
+    opts: Option<&mut ApplyConfigRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = ApplyConfigRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).entry(entry).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Deletes the config entry with the given kind and name.
+///
+/// See [DeleteConfigRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete(
+    client: &impl Client,
+    kind: ConfigEntryKind,
+    name: &str,
+    opts: Option<&mut DeleteConfigRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = DeleteConfigRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .kind(kind)
+        .name(name)
+        .build()
+        .unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Returns the config entry with the given kind and name.
+///
+/// See [GetConfigRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn get(
+    client: &impl Client,
+    kind: ConfigEntryKind,
+    name: &str,
+    opts: Option<&mut GetConfigRequestBuilder>,
+) -> Result<ApiResponse<ConfigEntry>, ClientError> {
+    let mut t = GetConfigRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .kind(kind)
+        .name(name)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists all config entries of the given kind.
+///
+/// See [ListConfigsRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    kind: ConfigEntryKind,
+    opts: Option<&mut ListConfigsRequestBuilder>,
+) -> Result<ApiResponse<Vec<ConfigEntry>>, ClientError> {
+    let mut t = ListConfigsRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).kind(kind).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
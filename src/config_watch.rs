@@ -0,0 +1,271 @@
+//! Configuration loaded from the KV store and reloaded whenever it changes.
+//!
+//! A [ConfigSource] deserializes a struct from either the JSON value of a
+//! single key or from every key under a prefix, then keeps it up to date using
+//! blocking queries. Services can read the latest configuration with
+//! [ConfigSource::get], subscribe to changes with [ConfigSource::subscribe],
+//! or register a callback with [ConfigSource::on_change].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::config_watch::ConfigSource;
+//! use serde::Deserialize;
+//! use std::sync::Arc;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Config {
+//!     workers: u32,
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let mut source = ConfigSource::<Config>::from_key(Arc::new(client), "my-service/config")
+//!     .await
+//!     .unwrap();
+//! println!("Starting {} workers", source.get().workers);
+//!
+//! source.on_change(|config| println!("Now running {} workers", config.workers));
+//! # })
+//! ```
+
+use std::{convert::TryInto, sync::Arc};
+
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use tokio::{
+    sync::{mpsc, oneshot, watch},
+    task::JoinHandle,
+};
+
+use crate::{
+    api::{
+        kv::{common::KVPair, requests::ReadKeyRequest},
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+    kv,
+};
+
+/// A configuration struct kept in sync with the KV store.
+///
+/// Reloading happens in a background task which stops when the
+/// [ConfigSource] is dropped. Values which fail to deserialize, as well as
+/// failed requests, are reported through [ConfigSource::errors] and leave the
+/// last good configuration in place.
+pub struct ConfigSource<T: DeserializeOwned + Send + Sync + 'static> {
+    errors: mpsc::UnboundedReceiver<ClientError>,
+    rx: watch::Receiver<Arc<T>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl<T: DeserializeOwned + Send + Sync + 'static> ConfigSource<T> {
+    /// Loads the configuration from the JSON value at the given key.
+    ///
+    /// Returns once the configuration has been loaded for the first time,
+    /// failing if the key doesn't exist or can't be deserialized.
+    pub async fn from_key<C: Client + 'static>(
+        client: Arc<C>,
+        key: &str,
+    ) -> Result<Self, ClientError> {
+        ConfigSource::start(client, key, false).await
+    }
+
+    /// Loads the configuration from every key under the given prefix.
+    ///
+    /// The keys are turned into a JSON object by splitting them on `/`
+    /// relative to the prefix, so `app/db/host` under the `app/` prefix
+    /// becomes `{"db": {"host": ...}}`. Values which are valid JSON are
+    /// inserted as such and all other values are inserted as strings. An empty
+    /// prefix becomes an empty object.
+    ///
+    /// Returns once the configuration has been loaded for the first time.
+    pub async fn from_prefix<C: Client + 'static>(
+        client: Arc<C>,
+        prefix: &str,
+    ) -> Result<Self, ClientError> {
+        ConfigSource::start(client, prefix, true).await
+    }
+
+    /// Returns a receiver which yields errors encountered while reloading the
+    /// configuration.
+    pub fn errors(&mut self) -> &mut mpsc::UnboundedReceiver<ClientError> {
+        &mut self.errors
+    }
+
+    /// Returns the latest configuration.
+    pub fn get(&self) -> Arc<T> {
+        self.rx.borrow().clone()
+    }
+
+    /// Calls the given function with the new configuration each time it
+    /// changes.
+    pub fn on_change(&mut self, f: impl Fn(Arc<T>) + Send + 'static) {
+        let mut rx = self.subscribe();
+        self.tasks.push(tokio::spawn(async move {
+            while rx.changed().await.is_ok() {
+                let config = rx.borrow_and_update().clone();
+                f(config);
+            }
+        }));
+    }
+
+    /// Stops reloading the configuration, which is equivalent to dropping it.
+    pub fn stop(self) {}
+
+    /// Returns a receiver which is notified each time the configuration
+    /// changes.
+    pub fn subscribe(&self) -> watch::Receiver<Arc<T>> {
+        let mut rx = self.rx.clone();
+        rx.borrow_and_update();
+        rx
+    }
+
+    async fn start<C: Client + 'static>(
+        client: Arc<C>,
+        key: &str,
+        recurse: bool,
+    ) -> Result<Self, ClientError> {
+        let (ready_tx, ready) = oneshot::channel();
+        let (errors_tx, errors) = mpsc::unbounded_channel();
+        let task = tokio::spawn(reload(
+            client,
+            key.to_string(),
+            recurse,
+            ready_tx,
+            errors_tx,
+        ));
+
+        match ready.await {
+            Ok(Ok(rx)) => Ok(ConfigSource {
+                errors,
+                rx,
+                tasks: vec![task],
+            }),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(ClientError::EmptyResponseError),
+        }
+    }
+}
+
+impl<T: DeserializeOwned + Send + Sync + 'static> Drop for ConfigSource<T> {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Watches the given key or prefix, publishing each new configuration.
+///
+/// The result of the first load is sent through `ready`, along with the
+/// receiver for all later configurations.
+async fn reload<C: Client, T: DeserializeOwned + Send + Sync + 'static>(
+    client: Arc<C>,
+    key: String,
+    recurse: bool,
+    ready: oneshot::Sender<Result<watch::Receiver<Arc<T>>, ClientError>>,
+    errors: mpsc::UnboundedSender<ClientError>,
+) {
+    let mut opts = ReadKeyRequest::builder();
+    opts.recurse(recurse);
+    let stream = kv::watch(client.as_ref(), &key, Some(&mut opts));
+    futures::pin_mut!(stream);
+
+    let parse =
+        |res: Result<ApiResponse<Vec<KVPair>>, ClientError>| -> Result<Arc<T>, ClientError> {
+            let kvs = res?.response;
+            if recurse {
+                parse_prefix(&key, kvs)
+            } else {
+                parse_key(kvs)
+            }
+            .map(Arc::new)
+        };
+
+    let tx = match stream.next().await.map(parse) {
+        Some(Ok(config)) => {
+            let (tx, rx) = watch::channel(config);
+            if ready.send(Ok(rx)).is_err() {
+                return;
+            }
+            tx
+        }
+        Some(Err(e)) => {
+            let _ = ready.send(Err(e));
+            return;
+        }
+        None => return,
+    };
+
+    while let Some(res) = stream.next().await {
+        match parse(res) {
+            Ok(config) => {
+                debug!("Reloaded configuration from {}", key);
+                if tx.send(config).is_err() {
+                    return;
+                }
+            }
+            Err(e) => {
+                warn!("Failed reloading configuration from {}: {}", key, e);
+                let _ = errors.send(e);
+            }
+        }
+    }
+}
+
+/// Deserializes the JSON value of the first key.
+fn parse_key<T: DeserializeOwned>(mut kvs: Vec<KVPair>) -> Result<T, ClientError> {
+    if kvs.is_empty() {
+        return Err(ClientError::EmptyResponseError);
+    }
+    let bytes = value(kvs.swap_remove(0))?.unwrap_or_default();
+    serde_json::from_slice(&bytes).map_err(|e| ClientError::JsonDeserializeError { source: e })
+}
+
+/// Deserializes the object built from every key under the prefix.
+fn parse_prefix<T: DeserializeOwned>(prefix: &str, kvs: Vec<KVPair>) -> Result<T, ClientError> {
+    let mut root = Map::new();
+    for kv in kvs {
+        let path = kv.key.strip_prefix(prefix).unwrap_or(&kv.key).to_string();
+        let bytes = match value(kv)? {
+            Some(b) => b,
+            None => continue,
+        };
+        let value = serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()));
+
+        let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let last = match segments.pop() {
+            Some(l) => l,
+            None => continue,
+        };
+        let mut node = &mut root;
+        for segment in segments {
+            let entry = node
+                .entry(segment)
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                *entry = Value::Object(Map::new());
+            }
+            node = entry.as_object_mut().unwrap();
+        }
+        node.insert(last.to_string(), value);
+    }
+
+    serde_json::from_value(Value::Object(root))
+        .map_err(|e| ClientError::JsonDeserializeError { source: e })
+}
+
+/// Returns the value of a key, if it has one.
+fn value(kv: KVPair) -> Result<Option<Vec<u8>>, ClientError> {
+    kv.value.map(|v| v.try_into()).transpose()
+}
@@ -0,0 +1,195 @@
+//! Manages Consul [Connect](https://www.consul.io/api-docs/connect).
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    net::SocketAddr,
+};
+
+use crate::{
+    api::{
+        self,
+        connect::{
+            common::ConnectAuthorization,
+            requests::{AuthorizeRequest, AuthorizeRequestBuilder},
+        },
+        discovery_chain::common::{
+            CompiledDiscoveryChain, DiscoveryGraphNodeType, DiscoveryResolver, DiscoveryTarget,
+        },
+        health::{common::ServiceEntry, requests::ListConnectNodesRequest},
+        ApiResponse, Features,
+    },
+    client::Client,
+    discovery, discovery_chain,
+    error::ClientError,
+    health,
+};
+
+pub mod ca;
+pub mod intention;
+#[cfg(feature = "connect-native")]
+pub mod native;
+pub mod proxy;
+
+/// Authorizes a connection from the client certificate to the target service.
+///
+/// The URI and serial number are taken from the certificate presented by the
+/// connecting service and are evaluated against the configured intentions.
+///
+/// See [AuthorizeRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn authorize(
+    client: &impl Client,
+    target: &str,
+    client_cert_uri: &str,
+    client_cert_serial: &str,
+    opts: Option<&mut AuthorizeRequestBuilder>,
+) -> Result<ApiResponse<ConnectAuthorization>, ClientError> {
+    let mut t = AuthorizeRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .target(target)
+        .client_cert_uri(client_cert_uri)
+        .client_cert_serial(client_cert_serial)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Picks an instance of the given upstream service to connect to, the same way
+/// a sidecar proxy would.
+///
+/// The compiled discovery chain of the service is followed from its start
+/// node, taking the default route of routers and a split of splitters chosen
+/// at random by weight, to a resolver. An instance is then picked at random
+/// among the healthy Connect-capable instances of its target, which are
+/// filtered by the target's subset, or of the first of its failover targets
+/// which has any. Instances with a warning check count as healthy unless the
+/// subset only allows passing instances.
+///
+/// Returns [None] if none of the targets has a healthy instance. The service
+/// the picked instance identifies as over mTLS is the service of its
+/// [Upstream::target], which differs from the given service when the chain
+/// redirects or fails over to another service.
+///
+/// See [discovery_chain::get] and [health::connect]
+#[instrument(skip(client), err)]
+pub async fn upstream_address(
+    client: &impl Client,
+    service: &str,
+) -> Result<Option<Upstream>, ClientError> {
+    let chain = discovery_chain::get(client, service, None)
+        .await?
+        .response
+        .chain;
+    let resolver = match chain_resolver(&chain) {
+        Some(resolver) => resolver,
+        None => {
+            warn!("The discovery chain of {} has no resolver", service);
+            return Ok(None);
+        }
+    };
+
+    let targets = chain.targets.unwrap_or_default();
+    let failover = resolver.failover.map(|f| f.targets).unwrap_or_default();
+    for id in std::iter::once(resolver.target).chain(failover) {
+        let target = match targets.get(&id) {
+            Some(target) => target,
+            None => {
+                warn!("The discovery chain of {} has no target {}", service, id);
+                continue;
+            }
+        };
+        let mut instances = target_instances(client, target).await?;
+        while !instances.is_empty() {
+            let instance = instances.swap_remove(random() as usize % instances.len());
+            if let Some(address) = discovery::socket_addrs(&instance).await.first() {
+                return Ok(Some(Upstream {
+                    address: *address,
+                    instance,
+                    target: target.clone(),
+                }));
+            }
+        }
+        debug!("Target {} of {} has no healthy instances", id, service);
+    }
+    Ok(None)
+}
+
+/// An instance of an upstream service picked by [upstream_address].
+#[derive(Clone, Debug)]
+pub struct Upstream {
+    /// The address to connect to.
+    pub address: SocketAddr,
+    /// The instance, which is a sidecar proxy unless the service is
+    /// Connect-native.
+    pub instance: ServiceEntry,
+    /// The target of the discovery chain the instance was picked from.
+    pub target: DiscoveryTarget,
+}
+
+/// Follows a discovery chain from its start node to a resolver.
+fn chain_resolver(chain: &CompiledDiscoveryChain) -> Option<DiscoveryResolver> {
+    let nodes = chain.nodes.as_ref()?;
+    let mut id = chain.start_node.as_ref()?;
+    // Bounded in case the chain is cyclic
+    for _ in 0..nodes.len() {
+        let node = nodes.get(id)?;
+        id = match node.node_type {
+            DiscoveryGraphNodeType::Resolver => return node.resolver.clone(),
+            // Consul appends the default route after all other routes
+            DiscoveryGraphNodeType::Router => &node.routes.as_ref()?.last()?.next_node,
+            DiscoveryGraphNodeType::Splitter => {
+                let splits = node.splits.as_ref()?;
+                let total: f32 = splits.iter().map(|s| s.weight).sum();
+                let mut point = (random() % 10_000) as f32 / 10_000.0 * total;
+                let split = splits.iter().find(|s| {
+                    point -= s.weight;
+                    point < 0.0
+                });
+                &split.or_else(|| splits.last())?.next_node
+            }
+        };
+    }
+    None
+}
+
+/// Returns the healthy Connect-capable instances of a discovery chain target.
+async fn target_instances(
+    client: &impl Client,
+    target: &DiscoveryTarget,
+) -> Result<Vec<ServiceEntry>, ClientError> {
+    let mut opts = ListConnectNodesRequest::builder();
+    opts.dc(&target.datacenter);
+    if let Some(ns) = target.namespace.as_deref().filter(|ns| *ns != "default") {
+        opts.ns(ns);
+    }
+    if let Some(partition) = target.partition.as_deref().filter(|p| *p != "default") {
+        opts.partition(partition);
+    }
+
+    let subset = target.subset.clone().unwrap_or_default();
+    if let Some(filter) = subset.filter.filter(|f| !f.is_empty()) {
+        opts.features(Features::builder().filter(filter).build().unwrap());
+    }
+    let only_passing = subset.only_passing.unwrap_or_default();
+    let instances = health::connect(client, &target.service, Some(&mut opts))
+        .await?
+        .response;
+    Ok(instances
+        .into_iter()
+        .filter(|instance| {
+            instance
+                .checks
+                .iter()
+                .all(|check| match check.status.as_deref() {
+                    Some("critical") | Some("maintenance") => false,
+                    Some("warning") => !only_passing,
+                    _ => true,
+                })
+        })
+        .collect())
+}
+
+fn random() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
@@ -0,0 +1,74 @@
+use crate::{
+    api::{
+        self,
+        connect::{
+            common::{CAConfig, CARootList, LeafCert},
+            requests::{
+                ListRootsRequest, ListRootsRequestBuilder, ReadConfigRequest,
+                ReadConfigRequestBuilder, ReadLeafRequest, ReadLeafRequestBuilder,
+                UpdateConfigRequest, UpdateConfigRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Reads the current CA configuration.
+///
+/// See [ReadConfigRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn config(
+    client: &impl Client,
+    opts: Option<&mut ReadConfigRequestBuilder>,
+) -> Result<ApiResponse<CAConfig>, ClientError> {
+    let mut t = ReadConfigRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the leaf certificate for the given service.
+///
+/// The certificate and its private key are returned PEM encoded. Leaf
+/// certificates are rotated by Consul, so long-running services should watch
+/// this endpoint using a blocking query.
+///
+/// See [ReadLeafRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn leaf(
+    client: &impl Client,
+    service: &str,
+    opts: Option<&mut ReadLeafRequestBuilder>,
+) -> Result<ApiResponse<LeafCert>, ClientError> {
+    let mut t = ReadLeafRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).service(service).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists the trusted CA root certificates.
+///
+/// See [ListRootsRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn roots(
+    client: &impl Client,
+    opts: Option<&mut ListRootsRequestBuilder>,
+) -> Result<ApiResponse<CARootList>, ClientError> {
+    let mut t = ListRootsRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the CA configuration using the given provider.
+///
+/// See [UpdateConfigRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn update_config(
+    client: &impl Client,
+    provider: &str,
+    opts: Option<&mut UpdateConfigRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = UpdateConfigRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).provider(provider).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
@@ -0,0 +1,286 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    api::{
+        self,
+        connect::{
+            common::{Intention, IntentionAction, IntentionCheck, IntentionMatchType},
+            requests::{
+                CheckIntentionRequest, CheckIntentionRequestBuilder, DeleteIntentionRequest,
+                DeleteIntentionRequestBuilder, ListIntentionsRequest, ListIntentionsRequestBuilder,
+                MatchIntentionsRequest, MatchIntentionsRequestBuilder, ReadIntentionRequest,
+                ReadIntentionRequestBuilder, UpsertIntentionRequest, UpsertIntentionRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    catalog,
+    client::Client,
+    error::ClientError,
+};
+
+/// The wildcard which matches any service in an intention.
+pub const WILDCARD: &str = "*";
+
+/// The services checked to find out the default intention, which no
+/// intention is expected to name.
+const DEFAULT_PROBE: (&str, &str) = ("consulrs-default-source", "consulrs-default-destination");
+
+/// Checks whether a connection from the source to the destination would be
+/// authorized.
+///
+/// See [CheckIntentionRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn check(
+    client: &impl Client,
+    source: &str,
+    destination: &str,
+    opts: Option<&mut CheckIntentionRequestBuilder>,
+) -> Result<ApiResponse<IntentionCheck>, ClientError> {
+    let mut t = CheckIntentionRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .source(source)
+        .destination(destination)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads every intention, the registered services and the default intention
+/// into an [IntentionGraph] which answers which services can connect to each
+/// other.
+///
+/// The default intention is the answer Consul gives when checking a
+/// connection between two services which no intention names, so it reflects
+/// both the ACL default policy and any wildcard intention.
+///
+/// See [ListIntentionsRequest], [catalog::services] and [check]
+#[instrument(skip(client, opts), err)]
+pub async fn graph(
+    client: &impl Client,
+    opts: Option<&mut ListIntentionsRequestBuilder>,
+) -> Result<IntentionGraph, ClientError> {
+    let intentions = list(client, opts).await?.response;
+    let services = catalog::services(client, None).await?.response;
+    let default = default_action(client).await?;
+
+    let mut graph = IntentionGraph::new(intentions, default);
+    graph.services.extend(services.into_keys());
+    Ok(graph)
+}
+
+/// Deletes the intention with the given source and destination.
+///
+/// See [DeleteIntentionRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete(
+    client: &impl Client,
+    source: &str,
+    destination: &str,
+    opts: Option<&mut DeleteIntentionRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = DeleteIntentionRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .source(source)
+        .destination(destination)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists all intentions.
+///
+/// See [ListIntentionsRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListIntentionsRequestBuilder>,
+) -> Result<ApiResponse<Vec<Intention>>, ClientError> {
+    let mut t = ListIntentionsRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists the intentions matching the given service name, keyed by the name.
+///
+/// See [MatchIntentionsRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn matches(
+    client: &impl Client,
+    by: IntentionMatchType,
+    name: &str,
+    opts: Option<&mut MatchIntentionsRequestBuilder>,
+) -> Result<ApiResponse<HashMap<String, Vec<Intention>>>, ClientError> {
+    let mut t = MatchIntentionsRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).by(by).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the intention with the given source and destination.
+///
+/// See [ReadIntentionRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read(
+    client: &impl Client,
+    source: &str,
+    destination: &str,
+    opts: Option<&mut ReadIntentionRequestBuilder>,
+) -> Result<ApiResponse<Intention>, ClientError> {
+    let mut t = ReadIntentionRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .source(source)
+        .destination(destination)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Creates or updates the intention with the given source and destination.
+///
+/// Either an action or L7 permissions must be supplied in the options.
+///
+/// See [UpsertIntentionRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn upsert(
+    client: &impl Client,
+    source: &str,
+    destination: &str,
+    opts: Option<&mut UpsertIntentionRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = UpsertIntentionRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .source(source)
+        .destination(destination)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// The intentions between services, resolved the same way Consul resolves
+/// them when authorizing a connection.
+///
+/// The intention deciding a connection is the most precise one matching it:
+/// one naming both services wins over one naming only the destination, which
+/// wins over one naming only the source, with the [WILDCARD] standing in for
+/// any other service. Connections no intention matches get the default
+/// intention. Intentions with L7 permissions in place of an action count as
+/// allowing connections, since their permissions only apply to requests.
+/// Namespaces and partitions are ignored.
+#[derive(Clone, Debug)]
+pub struct IntentionGraph {
+    default: IntentionAction,
+    intentions: Vec<Intention>,
+    services: BTreeSet<String>,
+}
+
+impl IntentionGraph {
+    /// Returns a new [IntentionGraph] of the given intentions, which falls
+    /// back to the given default for connections none of them match.
+    ///
+    /// The services queried are those named by the intentions, see [graph]
+    /// for including every registered service.
+    pub fn new(intentions: Vec<Intention>, default: IntentionAction) -> Self {
+        let services = intentions
+            .iter()
+            .flat_map(|i| [&i.source_name, &i.destination_name])
+            .flatten()
+            .filter(|name| name.as_str() != WILDCARD)
+            .cloned()
+            .collect();
+        IntentionGraph {
+            default,
+            intentions,
+            services,
+        }
+    }
+
+    /// Returns the action of the intention deciding connections from the
+    /// source to the destination.
+    pub fn action(&self, source: &str, destination: &str) -> IntentionAction {
+        match self.decisive(source, destination) {
+            Some(intention) => intention.action.unwrap_or(IntentionAction::Allow),
+            None => self.default,
+        }
+    }
+
+    /// Returns the default intention.
+    pub fn default_action(&self) -> IntentionAction {
+        self.default
+    }
+
+    /// Returns the intention deciding connections from the source to the
+    /// destination, or [None] if the default intention decides them.
+    pub fn decisive(&self, source: &str, destination: &str) -> Option<&Intention> {
+        self.intentions
+            .iter()
+            .filter_map(|i| {
+                let source = precision(i.source_name.as_deref(), source)?;
+                let destination = precision(i.destination_name.as_deref(), destination)?;
+                Some(((destination, source), i))
+            })
+            .max_by_key(|(precedence, _)| *precedence)
+            .map(|(_, i)| i)
+    }
+
+    /// Returns the services the given source may connect to.
+    pub fn destinations(&self, source: &str) -> Vec<&str> {
+        self.services
+            .iter()
+            .filter(|d| d.as_str() != source && self.is_allowed(source, d))
+            .map(|d| d.as_str())
+            .collect()
+    }
+
+    /// Returns the intentions.
+    pub fn intentions(&self) -> &[Intention] {
+        &self.intentions
+    }
+
+    /// Returns whether connections from the source to the destination are
+    /// allowed.
+    pub fn is_allowed(&self, source: &str, destination: &str) -> bool {
+        self.action(source, destination) == IntentionAction::Allow
+    }
+
+    /// Returns the services the graph is queried for.
+    pub fn services(&self) -> &BTreeSet<String> {
+        &self.services
+    }
+
+    /// Returns the services which may connect to the given destination.
+    pub fn sources(&self, destination: &str) -> Vec<&str> {
+        self.services
+            .iter()
+            .filter(|s| s.as_str() != destination && self.is_allowed(s, destination))
+            .map(|s| s.as_str())
+            .collect()
+    }
+}
+
+/// Returns how precisely the name in an intention matches the given service,
+/// i.e. 1 for the service itself and 0 for the wildcard, or [None] if it
+/// doesn't match it.
+fn precision(name: Option<&str>, service: &str) -> Option<u8> {
+    match name {
+        Some(name) if name == service => Some(1),
+        Some(WILDCARD) => Some(0),
+        _ => None,
+    }
+}
+
+/// Returns the action Consul takes for connections which no intention names.
+pub(crate) async fn default_action(client: &impl Client) -> Result<IntentionAction, ClientError> {
+    let (source, destination) = DEFAULT_PROBE;
+    let allowed = check(client, source, destination, None)
+        .await?
+        .response
+        .allowed;
+    Ok(match allowed {
+        true => IntentionAction::Allow,
+        false => IntentionAction::Deny,
+    })
+}
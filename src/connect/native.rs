@@ -0,0 +1,475 @@
+//! Provides TLS configurations for Connect-native services using rustls.
+//!
+//! A [TlsProvider] fetches the CA roots and the leaf certificate of a service
+//! and keeps them up to date with blocking queries, along with the intentions
+//! which decide who may connect to it. The configurations it returns present
+//! the leaf certificate and verify peers by the SPIFFE ID in their
+//! certificate rather than by host name:
+//!
+//! * [TlsProvider::server_config] accepts clients whose certificate chains to
+//!   the CA roots and whose service is allowed to connect by the intentions.
+//! * [TlsProvider::client_config] accepts servers whose certificate chains to
+//!   the CA roots and identifies them as the given service. The server name
+//!   used to connect is ignored.
+//!
+//! Configurations keep working across certificate and CA rotations, so they
+//! only need to be created once. They require the `connect-native` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::connect::native::TlsProvider;
+//! use std::{convert::TryInto, sync::Arc};
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let provider = TlsProvider::new(Arc::new(client), "web").await.unwrap();
+//!
+//! // Serve connections from services allowed to connect to web
+//! let acceptor = rustls::ServerConnection::new(provider.server_config()).unwrap();
+//!
+//! // Connect to db
+//! let connector =
+//!     rustls::ClientConnection::new(provider.client_config("db"), "db".try_into().unwrap())
+//!         .unwrap();
+//! # })
+//! ```
+use std::{convert::TryFrom, io::BufReader, sync::Arc, time::SystemTime};
+
+use futures::StreamExt;
+use rustls::{
+    client::{
+        verify_server_cert_signed_by_trust_anchor, ResolvesClientCert, ServerCertVerified,
+        ServerCertVerifier,
+    },
+    server::{
+        AllowAnyAuthenticatedClient, ClientCertVerified, ClientCertVerifier, ClientHello,
+        ParsedCertificate, ResolvesServerCert,
+    },
+    sign::{self, CertifiedKey},
+    Certificate, CertificateError, ClientConfig, DistinguishedName, PrivateKey, RootCertStore,
+    ServerConfig, ServerName, SignatureScheme,
+};
+use tokio::{
+    sync::{mpsc, watch},
+    task::JoinHandle,
+};
+use x509_parser::{certificate::X509Certificate, extensions::GeneralName, prelude::FromDer};
+
+use crate::{
+    api::{
+        connect::{
+            common::{CARootList, LeafCert},
+            requests::{ListIntentionsRequest, ListRootsRequest, ReadLeafRequest},
+        },
+        watch::Watcher,
+    },
+    client::Client,
+    connect::{
+        ca,
+        intention::{self, IntentionGraph},
+    },
+    error::ClientError,
+};
+
+/// The TLS configurations of a Connect-native service.
+///
+/// Certificates and intentions are refreshed in background tasks which stop
+/// when the [TlsProvider] is dropped, after which configurations created by it
+/// keep using the last known certificates and intentions. Failed requests are
+/// retried with the backoff of a [Watcher] and reported through
+/// [TlsProvider::errors].
+pub struct TlsProvider {
+    errors: mpsc::UnboundedReceiver<ClientError>,
+    server: Arc<ServerConfig>,
+    shared: Arc<Shared>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl TlsProvider {
+    /// Fetches the CA roots, the leaf certificate of the given service and
+    /// the intentions, and starts keeping them up to date.
+    ///
+    /// See [ca::roots], [ca::leaf] and [intention::list]
+    #[instrument(skip(client), err)]
+    pub async fn new<C: Client + 'static>(
+        client: Arc<C>,
+        service: &str,
+    ) -> Result<Self, ClientError> {
+        let roots = ca::roots(client.as_ref(), None).await?.response;
+        let leaf = ca::leaf(client.as_ref(), service, None).await?.response;
+        let intentions = intention::list(client.as_ref(), None).await?.response;
+        let default = intention::default_action(client.as_ref()).await?;
+
+        let (identity_tx, identity) = watch::channel(Arc::new(Identity::new(&roots, &leaf)?));
+        let (graph_tx, graph) = watch::channel(Arc::new(IntentionGraph::new(intentions, default)));
+        let (errors_tx, errors) = mpsc::unbounded_channel();
+        let shared = Arc::new(Shared {
+            graph,
+            identity,
+            service: service.to_string(),
+        });
+        let server = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_client_cert_verifier(Arc::new(IntentionVerifier(shared.clone())))
+            .with_cert_resolver(Arc::new(Resolver(shared.clone())));
+
+        let tasks = vec![
+            tokio::spawn(refresh_certs(
+                client.clone(),
+                service.to_string(),
+                roots,
+                leaf,
+                identity_tx,
+                errors_tx.clone(),
+            )),
+            tokio::spawn(refresh_intentions(client, graph_tx, errors_tx)),
+        ];
+        Ok(TlsProvider {
+            errors,
+            server: Arc::new(server),
+            shared,
+            tasks,
+        })
+    }
+
+    /// Returns a configuration for connecting to the given service.
+    pub fn client_config(&self, service: &str) -> Arc<ClientConfig> {
+        let config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(ServiceVerifier {
+                service: service.to_string(),
+                shared: self.shared.clone(),
+            }))
+            .with_client_cert_resolver(Arc::new(Resolver(self.shared.clone())));
+        Arc::new(config)
+    }
+
+    /// Returns a receiver which yields errors encountered while refreshing
+    /// the certificates or intentions.
+    pub fn errors(&mut self) -> &mut mpsc::UnboundedReceiver<ClientError> {
+        &mut self.errors
+    }
+
+    /// Returns the latest intentions.
+    pub fn intentions(&self) -> Arc<IntentionGraph> {
+        self.shared.graph.borrow().clone()
+    }
+
+    /// Returns a configuration for accepting connections to the service.
+    pub fn server_config(&self) -> Arc<ServerConfig> {
+        self.server.clone()
+    }
+
+    /// Returns the name of the service.
+    pub fn service(&self) -> &str {
+        &self.shared.service
+    }
+
+    /// Returns the trust domain of the CA roots.
+    pub fn trust_domain(&self) -> String {
+        self.shared.identity().trust_domain.clone()
+    }
+}
+
+impl Drop for TlsProvider {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// The certificates of a service.
+struct Identity {
+    clients: AllowAnyAuthenticatedClient,
+    key: Arc<CertifiedKey>,
+    roots: RootCertStore,
+    trust_domain: String,
+}
+
+impl Identity {
+    fn new(roots: &CARootList, leaf: &LeafCert) -> Result<Self, ClientError> {
+        let mut store = RootCertStore::empty();
+        for root in roots.roots.iter().flatten() {
+            let pem = root.root_cert.as_deref().unwrap_or_default();
+            for cert in pem_certs(pem)? {
+                store.add(&cert).map_err(tls_error)?;
+            }
+        }
+
+        let chain = pem_certs(leaf.cert_pem.as_deref().unwrap_or_default())?;
+        let key = pem_key(leaf.private_key_pem.as_deref().unwrap_or_default())?;
+        let key = sign::any_supported_type(&key).map_err(tls_error)?;
+        Ok(Identity {
+            clients: AllowAnyAuthenticatedClient::new(store.clone()),
+            key: Arc::new(CertifiedKey::new(chain, key)),
+            roots: store,
+            trust_domain: roots.trust_domain.clone().unwrap_or_default(),
+        })
+    }
+}
+
+/// The state shared by a [TlsProvider] and its configurations.
+struct Shared {
+    graph: watch::Receiver<Arc<IntentionGraph>>,
+    identity: watch::Receiver<Arc<Identity>>,
+    service: String,
+}
+
+impl Shared {
+    fn identity(&self) -> Arc<Identity> {
+        self.identity.borrow().clone()
+    }
+
+    /// Returns the service in the SPIFFE ID of a peer certificate, failing if
+    /// it doesn't have one in the trust domain.
+    fn peer(&self, identity: &Identity, cert: &Certificate) -> Result<String, rustls::Error> {
+        let peer = san_uris(&cert.0)
+            .unwrap_or_default()
+            .iter()
+            .find_map(|uri| spiffe_service(uri, &identity.trust_domain));
+        peer.ok_or(rustls::Error::InvalidCertificate(
+            CertificateError::NotValidForName,
+        ))
+    }
+}
+
+/// Presents the latest leaf certificate.
+struct Resolver(Arc<Shared>);
+
+impl ResolvesClientCert for Resolver {
+    fn resolve(&self, _: &[&[u8]], _: &[SignatureScheme]) -> Option<Arc<CertifiedKey>> {
+        Some(self.0.identity().key.clone())
+    }
+
+    fn has_certs(&self) -> bool {
+        true
+    }
+}
+
+impl ResolvesServerCert for Resolver {
+    fn resolve(&self, _: ClientHello) -> Option<Arc<CertifiedKey>> {
+        Some(self.0.identity().key.clone())
+    }
+}
+
+/// Accepts clients which the intentions allow to connect to the service.
+struct IntentionVerifier(Arc<Shared>);
+
+impl ClientCertVerifier for IntentionVerifier {
+    fn client_auth_root_subjects(&self) -> &[DistinguishedName] {
+        // The roots change over time, and clients only have one certificate
+        // to offer anyway
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        now: SystemTime,
+    ) -> Result<ClientCertVerified, rustls::Error> {
+        let identity = self.0.identity();
+        identity
+            .clients
+            .verify_client_cert(end_entity, intermediates, now)?;
+
+        let source = self.0.peer(&identity, end_entity)?;
+        if !self.0.graph.borrow().is_allowed(&source, &self.0.service) {
+            debug!(
+                "Rejecting connection from {} to {} denied by intentions",
+                source, self.0.service
+            );
+            return Err(rustls::Error::InvalidCertificate(
+                CertificateError::ApplicationVerificationFailure,
+            ));
+        }
+        Ok(ClientCertVerified::assertion())
+    }
+}
+
+/// Accepts servers identified as the given service.
+struct ServiceVerifier {
+    service: String,
+    shared: Arc<Shared>,
+}
+
+impl ServerCertVerifier for ServiceVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        _: &ServerName,
+        _: &mut dyn Iterator<Item = &[u8]>,
+        _: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let identity = self.shared.identity();
+        let cert = ParsedCertificate::try_from(end_entity)?;
+        verify_server_cert_signed_by_trust_anchor(&cert, &identity.roots, intermediates, now)?;
+
+        let service = self.shared.peer(&identity, end_entity)?;
+        if service != self.service {
+            debug!("Expected {} but the server is {}", self.service, service);
+            return Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName,
+            ));
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn request_scts(&self) -> bool {
+        false
+    }
+}
+
+/// Keeps the certificates of the service up to date.
+async fn refresh_certs<C: Client>(
+    client: Arc<C>,
+    service: String,
+    mut roots: CARootList,
+    mut leaf: LeafCert,
+    tx: watch::Sender<Arc<Identity>>,
+    errors: mpsc::UnboundedSender<ClientError>,
+) {
+    let endpoint = ListRootsRequest::builder().build().unwrap();
+    let roots_stream = Watcher::new(client.as_ref(), endpoint)
+        .stream()
+        .map(|res| res.map(|r| Update::Roots(r.response)));
+    let endpoint = ReadLeafRequest::builder().service(service).build().unwrap();
+    let leaf_stream = Watcher::new(client.as_ref(), endpoint)
+        .stream()
+        .map(|res| res.map(|r| Update::Leaf(r.response)));
+    let stream = futures::stream::select(roots_stream, leaf_stream);
+    futures::pin_mut!(stream);
+
+    while let Some(res) = stream.next().await {
+        let identity = res.and_then(|update| {
+            match update {
+                Update::Leaf(l) => leaf = l,
+                Update::Roots(r) => roots = r,
+            }
+            Identity::new(&roots, &leaf)
+        });
+        match identity {
+            Ok(identity) => {
+                debug!(
+                    "Updating certificates of {}",
+                    leaf.service.as_deref().unwrap_or_default()
+                );
+                if tx.send(Arc::new(identity)).is_err() {
+                    return;
+                }
+            }
+            Err(e) => {
+                warn!("Failed refreshing certificates: {}", e);
+                let _ = errors.send(e);
+            }
+        }
+    }
+}
+
+/// Keeps the intentions up to date.
+async fn refresh_intentions<C: Client>(
+    client: Arc<C>,
+    tx: watch::Sender<Arc<IntentionGraph>>,
+    errors: mpsc::UnboundedSender<ClientError>,
+) {
+    let endpoint = ListIntentionsRequest::builder().build().unwrap();
+    let stream = Watcher::new(client.as_ref(), endpoint).stream();
+    futures::pin_mut!(stream);
+
+    while let Some(res) = stream.next().await {
+        let graph = match res {
+            Ok(res) => intention::default_action(client.as_ref())
+                .await
+                .map(|default| IntentionGraph::new(res.response, default)),
+            Err(e) => Err(e),
+        };
+        match graph {
+            Ok(graph) => {
+                if tx.send(Arc::new(graph)).is_err() {
+                    return;
+                }
+            }
+            Err(e) => {
+                warn!("Failed refreshing intentions: {}", e);
+                let _ = errors.send(e);
+            }
+        }
+    }
+}
+
+/// A changed result watched by [refresh_certs].
+enum Update {
+    Leaf(LeafCert),
+    Roots(CARootList),
+}
+
+fn tls_error(e: impl std::fmt::Display) -> ClientError {
+    ClientError::TlsConfigError {
+        message: e.to_string(),
+    }
+}
+
+fn pem_certs(pem: &str) -> Result<Vec<Certificate>, ClientError> {
+    let certs = rustls_pemfile::certs(&mut BufReader::new(pem.as_bytes())).map_err(tls_error)?;
+    match certs.is_empty() {
+        true => Err(tls_error("no certificate found")),
+        false => Ok(certs.into_iter().map(Certificate).collect()),
+    }
+}
+
+fn pem_key(pem: &str) -> Result<PrivateKey, ClientError> {
+    let items = rustls_pemfile::read_all(&mut BufReader::new(pem.as_bytes())).map_err(tls_error)?;
+    items
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::ECKey(key)
+            | rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| tls_error("no private key found"))
+}
+
+/// Returns the service of a SPIFFE ID issued by Connect, i.e.
+/// `spiffe://<trust domain>/ns/default/dc/dc1/svc/web`, if it's in the given
+/// trust domain.
+fn spiffe_service(uri: &str, trust_domain: &str) -> Option<String> {
+    let mut parts = uri.strip_prefix("spiffe://")?.split('/');
+    if !parts.next()?.eq_ignore_ascii_case(trust_domain) {
+        return None;
+    }
+    let parts: Vec<&str> = parts.collect();
+    parts
+        .chunks(2)
+        .find(|pair| pair.len() == 2 && pair[0] == "svc")
+        .map(|pair| pair[1].to_string())
+}
+
+/// Returns the URIs among the subject alternative names of a DER encoded
+/// certificate.
+fn san_uris(cert: &[u8]) -> Option<Vec<String>> {
+    let (_, cert) = X509Certificate::from_der(cert).ok()?;
+    let san = match cert.subject_alternative_name().ok()? {
+        Some(san) => san.value,
+        None => return Some(Vec::new()),
+    };
+    let uris = san
+        .general_names
+        .iter()
+        .filter_map(|name| match name {
+            GeneralName::URI(uri) => Some(uri.to_string()),
+            _ => None,
+        })
+        .collect();
+    Some(uris)
+}
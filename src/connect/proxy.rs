@@ -0,0 +1,248 @@
+//! Generates Envoy bootstrap configurations for Connect proxies.
+//!
+//! Envoy is started with a bootstrap configuration which tells it how to reach
+//! the xDS server of the local Consul agent, from which it then receives the
+//! rest of its configuration. [bootstrap] generates the same configuration as
+//! `consul connect envoy -bootstrap` for a proxy registered on the agent, so
+//! a process supervisor can launch Envoy without the Consul CLI.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::connect::proxy::{self, BootstrapOptions};
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let config = proxy::bootstrap(&client, "web-sidecar-proxy", &BootstrapOptions::default())
+//!     .await
+//!     .unwrap();
+//! std::fs::write("envoy.json", serde_json::to_vec_pretty(&config).unwrap()).unwrap();
+//! // envoy --config-path envoy.json
+//! # })
+//! ```
+use derive_builder::Builder;
+use serde_json::{json, Value};
+
+use crate::{agent, client::Client, error::ClientError, service};
+
+/// The service kinds which are run by Envoy.
+const PROXY_KINDS: [&str; 4] = [
+    "connect-proxy",
+    "ingress-gateway",
+    "mesh-gateway",
+    "terminating-gateway",
+];
+
+/// Options for generating a bootstrap configuration with [bootstrap].
+///
+/// The address of the xDS server defaults to the gRPC port of the agent on
+/// the host the client connects to, using the TLS port when `ca_file` is set.
+/// An address starting with `unix://` is a Unix domain socket. The token
+/// defaults to the token of the client.
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into), default)]
+pub struct BootstrapOptions {
+    pub admin_access_log_path: String,
+    pub admin_bind: String,
+    #[builder(setter(into, strip_option))]
+    pub ca_file: Option<String>,
+    #[builder(setter(into, strip_option))]
+    pub grpc_address: Option<String>,
+    #[builder(setter(into, strip_option))]
+    pub token: Option<String>,
+}
+
+impl Default for BootstrapOptions {
+    fn default() -> Self {
+        BootstrapOptions {
+            admin_access_log_path: String::from("/dev/null"),
+            admin_bind: String::from("127.0.0.1:19000"),
+            ca_file: None,
+            grpc_address: None,
+            token: None,
+        }
+    }
+}
+
+impl BootstrapOptions {
+    /// Returns a default instance of [BootstrapOptionsBuilder] for
+    /// configuring a bootstrap configuration.
+    pub fn builder() -> BootstrapOptionsBuilder {
+        BootstrapOptionsBuilder::default()
+    }
+}
+
+/// Generates the Envoy bootstrap configuration of the proxy with the given ID
+/// registered on the local agent.
+///
+/// The proxy must be a sidecar proxy or a gateway. Its configuration and the
+/// gRPC ports are read from the agent, so the address of the xDS server only
+/// needs to be set in the options when Envoy reaches the agent through a
+/// different address than the client.
+///
+/// See [service::read] and [agent::self_info]
+#[instrument(skip(client, opts), err)]
+pub async fn bootstrap(
+    client: &impl Client,
+    proxy_id: &str,
+    opts: &BootstrapOptions,
+) -> Result<Value, ClientError> {
+    let proxy = service::read(client, proxy_id, None).await?.response;
+    let kind = proxy.kind.as_deref().unwrap_or_default();
+    if !PROXY_KINDS.contains(&kind) {
+        return Err(ClientError::ProxyBootstrapError {
+            message: format!("{} is not a proxy or gateway", proxy_id),
+        });
+    }
+
+    let xds = match &opts.grpc_address {
+        Some(address) => address.clone(),
+        None => grpc_address(client, opts.ca_file.is_some()).await?,
+    };
+    let mut cluster = json!({
+        "name": "local_agent",
+        "ignore_health_on_host_removal": false,
+        "connect_timeout": "1s",
+        "type": "STATIC",
+        "typed_extension_protocol_options": {
+            "envoy.extensions.upstreams.http.v3.HttpProtocolOptions": {
+                "@type": "type.googleapis.com/envoy.extensions.upstreams.http.v3.HttpProtocolOptions",
+                "explicit_http_config": {
+                    "http2_protocol_options": {}
+                }
+            }
+        },
+        "loaded_assignment": {
+            "cluster_name": "local_agent",
+            "endpoints": [{
+                "lb_endpoints": [{
+                    "endpoint": {
+                        "address": address(&xds)?
+                    }
+                }]
+            }]
+        }
+    });
+    if let Some(ca_file) = &opts.ca_file {
+        cluster["transport_socket"] = json!({
+            "name": "tls",
+            "typed_config": {
+                "@type": "type.googleapis.com/envoy.extensions.transport_sockets.tls.v3.UpstreamTlsContext",
+                "common_tls_context": {
+                    "validation_context": {
+                        "trusted_ca": {
+                            "filename": ca_file
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    let token = opts
+        .token
+        .clone()
+        .or_else(|| client.settings().token.clone())
+        .unwrap_or_default();
+    let service = proxy.service.unwrap_or_else(|| proxy_id.to_string());
+    Ok(json!({
+        "admin": {
+            "access_log_path": opts.admin_access_log_path,
+            "address": address(&opts.admin_bind)?
+        },
+        "node": {
+            "cluster": service,
+            "id": proxy_id,
+            "metadata": {
+                "namespace": proxy.namespace.unwrap_or_else(|| String::from("default")),
+                "partition": "default"
+            }
+        },
+        "static_resources": {
+            "clusters": [cluster]
+        },
+        "stats_config": {
+            "use_all_default_tags": true
+        },
+        "dynamic_resources": {
+            "lds_config": {
+                "ads": {},
+                "resource_api_version": "V3"
+            },
+            "cds_config": {
+                "ads": {},
+                "resource_api_version": "V3"
+            },
+            "ads_config": {
+                "api_type": "DELTA_GRPC",
+                "transport_api_version": "V3",
+                "grpc_services": {
+                    "initial_metadata": [{
+                        "key": "x-consul-token",
+                        "value": token
+                    }],
+                    "envoy_grpc": {
+                        "cluster_name": "local_agent"
+                    }
+                }
+            }
+        }
+    }))
+}
+
+/// Returns the address of the gRPC server of the agent, on the host the client
+/// connects to.
+async fn grpc_address(client: &impl Client, tls: bool) -> Result<String, ClientError> {
+    let config = agent::self_info(client, None).await?.response.debug_config;
+    let port = |name: &str| {
+        config
+            .as_ref()
+            .and_then(|c| c.get(name))
+            .and_then(Value::as_i64)
+            .filter(|p| *p > 0)
+    };
+    let port = match tls {
+        true => port("GRPCTLSPort"),
+        false => port("GRPCPort"),
+    };
+    let port = port.ok_or_else(|| ClientError::ProxyBootstrapError {
+        message: String::from("the agent doesn't serve gRPC"),
+    })?;
+
+    let address = &client.settings().address;
+    let host = url::Url::parse(address)
+        .ok()
+        .and_then(|u| u.host_str().map(String::from))
+        .unwrap_or_else(|| String::from("127.0.0.1"));
+    Ok(format!("{}:{}", host, port))
+}
+
+/// Returns the Envoy address of the given `host:port` or `unix://` address.
+fn address(address: &str) -> Result<Value, ClientError> {
+    if let Some(path) = address.strip_prefix("unix://") {
+        return Ok(json!({ "pipe": { "path": path } }));
+    }
+    let (host, port) = address
+        .rsplit_once(':')
+        .and_then(|(h, p)| {
+            Some((
+                h.trim_matches(|c| c == '[' || c == ']'),
+                p.parse::<u16>().ok()?,
+            ))
+        })
+        .ok_or_else(|| ClientError::ProxyBootstrapError {
+            message: format!("invalid address {}", address),
+        })?;
+    Ok(json!({
+        "socket_address": {
+            "address": host,
+            "port_value": port
+        }
+    }))
+}
@@ -0,0 +1,81 @@
+//! Reads and updates Consul [network coordinates](https://www.consul.io/api-docs/coordinate).
+//!
+//! The estimated round trip time between two nodes can be calculated from
+//! their coordinates using [Coordinate::distance_to].
+use crate::{
+    api::{
+        self,
+        coordinate::{
+            common::{Coordinate, CoordinateEntry, DatacenterCoordinates},
+            requests::{
+                ListDatacenterCoordinatesRequest, ListDatacenterCoordinatesRequestBuilder,
+                ListNodeCoordinatesRequest, ListNodeCoordinatesRequestBuilder,
+                ReadNodeCoordinatesRequest, ReadNodeCoordinatesRequestBuilder,
+                UpdateNodeCoordinatesRequest, UpdateNodeCoordinatesRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Returns the WAN coordinates of all servers, organized by datacenter.
+///
+/// See [ListDatacenterCoordinatesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn datacenters(
+    client: &impl Client,
+    opts: Option<&mut ListDatacenterCoordinatesRequestBuilder>,
+) -> Result<ApiResponse<Vec<DatacenterCoordinates>>, ClientError> {
+    let mut t = ListDatacenterCoordinatesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Returns the LAN coordinates of the given node.
+///
+/// See [ReadNodeCoordinatesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn node(
+    client: &impl Client,
+    node: &str,
+    opts: Option<&mut ReadNodeCoordinatesRequestBuilder>,
+) -> Result<ApiResponse<Vec<CoordinateEntry>>, ClientError> {
+    let mut t = ReadNodeCoordinatesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).node(node).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Returns the LAN coordinates of all nodes.
+///
+/// See [ListNodeCoordinatesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn nodes(
+    client: &impl Client,
+    opts: Option<&mut ListNodeCoordinatesRequestBuilder>,
+) -> Result<ApiResponse<Vec<CoordinateEntry>>, ClientError> {
+    let mut t = ListNodeCoordinatesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the LAN coordinates of the given node.
+///
+/// See [UpdateNodeCoordinatesRequest]
+#[instrument(skip(client, coord, opts), err)]
+pub async fn update(
+    client: &impl Client,
+    node: &str,
+    coord: Coordinate,
+    opts: Option<&mut UpdateNodeCoordinatesRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = UpdateNodeCoordinatesRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .node(node)
+        .coord(coord)
+        .build()
+        .unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
@@ -0,0 +1,309 @@
+//! Client-side service discovery and load balancing.
+//!
+//! A [ServiceResolver] keeps a cached list of the instances of a service which
+//! is updated using blocking queries, and picks an instance from it for each
+//! request using a [Strategy].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::discovery::ServiceResolver;
+//! use std::sync::Arc;
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let resolver = ServiceResolver::new(Arc::new(client), "billing").passing(true);
+//! if let Some(instance) = resolver.pick().await.unwrap() {
+//!     println!("Sending request to {:?}", instance.service.address);
+//! }
+//! # })
+//! ```
+
+#[cfg(feature = "consulrs-tower")]
+pub mod tower;
+
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hasher},
+    net::{IpAddr, SocketAddr},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use futures::StreamExt;
+use tokio::{
+    sync::{oneshot, watch, OnceCell},
+    task::JoinHandle,
+};
+
+use crate::{
+    api::health::{
+        common::ServiceEntry,
+        requests::{ListServiceNodesRequest, ListServiceNodesRequestBuilder},
+    },
+    client::Client,
+    error::ClientError,
+    health,
+};
+
+/// How a [ServiceResolver] picks an instance.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Strategy {
+    /// Picks the least recently picked instance, preferring instances which
+    /// were never picked.
+    LeastRecentlyUsed,
+    /// Picks a random instance.
+    Random,
+    /// Picks each instance in turn.
+    #[default]
+    RoundRobin,
+}
+
+/// Returns the socket addresses of the instances of the given service.
+///
+/// Each address is made up of the address of the service, or of its node when
+/// the service has none, and the port of the service, the same as Consul DNS.
+/// Instances registered with a hostname are resolved using the system
+/// resolver, skipping those which can't be resolved.
+#[instrument(skip(client), err)]
+pub async fn resolve(
+    client: &impl Client,
+    service: &str,
+    tag: Option<&str>,
+    passing: bool,
+) -> Result<Vec<SocketAddr>, ClientError> {
+    let mut opts = ListServiceNodesRequest::builder();
+    opts.passing(passing);
+    if let Some(tag) = tag {
+        opts.tag(tag);
+    }
+    let res = health::service(client, service, Some(&mut opts)).await?;
+
+    let mut addrs = Vec::new();
+    for entry in res.response {
+        addrs.extend(socket_addrs(&entry).await);
+    }
+    Ok(addrs)
+}
+
+/// A watch-updated list of the instances of a service.
+///
+/// The instances are loaded the first time they're needed, after which a
+/// background task keeps them up to date until the [ServiceResolver] is
+/// dropped. Failed updates are logged and leave the last known instances in
+/// place.
+pub struct ServiceResolver<C: Client + 'static> {
+    client: Arc<C>,
+    instances: OnceCell<Watch>,
+    last_used: Mutex<HashMap<String, Instant>>,
+    next: AtomicUsize,
+    passing: bool,
+    service: String,
+    strategy: Strategy,
+    tag: Option<String>,
+}
+
+impl<C: Client + 'static> ServiceResolver<C> {
+    /// Returns a new [ServiceResolver] for the given service.
+    pub fn new(client: Arc<C>, service: &str) -> Self {
+        ServiceResolver {
+            client,
+            instances: OnceCell::new(),
+            last_used: Mutex::new(HashMap::new()),
+            next: AtomicUsize::new(0),
+            passing: false,
+            service: service.to_string(),
+            strategy: Strategy::default(),
+            tag: None,
+        }
+    }
+
+    /// Sets whether only instances with all checks passing are returned.
+    pub fn passing(mut self, passing: bool) -> Self {
+        self.passing = passing;
+        self
+    }
+
+    /// Sets the [Strategy] used to pick an instance, which defaults to
+    /// [Strategy::RoundRobin].
+    pub fn strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the tag instances must have to be returned.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tag = Some(tag.to_string());
+        self
+    }
+
+    /// Returns the current instances of the service.
+    pub async fn instances(&self) -> Result<Arc<Vec<ServiceEntry>>, ClientError> {
+        let watch = self.instances.get_or_try_init(|| self.start()).await?;
+        let instances = watch.rx.borrow().clone();
+        Ok(instances)
+    }
+
+    /// Returns a receiver which is notified each time the instances of the
+    /// service change.
+    pub async fn subscribe(&self) -> Result<watch::Receiver<Arc<Vec<ServiceEntry>>>, ClientError> {
+        let watch = self.instances.get_or_try_init(|| self.start()).await?;
+        let mut rx = watch.rx.clone();
+        rx.borrow_and_update();
+        Ok(rx)
+    }
+
+    /// Picks an instance of the service using the configured [Strategy].
+    ///
+    /// Returns [None] if the service has no instances.
+    pub async fn pick(&self) -> Result<Option<ServiceEntry>, ClientError> {
+        let instances = self.instances().await?;
+        if instances.is_empty() {
+            return Ok(None);
+        }
+
+        let i = match self.strategy {
+            Strategy::LeastRecentlyUsed => {
+                let mut last_used = self.last_used.lock().unwrap();
+                let i = (0..instances.len())
+                    .min_by_key(|i| last_used.get(&instance_id(&instances[*i])))
+                    .unwrap();
+                last_used.insert(instance_id(&instances[i]), Instant::now());
+                last_used.retain(|id, _| instances.iter().any(|s| instance_id(s) == *id));
+                i
+            }
+            Strategy::Random => {
+                RandomState::new().build_hasher().finish() as usize % instances.len()
+            }
+            Strategy::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed) % instances.len(),
+        };
+        Ok(Some(instances[i].clone()))
+    }
+
+    async fn start(&self) -> Result<Watch, ClientError> {
+        let mut opts = ListServiceNodesRequest::builder();
+        opts.passing(self.passing);
+        if let Some(tag) = &self.tag {
+            opts.tag(tag);
+        }
+
+        let (ready_tx, ready) = oneshot::channel();
+        let task = tokio::spawn(watch_instances(
+            self.client.clone(),
+            self.service.clone(),
+            opts,
+            ready_tx,
+        ));
+        match ready.await {
+            Ok(Ok(rx)) => Ok(Watch { rx, task }),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(ClientError::EmptyResponseError),
+        }
+    }
+}
+
+impl<C: Client + 'static> Drop for ServiceResolver<C> {
+    fn drop(&mut self) {
+        if let Some(watch) = self.instances.get() {
+            watch.task.abort();
+        }
+    }
+}
+
+/// The running watch of a [ServiceResolver].
+struct Watch {
+    rx: watch::Receiver<Arc<Vec<ServiceEntry>>>,
+    task: JoinHandle<()>,
+}
+
+/// Returns the host of an instance, which is the address of the service or
+/// the address of its node when the service has none, the same as Consul DNS.
+fn host(entry: &ServiceEntry) -> &str {
+    entry
+        .service
+        .address
+        .as_deref()
+        .filter(|a| !a.is_empty())
+        .unwrap_or(&entry.node.address)
+}
+
+/// Returns the socket addresses of an instance, resolving its host if it's a
+/// hostname.
+pub(crate) async fn socket_addrs(entry: &ServiceEntry) -> Vec<SocketAddr> {
+    let port = entry.service.port.unwrap_or_default() as u16;
+    let host = host(entry);
+    match host.parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => match tokio::net::lookup_host((host, port)).await {
+            Ok(resolved) => resolved.collect(),
+            Err(e) => {
+                let service = entry.service.service.as_deref().unwrap_or_default();
+                warn!("Failed resolving {} for {}: {}", host, service, e);
+                Vec::new()
+            }
+        },
+    }
+}
+
+/// Returns the key identifying an instance across updates.
+fn instance_id(entry: &ServiceEntry) -> String {
+    format!(
+        "{}/{}",
+        entry.node.id,
+        entry.service.id.as_deref().unwrap_or_default()
+    )
+}
+
+/// Watches the instances of the given service, publishing each new list.
+///
+/// The result of the first request is sent through `ready`, along with the
+/// receiver for all later lists.
+async fn watch_instances<C: Client>(
+    client: Arc<C>,
+    service: String,
+    mut opts: ListServiceNodesRequestBuilder,
+    ready: oneshot::Sender<Result<watch::Receiver<Arc<Vec<ServiceEntry>>>, ClientError>>,
+) {
+    let stream = health::watch(client.as_ref(), &service, Some(&mut opts));
+    futures::pin_mut!(stream);
+
+    let tx = match stream.next().await {
+        Some(Ok(res)) => {
+            let (tx, rx) = watch::channel(Arc::new(res.response));
+            if ready.send(Ok(rx)).is_err() {
+                return;
+            }
+            tx
+        }
+        Some(Err(e)) => {
+            let _ = ready.send(Err(e));
+            return;
+        }
+        None => return,
+    };
+
+    while let Some(res) = stream.next().await {
+        match res {
+            Ok(res) => {
+                debug!(
+                    "Service {} now has {} instances",
+                    service,
+                    res.response.len()
+                );
+                if tx.send(Arc::new(res.response)).is_err() {
+                    return;
+                }
+            }
+            Err(e) => warn!("Failed updating instances of {}: {}", service, e),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Integration of [ServiceResolver] with [tower] and [hyper].
+//!
+//! Requires the `consulrs-tower` feature. [ServiceResolver::discover] turns a
+//! resolver into a [Discover][::tower::discover::Discover] for load balancing
+//! with tower, and [ConsulConnector] lets a [hyper::Client] send requests to
+//! `consul://<service>` URIs, picking a healthy instance for each connection.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::discovery::tower::ConsulConnector;
+//! use std::sync::Arc;
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let http = hyper::Client::builder().build::<_, hyper::Body>(ConsulConnector::new(Arc::new(client)));
+//! let res = http.get("consul://billing/invoices".parse().unwrap()).await;
+//! # })
+//! ```
+//!
+//! Note that reqwest doesn't support custom connectors, so the connector can
+//! only be used with hyper.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use ::tower::{discover::Change, BoxError, Service};
+use futures::stream::{self, BoxStream, StreamExt};
+use http::Uri;
+use hyper::client::HttpConnector;
+use tokio::sync::watch;
+
+use super::{host, instance_id, ServiceResolver};
+use crate::{api::health::common::ServiceEntry, client::Client, error::ClientError};
+
+/// The scheme of URIs which are resolved through Consul.
+pub const SCHEME: &str = "consul";
+
+impl<C: Client + 'static> ServiceResolver<C> {
+    /// Returns a [Discover][::tower::discover::Discover] which inserts a
+    /// service created with the given function for each new instance, and
+    /// removes it once the instance is gone.
+    ///
+    /// Instances are keyed by their node ID and service ID.
+    pub fn discover<S, F>(
+        self: Arc<Self>,
+        make: F,
+    ) -> BoxStream<'static, Result<Change<String, S>, ClientError>>
+    where
+        S: Send + 'static,
+        F: FnMut(&ServiceEntry) -> S + Send + 'static,
+    {
+        let state = DiscoverState {
+            known: HashSet::new(),
+            make,
+            pending: VecDeque::new(),
+            resolver: self,
+            rx: None,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(change) = state.pending.pop_front() {
+                    return Some((Ok(change), state));
+                }
+
+                let rx = match state.rx.as_mut() {
+                    Some(rx) => {
+                        if rx.changed().await.is_err() {
+                            return None;
+                        }
+                        rx
+                    }
+                    None => match state.resolver.subscribe().await {
+                        Ok(rx) => state.rx.insert(rx),
+                        Err(e) => return Some((Err(e), state)),
+                    },
+                };
+                let instances = rx.borrow_and_update().clone();
+
+                let current: HashMap<String, &ServiceEntry> =
+                    instances.iter().map(|e| (instance_id(e), e)).collect();
+                for id in state.known.iter().filter(|id| !current.contains_key(*id)) {
+                    state.pending.push_back(Change::Remove(id.clone()));
+                }
+                for (id, entry) in current.iter() {
+                    if !state.known.contains(id) {
+                        state
+                            .pending
+                            .push_back(Change::Insert(id.clone(), (state.make)(entry)));
+                    }
+                }
+                state.known = current.into_keys().collect();
+            }
+        })
+        .boxed()
+    }
+}
+
+/// The internal state threaded through a [ServiceResolver::discover] stream.
+struct DiscoverState<C: Client + 'static, S, F> {
+    known: HashSet<String>,
+    make: F,
+    pending: VecDeque<Change<String, S>>,
+    resolver: Arc<ServiceResolver<C>>,
+    rx: Option<watch::Receiver<Arc<Vec<ServiceEntry>>>>,
+}
+
+/// A [hyper] connector which resolves `consul://<service>` URIs to the address
+/// of a passing instance of the service.
+///
+/// A [ServiceResolver] is kept for each service which is connected to, and
+/// instances are picked from it in turn. URIs with any other scheme are
+/// connected to directly.
+pub struct ConsulConnector<C: Client + 'static> {
+    client: Arc<C>,
+    http: HttpConnector,
+    resolvers: Arc<Mutex<HashMap<String, Arc<ServiceResolver<C>>>>>,
+}
+
+impl<C: Client + 'static> ConsulConnector<C> {
+    /// Returns a new [ConsulConnector] which resolves services using the
+    /// given client.
+    pub fn new(client: Arc<C>) -> Self {
+        ConsulConnector {
+            client,
+            http: HttpConnector::new(),
+            resolvers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the resolver for the given service, creating it if needed.
+    fn resolver(&self, service: &str) -> Arc<ServiceResolver<C>> {
+        self.resolvers
+            .lock()
+            .unwrap()
+            .entry(service.to_string())
+            .or_insert_with(|| {
+                Arc::new(ServiceResolver::new(self.client.clone(), service).passing(true))
+            })
+            .clone()
+    }
+}
+
+impl<C: Client + 'static> Clone for ConsulConnector<C> {
+    fn clone(&self) -> Self {
+        ConsulConnector {
+            client: self.client.clone(),
+            http: self.http.clone(),
+            resolvers: self.resolvers.clone(),
+        }
+    }
+}
+
+impl<C: Client + 'static> Service<Uri> for ConsulConnector<C> {
+    type Response = <HttpConnector as Service<Uri>>::Response;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let mut http = self.http.clone();
+        if uri.scheme_str() != Some(SCHEME) {
+            return Box::pin(async move { http.call(uri).await.map_err(Into::into) });
+        }
+
+        let service = uri.host().unwrap_or_default().to_string();
+        let resolver = self.resolver(&service);
+        Box::pin(async move {
+            let instance = resolver
+                .pick()
+                .await?
+                .ok_or(ClientError::NoInstancesError { service })?;
+            let target: Uri = format!("http://{}", address(&instance)).parse()?;
+            debug!("Connecting to {} for {}", target, uri);
+            http.call(target).await.map_err(Into::into)
+        })
+    }
+}
+
+/// Returns the address of an instance to connect to.
+fn address(entry: &ServiceEntry) -> String {
+    let host = host(entry);
+    let host = if host.contains(':') {
+        format!("[{}]", host)
+    } else {
+        host.to_string()
+    };
+    match entry.service.port {
+        Some(port) => format!("{}:{}", host, port),
+        None => host,
+    }
+}
@@ -0,0 +1,28 @@
+//! Reads the compiled [discovery chain](https://www.consul.io/api-docs/discovery-chain)
+//! of a service.
+use crate::{
+    api::{
+        self,
+        discovery_chain::{
+            requests::{GetDiscoveryChainRequest, GetDiscoveryChainRequestBuilder},
+            responses::DiscoveryChainResponse,
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Returns the compiled discovery chain for the given service.
+///
+/// See [GetDiscoveryChainRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn get(
+    client: &impl Client,
+    service: &str,
+    opts: Option<&mut GetDiscoveryChainRequestBuilder>,
+) -> Result<ApiResponse<DiscoveryChainResponse>, ClientError> {
+    let mut t = GetDiscoveryChainRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).service(service).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
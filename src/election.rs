@@ -0,0 +1,148 @@
+//! Implements leader election on top of the [lock recipe][crate::lock].
+//!
+//! A [Candidate] campaigns for leadership by acquiring a [Lock] on the
+//! election key, storing its own value in the key while it leads. Changes in
+//! leadership are surfaced as a [Stream] of [LeadershipEvent]s and the
+//! candidate automatically campaigns again after being deposed.
+use std::{convert::TryInto, sync::Arc};
+
+use futures::stream::{self, Stream};
+use tokio::sync::watch;
+
+use crate::{
+    client::Client,
+    error::ClientError,
+    kv,
+    lock::{Lock, LockOptions},
+};
+
+/// A change in the leadership status of a [Candidate].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LeadershipEvent {
+    /// The candidate was elected as leader
+    Elected,
+    /// The candidate lost leadership (i.e. its session was invalidated)
+    Deposed,
+}
+
+/// A participant in a leader election.
+///
+/// # Example
+///
+/// ```no_run
+/// # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+/// use consulrs::election::{Candidate, LeadershipEvent};
+/// use consulrs::lock::LockOptions;
+/// use futures::StreamExt;
+/// use std::sync::Arc;
+///
+/// # tokio_test::block_on(async {
+/// # let client = ConsulClient::new(
+/// #     ConsulClientSettingsBuilder::default()
+/// #         .address("https://127.0.0.1:8200")
+/// #         .build()
+/// #         .unwrap()
+/// # ).unwrap();
+/// let candidate = Candidate::new(
+///     Arc::new(client),
+///     LockOptions::builder()
+///         .key("service/leader")
+///         .value(b"node-1".to_vec())
+///         .build()
+///         .unwrap(),
+/// );
+///
+/// let events = candidate.campaign();
+/// futures::pin_mut!(events);
+/// while let Some(event) = events.next().await {
+///     match event.unwrap() {
+///         LeadershipEvent::Elected => println!("Elected as leader"),
+///         LeadershipEvent::Deposed => println!("Lost leadership"),
+///     }
+/// }
+/// # })
+/// ```
+pub struct Candidate<C: Client + 'static> {
+    client: Arc<C>,
+    opts: LockOptions,
+}
+
+impl<C: Client + 'static> Candidate<C> {
+    /// Returns a new [Candidate] which campaigns using the given options.
+    pub fn new(client: Arc<C>, opts: LockOptions) -> Self {
+        Candidate { client, opts }
+    }
+
+    /// Returns the value stored in the election key by the current leader,
+    /// or [None] if there is no leader.
+    #[instrument(skip(self), fields(key = %self.opts.key), err)]
+    pub async fn leader(&self) -> Result<Option<Vec<u8>>, ClientError> {
+        let mut pairs = match kv::read(self.client.as_ref(), &self.opts.key, None).await {
+            Ok(res) => res.response,
+            Err(ClientError::APIError { code: 404, .. }) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        match pairs.pop() {
+            Some(pair) if pair.session.is_some() => match pair.value {
+                Some(value) => Ok(Some(value.try_into()?)),
+                None => Ok(Some(Vec::new())),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns a [Stream] which campaigns for leadership and yields an event
+    /// each time leadership is gained or lost.
+    ///
+    /// After being deposed the candidate immediately campaigns again. Errors
+    /// encountered while campaigning are yielded before retrying after the
+    /// configured retry time. Dropping the stream resigns leadership.
+    pub fn campaign(&self) -> impl Stream<Item = Result<LeadershipEvent, ClientError>> {
+        let retry_time = self.opts.retry_time;
+        let lock = Lock::new(self.client.clone(), self.opts.clone());
+
+        stream::unfold(
+            (lock, Phase::Campaigning),
+            move |(mut lock, phase)| async move {
+                match phase {
+                    Phase::Campaigning | Phase::Retrying => {
+                        if let Phase::Retrying = phase {
+                            tokio::time::sleep(retry_time).await;
+                        }
+                        match lock.acquire().await {
+                            Ok(held) => {
+                                Some((Ok(LeadershipEvent::Elected), (lock, Phase::Leading(held))))
+                            }
+                            Err(e) => Some((Err(e), (lock, Phase::Retrying))),
+                        }
+                    }
+                    Phase::Leading(held) => {
+                        wait_until_lost(held).await;
+                        if let Err(e) = lock.release().await {
+                            warn!("Failed cleaning up after losing leadership: {}", e);
+                        }
+                        Some((Ok(LeadershipEvent::Deposed), (lock, Phase::Campaigning)))
+                    }
+                }
+            },
+        )
+    }
+}
+
+/// The phase of a [Candidate] campaign.
+enum Phase {
+    Campaigning,
+    Leading(watch::Receiver<bool>),
+    Retrying,
+}
+
+/// Waits until the given receiver indicates the lock is no longer held.
+async fn wait_until_lost(mut held: watch::Receiver<bool>) {
+    while *held.borrow() {
+        // All senders are gone, so the lock can no longer be monitored
+        if held.changed().await.is_err() {
+            return;
+        }
+    }
+}
@@ -2,29 +2,88 @@ use std::str::Utf8Error;
 
 use thiserror::Error;
 
+use crate::api::txn::common::TxnError;
+
 /// The common error type returned by this crate
 #[derive(Error, Debug)]
 pub enum ClientError {
-    #[error("The Consul server returned an error (status code {code})")]
-    APIError { code: u16, message: Option<String> },
+    #[error("ACL bootstrap is no longer allowed (reset index: {reset_index})")]
+    ACLBootstrapError { reset_index: u64 },
+    #[error(
+        "The Consul server returned an error (status code {code}{})",
+        request_id_suffix(.request_id)
+    )]
+    APIError {
+        code: u16,
+        index: Option<u64>,
+        message: Option<String>,
+        request_id: Option<String>,
+        retry_after: Option<std::time::Duration>,
+    },
+    #[error("Error deserializing CBOR value")]
+    #[cfg(feature = "cbor")]
+    CborDeserializeError {
+        source: ciborium::de::Error<std::io::Error>,
+    },
+    #[error("Error serializing CBOR value")]
+    #[cfg(feature = "cbor")]
+    CborSerializeError {
+        source: ciborium::ser::Error<std::io::Error>,
+    },
+    #[error("The checksum of {key} doesn't match its manifest")]
+    ChecksumMismatchError { key: String },
+    #[error("Error compressing or decompressing a value")]
+    CompressionError { source: std::io::Error },
+    #[error("The key {key} can't be written with both flags and compression")]
+    CompressionFlagsError { key: String },
     #[error("Failed decoding Base64 response")]
     Base64DecodeError { source: base64::DecodeError },
     #[error("Empty response")]
     EmptyResponseError,
+    #[error("The key {key} is held by another session")]
+    EphemeralKeyHeldError { key: String },
     #[error("Error reading file: {path}")]
     FileReadError {
         source: std::io::Error,
         path: String,
     },
+    #[error("Error writing file: {path}")]
+    FileWriteError {
+        source: std::io::Error,
+        path: String,
+    },
+    #[error("The check is not configured correctly: {message}")]
+    InvalidCheckError { message: String },
+    #[error("The value of counter {key} is not an integer: {value:?}")]
+    InvalidCounterError { key: String, value: String },
+    #[error("The response contained an invalid or missing index: {index:?}")]
+    InvalidIndexError { index: Option<String> },
+    #[error("The client is not configured correctly: {message}")]
+    InvalidSettingsError { message: String },
     #[error("Error deserializing JSON string")]
     JsonDeserializeError { source: serde_json::Error },
     #[error("Error Serializing JSON string")]
     JsonSerializeError { source: serde_json::Error },
+    #[error("The chunk {key} is missing, the value may have been overwritten while reading")]
+    MissingChunkError { key: String },
+    #[error("The ACL token is missing permissions: {}", .missing.join(", "))]
+    MissingPermissionsError { missing: Vec<String> },
+    #[error("Error deserializing MessagePack value")]
+    #[cfg(feature = "msgpack")]
+    MsgpackDeserializeError { source: rmp_serde::decode::Error },
+    #[error("Error serializing MessagePack value")]
+    #[cfg(feature = "msgpack")]
+    MsgpackSerializeError { source: rmp_serde::encode::Error },
+    #[error("The service {service} has no instances")]
+    #[cfg(feature = "consulrs-tower")]
+    NoInstancesError { service: String },
     #[error("Error parsing CA certificate as PEM encoded certificate: {path}")]
     ParseCertificateError {
         source: reqwest::Error,
         path: String,
     },
+    #[error("Error generating proxy bootstrap configuration: {message}")]
+    ProxyBootstrapError { message: String },
     #[error("The request returned an empty response")]
     ResponseEmptyError,
     #[error("An error occurred with the request")]
@@ -34,6 +93,131 @@ pub enum ClientError {
     },
     #[error("Error configuring REST client")]
     RestClientBuildError { source: reqwest::Error },
+    #[error("Error parsing ACL rules: {message}")]
+    RuleParseError { message: String },
+    #[error("Error building the runtime for the blocking client")]
+    RuntimeBuildError { source: std::io::Error },
+    #[error("The semaphore limit of {limit} conflicts with the existing limit of {existing}")]
+    SemaphoreConflictError { limit: u32, existing: u32 },
+    #[error("Failed registering as a semaphore contender: {key}")]
+    SemaphoreContenderError { key: String },
+    #[error("Error streaming request or response body")]
+    StreamError { source: reqwest::Error },
+    #[error("Error rendering template: {message}")]
+    TemplateRenderError { message: String },
+    #[error("Error running test server: {message}")]
+    #[cfg(feature = "consulrs-test")]
+    TestServerError { message: String },
+    #[error("The request timed out after {timeout:?}")]
+    TimeoutError { timeout: std::time::Duration },
+    #[error("Error building TLS configuration: {message}")]
+    #[cfg(feature = "connect-native")]
+    TlsConfigError { message: String },
+    #[error("Refusing to send an ACL token as a query parameter")]
+    TokenInQueryError,
+    #[error("The transaction was rolled back: {errors:?}")]
+    TransactionError { errors: Vec<TxnError> },
+    #[error("Error sending request over unix socket: {path}")]
+    UnixSocketError {
+        source: std::io::Error,
+        path: String,
+    },
+    #[error("The key {key} kept being modified, giving up after {attempts} attempts")]
+    UpdateConflictError { key: String, attempts: u32 },
     #[error("Error decoding bytes into UTF-8 string")]
     Utf8DecodeError { source: Utf8Error },
+    #[error("The value of {key} is {size} bytes, which is over the limit of {limit} bytes")]
+    ValueTooLarge {
+        key: String,
+        size: usize,
+        limit: usize,
+    },
+    #[error("Error deserializing YAML value")]
+    #[cfg(feature = "yaml")]
+    YamlDeserializeError { source: serde_yaml::Error },
+    #[error("Error serializing YAML value")]
+    #[cfg(feature = "yaml")]
+    YamlSerializeError { source: serde_yaml::Error },
+}
+
+impl ClientError {
+    /// Returns whether the server rejected the request because its ACL token
+    /// is missing, unknown or lacks the required permissions.
+    pub fn is_acl_denied(&self) -> bool {
+        match self {
+            ClientError::APIError { code: 403, .. } => true,
+            e => e
+                .message()
+                .is_some_and(|m| m.contains("Permission denied") || m.contains("ACL not found")),
+        }
+    }
+
+    /// Returns whether the server couldn't handle the request because the
+    /// cluster currently has no leader.
+    pub fn is_no_leader(&self) -> bool {
+        self.message()
+            .is_some_and(|m| m.contains("No cluster leader"))
+    }
+
+    /// Returns whether the server rejected the request because a rate limit
+    /// was exceeded, in which case the request wasn't handled.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(429)
+    }
+
+    /// Returns whether the requested resource doesn't exist.
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(404)
+    }
+
+    /// Returns the index an error returned by the server was sent with in its
+    /// `X-Consul-Index` header, such as a blocking query for a missing key.
+    pub fn index(&self) -> Option<u64> {
+        match self {
+            ClientError::APIError { index, .. } => *index,
+            _ => None,
+        }
+    }
+
+    /// Returns the body of an error returned by the server, if it had one.
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            ClientError::APIError { message, .. } => message.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the ID of the request an error returned by the server was for,
+    /// if it was sent with one.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            ClientError::APIError { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns how long the server asked to wait before retrying the request,
+    /// as sent in the `Retry-After` header of the error.
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            ClientError::APIError { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Returns the status code of an error returned by the server.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ClientError::APIError { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+/// Formats the request ID of an error for its message.
+fn request_id_suffix(request_id: &Option<String>) -> String {
+    match request_id {
+        Some(id) => format!(", request ID {}", id),
+        None => String::new(),
+    }
 }
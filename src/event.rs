@@ -0,0 +1,90 @@
+//! Manages Consul [user events](https://www.consul.io/api-docs/event).
+use crate::{
+    api::{
+        self,
+        event::{
+            common::UserEvent,
+            requests::{
+                FireEventRequest, FireEventRequestBuilder, ListEventsRequest,
+                ListEventsRequestBuilder,
+            },
+        },
+        watch::Watcher,
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+use futures::{stream, Stream, StreamExt};
+
+/// Fires a new user event with the given name and payload.
+///
+/// See [FireEventRequest]
+#[instrument(skip(client, payload, opts), err)]
+pub async fn fire(
+    client: &impl Client,
+    name: &str,
+    payload: &[u8],
+    opts: Option<&mut FireEventRequestBuilder>,
+) -> Result<ApiResponse<UserEvent>, ClientError> {
+    let mut t = FireEventRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .name(name)
+        .payload(payload)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists the most recent events known by the agent.
+///
+/// See [ListEventsRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListEventsRequestBuilder>,
+) -> Result<ApiResponse<Vec<UserEvent>>, ClientError> {
+    let mut t = ListEventsRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Returns a [Stream] which yields each new event received by the agent.
+///
+/// Events which were already known when the stream started are not yielded.
+/// Events are tracked using their Lamport time, so the agent being watched
+/// should not change for the lifetime of the stream.
+///
+/// See [ListEventsRequest]
+pub fn watch<'a, C: Client>(
+    client: &'a C,
+    opts: Option<&mut ListEventsRequestBuilder>,
+) -> impl Stream<Item = Result<UserEvent, ClientError>> + 'a {
+    let mut t = ListEventsRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+
+    let mut seen: Option<u64> = None;
+    Watcher::new(client, endpoint)
+        .stream()
+        .flat_map(move |res| {
+            let events = match res {
+                Ok(res) => {
+                    let events = res.response;
+                    let latest = events.iter().map(|e| e.ltime).max().unwrap_or(0);
+                    let new = match seen {
+                        Some(last) => events
+                            .into_iter()
+                            .filter(|e| e.ltime > last)
+                            .map(Ok)
+                            .collect(),
+                        None => Vec::new(),
+                    };
+                    seen = Some(seen.map_or(latest, |last| last.max(latest)));
+                    new
+                }
+                Err(e) => vec![Err(e)],
+            };
+            stream::iter(events)
+        })
+}
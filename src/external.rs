@@ -0,0 +1,127 @@
+//! Registration of external services.
+//!
+//! External services run on nodes without a local Consul agent, such as a
+//! managed database, so they're registered directly in the catalog instead of
+//! through an agent. Their nodes are marked with the [EXTERNAL_NODE] and
+//! [EXTERNAL_PROBE] node metadata, which tells [Consul ESM][1] to run their
+//! health checks in place of an agent.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::api::check::common::{AgentCheckBuilder, HealthCheckDefinitionBuilder};
+//! use consulrs::api::service::common::AgentServiceBuilder;
+//! use consulrs::external;
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let service = AgentServiceBuilder::default()
+//!     .id("db")
+//!     .service("db")
+//!     .port(5432u64)
+//!     .build()
+//!     .unwrap();
+//! let check = AgentCheckBuilder::default()
+//!     .name("db")
+//!     .definition(
+//!         HealthCheckDefinitionBuilder::default()
+//!             .tcp("db.example.com:5432")
+//!             .interval("10s")
+//!             .build()
+//!             .unwrap(),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! external::register_service(&client, "db-node", "db.example.com", service, Some(check), None)
+//!     .await
+//!     .unwrap();
+//! # })
+//! ```
+//!
+//! [1]: https://github.com/hashicorp/consul-esm
+use std::collections::HashMap;
+
+use crate::{
+    api::{
+        catalog::requests::{
+            DeregisterEntityRequest, DeregisterEntityRequestBuilder, RegisterEntityRequest,
+            RegisterEntityRequestBuilder,
+        },
+        check::common::AgentCheck,
+        service::common::AgentService,
+        ApiResponse,
+    },
+    catalog,
+    client::Client,
+    error::ClientError,
+};
+
+/// The node metadata key marking a node as external.
+pub const EXTERNAL_NODE: &str = "external-node";
+/// The node metadata key asking Consul ESM to run the checks of a node.
+pub const EXTERNAL_PROBE: &str = "external-probe";
+
+/// Registers a service, and optionally a health check for it, on an external
+/// node at the given address.
+///
+/// The node is created if it doesn't exist and marked as external, replacing
+/// any `node_meta` set in `opts`. The check is bound to the node and, unless
+/// it names one itself, to the service.
+///
+/// See [RegisterEntityRequest]
+#[instrument(skip(client, service, check, opts), err)]
+pub async fn register_service(
+    client: &impl Client,
+    node: &str,
+    address: &str,
+    service: AgentService,
+    check: Option<AgentCheck>,
+    opts: Option<&mut RegisterEntityRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = RegisterEntityRequest::builder();
+    let builder = opts.unwrap_or(&mut t);
+    if let Some(mut check) = check {
+        check.node = Some(node.into());
+        if check.service_id.is_none() {
+            check.service_id = service.id.clone().or_else(|| service.service.clone());
+        }
+        builder.check(check);
+    }
+    let meta = HashMap::from([
+        (EXTERNAL_NODE.to_string(), "true".to_string()),
+        (EXTERNAL_PROBE.to_string(), "true".to_string()),
+    ]);
+    catalog::register(
+        client,
+        node,
+        address,
+        Some(builder.node_meta(meta).service(service)),
+    )
+    .await
+}
+
+/// Deregisters a service, along with its health checks, from an external
+/// node.
+///
+/// See [DeregisterEntityRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn deregister_service(
+    client: &impl Client,
+    node: &str,
+    service_id: &str,
+    opts: Option<&mut DeregisterEntityRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = DeregisterEntityRequest::builder();
+    catalog::deregister(
+        client,
+        node,
+        Some(opts.unwrap_or(&mut t).service_id(service_id)),
+    )
+    .await
+}
@@ -0,0 +1,177 @@
+use crate::{
+    api::{
+        self,
+        check::common::HealthCheck,
+        health::{
+            common::ServiceEntry,
+            requests::{
+                ListChecksInStateRequest, ListChecksInStateRequestBuilder, ListConnectNodesRequest,
+                ListConnectNodesRequestBuilder, ListNodeChecksRequest,
+                ListNodeChecksRequestBuilder, ListServiceChecksRequest,
+                ListServiceChecksRequestBuilder, ListServiceNodesRequest,
+                ListServiceNodesRequestBuilder,
+            },
+        },
+        watch::Watcher,
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+use futures::{Stream, StreamExt};
+
+/// Lists the checks associated with the given service.
+///
+/// See [ListServiceChecksRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn checks(
+    client: &impl Client,
+    service: &str,
+    opts: Option<&mut ListServiceChecksRequestBuilder>,
+) -> Result<ApiResponse<Vec<HealthCheck>>, ClientError> {
+    let mut t = ListServiceChecksRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).service(service).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists the Connect-capable instances of the given service along with their
+/// node and checks.
+///
+/// These are the instances Connect clients and sidecar proxies connect to,
+/// which are the sidecar proxies of the service and any instances which are
+/// Connect-native.
+///
+/// See [ListConnectNodesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn connect(
+    client: &impl Client,
+    service: &str,
+    opts: Option<&mut ListConnectNodesRequestBuilder>,
+) -> Result<ApiResponse<Vec<ServiceEntry>>, ClientError> {
+    let mut t = ListConnectNodesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).service(service).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists the checks specific to the given node.
+///
+/// See [ListNodeChecksRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn node(
+    client: &impl Client,
+    node: &str,
+    opts: Option<&mut ListNodeChecksRequestBuilder>,
+) -> Result<ApiResponse<Vec<HealthCheck>>, ClientError> {
+    let mut t = ListNodeChecksRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).node(node).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists the instances of the given service along with their node and checks.
+///
+/// See [ListServiceNodesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn service(
+    client: &impl Client,
+    service: &str,
+    opts: Option<&mut ListServiceNodesRequestBuilder>,
+) -> Result<ApiResponse<Vec<ServiceEntry>>, ClientError> {
+    let mut t = ListServiceNodesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).service(service).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Streams the healthy instances of the given service for reconciling
+/// against.
+///
+/// Only instances whose checks are all passing are included. The returned
+/// stream yields the full set of healthy instances, sorted by node and service
+/// ID so that successive sets can be diffed, and then yields it again each
+/// time an instance joins or leaves the set, is re-registered, or the status
+/// of one of its checks changes. Changes to check output alone are skipped.
+///
+/// See [watch] and [ListServiceNodesRequest]
+#[instrument(skip(client, opts))]
+pub fn service_stream<'a, C: Client>(
+    client: &'a C,
+    service: &str,
+    opts: Option<&mut ListServiceNodesRequestBuilder>,
+) -> impl Stream<Item = Result<ApiResponse<Vec<ServiceEntry>>, ClientError>> + 'a {
+    let mut t = ListServiceNodesRequest::builder();
+    let opts = opts.unwrap_or(&mut t).passing(true);
+    let mut last = None;
+    watch(client, service, Some(opts)).filter_map(move |res| {
+        let res = match res {
+            Ok(mut res) => {
+                res.response.sort_by(|a, b| {
+                    (&a.node.node, &a.service.id).cmp(&(&b.node.node, &b.service.id))
+                });
+                let current = membership(&res.response);
+                if last.as_ref() == Some(&current) {
+                    None
+                } else {
+                    last = Some(current);
+                    Some(Ok(res))
+                }
+            }
+            Err(e) => Some(Err(e)),
+        };
+        futures::future::ready(res)
+    })
+}
+
+/// Lists the checks in the given state.
+///
+/// See [ListChecksInStateRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn state(
+    client: &impl Client,
+    state: &str,
+    opts: Option<&mut ListChecksInStateRequestBuilder>,
+) -> Result<ApiResponse<Vec<HealthCheck>>, ClientError> {
+    let mut t = ListChecksInStateRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).state(state).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Watches the instances of the given service for changes.
+///
+/// The returned stream yields the current list of instances and then yields
+/// again each time the list, or the health of an instance, changes.
+///
+/// See [Watcher] and [ListServiceNodesRequest]
+#[instrument(skip(client, opts))]
+pub fn watch<'a, C: Client>(
+    client: &'a C,
+    service: &str,
+    opts: Option<&mut ListServiceNodesRequestBuilder>,
+) -> impl Stream<Item = Result<ApiResponse<Vec<ServiceEntry>>, ClientError>> + 'a {
+    let mut t = ListServiceNodesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).service(service).build().unwrap();
+    Watcher::new(client, endpoint).stream()
+}
+
+/// The parts of an instance whose changes [service_stream] yields on.
+#[derive(PartialEq)]
+struct Member {
+    checks: Vec<(Option<String>, Option<String>)>,
+    modify_index: Option<u64>,
+    node: String,
+    service: Option<String>,
+}
+
+fn membership(entries: &[ServiceEntry]) -> Vec<Member> {
+    entries
+        .iter()
+        .map(|e| Member {
+            checks: e
+                .checks
+                .iter()
+                .map(|c| (c.check_id.clone(), c.status.clone()))
+                .collect(),
+            modify_index: e.service.modify_index,
+            node: e.node.node.clone(),
+            service: e.service.id.clone(),
+        })
+        .collect()
+}
@@ -0,0 +1,62 @@
+//! Reads the internal endpoints which back the Consul UI.
+//!
+//! These endpoints summarize the services and nodes of a datacenter and the
+//! topology of the service mesh, which is what dashboards usually want. They
+//! are not part of the documented API, so Consul may change them between
+//! releases, and are only available with the `unstable-internal` feature.
+use crate::{
+    api::{
+        self,
+        internal::{
+            common::{NodeSummary, ServiceSummary, ServiceTopology},
+            requests::{
+                ListUiNodesRequest, ListUiNodesRequestBuilder, ListUiServicesRequest,
+                ListUiServicesRequestBuilder, ReadServiceTopologyRequest,
+                ReadServiceTopologyRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Lists the nodes in a datacenter along with their services and checks.
+///
+/// See [ListUiNodesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn nodes(
+    client: &impl Client,
+    opts: Option<&mut ListUiNodesRequestBuilder>,
+) -> Result<ApiResponse<Vec<NodeSummary>>, ClientError> {
+    let mut t = ListUiNodesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists a summary of each service in a datacenter.
+///
+/// See [ListUiServicesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn services(
+    client: &impl Client,
+    opts: Option<&mut ListUiServicesRequestBuilder>,
+) -> Result<ApiResponse<Vec<ServiceSummary>>, ClientError> {
+    let mut t = ListUiServicesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Returns the upstreams and downstreams of the given service.
+///
+/// See [ReadServiceTopologyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn topology(
+    client: &impl Client,
+    service: &str,
+    opts: Option<&mut ReadServiceTopologyRequestBuilder>,
+) -> Result<ApiResponse<ServiceTopology>, ClientError> {
+    let mut t = ReadServiceTopologyRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).service(service).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
@@ -1,22 +1,93 @@
-use std::convert::TryInto;
+use std::{collections::HashMap, convert::TryInto, sync::Arc, time::Duration};
 
 use crate::{
     api::{
         self,
         kv::{
-            common::{GenericKVPair, KVPair},
+            common::{
+                Compression, GenericKVPair, KVDiff, KVExportEntry, KVPair, KvFlags,
+                LargeValueManifest,
+            },
             requests::{
                 DeleteKeyRequest, DeleteKeyRequestBuilder, ReadKeyRequest, ReadKeyRequestBuilder,
                 ReadKeysRequest, ReadKeysRequestBuilder, ReadRawKeyRequest,
                 ReadRawKeyRequestBuilder, SetKeyRequest, SetKeyRequestBuilder,
             },
         },
-        ApiResponse,
+        session::{common::SessionBehavior, requests::CreateSessionRequest},
+        txn::{
+            common::{KVTxnOp, KVTxnVerb, TxnOp, TxnResult},
+            requests::TransactionRequestBuilder,
+        },
+        watch::Watcher,
+        ApiResponse, BodyStream,
     },
-    client::Client,
+    client::{Client, RetryPolicy},
     error::ClientError,
+    session::{self, RenewalTask},
+    txn::{self, Transaction},
 };
+use futures::{Stream, StreamExt};
 use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::{mpsc, watch, Mutex};
+
+/// The largest value Consul accepts by default, see the `kv_max_value_size`
+/// option of the Consul agent.
+pub const MAX_VALUE_SIZE: usize = 512 * 1024;
+
+/// The number of times [update] tries writing a key before giving up.
+pub const UPDATE_ATTEMPTS: u32 = 10;
+
+/// Acquires the lock on the given key using the given session.
+///
+/// The response is whether the lock was acquired. A value can be stored along
+/// with acquiring the lock by setting it in the options.
+///
+/// See [SetKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn acquire(
+    client: &impl Client,
+    key: &str,
+    session: &str,
+    opts: Option<&mut SetKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = SetKeyRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .key(key)
+        .acquire(session)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, prepare(endpoint)?).await
+}
+
+/// Sets the value at the given key only if its modify index still matches the
+/// given index.
+///
+/// The response is whether the value was set, which is false when the key was
+/// modified since the index was read. An index of 0 only sets the value if the
+/// key doesn't exist yet.
+///
+/// See [SetKeyRequest]
+#[instrument(skip(client, value, opts), err)]
+pub async fn cas_set(
+    client: &impl Client,
+    key: &str,
+    value: impl Into<Vec<u8>>,
+    modify_index: u64,
+    opts: Option<&mut SetKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = SetKeyRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .key(key)
+        .value(value)
+        .cas(modify_index)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, prepare(endpoint)?).await
+}
 
 /// Deletes the given key.
 ///
@@ -32,6 +103,217 @@ pub async fn delete(
     api::exec_with_result(client, endpoint).await
 }
 
+/// Deletes the given key only if its modify index still matches the given
+/// index.
+///
+/// The response is whether the key was deleted, which is false when the key
+/// was modified since the index was read.
+///
+/// See [DeleteKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete_cas(
+    client: &impl Client,
+    key: &str,
+    modify_index: u64,
+    opts: Option<&mut DeleteKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = DeleteKeyRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .key(key)
+        .cas(modify_index)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Deletes every key under the given prefix.
+///
+/// Note that the prefix is matched as a string, so deleting `foo` also deletes
+/// `foobar`. End the prefix with a `/` to only delete the keys in a folder.
+///
+/// See [DeleteKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete_tree(
+    client: &impl Client,
+    prefix: &str,
+    opts: Option<&mut DeleteKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = DeleteKeyRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .key(prefix)
+        .recurse(true)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Writes the given value to the given key, bound to an existing session
+/// and held only for as long as the session is alive.
+///
+/// The key is acquired with the session, so the response is whether the
+/// value was written, which is false when the key is held by another session.
+/// When the session was created with the [SessionBehavior::Delete] behavior
+/// the key is deleted once the session expires or is deleted. See [ephemeral]
+/// for creating the session and keeping it alive.
+///
+/// See [SetKeyRequest]
+#[instrument(skip(client, value, opts), err)]
+pub async fn ephemeral_set(
+    client: &impl Client,
+    key: &str,
+    value: impl Into<Vec<u8>>,
+    session: &str,
+    opts: Option<&mut SetKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = SetKeyRequest::builder();
+    acquire(
+        client,
+        key,
+        session,
+        Some(opts.unwrap_or(&mut t).value(value)),
+    )
+    .await
+}
+
+/// Writes the given value to the given key for as long as the returned
+/// [EphemeralKey] is alive, i.e. for registering the presence of an instance.
+///
+/// A session with the [SessionBehavior::Delete] behavior and the given TTL is
+/// created and the value written with [ephemeral_set], failing with a
+/// [ClientError::EphemeralKeyHeldError] when the key is held by another
+/// session. The session is then renewed in the background, see
+/// [EphemeralKey] for how the key is removed. Note that Consul requires a TTL
+/// of at least 10 seconds.
+#[instrument(skip(client, value), err)]
+pub async fn ephemeral<C: Client + 'static>(
+    client: Arc<C>,
+    key: &str,
+    value: impl Into<Vec<u8>>,
+    ttl: Duration,
+) -> Result<EphemeralKey<C>, ClientError> {
+    let session = session::create(
+        client.as_ref(),
+        Some(
+            CreateSessionRequest::builder()
+                .behavior(SessionBehavior::Delete)
+                .lock_delay("0s")
+                .name(key)
+                .ttl(format!("{}s", ttl.as_secs())),
+        ),
+    )
+    .await?
+    .response
+    .id;
+
+    let res = ephemeral_set(client.as_ref(), key, value, &session, None).await;
+    if !matches!(res, Ok(ApiResponse { response: true, .. })) {
+        let _ = session::delete(client.as_ref(), &session, None).await;
+        res?;
+        return Err(ClientError::EphemeralKeyHeldError {
+            key: key.to_string(),
+        });
+    }
+
+    let renewal = RenewalTask::start(client.clone(), &session, ttl);
+    Ok(EphemeralKey {
+        client,
+        key: key.to_string(),
+        renewal,
+    })
+}
+
+/// Reads every key under the given prefix in the format written by
+/// `consul kv export`.
+///
+/// Serializing the response with [serde_json] produces the same file as the
+/// CLI, which can be written back with [import].
+///
+/// See [ReadKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn export(
+    client: &impl Client,
+    prefix: &str,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+) -> Result<ApiResponse<Vec<KVExportEntry>>, ClientError> {
+    let mut t = ReadKeyRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .key(prefix)
+        .recurse(true)
+        .build()
+        .unwrap();
+    // Like the CLI, an empty or missing prefix exports no keys
+    let res = match api::optional(api::exec_with_result(client, endpoint).await)? {
+        Some(res) => res,
+        None => return Ok(ApiResponse::builder().response(Vec::new()).build().unwrap()),
+    };
+
+    Ok(res.map(|kvs| {
+        kvs.into_iter()
+            .map(|kv| KVExportEntry {
+                flags: kv.flags,
+                key: kv.key,
+                namespace: kv.namespace,
+                partition: None,
+                value: kv.value.unwrap_or_default(),
+            })
+            .collect()
+    }))
+}
+
+/// Writes the given keys, such as those read from a file written by
+/// `consul kv export` or by [export].
+///
+/// Without a batch size each key is written with its own request, the same as
+/// `consul kv import`. With a batch size the keys are instead written in
+/// transactions of at most that many keys, which Consul limits to 64
+/// operations. Each transaction is applied atomically, but keys written by
+/// earlier transactions are kept when a later one fails. Transactions can't
+/// target another admin partition, so the partition of each key is ignored.
+///
+/// See [SetKeyRequest] and [txn::execute]
+#[instrument(skip(client, entries), err)]
+pub async fn import(
+    client: &impl Client,
+    entries: &[KVExportEntry],
+    batch_size: Option<usize>,
+) -> Result<(), ClientError> {
+    match batch_size {
+        Some(size) => {
+            for batch in entries.chunks(size.max(1)) {
+                let txn = batch.iter().fold(Transaction::new(), |txn, entry| {
+                    txn.op(TxnOp::KV(KVTxnOp {
+                        verb: KVTxnVerb::Set,
+                        key: entry.key.clone(),
+                        flags: Some(entry.flags),
+                        namespace: entry.namespace.clone(),
+                        value: Some(entry.value.clone()),
+                        ..Default::default()
+                    }))
+                });
+                txn::execute(client, &txn, None).await?;
+            }
+        }
+        None => {
+            for entry in entries {
+                let value: Vec<u8> = entry.value.clone().try_into()?;
+                let mut opts = SetKeyRequest::builder();
+                opts.flags(entry.flags);
+                if let Some(ns) = &entry.namespace {
+                    opts.ns(ns);
+                }
+                if let Some(partition) = &entry.partition {
+                    opts.partition(partition);
+                }
+                set(client, &entry.key, value, Some(&mut opts)).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Lists all keys at the given path.
 ///
 /// See [ReadKeysRequest]
@@ -60,8 +342,28 @@ pub async fn read_raw(
     api::exec_with_raw(client, endpoint).await
 }
 
+/// Reads the raw value at the given key as a stream of chunks.
+///
+/// Unlike [read_raw] the value is never buffered in memory, which makes this
+/// suitable for large values.
+///
+/// See [ReadRawKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read_stream(
+    client: &impl Client,
+    key: &str,
+    opts: Option<&mut ReadRawKeyRequestBuilder>,
+) -> Result<ApiResponse<BodyStream>, ClientError> {
+    let mut t = ReadRawKeyRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).key(key).build().unwrap();
+    api::exec_with_stream(client, endpoint).await
+}
+
 /// Reads the value at the given key.
 ///
+/// Values marked as compressed are decompressed when the client is configured
+/// to `decompress` them, see [Compression].
+///
 /// See [ReadKeyRequest]
 #[instrument(skip(client, opts), err)]
 pub async fn read(
@@ -71,7 +373,114 @@ pub async fn read(
 ) -> Result<ApiResponse<Vec<KVPair>>, ClientError> {
     let mut t = ReadKeyRequest::builder();
     let endpoint = opts.unwrap_or(&mut t).key(key).build().unwrap();
-    api::exec_with_result(client, endpoint).await
+    let mut res = api::exec_with_result(client, endpoint).await?;
+    res.response = decompress_all(client, res.response)?;
+    Ok(res)
+}
+
+/// Reads a value written by [set_large], reassembling it from its chunks.
+///
+/// Every chunk and the reassembled value are checked against the checksums in
+/// the manifest. A read racing a [set_large] of the same key can fail with a
+/// [ClientError::MissingChunkError] when the chunks it's reading are replaced,
+/// in which case the read can be retried.
+///
+/// See [ReadKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read_large(
+    client: &impl Client,
+    key: &str,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+) -> Result<ApiResponse<Vec<u8>>, ClientError> {
+    let res = read_json::<LargeValueManifest, _>(client, key, opts).await?;
+    let manifest = &res.response.value;
+
+    let mut value = Vec::with_capacity(manifest.size as usize);
+    for (i, checksum) in manifest.chunks.iter().enumerate() {
+        let chunk_key = format!("{}{}", chunk_prefix(key, &manifest.checksum), i);
+        let chunk = match read_raw(client, &chunk_key, None).await {
+            Ok(res) => res.response,
+            Err(ClientError::APIError { code: 404, .. }) => {
+                return Err(ClientError::MissingChunkError { key: chunk_key })
+            }
+            Err(e) => return Err(e),
+        };
+        if sha256(&chunk) != *checksum {
+            return Err(ClientError::ChecksumMismatchError { key: chunk_key });
+        }
+        value.extend(chunk);
+    }
+    if sha256(&value) != manifest.checksum {
+        return Err(ClientError::ChecksumMismatchError {
+            key: key.to_string(),
+        });
+    }
+
+    Ok(res.map(|_| value))
+}
+
+/// Reads the given keys from a single consistent snapshot, returning them in
+/// the same order.
+///
+/// The keys are read by one transaction of `get` operations, so unlike
+/// separate reads no key can change in between. The transaction fails with a
+/// [ClientError::TransactionError] if any of the keys doesn't exist. Consul
+/// limits transactions to [txn::MAX_OPS] operations, which limits how many
+/// keys can be read at once.
+///
+/// See [TransactionRequest][crate::api::txn::requests::TransactionRequest]
+/// and [txn::execute]
+#[instrument(skip(client, opts), err)]
+pub async fn read_many(
+    client: &impl Client,
+    keys: &[&str],
+    opts: Option<&mut TransactionRequestBuilder>,
+) -> Result<ApiResponse<Vec<KVPair>>, ClientError> {
+    let txn = keys
+        .iter()
+        .fold(Transaction::new(), |txn, key| txn.get(key));
+    let mut res = txn::execute(client, &txn, opts).await?;
+
+    let kvs = res
+        .response
+        .results
+        .take()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|r| match r {
+            TxnResult::KV(kv) => Some(kv),
+            _ => None,
+        })
+        .collect();
+    let kvs = decompress_all(client, kvs)?;
+    Ok(res.map(|_| kvs))
+}
+
+/// Reads every key under the given prefix whose flags, decoded into the given
+/// type, match the given predicate.
+///
+/// Consul can't filter keys by their flags, so every key under the prefix is
+/// read and the filtering happens on the client.
+///
+/// See [ReadKeyRequest]
+#[instrument(skip(client, predicate, opts), err)]
+pub async fn read_by_flags<F: KvFlags, C: Client>(
+    client: &C,
+    prefix: &str,
+    predicate: impl Fn(&F) -> bool,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+) -> Result<ApiResponse<Vec<KVPair>>, ClientError> {
+    let mut t = ReadKeyRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .key(prefix)
+        .recurse(true)
+        .build()
+        .unwrap();
+    let mut res = api::exec_with_result(client, endpoint).await?;
+    res.response = decompress_all(client, res.response)?;
+    res.response.retain(|kv| predicate(&kv.flags_as()));
+    Ok(res)
 }
 
 /// Reads the JSON value at the given key and deserializes it into an object.
@@ -86,41 +495,114 @@ pub async fn read_json<T: DeserializeOwned, C: Client>(
     client: &C,
     key: &str,
     opts: Option<&mut ReadKeyRequestBuilder>,
+) -> Result<ApiResponse<GenericKVPair<T>>, ClientError> {
+    read_decoded(client, key, opts, from_json).await
+}
+
+/// Reads the CBOR value at the given key and deserializes it into an object.
+///
+/// See [read_json] for how the response is handled.
+///
+/// See [ReadKeyRequest]
+#[cfg(feature = "cbor")]
+#[instrument(skip(client, opts), err)]
+pub async fn read_cbor<T: DeserializeOwned, C: Client>(
+    client: &C,
+    key: &str,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+) -> Result<ApiResponse<GenericKVPair<T>>, ClientError> {
+    read_decoded(client, key, opts, |bytes| {
+        ciborium::de::from_reader(bytes)
+            .map_err(|e| ClientError::CborDeserializeError { source: e })
+    })
+    .await
+}
+
+/// Reads the MessagePack value at the given key and deserializes it into an
+/// object.
+///
+/// See [read_json] for how the response is handled.
+///
+/// See [ReadKeyRequest]
+#[cfg(feature = "msgpack")]
+#[instrument(skip(client, opts), err)]
+pub async fn read_msgpack<T: DeserializeOwned, C: Client>(
+    client: &C,
+    key: &str,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+) -> Result<ApiResponse<GenericKVPair<T>>, ClientError> {
+    read_decoded(client, key, opts, |bytes| {
+        rmp_serde::from_slice(bytes).map_err(|e| ClientError::MsgpackDeserializeError { source: e })
+    })
+    .await
+}
+
+/// Reads the YAML value at the given key and deserializes it into an object.
+///
+/// See [read_json] for how the response is handled.
+///
+/// See [ReadKeyRequest]
+#[cfg(feature = "yaml")]
+#[instrument(skip(client, opts), err)]
+pub async fn read_yaml<T: DeserializeOwned, C: Client>(
+    client: &C,
+    key: &str,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+) -> Result<ApiResponse<GenericKVPair<T>>, ClientError> {
+    read_decoded(client, key, opts, |bytes| {
+        serde_yaml::from_slice(bytes).map_err(|e| ClientError::YamlDeserializeError { source: e })
+    })
+    .await
+}
+
+/// Reads the value at the given key and decodes it with the given function.
+async fn read_decoded<T: DeserializeOwned, C: Client>(
+    client: &C,
+    key: &str,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+    decode: impl Fn(&[u8]) -> Result<T, ClientError>,
 ) -> Result<ApiResponse<GenericKVPair<T>>, ClientError> {
     let mut t = ReadKeyRequest::builder();
     let endpoint = opts.unwrap_or(&mut t).key(key).build().unwrap();
     let mut res = api::exec_with_result(client, endpoint).await?;
 
     if !res.response.is_empty() {
-        let kv = res.response.pop().unwrap();
-        let bytes: Vec<u8> = kv.value.unwrap().try_into()?;
-        let t = serde_json::from_slice(&bytes)
-            .map_err(|e| ClientError::JsonDeserializeError { source: e })?;
-        let gkv = GenericKVPair {
-            value: t,
-            create_index: kv.create_index,
-            flags: kv.flags,
-            key: kv.key,
-            lock_index: kv.lock_index,
-            modify_index: kv.modify_index,
-            namespace: kv.namespace,
-            session: kv.session,
-        };
-        Ok(ApiResponse {
-            response: gkv,
-            cache: res.cache,
-            content_hash: res.content_hash,
-            default_acl_policy: res.default_acl_policy,
-            index: res.index,
-            known_leader: res.known_leader,
-            last_contact: res.last_contact,
-            query_backend: res.query_backend,
-        })
+        let gkv = parse_value(client, res.response.pop().unwrap(), decode)?;
+        Ok(res.map(|_| gkv))
     } else {
         Err(ClientError::EmptyResponseError)
     }
 }
 
+/// Reads every JSON value under the given prefix and deserializes each of
+/// them into an object.
+///
+/// Keys without a value, such as the folders created by the UI, are skipped.
+///
+/// See [ReadKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read_json_recurse<T: DeserializeOwned, C: Client>(
+    client: &C,
+    prefix: &str,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+) -> Result<ApiResponse<Vec<GenericKVPair<T>>>, ClientError> {
+    let mut t = ReadKeyRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .key(prefix)
+        .recurse(true)
+        .build()
+        .unwrap();
+    let mut res = api::exec_with_result(client, endpoint).await?;
+
+    let pairs = std::mem::take(&mut res.response)
+        .into_iter()
+        .filter(|kv| kv.value.is_some())
+        .map(|kv| parse_value(client, kv, from_json))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(res.map(|_| pairs))
+}
+
 /// Reads the raw JSON value at the given key and deserializes it into an object.
 ///
 /// See [ReadRawKeyRequest]
@@ -137,30 +619,44 @@ pub async fn read_json_raw<T: DeserializeOwned, C: Client>(
     if !res.response.is_empty() {
         let t = serde_json::from_slice(&res.response)
             .map_err(|e| ClientError::JsonDeserializeError { source: e })?;
-        Ok(ApiResponse {
-            response: t,
-            cache: res.cache,
-            content_hash: res.content_hash,
-            default_acl_policy: res.default_acl_policy,
-            index: res.index,
-            known_leader: res.known_leader,
-            last_contact: res.last_contact,
-            query_backend: res.query_backend,
-        })
+        Ok(res.map(|_| t))
     } else {
         Err(ClientError::EmptyResponseError)
     }
 }
 
+/// Releases the lock on the given key held by the given session.
+///
+/// The response is whether the lock was released, which is false when the
+/// session doesn't hold the lock.
+///
+/// See [SetKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn release(
+    client: &impl Client,
+    key: &str,
+    session: &str,
+    opts: Option<&mut SetKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = SetKeyRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .key(key)
+        .release(session)
+        .build()
+        .unwrap();
+    api::exec_with_result(client, prepare(endpoint)?).await
+}
+
 /// Sets the value at the given key.
 ///
 /// See [SetKeyRequest]
 #[instrument(skip(client, value, opts), err)]
-pub async fn set<'a>(
-    client: &'a impl Client,
-    key: &'a str,
-    value: &'static [u8],
-    opts: Option<&'a mut SetKeyRequestBuilder>,
+pub async fn set(
+    client: &impl Client,
+    key: &str,
+    value: impl Into<Vec<u8>>,
+    opts: Option<&mut SetKeyRequestBuilder>,
 ) -> Result<ApiResponse<bool>, ClientError> {
     let mut t = SetKeyRequest::builder();
     let endpoint = opts
@@ -169,7 +665,109 @@ pub async fn set<'a>(
         .value(value)
         .build()
         .unwrap();
-    api::exec_with_result(client, endpoint).await
+    api::exec_with_result(client, prepare(endpoint)?).await
+}
+
+/// Sets the value at the given key only if the key doesn't exist yet.
+///
+/// The response is whether the value was set, which makes this suitable for
+/// initializing a key when several instances race to create it: exactly one of
+/// them wins. This is a [cas_set] with an index of 0.
+///
+/// See [SetKeyRequest]
+#[instrument(skip(client, value, opts), err)]
+pub async fn set_if_absent(
+    client: &impl Client,
+    key: &str,
+    value: impl Into<Vec<u8>>,
+    opts: Option<&mut SetKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    cas_set(client, key, value, 0, opts).await
+}
+
+/// Sets a value which may be larger than [MAX_VALUE_SIZE] at the given key.
+///
+/// The value is split into chunks which are written under the key, after
+/// which a [LargeValueManifest] pointing at them is written to the key itself.
+/// The manifest is swapped in a transaction which also deletes the chunks of
+/// the previous value, so readers using [read_large] either see the previous
+/// value or the new one. Conflicting writes are retried up to
+/// [UPDATE_ATTEMPTS] times.
+#[instrument(skip(client, value), err)]
+pub async fn set_large(
+    client: &impl Client,
+    key: &str,
+    value: impl Into<Vec<u8>>,
+) -> Result<(), ClientError> {
+    let value = value.into();
+    let manifest = LargeValueManifest {
+        checksum: sha256(&value),
+        chunks: value.chunks(MAX_VALUE_SIZE).map(sha256).collect(),
+        size: value.len() as u64,
+    };
+    let prefix = chunk_prefix(key, &manifest.checksum);
+    for (i, chunk) in value.chunks(MAX_VALUE_SIZE).enumerate() {
+        set(client, &format!("{}{}", prefix, i), chunk, None).await?;
+    }
+
+    let bytes =
+        serde_json::to_vec(&manifest).map_err(|e| ClientError::JsonSerializeError { source: e })?;
+    for attempt in 1..=UPDATE_ATTEMPTS {
+        let (index, previous) = match read(client, key, None).await {
+            Ok(mut res) => match res.response.pop() {
+                Some(kv) => {
+                    let index = kv.modify_index;
+                    let previous = parse_value(client, kv, from_json::<LargeValueManifest>)
+                        .ok()
+                        .map(|m| m.value.checksum);
+                    (index, previous)
+                }
+                None => (0, None),
+            },
+            Err(ClientError::APIError { code: 404, .. }) => (0, None),
+            Err(e) => return Err(e),
+        };
+
+        let mut txn = Transaction::new().cas(key, &bytes, index);
+        if let Some(previous) = previous.filter(|c| *c != manifest.checksum) {
+            txn = txn.delete_tree(&chunk_prefix(key, &previous));
+        }
+        match txn::execute(client, &txn, None).await {
+            Ok(_) => return Ok(()),
+            Err(ClientError::TransactionError { .. }) => debug!(
+                "Manifest of {} was modified during attempt {} of {}, retrying",
+                key, attempt, UPDATE_ATTEMPTS
+            ),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(ClientError::UpdateConflictError {
+        key: key.to_string(),
+        attempts: UPDATE_ATTEMPTS,
+    })
+}
+
+/// Sets the value at the given key along with the given flags.
+///
+/// See [SetKeyRequest]
+#[instrument(skip(client, value, flags, opts), err)]
+pub async fn set_with_flags(
+    client: &impl Client,
+    key: &str,
+    value: impl Into<Vec<u8>>,
+    flags: impl KvFlags,
+    opts: Option<&mut SetKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut t = SetKeyRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .key(key)
+        .value(value)
+        .flags(flags.into())
+        .build()
+        .unwrap();
+    api::exec_with_result(client, prepare(endpoint)?).await
 }
 
 /// Serializes the given value into JSON and stores it at the given key.
@@ -182,14 +780,558 @@ pub async fn set_json<T: Serialize>(
     value: &T,
     opts: Option<&mut SetKeyRequestBuilder>,
 ) -> Result<ApiResponse<bool>, ClientError> {
-    let mut t = SetKeyRequest::builder();
     let bytes =
         serde_json::to_vec(value).map_err(|e| ClientError::JsonSerializeError { source: e })?;
-    let endpoint = opts
-        .unwrap_or(&mut t)
-        .key(key)
-        .value(bytes)
+    set(client, key, bytes, opts).await
+}
+
+/// Serializes the given value into CBOR and stores it at the given key.
+///
+/// See [SetKeyRequest]
+#[cfg(feature = "cbor")]
+#[instrument(skip(client, value, opts), err)]
+pub async fn set_cbor<T: Serialize>(
+    client: &impl Client,
+    key: &str,
+    value: &T,
+    opts: Option<&mut SetKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(value, &mut bytes)
+        .map_err(|e| ClientError::CborSerializeError { source: e })?;
+    set(client, key, bytes, opts).await
+}
+
+/// Serializes the given value into MessagePack and stores it at the given key.
+///
+/// Structs are serialized as maps so that fields can be added or reordered
+/// without breaking existing values.
+///
+/// See [SetKeyRequest]
+#[cfg(feature = "msgpack")]
+#[instrument(skip(client, value, opts), err)]
+pub async fn set_msgpack<T: Serialize>(
+    client: &impl Client,
+    key: &str,
+    value: &T,
+    opts: Option<&mut SetKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let bytes = rmp_serde::to_vec_named(value)
+        .map_err(|e| ClientError::MsgpackSerializeError { source: e })?;
+    set(client, key, bytes, opts).await
+}
+
+/// Serializes the given value into YAML and stores it at the given key.
+///
+/// See [SetKeyRequest]
+#[cfg(feature = "yaml")]
+#[instrument(skip(client, value, opts), err)]
+pub async fn set_yaml<T: Serialize>(
+    client: &impl Client,
+    key: &str,
+    value: &T,
+    opts: Option<&mut SetKeyRequestBuilder>,
+) -> Result<ApiResponse<bool>, ClientError> {
+    let bytes =
+        serde_yaml::to_vec(value).map_err(|e| ClientError::YamlSerializeError { source: e })?;
+    set(client, key, bytes, opts).await
+}
+
+/// Makes the keys under the given prefix match `desired`, whose keys are
+/// relative to the prefix, returning the keys which were changed.
+///
+/// Desired keys which don't exist are created, keys whose value differs are
+/// updated, and keys under the prefix which aren't desired are deleted. Values
+/// are compared as stored, without decompressing them. The changes are written
+/// in transactions of at most [txn::MAX_OPS] operations, each of which checks
+/// the modify index of the keys it changes, so a transaction fails with a
+/// [ClientError::TransactionError] if any of them changed after the tree was
+/// read. Each transaction is applied atomically, but changes written by earlier
+/// transactions are kept when a later one fails.
+///
+/// See [ReadKeyRequest] and [txn::execute]
+#[instrument(skip(client, desired), err)]
+pub async fn sync(
+    client: &impl Client,
+    prefix: &str,
+    mut desired: HashMap<String, Vec<u8>>,
+) -> Result<KVDiff, ClientError> {
+    let endpoint = ReadKeyRequest::builder()
+        .key(prefix)
+        .recurse(true)
         .build()
         .unwrap();
-    api::exec_with_result(client, endpoint).await
+    let existing = match api::exec_with_result(client, endpoint).await {
+        Ok(res) => res.response,
+        Err(e) if e.is_not_found() => Vec::new(),
+        Err(e) => return Err(e),
+    };
+
+    let mut diff = KVDiff::default();
+    let mut ops = Transaction::new();
+    for kv in existing {
+        let relative = kv.key.strip_prefix(prefix).unwrap_or(&kv.key);
+        match desired.remove(relative) {
+            Some(value) => {
+                let current: Vec<u8> = match kv.value {
+                    Some(v) => v.try_into()?,
+                    None => Vec::new(),
+                };
+                if current != value {
+                    ops = ops.cas(&kv.key, &value, kv.modify_index);
+                    diff.updated.push(kv.key);
+                }
+            }
+            None => {
+                ops = ops.delete_cas(&kv.key, kv.modify_index);
+                diff.deleted.push(kv.key);
+            }
+        }
+    }
+    let mut created: Vec<_> = desired.into_iter().collect();
+    created.sort();
+    for (key, value) in created {
+        let key = format!("{}{}", prefix, key);
+        ops = ops.cas(&key, &value, 0);
+        diff.created.push(key);
+    }
+
+    for batch in ops.ops().chunks(txn::MAX_OPS) {
+        let txn = batch
+            .iter()
+            .fold(Transaction::new(), |txn, op| txn.op(op.clone()));
+        txn::execute(client, &txn, None).await?;
+    }
+    diff.deleted.sort();
+    diff.updated.sort();
+    Ok(diff)
+}
+
+/// Reads the value at the given key, returning [None] if it doesn't exist.
+///
+/// See [ReadKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn try_read(
+    client: &impl Client,
+    key: &str,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+) -> Result<Option<ApiResponse<Vec<KVPair>>>, ClientError> {
+    api::optional(read(client, key, opts).await)
+}
+
+/// Reads the JSON value at the given key and deserializes it into an object,
+/// returning [None] if the key doesn't exist.
+///
+/// See [read_json] for how the response is handled.
+///
+/// See [ReadKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn try_read_json<T: DeserializeOwned, C: Client>(
+    client: &C,
+    key: &str,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+) -> Result<Option<ApiResponse<GenericKVPair<T>>>, ClientError> {
+    api::optional(read_json(client, key, opts).await)
+}
+
+/// Reads the raw value at the given key, returning [None] if it doesn't
+/// exist.
+///
+/// See [ReadRawKeyRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn try_read_raw(
+    client: &impl Client,
+    key: &str,
+    opts: Option<&mut ReadRawKeyRequestBuilder>,
+) -> Result<Option<ApiResponse<Vec<u8>>>, ClientError> {
+    api::optional(read_raw(client, key, opts).await)
+}
+
+/// Updates the value at the given key by applying the given function to its
+/// current value, which is `None` when the key doesn't exist.
+///
+/// The new value is written with a check-and-set against the index the
+/// current value was read at. If the key was modified in the meantime the
+/// value is read and the function applied again, up to [UPDATE_ATTEMPTS]
+/// times before failing with a [ClientError::UpdateConflictError]. The flags
+/// of the key are written back unchanged. The response is the value which was
+/// written.
+///
+/// See [ReadKeyRequest] and [SetKeyRequest]
+#[instrument(skip(client, f), err)]
+pub async fn update<F>(
+    client: &impl Client,
+    key: &str,
+    mut f: F,
+) -> Result<ApiResponse<Vec<u8>>, ClientError>
+where
+    F: FnMut(Option<Vec<u8>>) -> Vec<u8>,
+{
+    for attempt in 1..=UPDATE_ATTEMPTS {
+        let (current, index, flags) = match read(client, key, None).await {
+            Ok(mut res) => match res.response.pop() {
+                Some(kv) => {
+                    let value = match kv.value {
+                        Some(v) => v.try_into()?,
+                        None => Vec::new(),
+                    };
+                    (Some(value), kv.modify_index, kv.flags)
+                }
+                None => (None, 0, 0),
+            },
+            Err(ClientError::APIError { code: 404, .. }) => (None, 0, 0),
+            Err(e) => return Err(e),
+        };
+
+        let value = f(current);
+        let mut opts = SetKeyRequest::builder();
+        opts.flags(flags);
+        let res = cas_set(client, key, value.clone(), index, Some(&mut opts)).await?;
+        if res.response {
+            return Ok(res.map(|_| value));
+        }
+        debug!(
+            "Key {} was modified during attempt {} of {}, retrying",
+            key, attempt, UPDATE_ATTEMPTS
+        );
+    }
+
+    Err(ClientError::UpdateConflictError {
+        key: key.to_string(),
+        attempts: UPDATE_ATTEMPTS,
+    })
+}
+
+/// Watches the given key for changes.
+///
+/// The returned stream yields the current value of the key and then yields
+/// again each time the value changes. Passing `recurse` in the options will
+/// watch all keys under the given prefix instead. Values are decompressed the
+/// same way as by [read].
+///
+/// See [Watcher] and [ReadKeyRequest]
+#[instrument(skip(client, opts))]
+pub fn watch<'a, C: Client>(
+    client: &'a C,
+    key: &str,
+    opts: Option<&mut ReadKeyRequestBuilder>,
+) -> impl Stream<Item = Result<ApiResponse<Vec<KVPair>>, ClientError>> + 'a {
+    let mut t = ReadKeyRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).key(key).build().unwrap();
+    Watcher::new(client, endpoint).stream().map(move |res| {
+        let mut res = res?;
+        res.response = decompress_all(client, res.response)?;
+        Ok(res)
+    })
+}
+
+/// A key written with [ephemeral] which exists for as long as its session is
+/// renewed.
+///
+/// Calling [EphemeralKey::delete] deletes the session and with it the key.
+/// Dropping an [EphemeralKey] only stops renewing the session, in which case
+/// the key is deleted once the session's TTL is reached. The key is also
+/// deleted if the session is invalidated (i.e. by an operator), which is
+/// signalled through [EphemeralKey::active].
+pub struct EphemeralKey<C: Client + 'static> {
+    client: Arc<C>,
+    key: String,
+    renewal: RenewalTask,
+}
+
+impl<C: Client + 'static> EphemeralKey<C> {
+    /// Returns a [watch::Receiver] whose value changes to `false` once the
+    /// session has expired and the key was deleted.
+    pub fn active(&self) -> watch::Receiver<bool> {
+        self.renewal.active()
+    }
+
+    /// Deletes the key by deleting its session.
+    #[instrument(skip(self), fields(key = %self.key), err)]
+    pub async fn delete(self) -> Result<(), ClientError> {
+        session::delete(self.client.as_ref(), self.renewal.session(), None).await?;
+        Ok(())
+    }
+
+    /// Returns a receiver which yields each error encountered while renewing
+    /// the session.
+    pub fn failures(&mut self) -> &mut mpsc::UnboundedReceiver<ClientError> {
+        self.renewal.failures()
+    }
+
+    /// Returns whether the session is still active.
+    pub fn is_active(&self) -> bool {
+        self.renewal.is_active()
+    }
+
+    /// Returns the key.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the ID of the session holding the key.
+    pub fn session(&self) -> &str {
+        self.renewal.session()
+    }
+}
+
+/// A signed integer stored as a decimal string at a key, which many instances
+/// can change concurrently.
+///
+/// Changes are written with a check-and-set against the index the current
+/// value was read at. Conflicting changes are retried after the backoff of
+/// the [RetryPolicy], up to its `max_attempts`, before failing with a
+/// [ClientError::UpdateConflictError]. A key which doesn't exist is counted
+/// as 0.
+pub struct Counter<C: Client> {
+    client: Arc<C>,
+    key: String,
+    policy: RetryPolicy,
+}
+
+impl<C: Client> Counter<C> {
+    /// Returns a new [Counter] stored at the given key.
+    ///
+    /// Conflicts are retried up to [UPDATE_ATTEMPTS] times by default.
+    pub fn new(client: Arc<C>, key: &str) -> Self {
+        Counter {
+            client,
+            key: key.to_string(),
+            policy: counter_policy(),
+        }
+    }
+
+    /// Sets how conflicting changes are retried.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Adds the given amount to the counter, returning its new value.
+    #[instrument(skip(self), fields(key = %self.key), err)]
+    pub async fn add(&self, delta: i64) -> Result<i64, ClientError> {
+        add_counter(self.client.as_ref(), &self.key, delta, &self.policy).await
+    }
+
+    /// Subtracts one from the counter, returning its new value.
+    pub async fn decrement(&self) -> Result<i64, ClientError> {
+        self.add(-1).await
+    }
+
+    /// Returns the current value of the counter.
+    #[instrument(skip(self), fields(key = %self.key), err)]
+    pub async fn get(&self) -> Result<i64, ClientError> {
+        Ok(read_counter(self.client.as_ref(), &self.key).await?.0)
+    }
+
+    /// Adds one to the counter, returning its new value.
+    pub async fn increment(&self) -> Result<i64, ClientError> {
+        self.add(1).await
+    }
+
+    /// Returns the key the counter is stored at.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+/// Generates monotonically increasing IDs, starting from 1, which are unique
+/// across every instance sharing the key they're stored at.
+///
+/// IDs are reserved from a [Counter] in blocks of `block_size`, which are
+/// then handed out without contacting Consul. With a block size of 1 (the
+/// default) every ID is reserved separately, so IDs increase across all
+/// instances. Larger blocks make far fewer writes, but each instance only
+/// hands out increasing IDs from its own blocks, and the unused part of a
+/// block is skipped when the [Sequencer] is dropped.
+pub struct Sequencer<C: Client> {
+    block_size: u64,
+    counter: Counter<C>,
+    reserved: Mutex<(u64, u64)>,
+}
+
+impl<C: Client> Sequencer<C> {
+    /// Returns a new [Sequencer] stored at the given key.
+    pub fn new(client: Arc<C>, key: &str) -> Self {
+        Sequencer {
+            block_size: 1,
+            counter: Counter::new(client, key),
+            reserved: Mutex::new((0, 0)),
+        }
+    }
+
+    /// Sets how many IDs are reserved at once.
+    pub fn block_size(mut self, block_size: u64) -> Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// Sets how conflicting reservations are retried, see [Counter::retry].
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.counter = self.counter.retry(policy);
+        self
+    }
+
+    /// Returns the next ID, reserving a new block if the current one is used
+    /// up.
+    pub async fn next(&self) -> Result<u64, ClientError> {
+        // The range of reserved IDs which haven't been handed out yet
+        let mut reserved = self.reserved.lock().await;
+        if reserved.0 >= reserved.1 {
+            let last = self.counter.add(self.block_size as i64).await? as u64;
+            *reserved = ((last + 1).saturating_sub(self.block_size), last + 1);
+        }
+        let id = reserved.0;
+        reserved.0 += 1;
+        Ok(id)
+    }
+}
+
+/// Deserializes a JSON value.
+fn from_json<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, ClientError> {
+    serde_json::from_slice(bytes).map_err(|e| ClientError::JsonDeserializeError { source: e })
+}
+
+/// Decodes the value of a [KVPair] into a [GenericKVPair] with the given
+/// function.
+fn parse_value<T: DeserializeOwned>(
+    client: &impl Client,
+    kv: KVPair,
+    decode: impl Fn(&[u8]) -> Result<T, ClientError>,
+) -> Result<GenericKVPair<T>, ClientError> {
+    let kv = decompress(client, kv)?;
+    let bytes: Vec<u8> = kv.value.unwrap_or_default().try_into()?;
+    let value = decode(&bytes)?;
+    Ok(GenericKVPair {
+        value,
+        create_index: kv.create_index,
+        flags: kv.flags,
+        key: kv.key,
+        lock_index: kv.lock_index,
+        modify_index: kv.modify_index,
+        namespace: kv.namespace,
+        session: kv.session,
+    })
+}
+
+/// Returns the prefix the chunks of a large value are stored under.
+fn chunk_prefix(key: &str, checksum: &str) -> String {
+    format!("{}/{}/", key, checksum)
+}
+
+/// Decompresses the value of a [KVPair] if its flags mark it as compressed
+/// and the client is configured to decompress values, clearing the marker
+/// from the flags.
+pub(crate) fn decompress(client: &impl Client, mut kv: KVPair) -> Result<KVPair, ClientError> {
+    if !client.settings().decompress {
+        return Ok(kv);
+    }
+    if let Some(compression) = Compression::from_flags(kv.flags) {
+        if let Some(value) = kv.value.take() {
+            let bytes: Vec<u8> = value.try_into()?;
+            kv.value = Some(compression.decompress(&bytes)?.as_slice().into());
+        }
+        kv.flags = 0;
+    }
+    Ok(kv)
+}
+
+/// Decompresses the values of every [KVPair], see [decompress].
+fn decompress_all(client: &impl Client, kvs: Vec<KVPair>) -> Result<Vec<KVPair>, ClientError> {
+    kvs.into_iter().map(|kv| decompress(client, kv)).collect()
+}
+
+/// Compresses the value of a [SetKeyRequest] if requested and checks that the
+/// result isn't larger than [MAX_VALUE_SIZE].
+fn prepare(mut endpoint: SetKeyRequest) -> Result<SetKeyRequest, ClientError> {
+    if let Some(compression) = endpoint.compression {
+        if endpoint.flags.unwrap_or_default() != 0 {
+            return Err(ClientError::CompressionFlagsError { key: endpoint.key });
+        }
+        endpoint.value = compression.compress(&endpoint.value)?;
+        endpoint.flags = Some(compression.flags());
+    }
+    if endpoint.value.len() > MAX_VALUE_SIZE {
+        return Err(ClientError::ValueTooLarge {
+            key: endpoint.key,
+            size: endpoint.value.len(),
+            limit: MAX_VALUE_SIZE,
+        });
+    }
+    Ok(endpoint)
+}
+
+/// Returns the hex encoded SHA-256 digest of the given bytes.
+fn sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Adds the given amount to the counter at the given key, see [Counter].
+pub(crate) async fn add_counter(
+    client: &impl Client,
+    key: &str,
+    delta: i64,
+    policy: &RetryPolicy,
+) -> Result<i64, ClientError> {
+    let attempts = policy.max_attempts.max(1);
+    for attempt in 1..=attempts {
+        let (current, index) = read_counter(client, key).await?;
+        let value = current.wrapping_add(delta);
+        let res = cas_set(client, key, value.to_string(), index, None).await?;
+        if res.response {
+            return Ok(value);
+        }
+        debug!(
+            "Counter {} was modified during attempt {} of {}, retrying",
+            key, attempt, attempts
+        );
+        if attempt < attempts {
+            tokio::time::sleep(policy.backoff(attempt)).await;
+        }
+    }
+
+    Err(ClientError::UpdateConflictError {
+        key: key.to_string(),
+        attempts,
+    })
+}
+
+/// Returns the policy a [Counter] retries conflicting changes with by
+/// default.
+pub(crate) fn counter_policy() -> RetryPolicy {
+    RetryPolicy {
+        initial_backoff: Duration::from_millis(10),
+        max_attempts: UPDATE_ATTEMPTS,
+        max_backoff: Duration::from_secs(1),
+        ..Default::default()
+    }
+}
+
+/// Reads the value of the counter at the given key and the index it was read
+/// at, which is 0 when the key doesn't exist.
+async fn read_counter(client: &impl Client, key: &str) -> Result<(i64, u64), ClientError> {
+    let kv = match try_read(client, key, None).await? {
+        Some(mut res) => match res.response.pop() {
+            Some(kv) => kv,
+            None => return Ok((0, 0)),
+        },
+        None => return Ok((0, 0)),
+    };
+    let value = match kv.value {
+        Some(v) => String::from_utf8(v.try_into()?).unwrap_or_default(),
+        None => String::new(),
+    };
+    if value.is_empty() {
+        return Ok((0, kv.modify_index));
+    }
+    match value.trim().parse() {
+        Ok(n) => Ok((n, kv.modify_index)),
+        Err(_) => Err(ClientError::InvalidCounterError {
+            key: key.to_string(),
+            value,
+        }),
+    }
 }
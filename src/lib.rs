@@ -16,12 +16,28 @@
 //!
 //! The following features are currently supported:
 //!
+//! * [ACLs](https://www.consul.io/api-docs/acl)
+//! * [Agent](https://www.consul.io/api-docs/agent)
 //! * [Catalog](https://www.consul.io/api-docs/catalogv)
 //! * [Checks](https://www.consul.io/api-docs/agent/check)
+//! * [Config Entries](https://www.consul.io/api-docs/config)
+//! * [Connect CA](https://www.consul.io/api-docs/connect/ca)
+//! * [Connect Intentions](https://www.consul.io/api-docs/connect/intentions)
+//! * [Coordinates](https://www.consul.io/api-docs/coordinate)
+//! * [Discovery Chain](https://www.consul.io/api-docs/discovery-chain)
+//! * [Events](https://www.consul.io/api-docs/event)
+//! * [Health](https://www.consul.io/api-docs/health)
 //! * [KV Store](https://www.consul.io/api-docs/kv)
+//! * [Namespaces](https://www.consul.io/api-docs/namespaces) (Enterprise)
+//! * [Operator Keyring](https://www.consul.io/api-docs/operator/keyring)
+//! * [Partitions](https://www.consul.io/api-docs/admin-partitions) (Enterprise)
+//! * [Peering](https://www.consul.io/api-docs/peering)
+//! * [Prepared Queries](https://www.consul.io/api-docs/query)
 //! * [Services](https://www.consul.io/api-docs/agent/service)
 //! * [Sessions](https://www.consul.io/api-docs/session)
 //! * [Snapshots](https://www.consul.io/api-docs/snapshot)
+//! * [Status](https://www.consul.io/api-docs/status)
+//! * [Transactions](https://www.consul.io/api-docs/txn)
 //!
 //! Additionally, all optional API features such as consistency modes, blocking,
 //! etc. are also supported.
@@ -131,6 +147,41 @@
 //! # })
 //! ```
 //!
+//! ### Blocking client
+//!
+//! Enabling the `blocking` feature adds synchronous versions of the API functions
+//! under `consulrs::blocking`, backed by an internal runtime:
+//!
+//! ```ignore
+//! use consulrs::blocking::{self, ConsulClient};
+//! use consulrs::client::ConsulClientSettingsBuilder;
+//!
+//! let client = ConsulClient::new(
+//!     ConsulClientSettingsBuilder::default()
+//!         .address("https://127.0.0.1:8200")
+//!         .build()
+//!         .unwrap()
+//! ).unwrap();
+//!
+//! blocking::kv::set(&client, "mykey", b"myvalue", None).unwrap();
+//! ```
+//!
+//! ### Serialization formats
+//!
+//! Besides JSON, values can be stored in the KV store as MessagePack, CBOR, or
+//! YAML by enabling the `msgpack`, `cbor`, or `yaml` feature respectively. Each
+//! adds a pair of functions parallel to `kv::set_json` and `kv::read_json`, such
+//! as `kv::set_msgpack` and `kv::read_msgpack`.
+//!
+//! ### Compression
+//!
+//! Values can be compressed before they're stored by enabling the `gzip` or `zstd`
+//! feature and setting the `compression` option when writing a key. The algorithm
+//! is marked by setting the flags of the key to a value of its own, so a client
+//! with the `decompress` setting enabled decompresses the value transparently when
+//! reading it. Decompression is off by default, as other clients may use the same
+//! flags for their own purposes.
+//!
 //! See the [examples](examples) directory for additional examples.
 //!
 //! ## Error Handling and Tracing
@@ -169,12 +220,51 @@
 #[macro_use]
 extern crate tracing;
 
+pub mod acl;
+pub mod agent;
 pub mod api;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cache;
 pub mod catalog;
 pub mod check;
 pub mod client;
+pub mod config;
+pub mod config_watch;
+pub mod connect;
+pub mod coordinate;
+pub mod discovery;
+pub mod discovery_chain;
+pub mod election;
 pub mod error;
+pub mod event;
+pub mod external;
+pub mod health;
+#[cfg(feature = "unstable-internal")]
+pub mod internal;
 pub mod kv;
+pub mod lock;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod multi_dc;
+pub mod namespace;
+pub mod operator;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod partition;
+pub mod peering;
+pub mod query;
+pub mod queue;
+#[cfg(feature = "replay")]
+pub mod replay;
 pub mod service;
 pub mod session;
 pub mod snapshot;
+pub mod status;
+pub mod template;
+#[cfg(feature = "consulrs-test")]
+pub mod testing;
+pub mod txn;
+pub mod watch_group;
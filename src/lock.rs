@@ -0,0 +1,817 @@
+//! Implements the Consul [lock](https://learn.hashicorp.com/tutorials/consul/application-leader-elections)
+//! and [semaphore](https://learn.hashicorp.com/tutorials/consul/distributed-semaphore)
+//! recipes.
+//!
+//! A [Lock] is held by a session which acquires a single KV key, while a
+//! [Semaphore] allows up to a configured number of sessions to hold it at once
+//! by tracking the current holders in a shared KV key. While either is held the
+//! session is renewed in the background and the KV store is monitored so that
+//! the holder is notified if it's lost (i.e. because the session was
+//! invalidated by an operator).
+use std::{collections::HashMap, convert::TryInto, future::Future, sync::Arc, time::Duration};
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use tokio::{sync::watch, task::JoinHandle};
+
+use crate::{
+    api::{
+        self,
+        blocking::parse_index,
+        features::Blocking,
+        kv::{
+            common::KVPair,
+            requests::{ReadKeyRequest, SetKeyRequest},
+        },
+        session::{common::SessionBehavior, requests::CreateSessionRequest},
+        Features,
+    },
+    client::Client,
+    error::ClientError,
+    kv, session,
+};
+
+/// The flag value set on lock keys, shared with the official Go client so
+/// that locks created by either client can be identified.
+pub const LOCK_FLAG_VALUE: u64 = 0x2ddccbc058a50c18;
+
+/// The flag value set on semaphore keys, shared with the official Go client.
+pub const SEMAPHORE_FLAG_VALUE: u64 = 0xe0f69a2baa414de0;
+
+/// The name of the key under a semaphore prefix which tracks its holders.
+pub const SEMAPHORE_LOCK_KEY: &str = ".lock";
+
+/// Options for configuring a [Lock].
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into))]
+pub struct LockOptions {
+    /// The key which is acquired to hold the lock
+    pub key: String,
+    /// The value written to the key while the lock is held
+    #[builder(default)]
+    pub value: Vec<u8>,
+    /// The lock delay applied to the session
+    #[builder(default = "Duration::from_secs(15)")]
+    pub lock_delay: Duration,
+    /// The number of times monitoring of the key is retried before the lock
+    /// is considered lost
+    #[builder(default = "3")]
+    pub monitor_retries: u32,
+    /// The time waited between failed attempts to acquire the key
+    #[builder(default = "Duration::from_secs(5)")]
+    pub retry_time: Duration,
+    /// The name given to the session
+    #[builder(default = "String::from(\"Consul API Lock\")")]
+    pub session_name: String,
+    /// The TTL of the session, which is renewed at half this interval
+    #[builder(default = "Duration::from_secs(15)")]
+    pub session_ttl: Duration,
+    /// The maximum time a blocking query against the key may wait
+    #[builder(default = "Duration::from_secs(15)")]
+    pub wait_time: Duration,
+}
+
+impl LockOptions {
+    /// Returns a default instance of [LockOptionsBuilder].
+    pub fn builder() -> LockOptionsBuilder {
+        LockOptionsBuilder::default()
+    }
+}
+
+/// A distributed lock backed by a Consul session and KV key.
+///
+/// The lock is released when [Lock::release] is called or, on a best-effort
+/// basis, when the lock is dropped while still held.
+///
+/// # Example
+///
+/// ```no_run
+/// # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+/// use consulrs::lock::{Lock, LockOptions};
+/// use std::sync::Arc;
+///
+/// # tokio_test::block_on(async {
+/// # let client = ConsulClient::new(
+/// #     ConsulClientSettingsBuilder::default()
+/// #         .address("https://127.0.0.1:8200")
+/// #         .build()
+/// #         .unwrap()
+/// # ).unwrap();
+/// let client = Arc::new(client);
+/// let mut lock = Lock::new(
+///     client,
+///     LockOptions::builder().key("service/leader").build().unwrap(),
+/// );
+///
+/// let guard = lock.guard().await.unwrap();
+/// // Do work while holding the lock
+/// guard.release().await.unwrap();
+/// # })
+/// ```
+pub struct Lock<C: Client + 'static> {
+    client: Arc<C>,
+    opts: LockOptions,
+    state: Option<LockState>,
+}
+
+/// The state of a [Lock] or [Semaphore] which is currently held.
+struct LockState {
+    held: watch::Receiver<bool>,
+    session: String,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl LockState {
+    /// Stops the background renewal and monitoring tasks.
+    fn abort(&self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+impl<C: Client + 'static> Lock<C> {
+    /// Returns a new [Lock] using the given options.
+    pub fn new(client: Arc<C>, opts: LockOptions) -> Self {
+        Lock {
+            client,
+            opts,
+            state: None,
+        }
+    }
+
+    /// Attempts to acquire the lock, waiting until it becomes available.
+    ///
+    /// Returns a [watch::Receiver] whose value changes to `false` if the lock
+    /// is lost after being acquired. Calling this method while the lock is
+    /// already held simply returns the existing receiver.
+    #[instrument(skip(self), fields(key = %self.opts.key), err)]
+    pub async fn acquire(&mut self) -> Result<watch::Receiver<bool>, ClientError> {
+        if let Some(state) = &self.state {
+            return Ok(state.held.clone());
+        }
+
+        let session = create_session(
+            self.client.as_ref(),
+            &self.opts.session_name,
+            self.opts.session_ttl,
+            self.opts.lock_delay,
+        )
+        .await?;
+        let (tx, rx) = watch::channel(true);
+        let renewal = AbortOnDrop(Some(tokio::spawn(session::keep_renewed(
+            self.client.clone(),
+            session.clone(),
+            self.opts.session_ttl,
+            tx.clone(),
+            None,
+        ))));
+
+        if let Err(e) = self.wait_and_acquire(&session).await {
+            let _ = session::delete(self.client.as_ref(), &session, None).await;
+            return Err(e);
+        }
+        info!("Acquired lock on {}", self.opts.key);
+
+        let holder = session.clone();
+        let monitor = tokio::spawn(monitor(
+            self.client.clone(),
+            self.opts.key.clone(),
+            self.opts.wait_time,
+            (self.opts.monitor_retries, self.opts.retry_time),
+            tx,
+            move |pairs: &[KVPair]| {
+                pairs.first().and_then(|p| p.session.as_deref()) == Some(holder.as_str())
+            },
+        ));
+        self.state = Some(LockState {
+            held: rx.clone(),
+            session,
+            tasks: vec![renewal.into_inner(), monitor],
+        });
+        Ok(rx)
+    }
+
+    /// Acquires the lock and returns a guard which releases it when dropped.
+    pub async fn guard(&mut self) -> Result<LockGuard<'_, C>, ClientError> {
+        self.acquire().await?;
+        Ok(LockGuard { lock: self })
+    }
+
+    /// Returns whether the lock is currently held.
+    pub fn is_held(&self) -> bool {
+        self.state
+            .as_ref()
+            .map(|s| *s.held.borrow())
+            .unwrap_or(false)
+    }
+
+    /// Returns the ID of the session holding the lock, if it's held.
+    pub fn session(&self) -> Option<&str> {
+        self.state.as_ref().map(|s| s.session.as_str())
+    }
+
+    /// Releases the lock if it's held.
+    #[instrument(skip(self), fields(key = %self.opts.key), err)]
+    pub async fn release(&mut self) -> Result<(), ClientError> {
+        match self.state.take() {
+            Some(state) => release_lock(self.client.as_ref(), &self.opts, state).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Waits for the lock key to be free and then attempts to acquire it with
+    /// the given session until successful.
+    async fn wait_and_acquire(&self, session: &str) -> Result<(), ClientError> {
+        let mut index = 0;
+        loop {
+            let (mut pairs, next) = read_keys(
+                self.client.as_ref(),
+                &self.opts.key,
+                false,
+                self.opts.wait_time,
+                index,
+            )
+            .await?;
+            let pair = pairs.pop();
+            index = next;
+
+            // Another session holds the lock, so block until the key changes
+            if let Some(KVPair {
+                session: Some(holder),
+                ..
+            }) = &pair
+            {
+                if holder != session {
+                    debug!("Lock is held by session {}, waiting", holder);
+                    continue;
+                }
+            }
+
+            let endpoint = SetKeyRequest::builder()
+                .key(self.opts.key.as_str())
+                .value(self.opts.value.clone())
+                .acquire(session)
+                .flags(LOCK_FLAG_VALUE)
+                .build()
+                .unwrap();
+            if api::exec_with_result(self.client.as_ref(), endpoint)
+                .await?
+                .response
+            {
+                return Ok(());
+            }
+
+            // The lock delay is likely in effect
+            debug!(
+                "Failed acquiring lock, retrying in {:?}",
+                self.opts.retry_time
+            );
+            tokio::time::sleep(self.opts.retry_time).await;
+        }
+    }
+}
+
+impl<C: Client + 'static> Drop for Lock<C> {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.take() {
+            state.abort();
+            let (client, opts) = (self.client.clone(), self.opts.clone());
+            spawn_release(self.opts.key.clone(), async move {
+                release_lock(client.as_ref(), &opts, state).await
+            });
+        }
+    }
+}
+
+/// A RAII guard over a held [Lock].
+///
+/// The lock is released in a background task when the guard is dropped. Use
+/// [LockGuard::release] to release the lock and wait for the result.
+pub struct LockGuard<'a, C: Client + 'static> {
+    lock: &'a mut Lock<C>,
+}
+
+impl<C: Client + 'static> LockGuard<'_, C> {
+    /// Returns a [watch::Receiver] whose value changes to `false` if the lock
+    /// is lost.
+    pub fn held(&self) -> Option<watch::Receiver<bool>> {
+        self.lock.state.as_ref().map(|s| s.held.clone())
+    }
+
+    /// Releases the lock.
+    pub async fn release(self) -> Result<(), ClientError> {
+        self.lock.release().await
+    }
+}
+
+impl<C: Client + 'static> Drop for LockGuard<'_, C> {
+    fn drop(&mut self) {
+        if let Some(state) = self.lock.state.take() {
+            state.abort();
+            let (client, opts) = (self.lock.client.clone(), self.lock.opts.clone());
+            spawn_release(self.lock.opts.key.clone(), async move {
+                release_lock(client.as_ref(), &opts, state).await
+            });
+        }
+    }
+}
+
+/// Options for configuring a [Semaphore].
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into))]
+pub struct SemaphoreOptions {
+    /// The prefix under which the contender and lock keys are stored
+    pub prefix: String,
+    /// The value written to the contender key of this session
+    #[builder(default)]
+    pub value: Vec<u8>,
+    /// The number of times monitoring of the lock key is retried before the
+    /// semaphore is considered lost
+    #[builder(default = "3")]
+    pub monitor_retries: u32,
+    /// The name given to the session
+    #[builder(default = "String::from(\"Consul API Semaphore\")")]
+    pub session_name: String,
+    /// The TTL of the session, which is renewed at half this interval
+    #[builder(default = "Duration::from_secs(15)")]
+    pub session_ttl: Duration,
+    /// The time waited between failed attempts to read the lock key
+    #[builder(default = "Duration::from_secs(5)")]
+    pub retry_time: Duration,
+    /// The maximum time a blocking query against the prefix may wait
+    #[builder(default = "Duration::from_secs(15)")]
+    pub wait_time: Duration,
+}
+
+impl SemaphoreOptions {
+    /// Returns a default instance of [SemaphoreOptionsBuilder].
+    pub fn builder() -> SemaphoreOptionsBuilder {
+        SemaphoreOptionsBuilder::default()
+    }
+}
+
+/// The contents of the lock key of a [Semaphore].
+///
+/// Uses the same format as the official Go client.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SemaphoreLock {
+    pub limit: u32,
+    pub holders: HashMap<String, bool>,
+}
+
+/// A distributed semaphore backed by a Consul session and KV prefix.
+///
+/// Each contender registers a key under the prefix which is acquired with its
+/// session. A contender holds the semaphore once its session has been added
+/// to the holders recorded in the lock key, which is updated using
+/// check-and-set. Holders whose contender key is no longer held by their
+/// session are pruned before the limit is checked.
+///
+/// The semaphore is released when [Semaphore::release] is called or, on a
+/// best-effort basis, when it's dropped while still held.
+///
+/// # Example
+///
+/// ```no_run
+/// # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+/// use consulrs::lock::{Semaphore, SemaphoreOptions};
+/// use std::sync::Arc;
+///
+/// # tokio_test::block_on(async {
+/// # let client = ConsulClient::new(
+/// #     ConsulClientSettingsBuilder::default()
+/// #         .address("https://127.0.0.1:8200")
+/// #         .build()
+/// #         .unwrap()
+/// # ).unwrap();
+/// let mut semaphore = Semaphore::new(
+///     Arc::new(client),
+///     SemaphoreOptions::builder().prefix("service/workers").build().unwrap(),
+/// );
+///
+/// semaphore.acquire(3).await.unwrap();
+/// // Do work while holding one of the three slots
+/// semaphore.release().await.unwrap();
+/// # })
+/// ```
+pub struct Semaphore<C: Client + 'static> {
+    client: Arc<C>,
+    opts: SemaphoreOptions,
+    state: Option<LockState>,
+}
+
+impl<C: Client + 'static> Semaphore<C> {
+    /// Returns a new [Semaphore] using the given options.
+    pub fn new(client: Arc<C>, opts: SemaphoreOptions) -> Self {
+        Semaphore {
+            client,
+            opts,
+            state: None,
+        }
+    }
+
+    /// Attempts to acquire one of `limit` slots in the semaphore, waiting
+    /// until one becomes available.
+    ///
+    /// All contenders must agree on the limit, otherwise a
+    /// [ClientError::SemaphoreConflictError] is returned. Returns a
+    /// [watch::Receiver] whose value changes to `false` if the semaphore is
+    /// lost after being acquired. Calling this method while the semaphore is
+    /// already held simply returns the existing receiver.
+    #[instrument(skip(self), fields(prefix = %self.opts.prefix), err)]
+    pub async fn acquire(&mut self, limit: u32) -> Result<watch::Receiver<bool>, ClientError> {
+        if let Some(state) = &self.state {
+            return Ok(state.held.clone());
+        }
+
+        let session = create_session(
+            self.client.as_ref(),
+            &self.opts.session_name,
+            self.opts.session_ttl,
+            Duration::from_secs(0),
+        )
+        .await?;
+        let (tx, rx) = watch::channel(true);
+        let renewal = AbortOnDrop(Some(tokio::spawn(session::keep_renewed(
+            self.client.clone(),
+            session.clone(),
+            self.opts.session_ttl,
+            tx.clone(),
+            None,
+        ))));
+
+        if let Err(e) = self.wait_and_acquire(&session, limit).await {
+            let contender = contender_key(&self.opts.prefix, &session);
+            let _ = kv::delete(self.client.as_ref(), &contender, None).await;
+            let _ = session::delete(self.client.as_ref(), &session, None).await;
+            return Err(e);
+        }
+        info!("Acquired semaphore on {}", self.opts.prefix);
+
+        let holder = session.clone();
+        let monitor = tokio::spawn(monitor(
+            self.client.clone(),
+            lock_key(&self.opts.prefix),
+            self.opts.wait_time,
+            (self.opts.monitor_retries, self.opts.retry_time),
+            tx,
+            move |pairs: &[KVPair]| {
+                pairs
+                    .first()
+                    .and_then(|p| decode_semaphore(p).ok())
+                    .map(|s| s.holders.contains_key(&holder))
+                    .unwrap_or(false)
+            },
+        ));
+        self.state = Some(LockState {
+            held: rx.clone(),
+            session,
+            tasks: vec![renewal.into_inner(), monitor],
+        });
+        Ok(rx)
+    }
+
+    /// Returns whether the semaphore is currently held.
+    pub fn is_held(&self) -> bool {
+        self.state
+            .as_ref()
+            .map(|s| *s.held.borrow())
+            .unwrap_or(false)
+    }
+
+    /// Returns the ID of the session holding the semaphore, if it's held.
+    pub fn session(&self) -> Option<&str> {
+        self.state.as_ref().map(|s| s.session.as_str())
+    }
+
+    /// Releases the semaphore if it's held.
+    #[instrument(skip(self), fields(prefix = %self.opts.prefix), err)]
+    pub async fn release(&mut self) -> Result<(), ClientError> {
+        match self.state.take() {
+            Some(state) => release_semaphore(self.client.as_ref(), &self.opts, state).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Registers the given session as a contender and then waits until it
+    /// can be added to the holders of the semaphore.
+    async fn wait_and_acquire(&self, session: &str, limit: u32) -> Result<(), ClientError> {
+        let contender = contender_key(&self.opts.prefix, session);
+        let endpoint = SetKeyRequest::builder()
+            .key(contender.as_str())
+            .value(self.opts.value.clone())
+            .acquire(session)
+            .flags(SEMAPHORE_FLAG_VALUE)
+            .build()
+            .unwrap();
+        if !api::exec_with_result(self.client.as_ref(), endpoint)
+            .await?
+            .response
+        {
+            return Err(ClientError::SemaphoreContenderError { key: contender });
+        }
+
+        let lock = lock_key(&self.opts.prefix);
+        let mut index = 0;
+        loop {
+            let (pairs, next) = read_keys(
+                self.client.as_ref(),
+                &format!("{}/", self.opts.prefix.trim_end_matches('/')),
+                true,
+                self.opts.wait_time,
+                index,
+            )
+            .await?;
+            index = next;
+
+            let (mut semaphore, modify_index) = match pairs.iter().find(|p| p.key == lock) {
+                Some(p) => (decode_semaphore(p)?, p.modify_index),
+                None => (
+                    SemaphoreLock {
+                        limit,
+                        holders: HashMap::new(),
+                    },
+                    0,
+                ),
+            };
+            if semaphore.limit != limit {
+                return Err(ClientError::SemaphoreConflictError {
+                    limit,
+                    existing: semaphore.limit,
+                });
+            }
+
+            // Prune holders which no longer hold their contender key
+            semaphore.holders.retain(|holder, _| {
+                let key = contender_key(&self.opts.prefix, holder);
+                pairs
+                    .iter()
+                    .any(|p| p.key == key && p.session.as_deref() == Some(holder.as_str()))
+            });
+
+            if semaphore.holders.len() >= limit as usize {
+                debug!("Semaphore is at its limit of {}, waiting", limit);
+                continue;
+            }
+
+            semaphore.holders.insert(session.into(), true);
+            if write_semaphore(self.client.as_ref(), &lock, &semaphore, modify_index).await? {
+                return Ok(());
+            }
+            debug!("Lost race updating semaphore holders, retrying");
+        }
+    }
+}
+
+impl<C: Client + 'static> Drop for Semaphore<C> {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.take() {
+            state.abort();
+            let (client, opts) = (self.client.clone(), self.opts.clone());
+            spawn_release(self.opts.prefix.clone(), async move {
+                release_semaphore(client.as_ref(), &opts, state).await
+            });
+        }
+    }
+}
+
+/// Aborts the wrapped task when dropped, so that a cancelled acquisition
+/// doesn't leave its session being renewed forever.
+struct AbortOnDrop(Option<JoinHandle<()>>);
+
+impl AbortOnDrop {
+    /// Returns the wrapped task without aborting it.
+    fn into_inner(mut self) -> JoinHandle<()> {
+        self.0.take().unwrap()
+    }
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if let Some(task) = &self.0 {
+            task.abort();
+        }
+    }
+}
+
+/// Creates the session used for holding a lock or semaphore.
+async fn create_session(
+    client: &impl Client,
+    name: &str,
+    ttl: Duration,
+    lock_delay: Duration,
+) -> Result<String, ClientError> {
+    Ok(session::create(
+        client,
+        Some(
+            CreateSessionRequest::builder()
+                .behavior(SessionBehavior::Release)
+                .lock_delay(format!("{}s", lock_delay.as_secs()))
+                .name(name)
+                .ttl(format!("{}s", ttl.as_secs())),
+        ),
+    )
+    .await?
+    .response
+    .id)
+}
+
+/// Reads the given key using a blocking query, returning an empty list if the
+/// key does not exist.
+async fn read_keys(
+    client: &impl Client,
+    key: &str,
+    recurse: bool,
+    wait: Duration,
+    index: u64,
+) -> Result<(Vec<KVPair>, u64), ClientError> {
+    let endpoint = ReadKeyRequest::builder()
+        .key(key)
+        .recurse(recurse)
+        .features(
+            Features::builder()
+                .blocking(Blocking {
+                    index,
+                    wait: Some(format!("{}s", wait.as_secs())),
+                })
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    match api::exec_with_result(client, endpoint).await {
+        Ok(res) => {
+            let index = parse_index(&res)?;
+            Ok((res.response, index))
+        }
+        Err(ClientError::APIError { code: 404, .. }) => Ok((Vec::new(), 0)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns the contender key of the given session under a semaphore prefix.
+fn contender_key(prefix: &str, session: &str) -> String {
+    format!("{}/{}", prefix.trim_end_matches('/'), session)
+}
+
+/// Returns the lock key under a semaphore prefix.
+fn lock_key(prefix: &str) -> String {
+    format!("{}/{}", prefix.trim_end_matches('/'), SEMAPHORE_LOCK_KEY)
+}
+
+/// Decodes the contents of a semaphore lock key.
+fn decode_semaphore(pair: &KVPair) -> Result<SemaphoreLock, ClientError> {
+    match pair.value.clone() {
+        Some(value) => {
+            let bytes: Vec<u8> = value.try_into()?;
+            serde_json::from_slice(&bytes)
+                .map_err(|e| ClientError::JsonDeserializeError { source: e })
+        }
+        None => Ok(SemaphoreLock::default()),
+    }
+}
+
+/// Writes the contents of a semaphore lock key if its modify index matches,
+/// returning whether the write succeeded.
+async fn write_semaphore(
+    client: &impl Client,
+    key: &str,
+    semaphore: &SemaphoreLock,
+    index: u64,
+) -> Result<bool, ClientError> {
+    let value =
+        serde_json::to_vec(semaphore).map_err(|e| ClientError::JsonSerializeError { source: e })?;
+    let endpoint = SetKeyRequest::builder()
+        .key(key)
+        .value(value)
+        .cas(index)
+        .flags(SEMAPHORE_FLAG_VALUE)
+        .build()
+        .unwrap();
+    Ok(api::exec_with_result(client, endpoint).await?.response)
+}
+
+/// Releases a held lock and destroys its session.
+async fn release_lock(
+    client: &impl Client,
+    opts: &LockOptions,
+    state: LockState,
+) -> Result<(), ClientError> {
+    state.abort();
+
+    let endpoint = SetKeyRequest::builder()
+        .key(opts.key.as_str())
+        .release(state.session.as_str())
+        .flags(LOCK_FLAG_VALUE)
+        .build()
+        .unwrap();
+    let res = api::exec_with_result(client, endpoint).await;
+    session::delete(client, &state.session, None).await?;
+    res?;
+
+    info!("Released lock on {}", opts.key);
+    Ok(())
+}
+
+/// Removes a held semaphore's session from its holders, deletes its contender
+/// key, and destroys its session.
+async fn release_semaphore(
+    client: &impl Client,
+    opts: &SemaphoreOptions,
+    state: LockState,
+) -> Result<(), ClientError> {
+    state.abort();
+
+    let lock = lock_key(&opts.prefix);
+    let res = loop {
+        let (mut pairs, _) = match read_keys(client, &lock, false, opts.wait_time, 0).await {
+            Ok(r) => r,
+            Err(e) => break Err(e),
+        };
+        let pair = match pairs.pop() {
+            Some(p) => p,
+            None => break Ok(()),
+        };
+        let mut semaphore = match decode_semaphore(&pair) {
+            Ok(s) => s,
+            Err(e) => break Err(e),
+        };
+        if semaphore.holders.remove(&state.session).is_none() {
+            break Ok(());
+        }
+        match write_semaphore(client, &lock, &semaphore, pair.modify_index).await {
+            Ok(true) => break Ok(()),
+            Ok(false) => debug!("Lost race updating semaphore holders, retrying"),
+            Err(e) => break Err(e),
+        }
+    };
+
+    let contender = contender_key(&opts.prefix, &state.session);
+    let deleted = kv::delete(client, &contender, None).await;
+    session::delete(client, &state.session, None).await?;
+    res?;
+    deleted?;
+
+    info!("Released semaphore on {}", opts.prefix);
+    Ok(())
+}
+
+/// Runs the release of a lock or semaphore, or of anything else held until
+/// dropped, in a background task if a runtime is available.
+pub(crate) fn spawn_release<F>(name: String, release: F)
+where
+    F: Future<Output = Result<(), ClientError>> + Send + 'static,
+{
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(async move {
+                if let Err(e) = release.await {
+                    warn!("Failed releasing {}: {}", name, e);
+                }
+            });
+        }
+        Err(_) => warn!("No runtime available to release {}", name),
+    }
+}
+
+/// Monitors the given key, signalling once `is_held` returns false for its
+/// contents or it can't be read after the given number of retries.
+async fn monitor<C, F>(
+    client: Arc<C>,
+    key: String,
+    wait: Duration,
+    (retries, retry_time): (u32, Duration),
+    held: watch::Sender<bool>,
+    is_held: F,
+) where
+    C: Client,
+    F: Fn(&[KVPair]) -> bool,
+{
+    let mut index = 0;
+    let mut remaining = retries;
+    loop {
+        match read_keys(client.as_ref(), &key, false, wait, index).await {
+            Ok((pairs, next)) => {
+                remaining = retries;
+                index = next;
+                if !is_held(&pairs) {
+                    warn!("Lost hold of {}", key);
+                    let _ = held.send(false);
+                    return;
+                }
+            }
+            Err(e) if remaining > 0 => {
+                warn!("Failed monitoring {}: {}", key, e);
+                remaining -= 1;
+                tokio::time::sleep(retry_time).await;
+            }
+            Err(e) => {
+                warn!("Failed monitoring {}: {}", key, e);
+                let _ = held.send(false);
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Client metrics recorded through the [metrics] crate.
+//!
+//! The [Facade] recorder reports every request made by a client to whichever
+//! exporter, such as Prometheus, is installed for the `metrics` crate:
+//!
+//! * `consul_client_requests_total`: a counter of requests
+//! * `consul_client_request_duration_seconds`: a histogram of request latency
+//! * `consul_client_errors_total`: a counter of failed requests, additionally
+//!   labeled with the `class` of the error, see
+//!   [ErrorClass][crate::client::ErrorClass]
+//! * `consul_client_retries_total`: a counter of attempts made beyond the
+//!   first one
+//!
+//! All metrics are labeled with the `endpoint` and `method` of the request.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::metrics::Facade;
+//!
+//! let client = ConsulClient::new(
+//!     ConsulClientSettingsBuilder::default()
+//!         .address("https://127.0.0.1:8200")
+//!         .metrics(Facade)
+//!         .build()
+//!         .unwrap()
+//! ).unwrap();
+//! ```
+use crate::client::{MetricsRecorder, RequestMetrics};
+
+/// A [MetricsRecorder] which records through the [metrics] crate.
+#[derive(Clone, Debug, Default)]
+pub struct Facade;
+
+impl MetricsRecorder for Facade {
+    fn record(&self, request: &RequestMetrics) {
+        let labels = [
+            ("endpoint", request.endpoint.to_string()),
+            ("method", request.method.clone()),
+        ];
+        ::metrics::counter!("consul_client_requests_total", &labels).increment(1);
+        ::metrics::histogram!("consul_client_request_duration_seconds", &labels)
+            .record(request.latency);
+        if request.attempts > 1 {
+            ::metrics::counter!("consul_client_retries_total", &labels)
+                .increment(u64::from(request.attempts - 1));
+        }
+        if let Some(class) = request.error {
+            ::metrics::counter!(
+                "consul_client_errors_total",
+                "endpoint" => request.endpoint,
+                "method" => request.method.clone(),
+                "class" => class.as_str()
+            )
+            .increment(1);
+        }
+    }
+}
@@ -0,0 +1,573 @@
+//! An in-memory stand-in for Consul for unit testing.
+//!
+//! A [MockClient] implements [Client] against an in-memory [MockServer]
+//! instead of a Consul server, so code using this crate can be tested without
+//! running Consul. Requests still go through the same request builders,
+//! interceptors and response parsing as with a [ConsulClient][crate::client::ConsulClient].
+//!
+//! The server supports the following endpoints:
+//!
+//! * KV: reading keys (including `recurse`, `keys`, `separator` and `raw`),
+//!   writing keys (including `cas`, `flags`, `acquire` and `release`) and
+//!   deleting keys (including `recurse` and `cas`)
+//! * Catalog: registering and deregistering nodes and services, and listing
+//!   datacenters, nodes, services, the nodes of a service and the services of
+//!   a node
+//!
+//! Every write bumps a single index which is returned as the Consul index of
+//! every response. Blocking queries wait until the index moves past the one
+//! they were given, or their `wait` time passes. Sessions aren't tracked, so
+//! any session may acquire a free key. Requests for any other datacenter fail
+//! the same as they do in Consul and all other endpoints respond with a 501.
+//!
+//! # Example
+//!
+//! ```
+//! use consulrs::{kv, mock::MockClient};
+//!
+//! # tokio_test::block_on(async {
+//! let client = MockClient::new();
+//! kv::set(&client, "mykey", b"myvalue", None).await.unwrap();
+//!
+//! let value = kv::read_raw(&client, "mykey", None).await.unwrap();
+//! assert_eq!(value.response, b"myvalue");
+//! # })
+//! ```
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Mutex,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use http::{Method, Request, Response, StatusCode};
+use rustify::clients::reqwest::Client as HTTPClient;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+use crate::{
+    api::{
+        catalog::common::{CatalogService, Node},
+        kv::common::KVPair,
+        service::common::AgentService,
+        EndpointMiddleware, Features,
+    },
+    client::{Client, ConsulClientSettings, ConsulClientSettingsBuilder},
+};
+
+/// The address a [MockClient] pretends to connect to.
+const ADDRESS: &str = "http://127.0.0.1:8500";
+
+/// The datacenter of a [MockServer].
+const DATACENTER: &str = "dc1";
+
+/// How long blocking queries wait when they don't set a wait time, the same
+/// as in Consul.
+const DEFAULT_WAIT: Duration = Duration::from_secs(300);
+
+/// A [Client] which serves requests from an in-memory [MockServer].
+pub struct MockClient {
+    http: HTTPClient,
+    server: MockServer,
+    settings: ConsulClientSettings,
+}
+
+impl MockClient {
+    /// Creates a new [MockClient] with an empty store.
+    pub fn new() -> Self {
+        MockClient::with_settings(
+            ConsulClientSettingsBuilder::default()
+                .address(ADDRESS)
+                .build()
+                .unwrap(),
+        )
+    }
+
+    /// Creates a new [MockClient] with an empty store using the given
+    /// settings, such as interceptors or default query options.
+    ///
+    /// Settings which configure connecting to Consul, such as its address,
+    /// have no effect.
+    pub fn with_settings(settings: ConsulClientSettings) -> Self {
+        MockClient {
+            http: HTTPClient::new(ADDRESS, reqwest::Client::new()),
+            server: MockServer::new(),
+            settings,
+        }
+    }
+
+    /// Returns the in-memory server behind this client.
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+}
+
+impl Default for MockClient {
+    fn default() -> Self {
+        MockClient::new()
+    }
+}
+
+#[async_trait]
+impl Client for MockClient {
+    fn http(&self) -> &HTTPClient {
+        &self.http
+    }
+
+    fn middle(&self, features: Option<Features>) -> EndpointMiddleware {
+        self.settings
+            .middleware(features, self.settings.token.clone())
+    }
+
+    fn settings(&self) -> &ConsulClientSettings {
+        &self.settings
+    }
+
+    fn mock(&self) -> Option<&MockServer> {
+        Some(&self.server)
+    }
+}
+
+/// The in-memory store which serves the requests of a [MockClient].
+#[derive(Debug)]
+pub struct MockServer {
+    changed: Notify,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    index: u64,
+    kv: BTreeMap<String, Entry>,
+    nodes: BTreeMap<String, Node>,
+    services: BTreeMap<(String, String), AgentService>,
+}
+
+/// A key stored in the KV store of a [MockServer].
+#[derive(Debug)]
+struct Entry {
+    create_index: u64,
+    flags: u64,
+    lock_index: u64,
+    modify_index: u64,
+    session: Option<String>,
+    value: Vec<u8>,
+}
+
+/// The body of a catalog registration.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Registration {
+    address: String,
+    node: String,
+    node_meta: Option<HashMap<String, String>>,
+    service: Option<AgentService>,
+    skip_node_update: Option<bool>,
+    tagged_addresses: Option<HashMap<String, String>>,
+}
+
+/// The body of a catalog deregistration.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Deregistration {
+    #[serde(rename = "CheckID")]
+    check_id: Option<String>,
+    node: String,
+    #[serde(rename = "ServiceID")]
+    service_id: Option<String>,
+}
+
+impl MockServer {
+    /// Creates a new [MockServer] with an empty store.
+    pub fn new() -> Self {
+        MockServer {
+            changed: Notify::new(),
+            state: Mutex::new(State {
+                index: 1,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Returns the current index, which is bumped by every write.
+    pub fn index(&self) -> u64 {
+        self.state.lock().unwrap().index
+    }
+
+    /// Serves a request, returning the response Consul would have returned.
+    pub(crate) async fn handle(&self, req: Request<Vec<u8>>) -> Response<Vec<u8>> {
+        // Requests start with the API version, i.e. `/v1/`
+        let path = req.uri().path().trim_start_matches('/');
+        let path = path.split_once('/').map(|(_, p)| p).unwrap_or_default();
+        let path = percent_encoding::percent_decode_str(path)
+            .decode_utf8_lossy()
+            .into_owned();
+        let query: Vec<(String, String)> =
+            url::form_urlencoded::parse(req.uri().query().unwrap_or_default().as_bytes())
+                .into_owned()
+                .collect();
+        if param(&query, "dc").is_some_and(|dc| dc != DATACENTER) {
+            return respond(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                0,
+                "No path to datacenter",
+            );
+        }
+        if let Some(index) = param(&query, "index").and_then(|i| i.parse().ok()) {
+            let wait = param(&query, "wait")
+                .and_then(parse_wait)
+                .unwrap_or(DEFAULT_WAIT);
+            self.wait(index, wait).await;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let before = state.index;
+        let res = match (req.method(), path.split_once('/').unwrap_or((&path, ""))) {
+            (&Method::GET, ("kv", key)) => state.read_key(key, &query),
+            (&Method::PUT, ("kv", key)) => state.set_key(key, req.body(), &query),
+            (&Method::DELETE, ("kv", key)) => state.delete_key(key, &query),
+            (&Method::PUT, ("catalog", "register")) => state.register(req.body()),
+            (&Method::PUT, ("catalog", "deregister")) => state.deregister(req.body()),
+            (&Method::GET, ("catalog", "datacenters")) => json(state.index, &[DATACENTER]),
+            (&Method::GET, ("catalog", "nodes")) => {
+                json(state.index, &state.nodes.values().collect::<Vec<_>>())
+            }
+            (&Method::GET, ("catalog", "services")) => state.list_services(),
+            (&Method::GET, ("catalog", rest)) => match rest.split_once('/') {
+                Some(("service", name)) => state.list_service_nodes(name),
+                Some(("node-services", node)) => state.list_node_services(node),
+                _ => unsupported(&path),
+            },
+            _ => unsupported(&path),
+        };
+        let changed = state.index != before;
+        drop(state);
+
+        if changed {
+            self.changed.notify_waiters();
+        }
+        res
+    }
+
+    /// Waits until the index moves past the given one or the wait time
+    /// passes.
+    async fn wait(&self, index: u64, wait: Duration) {
+        let deadline = tokio::time::Instant::now() + wait;
+        loop {
+            // Registered before checking the index so no change is missed
+            let changed = self.changed.notified();
+            if self.state.lock().unwrap().index > index {
+                return;
+            }
+            if tokio::time::timeout_at(deadline, changed).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+impl Default for MockServer {
+    fn default() -> Self {
+        MockServer::new()
+    }
+}
+
+impl State {
+    fn read_key(&self, key: &str, query: &[(String, String)]) -> Response<Vec<u8>> {
+        if param(query, "keys").is_some() {
+            let separator = param(query, "separator").filter(|s| !s.is_empty());
+            let keys: BTreeSet<&str> = self
+                .kv
+                .range(key.to_string()..)
+                .take_while(|(k, _)| k.starts_with(key))
+                .map(
+                    |(k, _)| match separator.and_then(|s| k[key.len()..].find(s)) {
+                        Some(i) => &k[..key.len() + i + separator.unwrap().len()],
+                        None => k.as_str(),
+                    },
+                )
+                .collect();
+            return match keys.is_empty() {
+                true => respond(StatusCode::NOT_FOUND, self.index, ""),
+                false => json(self.index, &keys),
+            };
+        }
+
+        let pairs: Vec<KVPair> = match param(query, "recurse").is_some() {
+            true => self
+                .kv
+                .range(key.to_string()..)
+                .take_while(|(k, _)| k.starts_with(key))
+                .map(|(k, e)| e.pair(k))
+                .collect(),
+            false => self.kv.get(key).map(|e| e.pair(key)).into_iter().collect(),
+        };
+        match (pairs.is_empty(), param(query, "raw").is_some()) {
+            (true, _) => respond(StatusCode::NOT_FOUND, self.index, ""),
+            (false, true) => respond(StatusCode::OK, self.index, self.kv[key].value.clone()),
+            (false, false) => json(self.index, &pairs),
+        }
+    }
+
+    fn set_key(
+        &mut self,
+        key: &str,
+        value: &[u8],
+        query: &[(String, String)],
+    ) -> Response<Vec<u8>> {
+        let existing = self.kv.get(key);
+        let cas = param(query, "cas").and_then(|c| c.parse::<u64>().ok());
+        let allowed = match (cas, existing) {
+            (Some(0), existing) => existing.is_none(),
+            (Some(cas), Some(e)) => e.modify_index == cas,
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        let acquire = param(query, "acquire");
+        let release = param(query, "release");
+        let session = existing.and_then(|e| e.session.as_deref());
+        let allowed = allowed
+            && match (acquire, release) {
+                (Some(s), _) => session.is_none() || session == Some(s),
+                (_, Some(s)) => session == Some(s),
+                _ => true,
+            };
+        if !allowed {
+            return json(self.index, &false);
+        }
+
+        self.index += 1;
+        let index = self.index;
+        let entry = self.kv.entry(key.to_string()).or_insert_with(|| Entry {
+            create_index: index,
+            flags: 0,
+            lock_index: 0,
+            modify_index: index,
+            session: None,
+            value: Vec::new(),
+        });
+        entry.flags = param(query, "flags")
+            .and_then(|f| f.parse().ok())
+            .unwrap_or(0);
+        entry.modify_index = index;
+        entry.value = value.to_vec();
+        if let Some(s) = acquire {
+            if entry.session.as_deref() != Some(s) {
+                entry.lock_index += 1;
+                entry.session = Some(s.to_string());
+            }
+        }
+        if release.is_some() {
+            entry.session = None;
+        }
+        json(self.index, &true)
+    }
+
+    fn delete_key(&mut self, key: &str, query: &[(String, String)]) -> Response<Vec<u8>> {
+        if let Some(cas) = param(query, "cas").and_then(|c| c.parse::<u64>().ok()) {
+            if self.kv.get(key).is_some_and(|e| e.modify_index != cas) {
+                return json(self.index, &false);
+            }
+        }
+
+        self.index += 1;
+        match param(query, "recurse").is_some() {
+            true => self.kv.retain(|k, _| !k.starts_with(key)),
+            false => {
+                self.kv.remove(key);
+            }
+        }
+        json(self.index, &true)
+    }
+
+    fn register(&mut self, body: &[u8]) -> Response<Vec<u8>> {
+        let reg: Registration = match serde_json::from_slice(body) {
+            Ok(reg) => reg,
+            Err(e) => return respond(StatusCode::BAD_REQUEST, self.index, e.to_string()),
+        };
+
+        self.index += 1;
+        let index = self.index;
+        // Existing nodes are left as they are when skipping node updates
+        let skip = reg.skip_node_update == Some(true) && self.nodes.contains_key(&reg.node);
+        let node = self.nodes.entry(reg.node.clone()).or_insert_with(|| Node {
+            create_index: index,
+            datacenter: DATACENTER.into(),
+            id: format!("00000000-0000-0000-0000-{:012x}", index),
+            node: reg.node.clone(),
+            ..Default::default()
+        });
+        if !skip {
+            node.address = reg.address;
+            node.meta = reg.node_meta;
+            node.modify_index = index;
+            node.tagged_addresses = reg.tagged_addresses;
+        }
+
+        if let Some(mut service) = reg.service {
+            let id = service.id.clone().or_else(|| service.service.clone());
+            let id = id.unwrap_or_default();
+            let create_index = self
+                .services
+                .get(&(reg.node.clone(), id.clone()))
+                .and_then(|s| s.create_index)
+                .unwrap_or(index);
+            service.create_index = Some(create_index);
+            service.datacenter = Some(DATACENTER.into());
+            service.id = Some(id.clone());
+            service.modify_index = Some(index);
+            self.services.insert((reg.node, id), service);
+        }
+        json(self.index, &true)
+    }
+
+    fn deregister(&mut self, body: &[u8]) -> Response<Vec<u8>> {
+        let dereg: Deregistration = match serde_json::from_slice(body) {
+            Ok(dereg) => dereg,
+            Err(e) => return respond(StatusCode::BAD_REQUEST, self.index, e.to_string()),
+        };
+
+        self.index += 1;
+        let Deregistration {
+            check_id,
+            node,
+            service_id,
+        } = dereg;
+        match (service_id, check_id) {
+            (Some(id), _) => {
+                self.services.remove(&(node, id));
+            }
+            // Checks aren't stored
+            (None, Some(_)) => {}
+            (None, None) => {
+                self.nodes.remove(&node);
+                self.services.retain(|(n, _), _| *n != node);
+            }
+        }
+        json(self.index, &true)
+    }
+
+    fn list_services(&self) -> Response<Vec<u8>> {
+        let mut services: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for service in self.services.values() {
+            let tags = services
+                .entry(service.service.as_deref().unwrap_or_default())
+                .or_default();
+            tags.extend(service.tags.iter().flatten().map(String::as_str));
+        }
+        json(self.index, &services)
+    }
+
+    fn list_service_nodes(&self, name: &str) -> Response<Vec<u8>> {
+        let entries: Vec<CatalogService> = self
+            .services
+            .iter()
+            .filter(|(_, s)| s.service.as_deref() == Some(name))
+            .filter_map(|((node, _), s)| {
+                let node = self.nodes.get(node)?;
+                Some(CatalogService {
+                    address: Some(node.address.clone()),
+                    create_index: s.create_index,
+                    datacenter: Some(DATACENTER.into()),
+                    id: Some(node.id.clone()),
+                    node_meta: node.meta.clone(),
+                    modify_index: s.modify_index,
+                    node: Some(node.node.clone()),
+                    service_address: s.address.clone(),
+                    service_id: s.id.clone(),
+                    service_meta: s.meta.clone(),
+                    service_name: s.service.clone(),
+                    service_port: s.port,
+                    service_tags: s.tags.clone(),
+                    tagged_addresses: node.tagged_addresses.clone(),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        json(self.index, &entries)
+    }
+
+    fn list_node_services(&self, name: &str) -> Response<Vec<u8>> {
+        let node = match self.nodes.get(name) {
+            Some(node) => node,
+            None => return respond(StatusCode::NOT_FOUND, self.index, ""),
+        };
+        let services: Vec<&AgentService> = self
+            .services
+            .iter()
+            .filter(|((n, _), _)| n == name)
+            .map(|(_, s)| s)
+            .collect();
+        json(
+            self.index,
+            &serde_json::json!({ "Node": node, "Services": services }),
+        )
+    }
+}
+
+impl Entry {
+    /// Returns the key as returned by the KV endpoints.
+    fn pair(&self, key: &str) -> KVPair {
+        KVPair {
+            create_index: self.create_index,
+            flags: self.flags,
+            key: key.to_string(),
+            lock_index: self.lock_index,
+            modify_index: self.modify_index,
+            namespace: None,
+            session: self.session.clone(),
+            value: Some(self.value[..].into()).filter(|_| !self.value.is_empty()),
+        }
+    }
+}
+
+/// Returns the value of the given query parameter, if it's set.
+fn param<'a>(query: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    query
+        .iter()
+        .find(|(k, _)| k == name)
+        .map(|(_, v)| v.as_str())
+}
+
+/// Parses a wait time such as `10s` or `5m`.
+fn parse_wait(wait: &str) -> Option<Duration> {
+    let split = wait.find(|c: char| !c.is_ascii_digit())?;
+    let (value, unit) = wait.split_at(split);
+    let value: u64 = value.parse().ok()?;
+    match unit {
+        "ms" => Some(Duration::from_millis(value)),
+        "s" => Some(Duration::from_secs(value)),
+        "m" => Some(Duration::from_secs(value * 60)),
+        "h" => Some(Duration::from_secs(value * 60 * 60)),
+        _ => None,
+    }
+}
+
+/// Returns a response with the given status, Consul index and body.
+fn respond(status: StatusCode, index: u64, body: impl Into<Vec<u8>>) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header("X-Consul-Index", index.to_string())
+        .header("X-Consul-KnownLeader", "true")
+        .body(body.into())
+        .unwrap()
+}
+
+/// Returns a successful response with the given value encoded as JSON.
+fn json<T: Serialize + ?Sized>(index: u64, value: &T) -> Response<Vec<u8>> {
+    let mut res = respond(StatusCode::OK, index, serde_json::to_vec(value).unwrap());
+    res.headers_mut().insert(
+        http::header::CONTENT_TYPE,
+        http::HeaderValue::from_static("application/json"),
+    );
+    res
+}
+
+/// Returns the response to an endpoint the server doesn't support.
+fn unsupported(path: &str) -> Response<Vec<u8>> {
+    respond(
+        StatusCode::NOT_IMPLEMENTED,
+        0,
+        format!("Endpoint not supported by the mock server: {}", path),
+    )
+}
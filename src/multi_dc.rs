@@ -0,0 +1,133 @@
+//! Queries spanning several datacenters.
+//!
+//! A [MultiDcClient] runs the same query against every known datacenter, or a
+//! configured subset of them, concurrently and returns the results keyed or
+//! tagged by the datacenter they came from.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::{api::catalog::requests::ListNodesRequest, catalog, multi_dc::MultiDcClient};
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let client = MultiDcClient::new(client);
+//! let nodes = client
+//!     .merge(|client, dc| async move {
+//!         catalog::nodes(client, Some(ListNodesRequest::builder().dc(dc))).await
+//!     })
+//!     .await
+//!     .unwrap();
+//! for node in nodes {
+//!     println!("{}: {}", node.datacenter, node.value.node);
+//! }
+//! # })
+//! ```
+use std::{collections::BTreeMap, future::Future};
+
+use crate::{api::ApiResponse, catalog, client::Client, error::ClientError};
+
+/// A value returned by a query against a single datacenter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tagged<T> {
+    /// The datacenter the value came from
+    pub datacenter: String,
+    /// The value
+    pub value: T,
+}
+
+/// A wrapper around a [Client] which runs queries against several
+/// datacenters at once.
+///
+/// Queries are given the client and the name of a datacenter, and are
+/// expected to pass the name along through the `dc` option of their request.
+/// The datacenters are discovered with [catalog::datacenters] on every query
+/// unless a subset is configured with [MultiDcClient::subset].
+pub struct MultiDcClient<C: Client> {
+    client: C,
+    subset: Option<Vec<String>>,
+}
+
+impl<C: Client> MultiDcClient<C> {
+    /// Returns a new [MultiDcClient] which queries every known datacenter.
+    pub fn new(client: C) -> Self {
+        MultiDcClient {
+            client,
+            subset: None,
+        }
+    }
+
+    /// Returns the wrapped client.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Returns the datacenters which are queried.
+    #[instrument(skip(self), err)]
+    pub async fn datacenters(&self) -> Result<Vec<String>, ClientError> {
+        match &self.subset {
+            Some(subset) => Ok(subset.clone()),
+            None => Ok(catalog::datacenters(&self.client, None).await?.response),
+        }
+    }
+
+    /// Runs the given query against each datacenter concurrently, returning
+    /// the result of each keyed by its datacenter.
+    ///
+    /// A query failing in one datacenter doesn't affect the others, which
+    /// makes this suitable for failing over between datacenters. An error is
+    /// only returned if the datacenters can't be discovered.
+    #[instrument(skip(self, query), err)]
+    pub async fn fan_out<'a, T, F, Fut>(
+        &'a self,
+        query: F,
+    ) -> Result<BTreeMap<String, Result<T, ClientError>>, ClientError>
+    where
+        F: Fn(&'a C, String) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let datacenters = self.datacenters().await?;
+        let results =
+            futures::future::join_all(datacenters.iter().map(|dc| query(&self.client, dc.clone())))
+                .await;
+        Ok(datacenters.into_iter().zip(results).collect())
+    }
+
+    /// Runs the given query against each datacenter concurrently, merging the
+    /// returned lists into a single list tagged by datacenter.
+    ///
+    /// Fails with the first error encountered, in order of datacenter name,
+    /// if the query fails in any datacenter. See [MultiDcClient::fan_out] for
+    /// handling failures of individual datacenters.
+    #[instrument(skip(self, query), err)]
+    pub async fn merge<'a, T, F, Fut>(&'a self, query: F) -> Result<Vec<Tagged<T>>, ClientError>
+    where
+        F: Fn(&'a C, String) -> Fut,
+        Fut: Future<Output = Result<ApiResponse<Vec<T>>, ClientError>>,
+    {
+        let mut merged = Vec::new();
+        for (datacenter, res) in self.fan_out(query).await? {
+            merged.extend(res?.response.into_iter().map(|value| Tagged {
+                datacenter: datacenter.clone(),
+                value,
+            }));
+        }
+        Ok(merged)
+    }
+
+    /// Only queries the given datacenters instead of every known datacenter.
+    pub fn subset<I, S>(mut self, datacenters: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.subset = Some(datacenters.into_iter().map(Into::into).collect());
+        self
+    }
+}
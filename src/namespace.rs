@@ -0,0 +1,87 @@
+//! Manages Consul Enterprise [namespaces](https://www.consul.io/api-docs/namespaces).
+use crate::{
+    api::{
+        self,
+        namespace::{
+            common::Namespace,
+            requests::{
+                CreateNamespaceRequest, CreateNamespaceRequestBuilder, DeleteNamespaceRequest,
+                DeleteNamespaceRequestBuilder, ListNamespacesRequest, ListNamespacesRequestBuilder,
+                ReadNamespaceRequest, ReadNamespaceRequestBuilder, UpdateNamespaceRequest,
+                UpdateNamespaceRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Creates a new namespace.
+///
+/// See [CreateNamespaceRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn create(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut CreateNamespaceRequestBuilder>,
+) -> Result<ApiResponse<Namespace>, ClientError> {
+    let mut t = CreateNamespaceRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Marks the namespace with the given name for deletion.
+///
+/// See [DeleteNamespaceRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut DeleteNamespaceRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = DeleteNamespaceRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Lists all namespaces.
+///
+/// See [ListNamespacesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListNamespacesRequestBuilder>,
+) -> Result<ApiResponse<Vec<Namespace>>, ClientError> {
+    let mut t = ListNamespacesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the namespace with the given name.
+///
+/// See [ReadNamespaceRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut ReadNamespaceRequestBuilder>,
+) -> Result<ApiResponse<Namespace>, ClientError> {
+    let mut t = ReadNamespaceRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the namespace with the given name, replacing its definition.
+///
+/// See [UpdateNamespaceRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn update(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut UpdateNamespaceRequestBuilder>,
+) -> Result<ApiResponse<Namespace>, ClientError> {
+    let mut t = UpdateNamespaceRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
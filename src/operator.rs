@@ -0,0 +1,32 @@
+//! Manages Consul [operator](https://www.consul.io/api-docs/operator) tasks.
+use crate::{
+    api::{
+        self,
+        operator::{
+            requests::{GetUsageRequest, GetUsageRequestBuilder},
+            responses::UsageResponse,
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+pub mod keyring;
+pub mod license;
+pub mod raft;
+
+/// Returns the number of services registered in the cluster.
+///
+/// Requires Consul 1.10 or later.
+///
+/// See [GetUsageRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn usage(
+    client: &impl Client,
+    opts: Option<&mut GetUsageRequestBuilder>,
+) -> Result<ApiResponse<UsageResponse>, ClientError> {
+    let mut t = GetUsageRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
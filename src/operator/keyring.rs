@@ -0,0 +1,73 @@
+use crate::{
+    api::{
+        self,
+        operator::{
+            requests::{
+                ChangeKeyRequest, ChangeKeyRequestBuilder, DeleteKeyRequest,
+                DeleteKeyRequestBuilder, InstallKeyRequest, InstallKeyRequestBuilder,
+                ListKeysRequest, ListKeysRequestBuilder,
+            },
+            responses::KeyringResponse,
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Installs a new gossip encryption key into the cluster.
+///
+/// See [InstallKeyRequest]
+#[instrument(skip(client, key, opts), err)]
+pub async fn install(
+    client: &impl Client,
+    key: &str,
+    opts: Option<&mut InstallKeyRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = InstallKeyRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).key(key).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Lists the gossip encryption keys installed in every known datacenter.
+///
+/// See [ListKeysRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListKeysRequestBuilder>,
+) -> Result<ApiResponse<Vec<KeyringResponse>>, ClientError> {
+    let mut t = ListKeysRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Removes a gossip encryption key from the cluster.
+///
+/// The primary key can not be removed.
+///
+/// See [DeleteKeyRequest]
+#[instrument(skip(client, key, opts), err)]
+pub async fn remove(
+    client: &impl Client,
+    key: &str,
+    opts: Option<&mut DeleteKeyRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = DeleteKeyRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).key(key).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Changes the primary gossip encryption key to an already installed key.
+///
+/// See [ChangeKeyRequest]
+#[instrument(skip(client, key, opts), err)]
+pub async fn use_key(
+    client: &impl Client,
+    key: &str,
+    opts: Option<&mut ChangeKeyRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = ChangeKeyRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).key(key).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
@@ -0,0 +1,59 @@
+use crate::{
+    api::{
+        self,
+        operator::{
+            requests::{
+                GetLicenseRequest, GetLicenseRequestBuilder, PutLicenseRequest,
+                PutLicenseRequestBuilder, ResetLicenseRequest, ResetLicenseRequestBuilder,
+            },
+            responses::LicenseResponse,
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Gets information about the current license.
+///
+/// See [GetLicenseRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn get(
+    client: &impl Client,
+    opts: Option<&mut GetLicenseRequestBuilder>,
+) -> Result<ApiResponse<LicenseResponse>, ClientError> {
+    let mut t = GetLicenseRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the license used by the cluster.
+///
+/// See [PutLicenseRequest]
+#[instrument(skip(client, license, opts), err)]
+pub async fn put(
+    client: &impl Client,
+    license: &str,
+    opts: Option<&mut PutLicenseRequestBuilder>,
+) -> Result<ApiResponse<LicenseResponse>, ClientError> {
+    let mut t = PutLicenseRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .license(license.as_bytes())
+        .build()
+        .unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Resets the license to the one the cluster was started with.
+///
+/// See [ResetLicenseRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn reset(
+    client: &impl Client,
+    opts: Option<&mut ResetLicenseRequestBuilder>,
+) -> Result<ApiResponse<LicenseResponse>, ClientError> {
+    let mut t = ResetLicenseRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
@@ -0,0 +1,29 @@
+use crate::{
+    api::{
+        self,
+        operator::{
+            requests::{TransferLeaderRequest, TransferLeaderRequestBuilder},
+            responses::TransferLeaderResponse,
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Transfers Raft leadership to the server with the given ID.
+///
+/// Draining leadership off a server before restarting it avoids waiting for
+/// an election once it is gone. Requires Consul 1.15 or later.
+///
+/// See [TransferLeaderRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn transfer_leader(
+    client: &impl Client,
+    id: &str,
+    opts: Option<&mut TransferLeaderRequestBuilder>,
+) -> Result<ApiResponse<TransferLeaderResponse>, ClientError> {
+    let mut t = TransferLeaderRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).id(id).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
@@ -0,0 +1,116 @@
+//! OpenTelemetry tracing of requests.
+//!
+//! With the `otel` feature enabled every request is recorded under a
+//! `consul.request` span, named after its method, which follows the
+//! OpenTelemetry conventions for HTTP clients. The span records the method and
+//! path of the request, the status code of the response, the Consul index
+//! returned, the request ID if one was sent, and how many times the request
+//! was resent by the [RetryPolicy][crate::client::RetryPolicy]. Spans are
+//! exported by installing a [tracing_opentelemetry] layer in the application's
+//! subscriber.
+//!
+//! Trace context is only sent to Consul when the [TraceContext] interceptor is
+//! added to the client, which injects the context of the current span using
+//! the globally configured propagator.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::otel::TraceContext;
+//!
+//! let client = ConsulClient::new(
+//!     ConsulClientSettingsBuilder::default()
+//!         .address("https://127.0.0.1:8200")
+//!         .interceptor(TraceContext)
+//!         .build()
+//!         .unwrap()
+//! ).unwrap();
+//! ```
+use http::{header::HeaderName, HeaderMap, HeaderValue, StatusCode};
+use opentelemetry::propagation::Injector;
+use rustify::enums::RequestMethod;
+use tracing::{field, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::{
+    client::{Client, Interceptor},
+    error::ClientError,
+};
+
+/// An [Interceptor] which adds the trace context of the current span to the
+/// headers of each request.
+///
+/// Headers are written by the propagator configured with
+/// [opentelemetry::global::set_text_map_propagator], which doesn't write any
+/// unless one is configured.
+#[derive(Clone, Debug, Default)]
+pub struct TraceContext;
+
+impl Interceptor for TraceContext {
+    fn on_request(&self, req: &mut http::Request<Vec<u8>>) {
+        let cx = Span::current().context();
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&cx, &mut HeaderInjector(req.headers_mut()))
+        });
+    }
+}
+
+/// Writes propagated fields to the headers of a request.
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+/// Returns a new span for recording a request with the given method and path.
+pub(crate) fn request_span(client: &impl Client, method: &RequestMethod, path: &str) -> Span {
+    let method = format!("{:?}", method);
+    info_span!(
+        "consul.request",
+        otel.name = %method,
+        otel.kind = "client",
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        http.request.method = %method,
+        url.path = %format!("/v{}/{}", client.settings().version, path),
+        http.response.status_code = field::Empty,
+        http.request.resend_count = field::Empty,
+        consul.index = field::Empty,
+        consul.request_id = field::Empty,
+    )
+}
+
+/// Records the outcome of a request, made in the given number of attempts,
+/// on its span.
+pub(crate) fn record(
+    span: &Span,
+    res: Result<(StatusCode, &HeaderMap), &ClientError>,
+    attempts: u32,
+) {
+    if attempts > 1 {
+        span.record("http.request.resend_count", attempts - 1);
+    }
+    match res {
+        Ok((status, headers)) => {
+            span.record("http.response.status_code", status.as_u16());
+            if let Some(index) = headers.get("X-Consul-Index").and_then(|i| i.to_str().ok()) {
+                span.record("consul.index", index);
+            }
+        }
+        Err(e) => {
+            if let Some(status) = e.status() {
+                span.record("http.response.status_code", status);
+            }
+            span.record("otel.status_code", "ERROR");
+            span.record("otel.status_message", field::display(e));
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Manages Consul Enterprise [admin partitions](https://www.consul.io/api-docs/admin-partitions).
+use crate::{
+    api::{
+        self,
+        partition::{
+            common::Partition,
+            requests::{
+                CreatePartitionRequest, CreatePartitionRequestBuilder, DeletePartitionRequest,
+                DeletePartitionRequestBuilder, ListPartitionsRequest, ListPartitionsRequestBuilder,
+                ReadPartitionRequest, ReadPartitionRequestBuilder, UpdatePartitionRequest,
+                UpdatePartitionRequestBuilder,
+            },
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Creates a new partition.
+///
+/// See [CreatePartitionRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn create(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut CreatePartitionRequestBuilder>,
+) -> Result<ApiResponse<Partition>, ClientError> {
+    let mut t = CreatePartitionRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Marks the partition with the given name for deletion.
+///
+/// See [DeletePartitionRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut DeletePartitionRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = DeletePartitionRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Lists all partitions.
+///
+/// See [ListPartitionsRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListPartitionsRequestBuilder>,
+) -> Result<ApiResponse<Vec<Partition>>, ClientError> {
+    let mut t = ListPartitionsRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the partition with the given name.
+///
+/// See [ReadPartitionRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut ReadPartitionRequestBuilder>,
+) -> Result<ApiResponse<Partition>, ClientError> {
+    let mut t = ReadPartitionRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the partition with the given name.
+///
+/// See [UpdatePartitionRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn update(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut UpdatePartitionRequestBuilder>,
+) -> Result<ApiResponse<Partition>, ClientError> {
+    let mut t = UpdatePartitionRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
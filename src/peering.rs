@@ -0,0 +1,98 @@
+//! Manages Consul [cluster peering](https://www.consul.io/api-docs/peering).
+//!
+//! Services imported from a peer can be discovered by setting the `peer`
+//! option on catalog and health requests. Requires Consul 1.13 or later.
+use crate::{
+    api::{
+        self,
+        peering::{
+            common::Peering,
+            requests::{
+                DeletePeeringRequest, DeletePeeringRequestBuilder, EstablishPeeringRequest,
+                EstablishPeeringRequestBuilder, GenerateTokenRequest, GenerateTokenRequestBuilder,
+                ListPeeringsRequest, ListPeeringsRequestBuilder, ReadPeeringRequest,
+                ReadPeeringRequestBuilder,
+            },
+            responses::GenerateTokenResponse,
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Marks the peering connection with the given name for deletion.
+///
+/// See [DeletePeeringRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut DeletePeeringRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = DeletePeeringRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Establishes a peering connection using a token generated by the peer.
+///
+/// See [EstablishPeeringRequest]
+#[instrument(skip(client, token, opts), err)]
+pub async fn establish(
+    client: &impl Client,
+    name: &str,
+    token: &str,
+    opts: Option<&mut EstablishPeeringRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = EstablishPeeringRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .peer_name(name)
+        .peering_token(token)
+        .build()
+        .unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Lists all peering connections.
+///
+/// See [ListPeeringsRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListPeeringsRequestBuilder>,
+) -> Result<ApiResponse<Vec<Peering>>, ClientError> {
+    let mut t = ListPeeringsRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the peering connection with the given name.
+///
+/// See [ReadPeeringRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut ReadPeeringRequestBuilder>,
+) -> Result<ApiResponse<Peering>, ClientError> {
+    let mut t = ReadPeeringRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Generates a token which the named peer can use to establish a peering
+/// connection with this cluster.
+///
+/// See [GenerateTokenRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn token(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut GenerateTokenRequestBuilder>,
+) -> Result<ApiResponse<GenerateTokenResponse>, ClientError> {
+    let mut t = GenerateTokenRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).peer_name(name).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
@@ -0,0 +1,123 @@
+//! Manages Consul [prepared queries](https://www.consul.io/api-docs/query).
+use crate::{
+    api::{
+        self,
+        query::{
+            common::{PreparedQueryDefinition, ServiceQuery},
+            requests::{
+                CreateQueryRequest, CreateQueryRequestBuilder, DeleteQueryRequest,
+                DeleteQueryRequestBuilder, ExecuteQueryRequest, ExecuteQueryRequestBuilder,
+                ExplainQueryRequest, ExplainQueryRequestBuilder, ListQueriesRequest,
+                ListQueriesRequestBuilder, ReadQueryRequest, ReadQueryRequestBuilder,
+                UpdateQueryRequest, UpdateQueryRequestBuilder,
+            },
+            responses::{CreateQueryResponse, ExecuteQueryResponse, ExplainQueryResponse},
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Creates a new prepared query for the given service query.
+///
+/// See [CreateQueryRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn create(
+    client: &impl Client,
+    service: ServiceQuery,
+    opts: Option<&mut CreateQueryRequestBuilder>,
+) -> Result<ApiResponse<CreateQueryResponse>, ClientError> {
+    let mut t = CreateQueryRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).service(service).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Deletes the prepared query with the given ID.
+///
+/// See [DeleteQueryRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn delete(
+    client: &impl Client,
+    uuid: &str,
+    opts: Option<&mut DeleteQueryRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = DeleteQueryRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).uuid(uuid).build().unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
+
+/// Executes the prepared query with the given ID or name.
+///
+/// See [ExecuteQueryRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn execute(
+    client: &impl Client,
+    query: &str,
+    opts: Option<&mut ExecuteQueryRequestBuilder>,
+) -> Result<ApiResponse<ExecuteQueryResponse>, ClientError> {
+    let mut t = ExecuteQueryRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).query(query).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Renders the prepared query with the given ID or name without executing it.
+///
+/// See [ExplainQueryRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn explain(
+    client: &impl Client,
+    query: &str,
+    opts: Option<&mut ExplainQueryRequestBuilder>,
+) -> Result<ApiResponse<ExplainQueryResponse>, ClientError> {
+    let mut t = ExplainQueryRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).query(query).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Lists all prepared queries.
+///
+/// See [ListQueriesRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn list(
+    client: &impl Client,
+    opts: Option<&mut ListQueriesRequestBuilder>,
+) -> Result<ApiResponse<Vec<PreparedQueryDefinition>>, ClientError> {
+    let mut t = ListQueriesRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Reads the prepared query with the given ID.
+///
+/// See [ReadQueryRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn read(
+    client: &impl Client,
+    uuid: &str,
+    opts: Option<&mut ReadQueryRequestBuilder>,
+) -> Result<ApiResponse<Vec<PreparedQueryDefinition>>, ClientError> {
+    let mut t = ReadQueryRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).uuid(uuid).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Updates the prepared query with the given ID, replacing its definition.
+///
+/// See [UpdateQueryRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn update(
+    client: &impl Client,
+    uuid: &str,
+    service: ServiceQuery,
+    opts: Option<&mut UpdateQueryRequestBuilder>,
+) -> Result<ApiResponse<()>, ClientError> {
+    let mut t = UpdateQueryRequest::builder();
+    let endpoint = opts
+        .unwrap_or(&mut t)
+        .uuid(uuid)
+        .service(service)
+        .build()
+        .unwrap();
+    api::exec_with_empty(client, endpoint).await
+}
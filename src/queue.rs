@@ -0,0 +1,240 @@
+//! Implements a work queue on top of the KV store and sessions.
+//!
+//! Items are pushed with [push], which writes each of them to its own key
+//! under the prefix of the queue. Keys are numbered by a counter stored at
+//! [QUEUE_SEQUENCE_KEY] under the same prefix, so they sort in the order the
+//! items were pushed.
+//!
+//! Consumers take items with [pop], which creates a session and acquires the
+//! lowest key which isn't held by another session. The item stays in the
+//! queue while it's processed, invisible to other consumers for as long as the
+//! session is renewed. Acknowledging it with [Message::ack] deletes it, while
+//! [Message::nack] releases it for another consumer straight away. If the
+//! consumer dies instead, its session expires once the visibility timeout
+//! passes without being renewed, which releases the item so that another
+//! consumer can take it.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::queue;
+//! use std::{sync::Arc, time::Duration};
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let client = Arc::new(client);
+//! queue::push(client.as_ref(), "jobs/", b"resize image 1").await.unwrap();
+//!
+//! let timeout = Duration::from_secs(30);
+//! while let Some(message) = queue::pop(client.clone(), "jobs/", timeout).await.unwrap() {
+//!     println!("Processing {:?}", message.payload());
+//!     message.ack().await.unwrap();
+//! }
+//! # })
+//! ```
+use std::{convert::TryInto, sync::Arc, time::Duration};
+
+use tokio::sync::watch;
+
+use crate::{
+    api::{
+        kv::requests::{ReadKeyRequest, SetKeyRequest},
+        session::{common::SessionBehavior, requests::CreateSessionRequest},
+    },
+    client::Client,
+    error::ClientError,
+    kv,
+    session::{self, RenewalTask},
+};
+
+/// The name of the key under a queue prefix which numbers its items.
+pub const QUEUE_SEQUENCE_KEY: &str = ".sequence";
+
+/// Pushes an item to the end of the queue at the given prefix, returning the
+/// key it was written to.
+///
+/// See [kv::Counter] and [SetKeyRequest]
+#[instrument(skip(client, payload), err)]
+pub async fn push(
+    client: &impl Client,
+    prefix: &str,
+    payload: impl Into<Vec<u8>>,
+) -> Result<String, ClientError> {
+    let prefix = normalize(prefix);
+    let sequence = format!("{}{}", prefix, QUEUE_SEQUENCE_KEY);
+    let id = kv::add_counter(client, &sequence, 1, &kv::counter_policy()).await?;
+    let key = format!("{}{:020}", prefix, id);
+    kv::set(client, &key, payload, None).await?;
+    Ok(key)
+}
+
+/// Takes the oldest item from the queue at the given prefix which isn't held
+/// by another consumer, returning [None] if there isn't one.
+///
+/// The item is held by a new session with the given TTL, which acts as the
+/// visibility timeout of the item and is renewed in the background until the
+/// returned [Message] is acknowledged, released or dropped. Note that Consul
+/// requires a TTL of at least 10 seconds.
+///
+/// See [ReadKeyRequest] and [SetKeyRequest]
+#[instrument(skip(client), err)]
+pub async fn pop<C: Client + 'static>(
+    client: Arc<C>,
+    prefix: &str,
+    visibility_timeout: Duration,
+) -> Result<Option<Message<C>>, ClientError> {
+    let prefix = normalize(prefix);
+    let sequence = format!("{}{}", prefix, QUEUE_SEQUENCE_KEY);
+    let items = kv::try_read(
+        client.as_ref(),
+        &prefix,
+        Some(ReadKeyRequest::builder().recurse(true)),
+    )
+    .await?
+    .map(|res| res.response)
+    .unwrap_or_default();
+    let mut items = items
+        .into_iter()
+        .filter(|kv| kv.key != sequence && kv.session.is_none())
+        .peekable();
+    if items.peek().is_none() {
+        return Ok(None);
+    }
+
+    let session = session::create(
+        client.as_ref(),
+        Some(
+            CreateSessionRequest::builder()
+                .behavior(SessionBehavior::Release)
+                .lock_delay("0s")
+                .name(format!("Consul API Queue {}", prefix))
+                .ttl(format!("{}s", visibility_timeout.as_secs())),
+        ),
+    )
+    .await?
+    .response
+    .id;
+
+    for item in items {
+        let payload: Vec<u8> = match item.value {
+            Some(v) => v.try_into()?,
+            None => Vec::new(),
+        };
+
+        // The payload is written back as acquiring a key replaces its value,
+        // and the index check leaves items changed since they were read alone
+        let res = kv::acquire(
+            client.as_ref(),
+            &item.key,
+            &session,
+            Some(
+                SetKeyRequest::builder()
+                    .cas(item.modify_index)
+                    .value(payload.clone()),
+            ),
+        )
+        .await;
+        match res {
+            Ok(res) if res.response => {
+                debug!("Took item {} with session {}", item.key, session);
+                let renewal = RenewalTask::start(client.clone(), &session, visibility_timeout);
+                return Ok(Some(Message {
+                    client,
+                    key: item.key,
+                    payload,
+                    renewal,
+                }));
+            }
+            Ok(_) => debug!("Item {} was taken by another consumer", item.key),
+            Err(e) => {
+                let _ = session::delete(client.as_ref(), &session, None).await;
+                return Err(e);
+            }
+        }
+    }
+
+    session::delete(client.as_ref(), &session, None).await?;
+    Ok(None)
+}
+
+/// An item taken from a queue with [pop].
+///
+/// The item stays hidden from other consumers until it's acknowledged with
+/// [Message::ack] or released with [Message::nack]. Dropping a [Message] only
+/// stops renewing its session, in which case the item is released once the
+/// visibility timeout passes.
+pub struct Message<C: Client + 'static> {
+    client: Arc<C>,
+    key: String,
+    payload: Vec<u8>,
+    renewal: RenewalTask,
+}
+
+impl<C: Client + 'static> Message<C> {
+    /// Deletes the item from the queue.
+    ///
+    /// The response is whether the item was deleted, which is false when the
+    /// visibility timeout passed and the item was released before it was
+    /// acknowledged, in which case another consumer may take it again.
+    #[instrument(skip(self), fields(key = %self.key), err)]
+    pub async fn ack(self) -> Result<bool, ClientError> {
+        let held = kv::try_read(self.client.as_ref(), &self.key, None)
+            .await?
+            .and_then(|mut res| res.response.pop())
+            .filter(|kv| kv.session.as_deref() == Some(self.session()));
+        let deleted = match held {
+            Some(kv) => {
+                kv::delete_cas(self.client.as_ref(), &self.key, kv.modify_index, None)
+                    .await?
+                    .response
+            }
+            None => false,
+        };
+        session::delete(self.client.as_ref(), self.session(), None).await?;
+        Ok(deleted)
+    }
+
+    /// Returns a [watch::Receiver] whose value changes to `false` once the
+    /// session holding the item has expired, after which the item may be taken
+    /// by another consumer.
+    pub fn active(&self) -> watch::Receiver<bool> {
+        self.renewal.active()
+    }
+
+    /// Returns the key the item is stored at.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Releases the item so that another consumer can take it.
+    #[instrument(skip(self), fields(key = %self.key), err)]
+    pub async fn nack(self) -> Result<(), ClientError> {
+        // Deleting the session releases the keys it holds
+        session::delete(self.client.as_ref(), self.session(), None).await?;
+        Ok(())
+    }
+
+    /// Returns the payload of the item.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Returns the ID of the session holding the item.
+    pub fn session(&self) -> &str {
+        self.renewal.session()
+    }
+}
+
+/// Returns the given prefix ending with a `/`.
+fn normalize(prefix: &str) -> String {
+    match prefix.ends_with('/') {
+        true => prefix.to_string(),
+        false => format!("{}/", prefix),
+    }
+}
@@ -0,0 +1,258 @@
+//! Recording and replaying responses for deterministic tests.
+//!
+//! Requires the `replay` feature. A [Cassette] configured on a client either
+//! records every response received from Consul to a JSON file, or replays the
+//! responses recorded in such a file without sending any requests, so that
+//! tests recorded once against a live Consul server can run in CI without
+//! one.
+//!
+//! Requests are matched to recorded responses by their method, path, and
+//! query, in the order they were recorded, so a test which makes the same
+//! request twice replays both responses in turn. Request headers and bodies
+//! aren't recorded, which keeps tokens out of the file. A request without a
+//! matching recorded response fails with a `501 Not Implemented` response.
+//!
+//! Streamed responses are buffered while recording.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::replay::Cassette;
+//!
+//! let client = ConsulClient::new(
+//!     ConsulClientSettingsBuilder::default()
+//!         .address("http://127.0.0.1:8500")
+//!         .cassette(Cassette::from_env("tests/fixtures/kv.json").unwrap())
+//!         .build()
+//!         .unwrap()
+//! ).unwrap();
+//! ```
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use http::{HeaderMap, HeaderName, HeaderValue, Method, Request, Response, StatusCode, Uri};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+
+/// The environment variable which makes [Cassette::from_env] record.
+pub const RECORD_ENV: &str = "CONSULRS_RECORD";
+
+/// Whether a [Cassette] records or replays responses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Record,
+    Replay,
+}
+
+/// A file of recorded responses.
+///
+/// While recording, the file is rewritten after every response. While
+/// replaying, each recorded response is played at most once.
+#[derive(Debug)]
+pub struct Cassette {
+    mode: Mode,
+    path: PathBuf,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    interactions: Vec<Interaction>,
+    played: Vec<bool>,
+}
+
+impl Cassette {
+    /// Creates a [Cassette] which records responses to the file at `path`,
+    /// replacing any responses recorded there before.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Cassette {
+            mode: Mode::Record,
+            path: path.into(),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Creates a [Cassette] which replays the responses recorded in the file
+    /// at `path`.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, ClientError> {
+        let path = path.into();
+        let content = std::fs::read(&path).map_err(|e| ClientError::FileReadError {
+            source: e,
+            path: path.display().to_string(),
+        })?;
+        let interactions: Vec<Interaction> = serde_json::from_slice(&content)
+            .map_err(|e| ClientError::JsonDeserializeError { source: e })?;
+        Ok(Cassette {
+            mode: Mode::Replay,
+            path,
+            state: Mutex::new(State {
+                played: vec![false; interactions.len()],
+                interactions,
+            }),
+        })
+    }
+
+    /// Creates a [Cassette] which records responses to the file at `path` if
+    /// the file doesn't exist yet or [RECORD_ENV] is set, and replays them
+    /// otherwise.
+    pub fn from_env(path: impl Into<PathBuf>) -> Result<Self, ClientError> {
+        let path = path.into();
+        if std::env::var_os(RECORD_ENV).is_some() || !path.exists() {
+            Ok(Cassette::record(path))
+        } else {
+            Cassette::replay(path)
+        }
+    }
+
+    /// Returns whether this cassette records or replays responses.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Returns the path of the file of recorded responses.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Returns the first recorded response to the given request which hasn't
+    /// been played yet.
+    pub(crate) fn play(&self, req: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+        let method = req.method().as_str();
+        let uri = path_and_query(req.uri());
+
+        let mut state = self.state.lock().unwrap();
+        let State {
+            interactions,
+            played,
+        } = &mut *state;
+        let found = interactions
+            .iter()
+            .zip(played.iter_mut())
+            .find(|(i, played)| !**played && i.request.method == method && i.request.uri == uri);
+        match found {
+            Some((interaction, played)) => {
+                *played = true;
+                interaction.response.to_response()
+            }
+            None => {
+                let mut res = Response::new(
+                    format!(
+                        "No recorded response to {} {} in {}",
+                        method,
+                        uri,
+                        self.path.display()
+                    )
+                    .into_bytes(),
+                );
+                *res.status_mut() = StatusCode::NOT_IMPLEMENTED;
+                res
+            }
+        }
+    }
+
+    /// Records the response to a request with the given method and URI.
+    pub(crate) fn record_response(&self, method: &Method, uri: &Uri, res: &Response<Vec<u8>>) {
+        let mut state = self.state.lock().unwrap();
+        state.interactions.push(Interaction {
+            request: RecordedRequest {
+                method: method.to_string(),
+                uri: path_and_query(uri),
+            },
+            response: RecordedResponse::from_response(res),
+        });
+
+        let written = serde_json::to_vec_pretty(&state.interactions)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                if let Some(dir) = self.path.parent() {
+                    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+                }
+                std::fs::write(&self.path, content).map_err(|e| e.to_string())
+            });
+        if let Err(e) = written {
+            warn!("Failed writing cassette {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// A recorded request and the response it received.
+#[derive(Debug, Deserialize, Serialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RecordedRequest {
+    method: String,
+    uri: String,
+}
+
+/// A recorded response, whose body is stored as a string if it's valid UTF-8
+/// and base64 encoded otherwise.
+#[derive(Debug, Deserialize, Serialize)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_base64: Option<String>,
+}
+
+impl RecordedResponse {
+    fn from_response(res: &Response<Vec<u8>>) -> Self {
+        let headers = res
+            .headers()
+            .iter()
+            .filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_string())))
+            .collect();
+        let (body, body_base64) = match std::str::from_utf8(res.body()) {
+            Ok("") => (None, None),
+            Ok(body) => (Some(body.to_string()), None),
+            Err(_) => (None, Some(base64::encode(res.body()))),
+        };
+        RecordedResponse {
+            status: res.status().as_u16(),
+            headers,
+            body,
+            body_base64,
+        }
+    }
+
+    fn to_response(&self) -> Response<Vec<u8>> {
+        let body = match (&self.body, &self.body_base64) {
+            (Some(body), _) => body.clone().into_bytes(),
+            (None, Some(body)) => base64::decode(body).unwrap_or_default(),
+            (None, None) => Vec::new(),
+        };
+        let mut res = Response::new(body);
+        *res.status_mut() = StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+        *res.headers_mut() = self
+            .headers
+            .iter()
+            .filter_map(|(k, v)| {
+                Some((
+                    HeaderName::from_bytes(k.as_bytes()).ok()?,
+                    HeaderValue::from_str(v).ok()?,
+                ))
+            })
+            .collect::<HeaderMap>();
+        res
+    }
+}
+
+/// Returns the path and query of a URI, which identify a request regardless
+/// of the address of the server it's sent to.
+fn path_and_query(uri: &Uri) -> String {
+    match uri.query().filter(|q| !q.is_empty()) {
+        Some(query) => format!("{}?{}", uri.path(), query),
+        None => uri.path().to_string(),
+    }
+}
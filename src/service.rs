@@ -121,3 +121,16 @@ pub async fn register(
     let endpoint = opts.unwrap_or(&mut t).name(name).build().unwrap();
     api::exec_with_empty(client, endpoint).await
 }
+
+/// Reads the given service's configuration on an agent, returning [None] if
+/// the service isn't registered.
+///
+/// See [ReadServiceRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn try_read(
+    client: &impl Client,
+    name: &str,
+    opts: Option<&mut ReadServiceRequestBuilder>,
+) -> Result<Option<ApiResponse<AgentService>>, ClientError> {
+    api::optional(read(client, name, opts).await)
+}
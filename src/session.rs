@@ -1,3 +1,10 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::{
+    sync::{mpsc, watch},
+    task::JoinHandle,
+};
+
 use crate::{
     api::{
         self,
@@ -99,3 +106,123 @@ pub async fn renew(
     let endpoint = opts.unwrap_or(&mut t).uuid(uuid).build().unwrap();
     api::exec_with_result(client, endpoint).await
 }
+
+/// A background task which keeps a TTL session from expiring.
+///
+/// The session is renewed at half of its TTL, which gives a failed request
+/// time to be retried before the session expires. Failed requests are reported
+/// through [RenewalTask::failures] and do not stop the task. If the session
+/// has expired or was deleted the value of [RenewalTask::active] changes to
+/// `false` and the task stops. Otherwise it stops when [RenewalTask::stop] is
+/// called or the [RenewalTask] is dropped, after which the session expires
+/// once its TTL is reached unless it's deleted first.
+///
+/// # Example
+///
+/// ```no_run
+/// # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+/// use consulrs::session::RenewalTask;
+/// use std::{sync::Arc, time::Duration};
+///
+/// # tokio_test::block_on(async {
+/// # let client = ConsulClient::new(
+/// #     ConsulClientSettingsBuilder::default()
+/// #         .address("https://127.0.0.1:8200")
+/// #         .build()
+/// #         .unwrap()
+/// # ).unwrap();
+/// let renewal = RenewalTask::start(Arc::new(client), "my-session", Duration::from_secs(15));
+/// let mut active = renewal.active();
+/// while *active.borrow() {
+///     active.changed().await.unwrap();
+/// }
+/// println!("Session {} has expired", renewal.session());
+/// # })
+/// ```
+pub struct RenewalTask {
+    active: watch::Receiver<bool>,
+    failures: mpsc::UnboundedReceiver<ClientError>,
+    session: String,
+    task: JoinHandle<()>,
+}
+
+impl RenewalTask {
+    /// Starts renewing the given session which has the given TTL.
+    pub fn start<C: Client + 'static>(client: Arc<C>, session: &str, ttl: Duration) -> Self {
+        let (failures_tx, failures) = mpsc::unbounded_channel();
+        let (active_tx, active) = watch::channel(true);
+        let task = tokio::spawn(keep_renewed(
+            client,
+            session.to_string(),
+            ttl,
+            active_tx,
+            Some(failures_tx),
+        ));
+
+        RenewalTask {
+            active,
+            failures,
+            session: session.to_string(),
+            task,
+        }
+    }
+
+    /// Returns a [watch::Receiver] whose value changes to `false` once the
+    /// session has expired.
+    pub fn active(&self) -> watch::Receiver<bool> {
+        self.active.clone()
+    }
+
+    /// Returns a receiver which yields each error encountered while renewing
+    /// the session.
+    pub fn failures(&mut self) -> &mut mpsc::UnboundedReceiver<ClientError> {
+        &mut self.failures
+    }
+
+    /// Returns whether the session is still active.
+    pub fn is_active(&self) -> bool {
+        *self.active.borrow()
+    }
+
+    /// Returns the ID of the session being renewed.
+    pub fn session(&self) -> &str {
+        &self.session
+    }
+
+    /// Stops renewing the session, which is equivalent to dropping it.
+    pub fn stop(self) {}
+}
+
+impl Drop for RenewalTask {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Renews the given session at half its TTL until it expires, at which point
+/// `active` is set to false.
+pub(crate) async fn keep_renewed<C: Client>(
+    client: Arc<C>,
+    session: String,
+    ttl: Duration,
+    active: watch::Sender<bool>,
+    failures: Option<mpsc::UnboundedSender<ClientError>>,
+) {
+    loop {
+        tokio::time::sleep(ttl / 2).await;
+        match renew(client.as_ref(), &session, None).await {
+            Ok(_) => debug!("Renewed session {}", session),
+            Err(e) if e.is_not_found() => {
+                warn!("Session {} has expired", session);
+                let _ = active.send(false);
+                return;
+            }
+            Err(e) => {
+                warn!("Failed renewing session {}: {}", session, e);
+                if let Some(failures) = &failures {
+                    let _ = failures.send(e);
+                }
+            }
+        }
+    }
+}
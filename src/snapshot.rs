@@ -1,3 +1,5 @@
+use tokio::io::AsyncRead;
+
 use crate::{
     api::{
         self,
@@ -5,7 +7,7 @@ use crate::{
             GenerateSnapshotRequest, GenerateSnapshotRequestBuilder, RestoreSnapshotRequest,
             RestoreSnapshotRequestBuilder,
         },
-        ApiResponse,
+        ApiResponse, BodyStream,
     },
     client::Client,
     error::ClientError,
@@ -13,6 +15,9 @@ use crate::{
 
 /// Takes a point-in-time snapshot of the Consul cluster.
 ///
+/// The entire snapshot is buffered in memory, see [save] for streaming it
+/// instead.
+///
 /// See [GenerateSnapshotRequest]
 #[instrument(skip(client, opts), err)]
 pub async fn backup(
@@ -24,16 +29,31 @@ pub async fn backup(
     api::exec_with_raw(client, endpoint).await
 }
 
-/// Restores a snapshot to the Consul cluster.
+/// Restores a snapshot to the Consul cluster, streaming it from the given
+/// reader.
 ///
 /// See [RestoreSnapshotRequest]
-#[instrument(skip(client, opts), err)]
-pub async fn restore(
+#[instrument(skip(client, reader, opts), err)]
+pub async fn restore<R: AsyncRead + Send + Sync + 'static>(
     client: &impl Client,
-    data: &[u8],
+    reader: R,
     opts: Option<&mut RestoreSnapshotRequestBuilder>,
 ) -> Result<ApiResponse<()>, ClientError> {
     let mut t = RestoreSnapshotRequest::builder();
-    let endpoint = opts.unwrap_or(&mut t).data(data).build().unwrap();
-    api::exec_with_empty(client, endpoint).await
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_reader(client, endpoint, reader).await
+}
+
+/// Takes a point-in-time snapshot of the Consul cluster, returning it as a
+/// stream of chunks which can be written out without buffering it in memory.
+///
+/// See [GenerateSnapshotRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn save(
+    client: &impl Client,
+    opts: Option<&mut GenerateSnapshotRequestBuilder>,
+) -> Result<ApiResponse<BodyStream>, ClientError> {
+    let mut t = GenerateSnapshotRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_stream(client, endpoint).await
 }
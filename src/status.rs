@@ -0,0 +1,41 @@
+//! Reads the Raft [status](https://www.consul.io/api-docs/status) of a
+//! Consul datacenter.
+use crate::{
+    api::{
+        self,
+        status::requests::{
+            GetLeaderRequest, GetLeaderRequestBuilder, ListPeersRequest, ListPeersRequestBuilder,
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// Returns the address of the Raft leader.
+///
+/// The address is empty if the datacenter currently has no leader.
+///
+/// See [GetLeaderRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn leader(
+    client: &impl Client,
+    opts: Option<&mut GetLeaderRequestBuilder>,
+) -> Result<ApiResponse<String>, ClientError> {
+    let mut t = GetLeaderRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
+
+/// Returns the addresses of the Raft peers.
+///
+/// See [ListPeersRequest]
+#[instrument(skip(client, opts), err)]
+pub async fn peers(
+    client: &impl Client,
+    opts: Option<&mut ListPeersRequestBuilder>,
+) -> Result<ApiResponse<Vec<String>>, ClientError> {
+    let mut t = ListPeersRequest::builder();
+    let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+    api::exec_with_result(client, endpoint).await
+}
@@ -0,0 +1,419 @@
+//! Files rendered from the KV store and the catalog, in the manner of
+//! consul-template.
+//!
+//! A [Template] watches a set of KV prefixes and services and renders a file
+//! from them with a [Render] implementation, which is usually a closure, each
+//! time any of them changes. Changes are debounced: once something changes,
+//! the file is rendered after nothing has changed for `min_wait`, but no later
+//! than `max_wait` after the first change. The file is only rewritten when its
+//! content changes, and is replaced atomically.
+//!
+//! The inputs are handed to the renderer as [TemplateData], which implements
+//! [Serialize] so that it can be passed as the context of a template engine
+//! such as Handlebars or Tera from within a [Render] implementation.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::template::{Template, TemplateConfig, TemplateData};
+//! use std::{fmt::Write, sync::Arc, time::Duration};
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let config = TemplateConfig::builder()
+//!     .destination("/etc/haproxy/backends.cfg")
+//!     .keys(vec!["haproxy/".to_string()])
+//!     .services(vec!["web".to_string()])
+//!     .min_wait(Duration::from_secs(2))
+//!     .build()
+//!     .unwrap();
+//! let template = Template::start(Arc::new(client), config, |data: &TemplateData| {
+//!     let mut out = String::new();
+//!     let timeout = data.key("haproxy/timeout").unwrap_or("5s");
+//!     writeln!(out, "timeout server {}", timeout).unwrap();
+//!     for entry in data.service("web") {
+//!         let address = entry.service.address.as_deref().unwrap_or(&entry.node.address);
+//!         writeln!(out, "server {} {}", entry.node.node, address).unwrap();
+//!     }
+//!     Ok(out)
+//! })
+//! .await
+//! .unwrap();
+//! # })
+//! ```
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::TryInto,
+    ops::Bound,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use derive_builder::Builder;
+use futures::{stream::BoxStream, StreamExt};
+use serde::Serialize;
+use tokio::{
+    sync::{mpsc, oneshot, watch},
+    task::JoinHandle,
+    time::Instant,
+};
+
+use crate::{
+    api::{
+        health::common::ServiceEntry,
+        kv::{common::KVPair, requests::ReadKeyRequest},
+    },
+    client::Client,
+    error::ClientError,
+    health, kv,
+};
+
+/// Renders the content of a file from the watched inputs.
+///
+/// Implemented for closures taking [TemplateData]. Failures can be reported
+/// with a [ClientError::TemplateRenderError].
+pub trait Render: Send + Sync + 'static {
+    fn render(&self, data: &TemplateData) -> Result<String, ClientError>;
+}
+
+impl<F> Render for F
+where
+    F: Fn(&TemplateData) -> Result<String, ClientError> + Send + Sync + 'static,
+{
+    fn render(&self, data: &TemplateData) -> Result<String, ClientError> {
+        self(data)
+    }
+}
+
+/// The inputs a [Template] is rendered from.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct TemplateData {
+    /// The value of every key under the watched prefixes, keyed by the full
+    /// key. Values which aren't valid UTF-8 are converted lossily.
+    pub keys: BTreeMap<String, String>,
+    /// The healthy instances of every watched service, keyed by the name of
+    /// the service.
+    pub services: BTreeMap<String, Vec<ServiceEntry>>,
+}
+
+impl TemplateData {
+    /// Returns the value of the given key, if it's under a watched prefix.
+    pub fn key(&self, key: &str) -> Option<&str> {
+        self.keys.get(key).map(String::as_str)
+    }
+
+    /// Returns the keys under the given prefix with their values.
+    pub fn keys<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+        self.keys
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(move |(k, _)| k.starts_with(prefix))
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns the healthy instances of the given service, if it's watched.
+    pub fn service(&self, name: &str) -> &[ServiceEntry] {
+        self.services
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Configuration for a [Template].
+///
+/// The file at `destination` is rendered from every key under the `keys`
+/// prefixes and the healthy instances of the `services`. A `max_wait` of
+/// [None] defaults to four times `min_wait`, and a `min_wait` of zero renders
+/// on every change.
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into))]
+pub struct TemplateConfig {
+    pub destination: PathBuf,
+    #[builder(default)]
+    pub keys: Vec<String>,
+    #[builder(setter(strip_option), default)]
+    pub max_wait: Option<Duration>,
+    #[builder(default)]
+    pub min_wait: Duration,
+    #[builder(default)]
+    pub services: Vec<String>,
+}
+
+impl TemplateConfig {
+    /// Returns a default instance of [TemplateConfigBuilder] for configuring
+    /// a template.
+    pub fn builder() -> TemplateConfigBuilder {
+        TemplateConfigBuilder::default()
+    }
+
+    /// Returns how long to wait after the first of a series of changes.
+    fn max_wait(&self) -> Duration {
+        self.max_wait
+            .unwrap_or_else(|| self.min_wait.saturating_mul(4))
+    }
+}
+
+/// A file kept up to date with the KV store and the catalog.
+///
+/// Rendering happens in a background task which stops when the [Template] is
+/// dropped. Failed requests, renders, and writes are reported through
+/// [Template::errors] and leave the last rendered file in place. A prefix
+/// without any keys renders as empty, and is polled with the backoff of a
+/// [Watcher][crate::api::watch::Watcher] until keys are written under it.
+pub struct Template {
+    errors: mpsc::UnboundedReceiver<ClientError>,
+    rx: watch::Receiver<Arc<TemplateData>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Template {
+    /// Starts watching the inputs of the given configuration and rendering
+    /// them to its destination.
+    ///
+    /// Returns once the file has been rendered for the first time, failing if
+    /// any of the inputs can't be read or the first render fails.
+    pub async fn start<C: Client + 'static>(
+        client: Arc<C>,
+        config: TemplateConfig,
+        render: impl Render,
+    ) -> Result<Self, ClientError> {
+        let (ready_tx, ready) = oneshot::channel();
+        let (errors_tx, errors) = mpsc::unbounded_channel();
+        let task = tokio::spawn(run(client, config, Arc::new(render), ready_tx, errors_tx));
+
+        match ready.await {
+            Ok(Ok(rx)) => Ok(Template {
+                errors,
+                rx,
+                tasks: vec![task],
+            }),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(ClientError::EmptyResponseError),
+        }
+    }
+
+    /// Returns a receiver which yields errors encountered while rendering.
+    pub fn errors(&mut self) -> &mut mpsc::UnboundedReceiver<ClientError> {
+        &mut self.errors
+    }
+
+    /// Returns the inputs the file was last rendered from.
+    pub fn get(&self) -> Arc<TemplateData> {
+        self.rx.borrow().clone()
+    }
+
+    /// Calls the given function with the inputs each time the file is
+    /// rewritten, i.e. to reload the process reading it.
+    pub fn on_render(&mut self, f: impl Fn(Arc<TemplateData>) + Send + 'static) {
+        let mut rx = self.subscribe();
+        self.tasks.push(tokio::spawn(async move {
+            while rx.changed().await.is_ok() {
+                let data = rx.borrow_and_update().clone();
+                f(data);
+            }
+        }));
+    }
+
+    /// Stops rendering, which is equivalent to dropping the template.
+    pub fn stop(self) {}
+
+    /// Returns a receiver which is notified each time the file is rewritten.
+    pub fn subscribe(&self) -> watch::Receiver<Arc<TemplateData>> {
+        let mut rx = self.rx.clone();
+        rx.borrow_and_update();
+        rx
+    }
+}
+
+impl Drop for Template {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// A change to one of the inputs of a template.
+enum Update {
+    Keys(String, Result<Vec<KVPair>, ClientError>),
+    Service(String, Result<Vec<ServiceEntry>, ClientError>),
+}
+
+/// Watches the inputs of a template, rendering it whenever they change.
+///
+/// The result of the first render is sent through `ready`, along with the
+/// receiver for the inputs of all later renders.
+async fn run<C: Client>(
+    client: Arc<C>,
+    config: TemplateConfig,
+    render: Arc<dyn Render>,
+    ready: oneshot::Sender<Result<watch::Receiver<Arc<TemplateData>>, ClientError>>,
+    errors: mpsc::UnboundedSender<ClientError>,
+) {
+    let mut inputs: Vec<BoxStream<'_, Update>> = Vec::new();
+    for prefix in &config.keys {
+        let mut opts = ReadKeyRequest::builder();
+        opts.recurse(true);
+        let name = prefix.clone();
+        let stream = kv::watch(client.as_ref(), prefix, Some(&mut opts))
+            .map(move |res| Update::Keys(name.clone(), res.map(|r| r.response)));
+        inputs.push(stream.boxed());
+    }
+    for service in &config.services {
+        let name = service.clone();
+        let stream = health::service_stream(client.as_ref(), service, None)
+            .map(move |res| Update::Service(name.clone(), res.map(|r| r.response)));
+        inputs.push(stream.boxed());
+    }
+    let mut updates = futures::stream::select_all(inputs);
+
+    // Nothing is rendered until every input has been read once
+    let mut pending: HashSet<(bool, String)> = config
+        .keys
+        .iter()
+        .map(|k| (true, k.clone()))
+        .chain(config.services.iter().map(|s| (false, s.clone())))
+        .collect();
+    let mut prefixes: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    let mut data = TemplateData::default();
+    while !pending.is_empty() {
+        match updates.next().await {
+            Some(update) => match apply(&mut prefixes, &mut data, update) {
+                Ok(input) => {
+                    pending.remove(&input);
+                }
+                Err(e) => {
+                    let _ = ready.send(Err(e));
+                    return;
+                }
+            },
+            None => return,
+        }
+    }
+
+    let data = Arc::new(data);
+    let mut last = match write(&config.destination, render.as_ref(), &data) {
+        Ok(content) => content,
+        Err(e) => {
+            let _ = ready.send(Err(e));
+            return;
+        }
+    };
+    let (tx, rx) = watch::channel(data.clone());
+    if ready.send(Ok(rx)).is_err() {
+        return;
+    }
+
+    // The first change not rendered yet, and the last change since
+    let mut changed: Option<(Instant, Instant)> = None;
+    let mut data = (*data).clone();
+    loop {
+        let update = match changed {
+            Some((first, latest)) => {
+                let deadline = std::cmp::min(first + config.max_wait(), latest + config.min_wait);
+                match tokio::time::timeout_at(deadline, updates.next()).await {
+                    Ok(update) => update,
+                    Err(_) => {
+                        changed = None;
+                        let rendered = Arc::new(data.clone());
+                        match write(&config.destination, render.as_ref(), &rendered) {
+                            Ok(content) if content == last => {}
+                            Ok(content) => {
+                                debug!("Rendered {}", config.destination.display());
+                                last = content;
+                                if tx.send(rendered).is_err() {
+                                    return;
+                                }
+                            }
+                            Err(e) => {
+                                warn!("Failed rendering {}: {}", config.destination.display(), e);
+                                let _ = errors.send(e);
+                            }
+                        }
+                        continue;
+                    }
+                }
+            }
+            None => updates.next().await,
+        };
+
+        let update = match update {
+            Some(update) => update,
+            None => return,
+        };
+        // Watches only yield when their results change
+        match apply(&mut prefixes, &mut data, update) {
+            Ok(_) => {
+                let now = Instant::now();
+                changed = Some((changed.map_or(now, |(first, _)| first), now));
+            }
+            Err(e) => {
+                warn!(
+                    "Failed watching the inputs of {}: {}",
+                    config.destination.display(),
+                    e
+                );
+                let _ = errors.send(e);
+            }
+        }
+    }
+}
+
+/// Applies an update to the inputs, returning which input it was for.
+fn apply(
+    prefixes: &mut BTreeMap<String, BTreeMap<String, String>>,
+    data: &mut TemplateData,
+    update: Update,
+) -> Result<(bool, String), ClientError> {
+    match update {
+        Update::Keys(prefix, res) => {
+            let kvs = res?;
+            let mut values = BTreeMap::new();
+            for kv in kvs {
+                let bytes: Vec<u8> = match kv.value {
+                    Some(v) => v.try_into()?,
+                    None => Vec::new(),
+                };
+                values.insert(kv.key, String::from_utf8_lossy(&bytes).into_owned());
+            }
+            prefixes.insert(prefix.clone(), values);
+            data.keys = prefixes
+                .values()
+                .flat_map(|v| v.iter().map(|(k, v)| (k.clone(), v.clone())))
+                .collect();
+            Ok((true, prefix))
+        }
+        Update::Service(service, res) => {
+            data.services.insert(service.clone(), res?);
+            Ok((false, service))
+        }
+    }
+}
+
+/// Renders the template and replaces the file at `path` with the result if
+/// it differs from the file, returning the rendered content.
+fn write(path: &Path, render: &dyn Render, data: &TemplateData) -> Result<String, ClientError> {
+    let content = render.render(data)?;
+    if std::fs::read(path).ok().as_deref() == Some(content.as_bytes()) {
+        return Ok(content);
+    }
+
+    // Written next to the destination and renamed so readers never see a
+    // partially written file
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = path.with_file_name(format!(".{}.tmp", name));
+    std::fs::write(&tmp, content.as_bytes())
+        .and_then(|_| std::fs::rename(&tmp, path))
+        .map_err(|e| ClientError::FileWriteError {
+            source: e,
+            path: path.display().to_string(),
+        })?;
+    Ok(content)
+}
@@ -0,0 +1,242 @@
+//! Consul servers in Docker for integration tests.
+//!
+//! Requires the `consulrs-test` feature and a local Docker daemon. A
+//! [ConsulServer] runs a Consul agent in development mode inside a new
+//! container, which is published on a random port of the loopback interface
+//! and removed again when the server is dropped. Starting a server waits for
+//! a leader to be elected and, when ACLs are enabled, bootstraps the ACL
+//! system so that [ConsulServer::client] is authorized to do anything.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use consulrs::kv;
+//! use consulrs::testing::{ConsulServer, ConsulServerConfig};
+//!
+//! # tokio_test::block_on(async {
+//! let server = ConsulServer::start_with(
+//!     ConsulServerConfig::builder()
+//!         .version("1.15.4")
+//!         .acl(true)
+//!         .build()
+//!         .unwrap(),
+//! )
+//! .await
+//! .unwrap();
+//!
+//! kv::set(&server.client(), "test", b"value", None).await.unwrap();
+//! # })
+//! ```
+use std::{
+    process::Stdio,
+    time::{Duration, Instant},
+};
+
+use derive_builder::Builder;
+use tokio::process::Command;
+
+use crate::{
+    acl,
+    client::{ConsulClient, ConsulClientSettingsBuilder},
+    error::ClientError,
+    status,
+};
+
+/// The image used for running Consul.
+pub const DEFAULT_IMAGE: &str = "hashicorp/consul";
+/// The version of Consul run by default.
+pub const DEFAULT_VERSION: &str = "1.9.9";
+
+/// Configuration for starting a [ConsulServer].
+///
+/// `args` are appended to the `consul agent -dev` command line, and `config`
+/// is passed to the agent as `CONSUL_LOCAL_CONFIG` alongside the ACL
+/// configuration. Starting fails if the agent hasn't elected a leader, and
+/// bootstrapped ACLs if enabled, within `startup_timeout`.
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into), default)]
+pub struct ConsulServerConfig {
+    pub acl: bool,
+    pub args: Vec<String>,
+    pub config: Option<serde_json::Value>,
+    pub image: String,
+    pub startup_timeout: Duration,
+    pub version: String,
+}
+
+impl Default for ConsulServerConfig {
+    fn default() -> Self {
+        ConsulServerConfig {
+            acl: false,
+            args: Vec::new(),
+            config: None,
+            image: DEFAULT_IMAGE.into(),
+            startup_timeout: Duration::from_secs(30),
+            version: DEFAULT_VERSION.into(),
+        }
+    }
+}
+
+impl ConsulServerConfig {
+    /// Returns a default instance of [ConsulServerConfigBuilder] for
+    /// configuring a server.
+    pub fn builder() -> ConsulServerConfigBuilder {
+        ConsulServerConfigBuilder::default()
+    }
+}
+
+/// A Consul agent running in a Docker container.
+///
+/// The container is removed when the server is dropped.
+#[derive(Debug)]
+pub struct ConsulServer {
+    address: String,
+    container: String,
+    token: Option<String>,
+    version: String,
+}
+
+impl ConsulServer {
+    /// Starts a server running the default version of Consul without ACLs.
+    pub async fn start() -> Result<ConsulServer, ClientError> {
+        ConsulServer::start_with(ConsulServerConfig::default()).await
+    }
+
+    /// Starts a server with the given configuration.
+    pub async fn start_with(config: ConsulServerConfig) -> Result<ConsulServer, ClientError> {
+        let mut local = config.config.unwrap_or_else(|| serde_json::json!({}));
+        if config.acl {
+            local["acl"] = serde_json::json!({
+                "enabled": true,
+                "default_policy": "deny",
+            });
+        }
+        let env = format!("CONSUL_LOCAL_CONFIG={}", local);
+        let image = format!("{}:{}", config.image, config.version);
+        let mut args = vec![
+            "run",
+            "-d",
+            "--rm",
+            "-p",
+            "127.0.0.1::8500",
+            "-e",
+            env.as_str(),
+            image.as_str(),
+            "agent",
+            "-dev",
+            "-client",
+            "0.0.0.0",
+        ];
+        args.extend(config.args.iter().map(String::as_str));
+
+        // From here on the container is removed if starting fails
+        let mut server = ConsulServer {
+            address: String::new(),
+            container: docker(&args).await?,
+            token: None,
+            version: config.version,
+        };
+        let port = docker(&["port", &server.container, "8500/tcp"]).await?;
+        server.address = match port.lines().next() {
+            Some(p) => format!("http://{}", p),
+            None => {
+                return Err(ClientError::TestServerError {
+                    message: format!("Container {} has no published port", server.container),
+                })
+            }
+        };
+        server.wait(config.acl, config.startup_timeout).await?;
+        Ok(server)
+    }
+
+    /// Returns the address of the HTTP API of the server.
+    pub fn address(&self) -> &str {
+        self.address.as_str()
+    }
+
+    /// Returns a [ConsulClient] connected to the server, using the initial
+    /// management token if ACLs are enabled.
+    pub fn client(&self) -> ConsulClient {
+        ConsulClient::new(self.settings().build().unwrap()).unwrap()
+    }
+
+    /// Returns a [ConsulClientSettingsBuilder] configured to connect to the
+    /// server, using the initial management token if ACLs are enabled.
+    pub fn settings(&self) -> ConsulClientSettingsBuilder {
+        let mut settings = ConsulClientSettingsBuilder::default();
+        settings.address(self.address.as_str());
+        if let Some(token) = &self.token {
+            settings.token(token.clone());
+        }
+        settings
+    }
+
+    /// Returns the initial management token if ACLs are enabled.
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Returns the version of Consul run by the server.
+    pub fn version(&self) -> &str {
+        self.version.as_str()
+    }
+
+    /// Waits for a leader to be elected, then bootstraps ACLs if enabled.
+    async fn wait(&mut self, acl: bool, timeout: Duration) -> Result<(), ClientError> {
+        let client = ConsulClient::new(
+            ConsulClientSettingsBuilder::default()
+                .address(self.address.as_str())
+                .build()
+                .unwrap(),
+        )?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let mut ready = matches!(
+                status::leader(&client, None).await,
+                Ok(res) if !res.response.is_empty()
+            );
+            if ready && acl {
+                match acl::bootstrap(&client, None).await {
+                    Ok(res) => self.token = res.response.secret_id,
+                    Err(_) => ready = false,
+                }
+            }
+            if ready {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(ClientError::TestServerError {
+                    message: format!("Consul wasn't ready within {:?}", timeout),
+                });
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    }
+}
+
+impl Drop for ConsulServer {
+    fn drop(&mut self) {
+        let _ = std::process::Command::new("docker")
+            .args(["rm", "-f", &self.container])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+/// Runs docker with the given arguments, returning its trimmed output.
+async fn docker(args: &[&str]) -> Result<String, ClientError> {
+    let output = Command::new("docker")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| ClientError::TestServerError {
+            message: format!("Error running docker: {}", e),
+        })?;
+    if !output.status.success() {
+        return Err(ClientError::TestServerError {
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,176 @@
+use crate::{
+    api::{
+        self,
+        kv::common::Base64String,
+        txn::{
+            common::{KVTxnOp, KVTxnVerb, TxnOp},
+            requests::{TransactionRequest, TransactionRequestBuilder},
+            responses::TransactionResponse,
+        },
+        ApiResponse,
+    },
+    client::Client,
+    error::ClientError,
+};
+
+/// The most operations Consul accepts in a single transaction.
+pub const MAX_OPS: usize = 64;
+
+/// A list of operations to be applied atomically by Consul.
+///
+/// Provides shortcuts for the KV verbs which are most commonly used in
+/// transactions. Node, service, and check operations can be added using
+/// [Transaction::op].
+///
+/// ```
+/// use consulrs::txn::Transaction;
+///
+/// let txn = Transaction::new()
+///     .set("a", b"1")
+///     .delete("b")
+///     .check_index("c", 42);
+/// assert_eq!(txn.ops().len(), 3);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Transaction {
+    ops: Vec<TxnOp>,
+}
+
+impl Transaction {
+    /// Returns a new, empty [Transaction].
+    pub fn new() -> Self {
+        Transaction::default()
+    }
+
+    /// Adds the given operation to the transaction.
+    pub fn op(mut self, op: TxnOp) -> Self {
+        self.ops.push(op);
+        self
+    }
+
+    /// Returns the operations contained in the transaction.
+    pub fn ops(&self) -> &[TxnOp] {
+        &self.ops
+    }
+
+    /// Sets the key to the given value.
+    pub fn set(self, key: &str, value: &[u8]) -> Self {
+        self.kv(KVTxnVerb::Set, key, Some(value), None, None)
+    }
+
+    /// Sets the key to the given value if its modify index matches.
+    pub fn cas(self, key: &str, value: &[u8], index: u64) -> Self {
+        self.kv(KVTxnVerb::Cas, key, Some(value), Some(index), None)
+    }
+
+    /// Locks the key with the given session and sets it to the given value.
+    pub fn lock(self, key: &str, value: &[u8], session: &str) -> Self {
+        self.kv(KVTxnVerb::Lock, key, Some(value), None, Some(session))
+    }
+
+    /// Unlocks the key held by the given session and sets it to the given
+    /// value.
+    pub fn unlock(self, key: &str, value: &[u8], session: &str) -> Self {
+        self.kv(KVTxnVerb::Unlock, key, Some(value), None, Some(session))
+    }
+
+    /// Reads the key, failing the transaction if it does not exist.
+    pub fn get(self, key: &str) -> Self {
+        self.kv(KVTxnVerb::Get, key, None, None, None)
+    }
+
+    /// Reads all keys under the given prefix.
+    pub fn get_tree(self, prefix: &str) -> Self {
+        self.kv(KVTxnVerb::GetTree, prefix, None, None, None)
+    }
+
+    /// Fails the transaction if the modify index of the key does not match.
+    pub fn check_index(self, key: &str, index: u64) -> Self {
+        self.kv(KVTxnVerb::CheckIndex, key, None, Some(index), None)
+    }
+
+    /// Fails the transaction if the key is not locked by the given session.
+    pub fn check_session(self, key: &str, session: &str) -> Self {
+        self.kv(KVTxnVerb::CheckSession, key, None, None, Some(session))
+    }
+
+    /// Fails the transaction if the key exists.
+    pub fn check_not_exists(self, key: &str) -> Self {
+        self.kv(KVTxnVerb::CheckNotExists, key, None, None, None)
+    }
+
+    /// Deletes the key.
+    pub fn delete(self, key: &str) -> Self {
+        self.kv(KVTxnVerb::Delete, key, None, None, None)
+    }
+
+    /// Deletes all keys under the given prefix.
+    pub fn delete_tree(self, prefix: &str) -> Self {
+        self.kv(KVTxnVerb::DeleteTree, prefix, None, None, None)
+    }
+
+    /// Deletes the key if its modify index matches.
+    pub fn delete_cas(self, key: &str, index: u64) -> Self {
+        self.kv(KVTxnVerb::DeleteCas, key, None, Some(index), None)
+    }
+
+    fn kv(
+        self,
+        verb: KVTxnVerb,
+        key: &str,
+        value: Option<&[u8]>,
+        index: Option<u64>,
+        session: Option<&str>,
+    ) -> Self {
+        self.op(TxnOp::KV(KVTxnOp {
+            verb,
+            key: key.into(),
+            index,
+            session: session.map(|s| s.into()),
+            value: value.map(Base64String::from),
+            ..Default::default()
+        }))
+    }
+}
+
+/// Executes the given transaction.
+///
+/// If Consul rolls back the transaction the reasons are returned in a
+/// [ClientError::TransactionError].
+///
+/// See [TransactionRequest]
+#[instrument(skip(client, txn, opts), err)]
+pub async fn execute(
+    client: &impl Client,
+    txn: &Transaction,
+    opts: Option<&mut TransactionRequestBuilder>,
+) -> Result<ApiResponse<TransactionResponse>, ClientError> {
+    let mut t = TransactionRequest::builder();
+    let data =
+        serde_json::to_vec(&txn.ops).map_err(|e| ClientError::JsonSerializeError { source: e })?;
+    let endpoint = opts.unwrap_or(&mut t).data(data).build().unwrap();
+    api::exec_with_result(client, endpoint)
+        .await
+        .map_err(|e| match e {
+            ClientError::APIError {
+                code: 409,
+                index,
+                message: Some(m),
+                request_id,
+                retry_after,
+            } => match serde_json::from_str::<TransactionResponse>(&m) {
+                Ok(TransactionResponse {
+                    errors: Some(errors),
+                    ..
+                }) => ClientError::TransactionError { errors },
+                _ => ClientError::APIError {
+                    code: 409,
+                    index,
+                    message: Some(m),
+                    request_id,
+                    retry_after,
+                },
+            },
+            e => e,
+        })
+}
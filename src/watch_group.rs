@@ -0,0 +1,264 @@
+//! Many KV watches served by a few blocking queries.
+//!
+//! Watching dozens of keys with [kv::watch] runs one blocking query per key.
+//! A [WatchGroup] instead runs a single recursive blocking query per prefix
+//! and dispatches the results to the subscribers of every key and prefix
+//! under it, each of which is only notified when the keys it watches change.
+//!
+//! A subscription is served by any running query whose prefix covers it.
+//! Otherwise a new query is started on the directory of the key, i.e.
+//! `app/db/` for `app/db/host`, or on the prefix itself, so that later
+//! subscriptions to sibling keys share it. A query stops once all of its
+//! subscriptions are dropped.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use consulrs::client::{ConsulClient, ConsulClientSettingsBuilder};
+//! use consulrs::watch_group::WatchGroup;
+//! use futures::StreamExt;
+//! use std::sync::Arc;
+//!
+//! # tokio_test::block_on(async {
+//! # let client = ConsulClient::new(
+//! #     ConsulClientSettingsBuilder::default()
+//! #         .address("https://127.0.0.1:8200")
+//! #         .build()
+//! #         .unwrap()
+//! # ).unwrap();
+//! let group = WatchGroup::new(Arc::new(client));
+//! let mut host = group.key("app/db/host");
+//! let mut port = group.key("app/db/port");
+//! assert_eq!(group.prefixes(), vec!["app/db/"]);
+//!
+//! while let Some(kv) = host.next().await {
+//!     println!("Host is now {:?}", kv.and_then(|kv| kv.value));
+//! }
+//! # })
+//! ```
+use std::{
+    collections::BTreeMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt};
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use crate::{
+    api::kv::{common::KVPair, requests::ReadKeyRequest},
+    client::Client,
+    error::ClientError,
+    kv,
+};
+
+type Queries = Arc<Mutex<BTreeMap<String, (Arc<Mutex<Query>>, JoinHandle<()>)>>>;
+
+/// A group of KV watches which share blocking queries.
+///
+/// Queries run in background tasks which stop when the [WatchGroup] is
+/// dropped, ending all of its subscriptions. Failed requests are retried
+/// with the backoff of a [Watcher][crate::api::watch::Watcher] and reported
+/// through [WatchGroup::errors].
+pub struct WatchGroup<C: Client + 'static> {
+    client: Arc<C>,
+    errors: mpsc::UnboundedReceiver<ClientError>,
+    errors_tx: mpsc::UnboundedSender<ClientError>,
+    queries: Queries,
+}
+
+impl<C: Client + 'static> WatchGroup<C> {
+    /// Returns a new [WatchGroup] without any watches.
+    pub fn new(client: Arc<C>) -> Self {
+        let (errors_tx, errors) = mpsc::unbounded_channel();
+        WatchGroup {
+            client,
+            errors,
+            errors_tx,
+            queries: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Returns a receiver which yields errors encountered by the queries.
+    pub fn errors(&mut self) -> &mut mpsc::UnboundedReceiver<ClientError> {
+        &mut self.errors
+    }
+
+    /// Subscribes to the given key.
+    ///
+    /// The subscription yields the current value of the key, or [None] if it
+    /// doesn't exist, and then yields again each time it changes.
+    pub fn key(&self, key: &str) -> Subscription<Option<KVPair>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let dir = match key.rfind('/') {
+            Some(i) => &key[..=i],
+            None => key,
+        };
+        self.subscribe(dir, Target::Key(key.to_string(), tx));
+        Subscription { rx }
+    }
+
+    /// Subscribes to every key under the given prefix.
+    ///
+    /// The subscription yields the current keys and then yields again each
+    /// time any of them is created, changed, or deleted.
+    pub fn prefix(&self, prefix: &str) -> Subscription<Vec<KVPair>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.subscribe(prefix, Target::Prefix(prefix.to_string(), tx));
+        Subscription { rx }
+    }
+
+    /// Returns the prefixes of the running queries.
+    pub fn prefixes(&self) -> Vec<String> {
+        self.queries.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Adds a subscriber to the query covering it, starting a query on the
+    /// given prefix if there isn't one.
+    fn subscribe(&self, prefix: &str, target: Target) {
+        let mut subscriber = Subscriber { last: None, target };
+        let mut queries = self.queries.lock().unwrap();
+        let covering = queries
+            .iter()
+            .filter(|(p, _)| subscriber.target.path().starts_with(p.as_str()))
+            .max_by_key(|(p, _)| p.len())
+            .map(|(_, (query, _))| query.clone());
+        if let Some(query) = covering {
+            let mut query = query.lock().unwrap();
+            if let Some(kvs) = &query.kvs {
+                subscriber.dispatch(kvs);
+            }
+            query.subscribers.push(subscriber);
+            return;
+        }
+
+        debug!("Starting a watch on prefix {}", prefix);
+        let query = Arc::new(Mutex::new(Query {
+            kvs: None,
+            subscribers: vec![subscriber],
+        }));
+        let task = tokio::spawn(run(
+            self.client.clone(),
+            prefix.to_string(),
+            query.clone(),
+            self.queries.clone(),
+            self.errors_tx.clone(),
+        ));
+        queries.insert(prefix.to_string(), (query, task));
+    }
+}
+
+impl<C: Client + 'static> Drop for WatchGroup<C> {
+    fn drop(&mut self) {
+        for (_, task) in self.queries.lock().unwrap().values() {
+            task.abort();
+        }
+    }
+}
+
+/// A stream of the values of a key or prefix watched by a [WatchGroup].
+pub struct Subscription<T> {
+    rx: mpsc::UnboundedReceiver<T>,
+}
+
+impl<T> Stream for Subscription<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+/// The state of the blocking query on a prefix.
+struct Query {
+    kvs: Option<Vec<KVPair>>,
+    subscribers: Vec<Subscriber>,
+}
+
+/// What a subscriber watches, and where its values are sent.
+enum Target {
+    Key(String, mpsc::UnboundedSender<Option<KVPair>>),
+    Prefix(String, mpsc::UnboundedSender<Vec<KVPair>>),
+}
+
+impl Target {
+    fn path(&self) -> &str {
+        match self {
+            Target::Key(path, _) | Target::Prefix(path, _) => path,
+        }
+    }
+}
+
+struct Subscriber {
+    /// The keys and modify indexes last sent to the subscriber.
+    last: Option<Vec<(String, u64)>>,
+    target: Target,
+}
+
+impl Subscriber {
+    /// Sends the keys the subscriber watches if they changed since they were
+    /// last sent, returning whether the subscriber is still listening.
+    fn dispatch(&mut self, kvs: &[KVPair]) -> bool {
+        let matched: Vec<&KVPair> = kvs
+            .iter()
+            .filter(|kv| match &self.target {
+                Target::Key(key, _) => kv.key == *key,
+                Target::Prefix(prefix, _) => kv.key.starts_with(prefix.as_str()),
+            })
+            .collect();
+        let version = matched
+            .iter()
+            .map(|kv| (kv.key.clone(), kv.modify_index))
+            .collect();
+        if self.last.as_ref() == Some(&version) {
+            return match &self.target {
+                Target::Key(_, tx) => !tx.is_closed(),
+                Target::Prefix(_, tx) => !tx.is_closed(),
+            };
+        }
+        self.last = Some(version);
+
+        match &self.target {
+            Target::Key(_, tx) => tx.send(matched.first().map(|kv| (*kv).clone())).is_ok(),
+            Target::Prefix(_, tx) => tx.send(matched.into_iter().cloned().collect()).is_ok(),
+        }
+    }
+}
+
+/// Watches every key under the prefix, dispatching each result to the
+/// subscribers of the query until none are left.
+async fn run<C: Client>(
+    client: Arc<C>,
+    prefix: String,
+    query: Arc<Mutex<Query>>,
+    queries: Queries,
+    errors: mpsc::UnboundedSender<ClientError>,
+) {
+    let mut opts = ReadKeyRequest::builder();
+    opts.recurse(true);
+    let stream = kv::watch(client.as_ref(), &prefix, Some(&mut opts));
+    futures::pin_mut!(stream);
+
+    while let Some(res) = stream.next().await {
+        let kvs = match res {
+            Ok(res) => res.response,
+            Err(e) => {
+                warn!("Failed watching prefix {}: {}", prefix, e);
+                let _ = errors.send(e);
+                continue;
+            }
+        };
+
+        // Locked in the same order as when subscribing
+        let mut queries = queries.lock().unwrap();
+        let mut state = query.lock().unwrap();
+        state.subscribers.retain_mut(|s| s.dispatch(&kvs));
+        state.kvs = Some(kvs);
+        if state.subscribers.is_empty() {
+            debug!("Stopping the watch on prefix {}", prefix);
+            queries.remove(&prefix);
+            return;
+        }
+    }
+}
@@ -0,0 +1,345 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    acl::{
+        self, auth_method, binding_rule, policy, role,
+        rules::{Access, Rules},
+        token,
+    },
+    agent,
+    api::{
+        acl::{
+            common::{
+                ACLAccess, ACLAuthorizationBuilder, ACLBindType, ACLLinkBuilder, ACLResource,
+            },
+            requests::{
+                CreateAuthMethodRequest, CreateBindingRuleRequest, CreatePolicyRequest,
+                CreateRoleRequest, ListBindingRulesRequest, UpdateAuthMethodRequest,
+                UpdatePolicyRequest,
+            },
+        },
+        agent::common::AgentTokenKind,
+    },
+    client::Client,
+    error::ClientError,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_acl_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.acl_client();
+
+        let policy = test_policy_create(&client, "test").await;
+        test_policy_read(&client, &policy).await;
+        test_policy_read_by_name(&client, "test").await;
+        test_policy_list(&client).await;
+        test_policy_update(&client, &policy, "test").await;
+
+        let role = test_role_create(&client, "test", &policy).await;
+        test_role_read(&client, &role).await;
+        test_role_read_by_name(&client, "test").await;
+        test_role_list(&client).await;
+        test_role_update(&client, &role, "test").await;
+        test_role_delete(&client, &role).await;
+
+        test_policy_delete(&client, &policy).await;
+
+        test_auth_method_create(&client, "test").await;
+        test_auth_method_read(&client, "test").await;
+        test_auth_method_list(&client).await;
+        test_auth_method_update(&client, "test").await;
+
+        let rule = test_binding_rule_create(&client, "test").await;
+        test_binding_rule_read(&client, &rule).await;
+        test_binding_rule_list(&client, "test").await;
+        test_binding_rule_update(&client, &rule, "test").await;
+        test_login(&client, "test").await;
+        test_binding_rule_delete(&client, &rule).await;
+
+        test_auth_method_delete(&client, "test").await;
+
+        test_bootstrap(&client).await;
+        test_replication_status(&client).await;
+        test_update_agent_token(&client).await;
+        test_token_read_self(&client).await;
+        test_authorize(&client).await;
+    });
+}
+
+const PUBLIC_KEY: &str = "-----BEGIN PUBLIC KEY-----
+MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEA/YcUEBbmfcDls01kFAIz
+wb9XGpQezh9lFkalGYg34rLXuyT1peCItOhf3VWIRkJ7erKpGWq2IkA64K0aoeC0
+hfbwe80gKcUgfELO4eu4S+SzHiaPDeAuEMBCBs/UjBnAFE/vdTAR68sXhr1UWBO/
+JX8I8+8nwfu7/obifE8s5aNgiApkbk7jdFYe8X0sXtPtJSWp4BXuKLuGuHCcBU31
+ksqNdhbznQ782AyhCYFG+LHUo4qx9+S0e2lbdtnymaQOpsfo2l/QiTxjsvaC2gHS
+LXOPMuM+isiz04/sa8p5CqJM4DtEGDZjwWB2aoiYUxbBGmFdy4zpgEdN1QuuMxmN
++wIDAQAB
+-----END PUBLIC KEY-----";
+
+fn jwt_config() -> HashMap<String, serde_json::Value> {
+    let mut config = HashMap::new();
+    config.insert(
+        "JWTValidationPubKeys".to_string(),
+        serde_json::json!([PUBLIC_KEY]),
+    );
+    config.insert("BoundAudiences".to_string(), serde_json::json!(["test"]));
+    config
+}
+
+async fn test_authorize(client: &impl Client) {
+    let res = acl::can(client, ACLResource::Key, "app/config", ACLAccess::Write).await;
+    assert!(res.unwrap());
+
+    let permissions = vec![ACLAuthorizationBuilder::default()
+        .resource(ACLResource::Operator)
+        .access(ACLAccess::Read)
+        .build()
+        .unwrap()];
+    let res = acl::authorize(client, &permissions, None).await;
+    assert_eq!(res.unwrap().response[0].allow, Some(true));
+    assert!(acl::require(client, &permissions).await.is_ok());
+}
+
+async fn test_auth_method_create(client: &impl Client, name: &str) {
+    let res = auth_method::create(
+        client,
+        name,
+        "jwt",
+        Some(CreateAuthMethodRequest::builder().config(jwt_config())),
+    )
+    .await;
+    assert!(res.is_ok());
+}
+
+async fn test_auth_method_delete(client: &impl Client, name: &str) {
+    let res = auth_method::delete(client, name, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_auth_method_list(client: &impl Client) {
+    let res = auth_method::list(client, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_auth_method_read(client: &impl Client, name: &str) {
+    let res = auth_method::read(client, name, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_auth_method_update(client: &impl Client, name: &str) {
+    let res = auth_method::update(
+        client,
+        name,
+        "jwt",
+        Some(
+            UpdateAuthMethodRequest::builder()
+                .config(jwt_config())
+                .description("updated"),
+        ),
+    )
+    .await;
+    assert!(res.is_ok());
+}
+
+async fn test_bootstrap(client: &impl Client) {
+    // The master token may have already bootstrapped the cluster, so only the
+    // second attempt is guaranteed to be rejected
+    let _ = acl::bootstrap(client, None).await;
+    let res = acl::bootstrap(client, None).await;
+    assert!(matches!(res, Err(ClientError::ACLBootstrapError { .. })));
+}
+
+async fn test_binding_rule_create(client: &impl Client, method: &str) -> String {
+    let res = binding_rule::create(
+        client,
+        method,
+        ACLBindType::Service,
+        "test",
+        Some(CreateBindingRuleRequest::builder().description("test")),
+    )
+    .await;
+    assert!(res.is_ok());
+    res.unwrap().response.id
+}
+
+async fn test_binding_rule_delete(client: &impl Client, id: &str) {
+    let res = binding_rule::delete(client, id, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_binding_rule_list(client: &impl Client, method: &str) {
+    let res = binding_rule::list(
+        client,
+        Some(ListBindingRulesRequest::builder().authmethod(method)),
+    )
+    .await;
+    assert!(res.is_ok());
+}
+
+async fn test_binding_rule_read(client: &impl Client, id: &str) {
+    let res = binding_rule::read(client, id, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_binding_rule_update(client: &impl Client, id: &str, method: &str) {
+    let res = binding_rule::update(client, id, method, ACLBindType::Node, "test", None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_login(client: &impl Client, method: &str) {
+    // The bearer token is not a JWT signed by the configured key
+    let res = acl::login(client, method, "invalid", None).await;
+    assert!(res.is_err());
+}
+
+async fn test_policy_create(client: &impl Client, name: &str) -> String {
+    let res = policy::create(
+        client,
+        name,
+        Some(CreatePolicyRequest::builder().rules(r#"key_prefix "" { policy = "read" }"#)),
+    )
+    .await;
+    assert!(res.is_ok());
+    res.unwrap().response.id
+}
+
+async fn test_policy_delete(client: &impl Client, id: &str) {
+    let res = policy::delete(client, id, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_policy_list(client: &impl Client) {
+    let res = policy::list(client, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_policy_read(client: &impl Client, id: &str) {
+    let res = policy::read(client, id, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_policy_read_by_name(client: &impl Client, name: &str) {
+    let res = policy::read_by_name(client, name, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_policy_update(client: &impl Client, id: &str, name: &str) {
+    let rules = Rules::new()
+        .key_prefix("app/", Access::Write)
+        .service("web", Access::Read);
+    let res = policy::update(
+        client,
+        id,
+        name,
+        Some(
+            UpdatePolicyRequest::builder()
+                .description("updated")
+                .rules(rules.to_string()),
+        ),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let res = policy::read(client, id, None).await;
+    let read: Rules = res.unwrap().response.rules.unwrap().parse().unwrap();
+    assert_eq!(read, rules);
+}
+
+#[test]
+fn test_rules() {
+    let rules: Rules = r#"
+        # Applications read their configuration
+        operator = "read"
+        key_prefix "app/" {
+          policy = "list"
+        }
+        service "web" {
+          policy = "write"
+          intentions = "read"
+        }
+        /* Nothing else */
+        service_prefix "" { policy = "deny" }
+    "#
+    .parse()
+    .unwrap();
+    let expected = Rules::new()
+        .operator(Access::Read)
+        .key_prefix("app/", Access::List)
+        .service("web", Access::Write)
+        .intentions("web", Access::Read)
+        .service_prefix("", Access::Deny);
+    assert_eq!(rules, expected);
+
+    let json = expected.to_json().unwrap();
+    assert_eq!(json.parse::<Rules>().unwrap(), expected);
+    assert_eq!(expected.to_string().parse::<Rules>().unwrap(), expected);
+
+    let res = "key_prefix \"app/\" { policy = \"all\" }".parse::<Rules>();
+    assert!(matches!(res, Err(ClientError::RuleParseError { .. })));
+    let res = "files \"app/\" { policy = \"read\" }".parse::<Rules>();
+    assert!(matches!(res, Err(ClientError::RuleParseError { .. })));
+}
+
+async fn test_replication_status(client: &impl Client) {
+    let res = acl::replication_status(client, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_role_create(client: &impl Client, name: &str, policy: &str) -> String {
+    let res = role::create(
+        client,
+        name,
+        Some(
+            CreateRoleRequest::builder()
+                .policies(vec![ACLLinkBuilder::default().id(policy).build().unwrap()]),
+        ),
+    )
+    .await;
+    assert!(res.is_ok());
+    res.unwrap().response.id
+}
+
+async fn test_role_delete(client: &impl Client, id: &str) {
+    let res = role::delete(client, id, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_role_list(client: &impl Client) {
+    let res = role::list(client, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_role_read(client: &impl Client, id: &str) {
+    let res = role::read(client, id, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_role_read_by_name(client: &impl Client, name: &str) {
+    let res = role::read_by_name(client, name, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_role_update(client: &impl Client, id: &str, name: &str) {
+    let res = role::update(client, id, name, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_update_agent_token(client: &impl Client) {
+    let res = agent::update_token(client, AgentTokenKind::Default, common::ACL_TOKEN, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_token_read_self(client: &impl Client) {
+    let res = token::read_self(client, None).await;
+    assert!(res.is_ok());
+    assert_eq!(
+        res.unwrap().response.secret_id.as_deref(),
+        client.settings().token.as_deref()
+    );
+}
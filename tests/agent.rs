@@ -0,0 +1,170 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    agent,
+    api::{
+        agent::{
+            common::{AgentMemberStatus, LogLevel},
+            requests::{EnableMaintenanceRequest, MonitorRequest},
+        },
+        service::requests::RegisterServiceRequest,
+    },
+    client::{Client, ConsulClient},
+    service,
+};
+use futures::StreamExt;
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+
+        test_host(&client).await;
+        test_maintenance(&client).await;
+        test_members(&client).await;
+        test_members_wan(&client).await;
+        test_metrics(&client).await;
+        test_monitor(&client).await;
+        test_monitor_json(&client).await;
+        test_register_service(Arc::new(server.client())).await;
+        test_reload(&client).await;
+        test_self_info(&client).await;
+        test_leave(&client).await;
+    });
+}
+
+async fn test_host(client: &impl Client) {
+    let res = agent::host(client, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response.host.is_some());
+}
+
+async fn test_leave(client: &impl Client) {
+    let res = agent::leave(client, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_maintenance(client: &impl Client) {
+    let res = agent::maintenance(
+        client,
+        true,
+        Some(EnableMaintenanceRequest::builder().reason("testing")),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let res = agent::maintenance(client, false, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_members(client: &impl Client) {
+    let res = agent::members(client, false, None).await;
+    assert!(res.is_ok());
+
+    let members = res.unwrap().response;
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].status, AgentMemberStatus::Alive);
+}
+
+async fn test_members_wan(client: &impl Client) {
+    let res = agent::members(client, true, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response[0].name.ends_with(".dc1"));
+}
+
+async fn test_metrics(client: &impl Client) {
+    let res = agent::metrics(client, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_monitor(client: &impl Client) {
+    let res = agent::monitor(client, None).await;
+    assert!(res.is_ok());
+
+    let mut logs = res.unwrap().response;
+    agent::reload(client, None).await.unwrap();
+    let entry = logs.next().await.unwrap();
+    assert!(entry.is_ok());
+    assert!(entry.unwrap().level.is_some());
+}
+
+async fn test_monitor_json(client: &impl Client) {
+    let res = agent::monitor(
+        client,
+        Some(
+            MonitorRequest::builder()
+                .logjson(true)
+                .loglevel(LogLevel::Debug),
+        ),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let mut logs = res.unwrap().response;
+    agent::reload(client, None).await.unwrap();
+    let entry = logs.next().await.unwrap();
+    assert!(entry.is_ok());
+    assert!(!entry.unwrap().message.is_empty());
+}
+
+async fn test_register_service(client: Arc<ConsulClient>) {
+    let res = agent::register_service(
+        client.clone(),
+        "guarded",
+        Some(RegisterServiceRequest::builder().id("guarded-1")),
+        true,
+    )
+    .await;
+    assert!(res.is_ok());
+    let handle = res.unwrap();
+    assert_eq!(handle.id(), "guarded-1");
+
+    // The service is registered again after going missing
+    let res = service::deregister(client.as_ref(), "guarded-1", None).await;
+    assert!(res.is_ok());
+    assert!(wait_for_service(client.as_ref(), "guarded-1", true).await);
+
+    let res = handle.shutdown().await;
+    assert!(res.is_ok());
+    let res = service::try_read(client.as_ref(), "guarded-1", None).await;
+    assert!(res.unwrap().is_none());
+
+    // Dropping the handle deregisters the service in the background
+    let res = agent::register_service(client.clone(), "guarded", None, false).await;
+    assert!(res.is_ok());
+    drop(res);
+    assert!(wait_for_service(client.as_ref(), "guarded", false).await);
+}
+
+async fn test_reload(client: &impl Client) {
+    let res = agent::reload(client, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_self_info(client: &impl Client) {
+    let res = agent::self_info(client, None).await;
+    assert!(res.is_ok());
+
+    let info = res.unwrap().response;
+    assert_eq!(info.config.datacenter, "dc1");
+    assert_eq!(info.config.node_name, info.member.name);
+}
+
+/// Polls for whether the given service is registered on the agent, returning
+/// whether it reached the expected state in time.
+async fn wait_for_service(client: &impl Client, id: &str, registered: bool) -> bool {
+    for _ in 0..20 {
+        let res = service::try_read(client, id, None).await;
+        if res.unwrap().is_some() == registered {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    false
+}
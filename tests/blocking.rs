@@ -0,0 +1,39 @@
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::{blocking::BlockingQuery, kv::requests::ReadKeyRequest},
+    client::Client,
+    kv,
+};
+use futures::StreamExt;
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+        let key = "test";
+
+        test_stream(&client, key).await;
+    });
+}
+
+async fn test_stream(client: &impl Client, key: &str) {
+    let res = kv::set(client, key, b"test", None).await;
+    assert!(res.is_ok());
+
+    let endpoint = ReadKeyRequest::builder().key(key).build().unwrap();
+    let stream = BlockingQuery::new(client, endpoint).wait("1s").stream();
+    futures::pin_mut!(stream);
+
+    let first = stream.next().await.unwrap();
+    assert!(first.is_ok());
+
+    // The second request blocks until the wait time is reached
+    let second = stream.next().await.unwrap();
+    assert!(second.is_ok());
+    assert_eq!(first.unwrap().index, second.unwrap().index);
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use common::ConsulServer;
+use consulrs::{
+    blocking::{self, ConsulClient},
+    client::ConsulClientSettingsBuilder,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let address = server.external_url();
+
+        // The blocking client can't be used from within the test's runtime
+        tokio::task::spawn_blocking(move || {
+            let client = ConsulClient::new(
+                ConsulClientSettingsBuilder::default()
+                    .address(address)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+
+            test_kv(&client);
+            test_status(&client);
+        })
+        .await
+        .unwrap();
+    });
+}
+
+fn test_kv(client: &ConsulClient) {
+    let res = blocking::kv::set(client, "blocking", b"test", None);
+    assert!(res.is_ok());
+    assert!(res.unwrap().response);
+
+    let res = blocking::kv::read_raw(client, "blocking", None);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response, b"test");
+}
+
+fn test_status(client: &ConsulClient) {
+    let res = blocking::status::leader(client, None);
+    assert!(res.is_ok());
+    assert!(!res.unwrap().response.is_empty());
+}
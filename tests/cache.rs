@@ -0,0 +1,44 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    cache::{CacheConfig, CachedHealth, CachedKv},
+    client::ConsulClient,
+    kv,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = Arc::new(server.client());
+
+        test_health(client.clone()).await;
+        test_kv(client).await;
+    });
+}
+
+async fn test_health(client: Arc<ConsulClient>) {
+    let cache = CachedHealth::new(client, CacheConfig::default());
+    for _ in 0..3 {
+        let res = cache.service("consul").await;
+        assert_eq!(res.unwrap().len(), 1);
+    }
+    assert_eq!(cache.stats().hits, 2);
+}
+
+async fn test_kv(client: Arc<ConsulClient>) {
+    let res = kv::set(client.as_ref(), "cache/test", b"test", None).await;
+    assert!(res.is_ok());
+
+    let cache = CachedKv::new(client, CacheConfig::default());
+    for _ in 0..3 {
+        let res = cache.get_raw("cache/test").await;
+        assert_eq!(res.unwrap(), Some(b"test".to_vec()));
+    }
+    assert_eq!(cache.stats().misses, 1);
+}
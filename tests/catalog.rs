@@ -3,12 +3,23 @@ mod common;
 use common::{ConsulServer, ConsulServerHelper, CountingServer};
 use consulrs::{
     api::{
-        catalog::requests::{DeregisterEntityRequest, RegisterEntityRequest},
-        check::common::AgentCheckBuilder,
+        catalog::{
+            common::GatewayKind,
+            requests::{
+                DeregisterEntityRequest, ListNodeServicesRequest, ListNodesRequest,
+                RegisterEntityRequest,
+            },
+        },
+        check::common::{AgentCheckBuilder, HealthCheckDefinitionBuilder},
+        config::common::{ConfigEntry, LinkedServiceBuilder, TerminatingGatewayConfigEntryBuilder},
+        features::{ConsistencyMode, Filter},
+        service::common::AgentServiceBuilder,
     },
     catalog,
-    client::Client,
+    client::{Client, ConsulClient, ConsulClientSettingsBuilder, QueryOptions},
+    config, external,
 };
+use futures::StreamExt;
 use test_env_log::test;
 
 #[test]
@@ -24,12 +35,18 @@ fn test() {
         test_datacenters(&client).await;
         test_gateway(&client, "test").await;
         test_node(&client, &node).await;
+        test_node_filter(&client, &node).await;
         test_nodes(&client).await;
+        test_nodes_consistency(&client).await;
+        test_nodes_default_options(&server).await;
+        test_nodes_filter(&client, &node).await;
         test_nodes_with_service(&client, "consul").await;
         test_nodes_with_connect_service(&client, "consul").await;
         test_services(&client).await;
         test_register(&client, &node, "test").await;
         test_deregister(&client, &node, "test").await;
+        test_external(&client).await;
+        test_watch(&client, "consul").await;
     });
 }
 
@@ -48,9 +65,65 @@ async fn test_deregister(client: &impl Client, node: &str, check: &str) {
     assert!(res.is_ok());
 }
 
+async fn test_external(client: &impl Client) {
+    let service = AgentServiceBuilder::default()
+        .id("external")
+        .service("external")
+        .port(5432u64)
+        .build()
+        .unwrap();
+    let check = AgentCheckBuilder::default()
+        .name("external")
+        .definition(
+            HealthCheckDefinitionBuilder::default()
+                .tcp("10.0.0.1:5432")
+                .interval("10s")
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    let res =
+        external::register_service(client, "external", "10.0.0.1", service, Some(check), None)
+            .await;
+    assert!(res.is_ok());
+
+    let res = catalog::nodes_with_service(client, "external", None).await;
+    assert_eq!(
+        res.unwrap().response[0].address.as_deref(),
+        Some("10.0.0.1")
+    );
+
+    let res = external::deregister_service(client, "external", "external", None).await;
+    assert!(res.is_ok());
+}
+
 async fn test_gateway(client: &impl Client, gateway: &str) {
     let res = catalog::gateway(client, gateway, None).await;
     assert!(res.is_ok());
+
+    let entry = TerminatingGatewayConfigEntryBuilder::default()
+        .name(gateway)
+        .services(vec![LinkedServiceBuilder::default()
+            .name("billing")
+            .sni("billing.example.com")
+            .build()
+            .unwrap()])
+        .build()
+        .unwrap();
+    config::apply(client, ConfigEntry::TerminatingGateway(entry), None)
+        .await
+        .unwrap();
+
+    let res = catalog::gateway(client, gateway, None).await;
+    assert!(res.is_ok());
+
+    let services = res.unwrap().response.unwrap_or_default();
+    assert_eq!(services.len(), 1);
+    assert_eq!(services[0].gateway.name, gateway);
+    assert_eq!(services[0].gateway_kind, GatewayKind::TerminatingGateway);
+    assert_eq!(services[0].service.name, "billing");
+    assert_eq!(services[0].sni, Some("billing.example.com".into()));
 }
 
 async fn test_node(client: &impl Client, node: &str) {
@@ -58,11 +131,103 @@ async fn test_node(client: &impl Client, node: &str) {
     assert!(res.is_ok());
 }
 
+async fn test_node_filter(client: &impl Client, node: &str) {
+    let res = catalog::node(
+        client,
+        node,
+        Some(ListNodeServicesRequest::builder().filter(Filter::selector("Service").eq("consul"))),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let services = res.unwrap().response.services;
+    assert!(!services.is_empty());
+    assert!(services
+        .iter()
+        .all(|s| s.service.as_deref() == Some("consul")));
+
+    let res = catalog::node(
+        client,
+        node,
+        Some(ListNodeServicesRequest::builder().filter(Filter::selector("Service").eq("missing"))),
+    )
+    .await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response.services.is_empty());
+}
+
 async fn test_nodes(client: &impl Client) {
     let res = catalog::nodes(client, None).await;
     assert!(res.is_ok());
 }
 
+async fn test_nodes_consistency(client: &impl Client) {
+    let res = catalog::nodes(
+        client,
+        Some(ListNodesRequest::builder().consistency(ConsistencyMode::STALE)),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let res = res.unwrap();
+    assert_eq!(res.has_leader(), Some(true));
+    assert!(res.staleness().is_some());
+}
+
+async fn test_nodes_default_options(server: &ConsulServer) {
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(server.external_url())
+            .default_query_options(
+                QueryOptions::builder()
+                    .consistency(ConsistencyMode::STALE)
+                    .dc("dc1")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let res = catalog::nodes(
+        &client,
+        Some(ListNodesRequest::builder().consistency(ConsistencyMode::DEFAULT)),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let res = catalog::nodes(&client, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().staleness().is_some());
+}
+
+async fn test_nodes_filter(client: &impl Client, node: &str) {
+    let res = catalog::nodes(
+        client,
+        Some(
+            ListNodesRequest::builder().filter(
+                Filter::selector("Node")
+                    .eq(node)
+                    .or(Filter::selector("Node").eq("missing")),
+            ),
+        ),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let nodes = res.unwrap().response;
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].node, node);
+
+    let res = catalog::nodes(
+        client,
+        Some(ListNodesRequest::builder().filter(Filter::selector("Node").eq(node).not())),
+    )
+    .await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response.is_empty());
+}
+
 async fn test_nodes_with_service(client: &impl Client, service: &str) {
     let res = catalog::nodes_with_service(client, service, None).await;
     assert!(res.is_ok());
@@ -91,3 +256,11 @@ async fn test_services(client: &impl Client) {
     let res = catalog::services(client, None).await;
     assert!(res.is_ok());
 }
+
+async fn test_watch(client: &impl Client, service: &str) {
+    let stream = catalog::watch(client, service, None);
+    futures::pin_mut!(stream);
+
+    let res = stream.next().await.unwrap();
+    assert!(res.is_ok());
+}
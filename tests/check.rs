@@ -1,7 +1,17 @@
 mod common;
 
 use common::{ConsulServer, ConsulServerHelper, CountingServer};
-use consulrs::{api::check::requests::RegisterCheckRequest, check, client::Client};
+use std::{sync::Arc, time::Duration};
+
+use consulrs::{
+    api::check::{
+        common::CheckKind,
+        requests::{RegisterCheckRequest, TtlCheckPassRequest},
+    },
+    check::{self, CheckKeepAlive},
+    client::{Client, ConsulClient},
+    error::ClientError,
+};
 use test_env_log::test;
 
 #[test]
@@ -15,12 +25,17 @@ fn test() {
         let name = "test";
 
         test_register(&client, name).await;
+        test_register_http(&client, &counting.internal_url()).await;
+        test_register_invalid(&client).await;
+        test_register_kind(&client).await;
         test_list(&client).await;
         test_fail(&client, name).await;
         test_pass(&client, name).await;
+        test_pass_with_note(&client, name).await;
         test_warn(&client, name).await;
         test_set_status(&client, name, "critical").await;
         test_deregister(&client, name).await;
+        test_keep_alive(Arc::new(client)).await;
     });
 }
 
@@ -34,6 +49,31 @@ async fn test_fail(client: &impl Client, name: &str) {
     assert!(res.is_ok());
 }
 
+async fn test_keep_alive(client: Arc<ConsulClient>) {
+    let res = check::register(
+        client.as_ref(),
+        "keepalive",
+        Some(&mut RegisterCheckRequest::ttl("2s")),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let mut keep_alive = CheckKeepAlive::start(client.clone(), "keepalive", Duration::from_secs(2));
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    assert!(keep_alive.failures().try_recv().is_err());
+
+    let res = check::list(client.as_ref(), None).await;
+    assert!(res.is_ok());
+    assert_eq!(
+        res.unwrap().response["keepalive"].status.as_deref(),
+        Some("passing")
+    );
+
+    keep_alive.pause();
+    assert!(keep_alive.is_paused());
+    keep_alive.stop();
+}
+
 async fn test_list(client: &impl Client) {
     let res = check::list(client, None).await;
     assert!(res.is_ok());
@@ -44,6 +84,23 @@ async fn test_pass(client: &impl Client, name: &str) {
     assert!(res.is_ok());
 }
 
+async fn test_pass_with_note(client: &impl Client, name: &str) {
+    let res = check::pass(
+        client,
+        name,
+        Some(TtlCheckPassRequest::builder().note("testing")),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let res = check::list(client, None).await;
+    assert!(res.is_ok());
+    assert_eq!(
+        res.unwrap().response[name].output.as_deref(),
+        Some("testing")
+    );
+}
+
 async fn test_set_status(client: &impl Client, name: &str, status: &str) {
     let res = check::set_status(client, name, status, None).await;
     assert!(res.is_ok());
@@ -59,6 +116,63 @@ async fn test_register(client: &impl Client, name: &str) {
     assert!(res.is_ok());
 }
 
+async fn test_register_http(client: &impl Client, url: &str) {
+    let res = check::register(
+        client,
+        "http",
+        Some(&mut RegisterCheckRequest::http(url, "10s")),
+    )
+    .await;
+    assert!(res.is_ok());
+}
+
+async fn test_register_invalid(client: &impl Client) {
+    let res = check::register(
+        client,
+        "invalid",
+        Some(RegisterCheckRequest::builder().tcp("localhost:22")),
+    )
+    .await;
+    assert!(matches!(res, Err(ClientError::InvalidCheckError { .. })));
+}
+
+async fn test_register_kind(client: &impl Client) {
+    let kinds = vec![
+        CheckKind::AliasService {
+            service: "consul".into(),
+            node: None,
+        },
+        CheckKind::Grpc {
+            address: "localhost:8502".into(),
+            interval: "10s".into(),
+            use_tls: true,
+        },
+        CheckKind::H2Ping {
+            address: "localhost:8501".into(),
+            interval: "10s".into(),
+            use_tls: false,
+        },
+    ];
+    for (i, kind) in kinds.into_iter().enumerate() {
+        let res = check::register(
+            client,
+            &format!("kind-{}", i),
+            Some(RegisterCheckRequest::builder().kind(kind)),
+        )
+        .await;
+        assert!(res.is_ok());
+    }
+
+    // Options for other kinds of check are rejected
+    let res = check::register(
+        client,
+        "invalid",
+        Some(&mut RegisterCheckRequest::tcp("localhost:22", "10s").method("POST")),
+    )
+    .await;
+    assert!(matches!(res, Err(ClientError::InvalidCheckError { .. })));
+}
+
 async fn test_warn(client: &impl Client, name: &str) {
     let res = check::warn(client, name, None).await;
     assert!(res.is_ok());
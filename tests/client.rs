@@ -0,0 +1,998 @@
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use async_trait::async_trait;
+use common::ConsulServer;
+use consulrs::{
+    catalog,
+    client::{
+        Client, ConsulClient, ConsulClientSettings, ConsulClientSettingsBuilder, ErrorClass,
+        Interceptor, MetricsRecorder, Priority, QueryOptions, RateLimit, RequestMetrics,
+        RetryPolicy, RoundRobin, ScopedClient, SelectionPolicy, ServerStatus, TokenSource,
+    },
+    error::ClientError,
+    kv, status,
+};
+use test_env_log::test;
+
+// Nothing listens on this address, so every attempt fails to connect.
+const UNREACHABLE: &str = "http://127.0.0.1:1";
+
+fn client(policy: RetryPolicy) -> ConsulClient {
+    ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(UNREACHABLE)
+            .retry(policy)
+            .build()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+fn policy() -> RetryPolicy {
+    RetryPolicy::builder()
+        .initial_backoff(Duration::from_millis(50))
+        .jitter(false)
+        .max_attempts(3u32)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_backoff() {
+    let policy = RetryPolicy::builder()
+        .initial_backoff(Duration::from_millis(100))
+        .max_backoff(Duration::from_millis(300))
+        .jitter(false)
+        .build()
+        .unwrap();
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(300));
+
+    let policy = RetryPolicy::builder()
+        .initial_backoff(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let backoff = policy.backoff(1);
+    assert!(backoff >= Duration::from_millis(50) && backoff <= Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn test_retry_reads() {
+    let client = client(policy());
+    let start = Instant::now();
+    let res = catalog::nodes(&client, None).await;
+    assert!(matches!(res, Err(ClientError::RestClientError { .. })));
+    assert!(start.elapsed() >= Duration::from_millis(150));
+}
+
+#[tokio::test]
+async fn test_no_retry_writes() {
+    let client = client(policy());
+    let start = Instant::now();
+    let res = kv::set(&client, "test", b"test", None).await;
+    assert!(res.is_err());
+    assert!(start.elapsed() < Duration::from_millis(50));
+}
+
+#[test]
+fn test_failover() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = ConsulClient::new(
+            ConsulClientSettingsBuilder::default()
+                .address(UNREACHABLE)
+                .addresses(vec![server.external_url()])
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let res = catalog::nodes(&client, None).await;
+        assert!(res.is_ok());
+
+        let statuses = client.pool().unwrap().statuses();
+        assert!(!statuses[0].healthy);
+        assert!(statuses[1].healthy);
+    });
+}
+
+#[derive(Debug, Default)]
+struct CountingInterceptor {
+    errors: Arc<AtomicUsize>,
+    requests: Arc<AtomicUsize>,
+}
+
+impl Interceptor for CountingInterceptor {
+    fn on_request(&self, req: &mut http::Request<Vec<u8>>) {
+        req.headers_mut()
+            .insert("X-Test", http::HeaderValue::from_static("test"));
+        self.requests.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn on_response(&self, res: &http::Response<Vec<u8>>) {
+        if !res.status().is_success() {
+            self.errors.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct RecordingMetrics {
+    requests: Arc<Mutex<Vec<RequestMetrics>>>,
+}
+
+impl MetricsRecorder for RecordingMetrics {
+    fn record(&self, request: &RequestMetrics) {
+        self.requests.lock().unwrap().push(request.clone());
+    }
+}
+
+#[tokio::test]
+async fn test_metrics_retries() {
+    let recorder = RecordingMetrics::default();
+    let requests = recorder.requests.clone();
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(UNREACHABLE)
+            .metrics(recorder)
+            .retry(policy())
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let res = catalog::nodes(&client, None).await;
+    assert!(res.is_err());
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].attempts, 3);
+    assert_eq!(requests[0].endpoint, "ListNodesRequest");
+    assert_eq!(requests[0].error, Some(ErrorClass::Connection));
+    assert!(requests[0].latency >= Duration::from_millis(150));
+}
+
+#[test]
+fn test_interceptor() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let interceptor = CountingInterceptor::default();
+        let errors = interceptor.errors.clone();
+        let requests = interceptor.requests.clone();
+        let client = ConsulClient::new(
+            ConsulClientSettingsBuilder::default()
+                .address(server.external_url())
+                .interceptor(interceptor)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let res = catalog::nodes(&client, None).await;
+        assert!(res.is_ok());
+        let res = kv::read(&client, "missing", None).await;
+        assert!(res.is_err());
+
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        assert_eq!(errors.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn test_selection() {
+    let statuses = vec![
+        ServerStatus {
+            address: "a".into(),
+            healthy: false,
+        },
+        ServerStatus {
+            address: "b".into(),
+            healthy: true,
+        },
+        ServerStatus {
+            address: "c".into(),
+            healthy: true,
+        },
+    ];
+    assert_eq!(Priority.order(&statuses), vec![1, 2, 0]);
+
+    let policy = RoundRobin::default();
+    assert_eq!(policy.order(&statuses), vec![1, 2, 0]);
+    assert_eq!(policy.order(&statuses), vec![2, 1, 0]);
+    assert_eq!(policy.order(&statuses), vec![1, 2, 0]);
+}
+
+/// Serves a fake agent on a unix socket, which answers every request with a
+/// leader address and the status code returned by `status` for the request.
+#[cfg(unix)]
+fn fake_agent(name: &str, status: fn(&str) -> u16) -> (std::path::PathBuf, String) {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::UnixListener,
+    };
+
+    let path = std::env::temp_dir().join(format!("consulrs-{}-{}.sock", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut req = Vec::new();
+            let mut buf = [0; 1024];
+            while !req.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                req.extend_from_slice(&buf[..n]);
+            }
+
+            let body = "\"127.0.0.1:8300\"";
+            let status = status(std::str::from_utf8(&req).unwrap());
+            let retry_after = if status == 429 {
+                "Retry-After: 1\r\n"
+            } else {
+                ""
+            };
+            let res = format!(
+                "HTTP/1.1 {} \r\nContent-Type: application/json\r\nContent-Length: {}\r\nX-Cache: HIT\r\nAge: 5\r\nX-Consul-Results-Filtered-By-ACLs: true\r\n{}\r\n{}",
+                status,
+                body.len(),
+                retry_after,
+                body
+            );
+            stream.write_all(res.as_bytes()).await.unwrap();
+        }
+    });
+
+    let address = format!("unix://{}", path.display());
+    (path, address)
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_unix_socket() {
+    use futures::StreamExt;
+
+    let (path, address) = fake_agent("unix", |req| {
+        assert!(req.starts_with("GET /v1/"));
+        200
+    });
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(address.as_str())
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(client.pool().unwrap().statuses()[0].address, address);
+
+    let res = status::leader(&client, None).await;
+    assert!(res.is_ok());
+    let res = res.unwrap();
+    assert_eq!(res.response, "127.0.0.1:8300");
+    assert_eq!(res.header("content-type"), Some("application/json"));
+    assert_eq!(res.filtered_by_acls(), Some(true));
+
+    let res = kv::read_stream(&client, "test", None).await;
+    assert!(res.is_ok());
+    let chunks: Vec<_> = res.unwrap().response.collect().await;
+    let body: Vec<u8> = chunks.into_iter().flat_map(|c| c.unwrap()).collect();
+    assert_eq!(body, b"\"127.0.0.1:8300\"");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_from_env() {
+    let token = std::env::temp_dir().join(format!("consulrs-{}.token", std::process::id()));
+    std::fs::write(&token, "file-token\n").unwrap();
+
+    std::env::set_var("CONSUL_HTTP_ADDR", "consul.example.com:8501");
+    std::env::set_var("CONSUL_HTTP_SSL", "1");
+    std::env::set_var("CONSUL_HTTP_SSL_VERIFY", "f");
+    std::env::set_var("CONSUL_HTTP_TOKEN", "env-token");
+    std::env::set_var("CONSUL_HTTP_TOKEN_FILE", &token);
+    std::env::set_var("CONSUL_NAMESPACE", "team");
+
+    let settings = ConsulClientSettings::from_env();
+
+    for name in [
+        "CONSUL_HTTP_ADDR",
+        "CONSUL_HTTP_SSL",
+        "CONSUL_HTTP_SSL_VERIFY",
+        "CONSUL_HTTP_TOKEN",
+        "CONSUL_HTTP_TOKEN_FILE",
+        "CONSUL_NAMESPACE",
+    ] {
+        std::env::remove_var(name);
+    }
+    std::fs::remove_file(&token).unwrap();
+
+    let settings = settings.unwrap();
+    assert_eq!(settings.address, "https://consul.example.com:8501");
+    assert_eq!(settings.token.as_deref(), Some("env-token"));
+    assert_eq!(
+        settings.token_file,
+        Some(token.to_str().unwrap().to_string())
+    );
+    assert!(!settings.verify);
+    assert_eq!(settings.default_query_options.ns.as_deref(), Some("team"));
+}
+
+#[test]
+fn test_token_file() {
+    let path = std::env::temp_dir().join(format!("consulrs-{}.rotate", std::process::id()));
+    std::fs::write(&path, "first\n").unwrap();
+
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(UNREACHABLE)
+            .token("static")
+            .token_file(path.to_str().unwrap())
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(client.middle(None).token.as_deref(), Some("first"));
+
+    // Rotating the token changes the modification time of the file
+    std::fs::write(&path, "second\n").unwrap();
+    let file = std::fs::File::options().write(true).open(&path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(client.middle(None).token.as_deref(), Some("second"));
+
+    // The last token read is kept when the file goes missing
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(client.middle(None).token.as_deref(), Some("second"));
+}
+
+#[derive(Debug, Default)]
+struct RotatingTokenSource {
+    refreshes: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl TokenSource for RotatingTokenSource {
+    async fn token(&self) -> Result<String, ClientError> {
+        match self.refreshes.load(Ordering::SeqCst) {
+            0 => Ok("expired".into()),
+            _ => Ok("rotated".into()),
+        }
+    }
+
+    async fn refresh(&self) -> Result<String, ClientError> {
+        self.refreshes.fetch_add(1, Ordering::SeqCst);
+        self.token().await
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_token_source() {
+    // Rejects every request which doesn't carry the rotated token
+    let (path, address) = fake_agent("token", |req| {
+        match req.contains("x-consul-token: rotated\r\n") {
+            true => 200,
+            false => 403,
+        }
+    });
+    let source = RotatingTokenSource::default();
+    let refreshes = source.refreshes.clone();
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(address.as_str())
+            .token_source(source)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let res = status::leader(&client, None).await;
+    assert!(res.is_ok());
+    assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+
+    let res = status::leader(&client, None).await;
+    assert!(res.is_ok());
+    assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_cached() {
+    use consulrs::api::{features::CacheOptions, status::requests::GetLeaderRequest};
+
+    let (path, address) = fake_agent("cached", |req| {
+        let req = req.to_lowercase();
+        if req.starts_with("get /v1/status/leader?cached ")
+            && req.contains("cache-control: max-age=30, stale-if-error=60\r\n")
+        {
+            200
+        } else {
+            400
+        }
+    });
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(address.as_str())
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let res = status::leader(
+        &client,
+        Some(
+            GetLeaderRequest::builder().cached(
+                CacheOptions::builder()
+                    .max_age(Duration::from_secs(30))
+                    .stale_if_error(Duration::from_secs(60))
+                    .build()
+                    .unwrap(),
+            ),
+        ),
+    )
+    .await;
+    assert!(res.is_ok());
+    let res = res.unwrap();
+    assert_eq!(res.cache_hit(), Some(true));
+    assert_eq!(res.cache_age(), Some(Duration::from_secs(5)));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_api_error() {
+    let (path, address) = fake_agent("error", |_| 403);
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(address.as_str())
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let res = status::leader(&client, None).await;
+    let err = res.unwrap_err();
+    assert_eq!(err.status(), Some(403));
+    assert_eq!(err.message(), Some("\"127.0.0.1:8300\""));
+    assert!(err.is_acl_denied());
+    assert!(!err.is_no_leader());
+    assert!(!err.is_not_found());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(all(unix, feature = "otel"))]
+#[tokio::test]
+async fn test_trace_context() {
+    use consulrs::otel::TraceContext;
+    use opentelemetry::{
+        propagation::{text_map_propagator::FieldIter, Extractor, Injector, TextMapPropagator},
+        Context,
+    };
+
+    // Writes a fixed header, standing in for a real propagator
+    #[derive(Debug)]
+    struct FixedPropagator(Vec<String>);
+
+    impl TextMapPropagator for FixedPropagator {
+        fn inject_context(&self, _: &Context, injector: &mut dyn Injector) {
+            injector.set(
+                "traceparent",
+                "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".into(),
+            );
+        }
+
+        fn extract_with_context(&self, cx: &Context, _: &dyn Extractor) -> Context {
+            cx.clone()
+        }
+
+        fn fields(&self) -> FieldIter<'_> {
+            FieldIter::new(&self.0)
+        }
+    }
+
+    opentelemetry::global::set_text_map_propagator(FixedPropagator(vec!["traceparent".into()]));
+    let (path, address) = fake_agent("otel", |req| {
+        if req.contains("traceparent: 00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01") {
+            200
+        } else {
+            400
+        }
+    });
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(address.as_str())
+            .interceptor(TraceContext)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let res = status::leader(&client, None).await;
+    assert!(res.is_ok());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_metrics() {
+    let (path, address) = fake_agent("metrics", |req| {
+        if req.starts_with("GET /v1/status/leader") {
+            200
+        } else {
+            404
+        }
+    });
+    let recorder = RecordingMetrics::default();
+    let requests = recorder.requests.clone();
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(address.as_str())
+            .metrics(recorder)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let res = status::leader(&client, None).await;
+    assert!(res.is_ok());
+    let res = status::peers(&client, None).await;
+    assert!(res.is_err());
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].attempts, 1);
+    assert_eq!(requests[0].endpoint, "GetLeaderRequest");
+    assert_eq!(requests[0].error, None);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "status/leader");
+    assert_eq!(requests[1].endpoint, "ListPeersRequest");
+    assert_eq!(requests[1].error, Some(ErrorClass::NotFound));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[derive(Debug, Default)]
+struct InFlightInterceptor {
+    current: AtomicUsize,
+    max: Arc<AtomicUsize>,
+}
+
+impl Interceptor for InFlightInterceptor {
+    fn on_request(&self, _: &mut http::Request<Vec<u8>>) {
+        let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max.fetch_max(current, Ordering::SeqCst);
+    }
+
+    fn on_response(&self, _: &http::Response<Vec<u8>>) {
+        self.current.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_max_in_flight() {
+    let (path, address) = fake_agent("in-flight", |_| 200);
+    let interceptor = InFlightInterceptor::default();
+    let max = interceptor.max.clone();
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(address.as_str())
+            .interceptor(interceptor)
+            .max_in_flight(1usize)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let results = futures::future::join_all((0..5).map(|_| status::leader(&client, None))).await;
+    assert!(results.iter().all(|r| r.is_ok()));
+    assert_eq!(max.load(Ordering::SeqCst), 1);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_rate_limit() {
+    let (path, address) = fake_agent("rate-limit", |_| 200);
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(address.as_str())
+            .rate_limit(
+                RateLimit::builder()
+                    .burst(2u32)
+                    .per_second(20.0)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    // The first two requests use up the burst, the next two wait 50ms each
+    let start = Instant::now();
+    for _ in 0..4 {
+        status::leader(&client, None).await.unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(90));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_invalid_limits() {
+    for per_second in &[0.0, -1.0, f64::NAN] {
+        let res = ConsulClient::new(
+            ConsulClientSettingsBuilder::default()
+                .address("http://127.0.0.1:8500")
+                .rate_limit(
+                    RateLimit::builder()
+                        .per_second(*per_second)
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        );
+        assert!(matches!(res, Err(ClientError::InvalidSettingsError { .. })));
+    }
+
+    let res = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address("http://127.0.0.1:8500")
+            .max_in_flight(0usize)
+            .build()
+            .unwrap(),
+    );
+    assert!(matches!(res, Err(ClientError::InvalidSettingsError { .. })));
+}
+
+// Accepts connections without ever responding.
+#[cfg(unix)]
+fn silent_agent(name: &str) -> (std::path::PathBuf, String) {
+    use tokio::net::UnixListener;
+
+    let path = std::env::temp_dir().join(format!("consulrs-{}-{}.sock", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).unwrap();
+
+    tokio::spawn(async move {
+        let mut streams = Vec::new();
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            streams.push(stream);
+        }
+    });
+
+    let address = format!("unix://{}", path.display());
+    (path, address)
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_timeouts() {
+    use consulrs::api::{
+        features::{Blocking, Features},
+        kv::requests::ReadKeyRequest,
+        status::requests::GetLeaderRequest,
+    };
+
+    let (path, address) = silent_agent("timeouts");
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(address.as_str())
+            .blocking_timeout(Duration::from_millis(300))
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let start = Instant::now();
+    let res = status::leader(&client, None).await;
+    assert!(matches!(res, Err(ClientError::TimeoutError { .. })));
+    assert!(start.elapsed() < Duration::from_millis(300));
+
+    let start = Instant::now();
+    let res = status::leader(
+        &client,
+        Some(GetLeaderRequest::builder().request_timeout(Duration::from_millis(150))),
+    )
+    .await;
+    assert!(matches!(res, Err(ClientError::TimeoutError { .. })));
+    assert!(start.elapsed() >= Duration::from_millis(150));
+
+    let start = Instant::now();
+    let features = Features::builder()
+        .blocking(Blocking {
+            index: 1,
+            wait: None,
+        })
+        .build()
+        .unwrap();
+    let res = kv::read(
+        &client,
+        "test",
+        Some(ReadKeyRequest::builder().features(features)),
+    )
+    .await;
+    assert!(matches!(res, Err(ClientError::TimeoutError { .. })));
+    assert!(start.elapsed() >= Duration::from_millis(300));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_scoped_client() {
+    use consulrs::{api::kv::requests::ReadRawKeyRequest, client::Scope};
+
+    let (path, address) = fake_agent("scoped", |req| {
+        let line = req.lines().next().unwrap();
+        let scoped = line.contains("ns=team") && line.contains("partition=tenant");
+        match (scoped, line.contains("dc=dc1"), line.contains("dc=dc2")) {
+            (true, true, false) | (true, false, true) => 200,
+            _ => 400,
+        }
+    });
+    let client = Arc::new(
+        ConsulClient::new(
+            ConsulClientSettingsBuilder::default()
+                .address(address.as_str())
+                .default_query_options(QueryOptions {
+                    dc: Some("dc1".into()),
+                    ns: Some("default".into()),
+                    ..Default::default()
+                })
+                .build()
+                .unwrap(),
+        )
+        .unwrap(),
+    );
+    let scoped = ScopedClient::new(
+        client.clone(),
+        Scope::builder()
+            .ns("team")
+            .partition("tenant")
+            .build()
+            .unwrap(),
+    );
+    assert_eq!(
+        scoped.settings().default_query_options.dc.as_deref(),
+        Some("dc1")
+    );
+
+    let res = status::leader(&scoped, None).await;
+    assert!(res.is_ok());
+    let res = kv::read_raw(
+        &scoped,
+        "test",
+        Some(ReadRawKeyRequest::builder().dc("dc2")),
+    )
+    .await;
+    assert!(res.is_ok());
+    let res = status::leader(client.as_ref(), None).await;
+    assert_eq!(res.unwrap_err().status(), Some(400));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_token_redacted() {
+    let settings = ConsulClientSettingsBuilder::default()
+        .address(UNREACHABLE)
+        .token("secret-token".to_string())
+        .build()
+        .unwrap();
+    assert!(!format!("{:?}", settings).contains("secret-token"));
+    let client = ConsulClient::new(settings).unwrap();
+    assert!(!format!("{:?}", client.middle(None)).contains("secret-token"));
+}
+
+/// Adds an ACL token to the query string of every request.
+#[derive(Debug)]
+struct QueryTokenInterceptor;
+
+impl Interceptor for QueryTokenInterceptor {
+    fn on_request(&self, req: &mut http::Request<Vec<u8>>) {
+        let uri = req.uri().to_string();
+        let uri = format!("{}?token=secret", uri.trim_end_matches('?'));
+        *req.uri_mut() = uri.parse().unwrap();
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_token_header() {
+    let (path, address) = fake_agent("token", |req| {
+        let req = req.to_lowercase();
+        match (
+            req.contains("token="),
+            req.contains("x-consul-token: secret"),
+        ) {
+            (false, true) => 200,
+            _ => 400,
+        }
+    });
+    for enforce in [false, true] {
+        let client = ConsulClient::new(
+            ConsulClientSettingsBuilder::default()
+                .address(address.as_str())
+                .interceptor(QueryTokenInterceptor)
+                .enforce_token_header(enforce)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let res = status::leader(&client, None).await;
+        match enforce {
+            false => assert!(res.is_ok()),
+            true => assert!(matches!(res, Err(ClientError::TokenInQueryError))),
+        }
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_request_id() {
+    use consulrs::api::status::requests::GetLeaderRequest;
+
+    let (path, address) = fake_agent("request-id", |req| {
+        let req = req.to_lowercase();
+        if req.contains("x-request-id: given\r\n") {
+            200
+        } else if req.contains("x-correlation-id: ") {
+            500
+        } else {
+            400
+        }
+    });
+    let client = |header: Option<&str>| {
+        let mut settings = ConsulClientSettingsBuilder::default();
+        settings.address(address.as_str());
+        if let Some(header) = header {
+            settings.request_id_header(header);
+        }
+        ConsulClient::new(settings.build().unwrap()).unwrap()
+    };
+
+    let res = status::leader(&client(None), None).await;
+    assert_eq!(res.unwrap_err().request_id(), None);
+    let res = status::leader(
+        &client(None),
+        Some(GetLeaderRequest::builder().request_id("given")),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let res = status::leader(&client(Some("X-Correlation-Id")), None).await;
+    let err = res.unwrap_err();
+    assert_eq!(err.status(), Some(500));
+    let id = err.request_id().unwrap();
+    assert_eq!(id.len(), 36);
+    assert_eq!(&id[14..15], "4");
+    assert!(err.to_string().contains(id));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_retry_after() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    // Only the second request succeeds
+    let (path, address) = fake_agent("retry-after", |_| {
+        match CALLS.fetch_add(1, Ordering::SeqCst) {
+            1 => 200,
+            _ => 429,
+        }
+    });
+    let client = |max_attempts: u32| {
+        ConsulClient::new(
+            ConsulClientSettingsBuilder::default()
+                .address(address.as_str())
+                .retry(
+                    RetryPolicy::builder()
+                        .max_attempts(max_attempts)
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    let start = Instant::now();
+    let res = status::leader(&client(2), None).await;
+    assert!(res.is_ok());
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+
+    let res = status::leader(&client(1), None).await;
+    let err = res.unwrap_err();
+    assert!(err.is_rate_limited());
+    assert_eq!(err.retry_after(), Some(Duration::from_secs(1)));
+    assert_eq!(ErrorClass::of(&err), ErrorClass::RateLimited);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_connection_pool() {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    // Counts the connections made, answering every request on each of them
+    async fn agent() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = connections.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buf = [0; 1024];
+                    while let Ok(n) = stream.read(&mut buf).await {
+                        if n == 0 {
+                            break;
+                        }
+                        let body = "\"127.0.0.1:8300\"";
+                        let res = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        stream.write_all(res.as_bytes()).await.unwrap();
+                    }
+                });
+            }
+        });
+        (address, connections)
+    }
+
+    for (max_idle, expected) in [(None, 1), (Some(0usize), 2)] {
+        let (address, connections) = agent().await;
+        let mut settings = ConsulClientSettingsBuilder::default();
+        settings
+            .address(address.as_str())
+            .http2(false)
+            .pool_idle_timeout(Duration::from_secs(60))
+            .tcp_keepalive(Duration::from_secs(30));
+        if let Some(max_idle) = max_idle {
+            settings.pool_max_idle_per_host(max_idle);
+        }
+        let client = ConsulClient::new(settings.build().unwrap()).unwrap();
+
+        for _ in 0..2 {
+            status::leader(&client, None).await.unwrap();
+        }
+        assert_eq!(connections.load(Ordering::SeqCst), expected);
+    }
+}
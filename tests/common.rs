@@ -16,6 +16,8 @@ pub use dockertest_server::servers::hashi::{
 };
 use dockertest_server::Test;
 
+pub const ACL_TOKEN: &str = "root";
+pub const ENCRYPT_KEY: &str = "Y29uc3VscnMtdGVzdC1nb3NzaXAtZW5jcnlwdC1rZXk=";
 pub const CHECK_NAME: &str = "health";
 pub const CONSUL_PORT: u32 = 9500;
 pub const COUNTING_PORT: u32 = 9100;
@@ -27,6 +29,11 @@ pub trait ConsulServerHelper {
     /// Returns a [ConsulClient] configured to connect to the [ConsulServer].
     fn client(&self) -> ConsulClient;
 
+    /// Returns a [ConsulClient] configured to connect to the [ConsulServer]
+    /// using the initial management token.
+    #[allow(dead_code)]
+    fn acl_client(&self) -> ConsulClient;
+
     /// Returns the node ID for the default node of the [ConsulServer].
     async fn node(&self) -> String;
 }
@@ -42,6 +49,16 @@ impl ConsulServerHelper for ConsulServer {
         )
         .unwrap()
     }
+    fn acl_client(&self) -> ConsulClient {
+        ConsulClient::new(
+            ConsulClientSettingsBuilder::default()
+                .address(self.external_url())
+                .token(ACL_TOKEN.to_string())
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+    }
     async fn node(&self) -> String {
         let res = catalog::nodes(&self.client(), None).await;
         res.unwrap().response.pop().unwrap().node
@@ -72,6 +89,40 @@ pub fn new_test() -> Test {
     test
 }
 
+// Sets up a new test with ACLs enabled.
+#[allow(dead_code)]
+pub fn new_acl_test() -> Test {
+    new_config_test(serde_json::json!({
+        "acl": {
+            "enabled": true,
+            "default_policy": "allow",
+            "tokens": { "master": ACL_TOKEN }
+        }
+    }))
+}
+
+// Sets up a new test with gossip encryption enabled.
+#[allow(dead_code)]
+pub fn new_encrypt_test() -> Test {
+    new_config_test(serde_json::json!({ "encrypt": ENCRYPT_KEY }))
+}
+
+// Sets up a new test using the given local agent configuration.
+#[allow(dead_code)]
+fn new_config_test(config: serde_json::Value) -> Test {
+    let mut test = Test::default();
+    let mut env = HashMap::new();
+    env.insert("CONSUL_LOCAL_CONFIG".to_string(), config.to_string());
+    let consul_config = ConsulServerConfig::builder()
+        .port(CONSUL_PORT)
+        .version(VERSION.into())
+        .env(env)
+        .build()
+        .unwrap();
+    test.register(consul_config);
+    test
+}
+
 #[allow(dead_code)]
 pub async fn setup(client: &impl Client, counting: &CountingServer) -> TestService {
     // Setup test service
@@ -0,0 +1,112 @@
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::config::{
+        common::{
+            ConfigEntry, ConfigEntryKind, ServiceConfigEntryBuilder,
+            ServiceResolverConfigEntryBuilder, ServiceSplitBuilder,
+            ServiceSplitterConfigEntryBuilder,
+        },
+        requests::ApplyConfigRequest,
+    },
+    client::Client,
+    config,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+
+        test_apply(&client).await;
+        test_apply_cas(&client).await;
+        test_get(&client).await;
+        test_list(&client).await;
+        test_resolver(&client).await;
+        test_splitter(&client).await;
+        test_delete(&client).await;
+    });
+}
+
+async fn test_apply(client: &impl Client) {
+    let entry = ServiceConfigEntryBuilder::default()
+        .name("test")
+        .protocol("http")
+        .build()
+        .unwrap();
+    let res = config::apply(client, ConfigEntry::ServiceDefaults(entry), None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response);
+}
+
+async fn test_apply_cas(client: &impl Client) {
+    let entry = ServiceConfigEntryBuilder::default()
+        .name("test")
+        .protocol("http")
+        .build()
+        .unwrap();
+    let res = config::apply(
+        client,
+        ConfigEntry::ServiceDefaults(entry),
+        Some(ApplyConfigRequest::builder().cas(1u64)),
+    )
+    .await;
+    assert!(res.is_ok());
+    assert!(!res.unwrap().response);
+}
+
+async fn test_delete(client: &impl Client) {
+    let res = config::delete(client, ConfigEntryKind::ServiceSplitter, "test", None).await;
+    assert!(res.is_ok());
+
+    let res = config::list(client, ConfigEntryKind::ServiceSplitter, None).await;
+    assert!(res.unwrap().response.is_empty());
+}
+
+async fn test_get(client: &impl Client) {
+    let res = config::get(client, ConfigEntryKind::ServiceDefaults, "test", None).await;
+    assert!(res.is_ok());
+
+    let entry = res.unwrap().response;
+    assert_eq!(entry.kind(), ConfigEntryKind::ServiceDefaults);
+    match entry {
+        ConfigEntry::ServiceDefaults(e) => {
+            assert_eq!(e.name, "test");
+            assert_eq!(e.protocol, Some("http".into()));
+        }
+        _ => panic!("unexpected config entry kind"),
+    }
+}
+
+async fn test_list(client: &impl Client) {
+    let res = config::list(client, ConfigEntryKind::ServiceDefaults, None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.len(), 1);
+}
+
+async fn test_resolver(client: &impl Client) {
+    let entry = ServiceResolverConfigEntryBuilder::default()
+        .name("test")
+        .connect_timeout("15s")
+        .build()
+        .unwrap();
+    let res = config::apply(client, ConfigEntry::ServiceResolver(entry), None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_splitter(client: &impl Client) {
+    let entry = ServiceSplitterConfigEntryBuilder::default()
+        .name("test")
+        .splits(vec![ServiceSplitBuilder::default()
+            .weight(100.0)
+            .build()
+            .unwrap()])
+        .build()
+        .unwrap();
+    let res = config::apply(client, ConfigEntry::ServiceSplitter(entry), None).await;
+    assert!(res.is_ok());
+}
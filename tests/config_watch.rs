@@ -0,0 +1,107 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{client::ConsulClient, config_watch::ConfigSource, kv};
+use serde::{Deserialize, Serialize};
+use test_env_log::test;
+use tokio::sync::mpsc;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Database {
+    host: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Config {
+    db: Database,
+    name: String,
+    port: u16,
+}
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = Arc::new(server.client());
+
+        test_from_key(client.clone()).await;
+        test_from_prefix(client.clone()).await;
+        test_missing(client).await;
+    });
+}
+
+async fn test_from_key(client: Arc<ConsulClient>) {
+    let key = "config/service";
+    let config = Config {
+        db: Database {
+            host: "localhost".into(),
+        },
+        name: "service".into(),
+        port: 8080,
+    };
+    let res = kv::set_json(client.as_ref(), key, &config, None).await;
+    assert!(res.is_ok());
+
+    let res = ConfigSource::<Config>::from_key(client.clone(), key).await;
+    assert!(res.is_ok());
+    let mut source = res.unwrap();
+    assert_eq!(*source.get(), config);
+
+    let (tx, mut changes) = mpsc::unbounded_channel();
+    source.on_change(move |c| {
+        let _ = tx.send(c);
+    });
+    let rx = source.subscribe();
+
+    let config = Config {
+        port: 9090,
+        ..config
+    };
+    let res = kv::set_json(client.as_ref(), key, &config, None).await;
+    assert!(res.is_ok());
+
+    let res = tokio::time::timeout(Duration::from_secs(5), changes.recv()).await;
+    assert_eq!(*res.unwrap().unwrap(), config);
+    assert!(rx.has_changed().unwrap());
+    assert_eq!(*source.get(), config);
+
+    let res = kv::set(client.as_ref(), key, b"invalid", None).await;
+    assert!(res.is_ok());
+
+    let res = tokio::time::timeout(Duration::from_secs(5), source.errors().recv()).await;
+    assert!(res.unwrap().is_some());
+    assert_eq!(*source.get(), config);
+}
+
+async fn test_from_prefix(client: Arc<ConsulClient>) {
+    let prefix = "prefix/";
+    for (key, value) in [
+        ("name", "service"),
+        ("port", "8080"),
+        ("db/host", "localhost"),
+    ] {
+        let res = kv::set(client.as_ref(), &format!("{}{}", prefix, key), value, None).await;
+        assert!(res.is_ok());
+    }
+
+    let res = ConfigSource::<Config>::from_prefix(client, prefix).await;
+    assert!(res.is_ok());
+    assert_eq!(
+        *res.unwrap().get(),
+        Config {
+            db: Database {
+                host: "localhost".into()
+            },
+            name: "service".into(),
+            port: 8080,
+        }
+    );
+}
+
+async fn test_missing(client: Arc<ConsulClient>) {
+    let res = ConfigSource::<Config>::from_key(client, "missing").await;
+    assert!(res.is_err());
+}
@@ -0,0 +1,264 @@
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::{
+        connect::{
+            common::{IntentionAction, IntentionBuilder, IntentionMatchType},
+            requests::{UpdateConfigRequest, UpsertIntentionRequest},
+        },
+        service::{
+            common::{AgentServiceConnectBuilder, AgentServiceConnectProxyBuilder},
+            requests::RegisterServiceRequest,
+        },
+    },
+    client::Client,
+    connect::{
+        self, ca,
+        intention::{self, IntentionGraph},
+        proxy::{self, BootstrapOptions},
+    },
+    service,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+
+        test_ca_roots(&client).await;
+        test_ca_config(&client).await;
+        test_ca_update_config(&client).await;
+        test_ca_leaf(&client, "test").await;
+
+        test_intention_upsert(&client, "web", "db").await;
+        test_intention_read(&client, "web", "db").await;
+        test_intention_list(&client).await;
+        test_intention_check(&client, "web", "db").await;
+        test_authorize(&client, "web", "db").await;
+        test_intention_match(&client, "db").await;
+        test_intention_graph(&client, "web", "db").await;
+        test_intention_delete(&client, "web", "db").await;
+
+        test_proxy_bootstrap(&client, "web").await;
+        test_upstream_address(&client, "billing").await;
+    });
+}
+
+async fn test_authorize(client: &impl Client, source: &str, destination: &str) {
+    let leaf = ca::leaf(client, source, None).await.unwrap().response;
+    let res = connect::authorize(
+        client,
+        destination,
+        &leaf.service_uri.unwrap(),
+        &leaf.serial_number.unwrap(),
+        None,
+    )
+    .await;
+    assert!(res.is_ok());
+    assert!(!res.unwrap().response.authorized);
+}
+
+async fn test_ca_config(client: &impl Client) {
+    let res = ca::config(client, None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.provider.unwrap(), "consul");
+}
+
+async fn test_ca_leaf(client: &impl Client, service: &str) {
+    let res = ca::leaf(client, service, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response.cert_pem.is_some());
+}
+
+async fn test_ca_roots(client: &impl Client) {
+    let res = ca::roots(client, None).await;
+    assert!(res.is_ok());
+    assert!(!res.unwrap().response.roots.unwrap().is_empty());
+}
+
+async fn test_ca_update_config(client: &impl Client) {
+    let config = ca::config(client, None)
+        .await
+        .unwrap()
+        .response
+        .config
+        .unwrap();
+    let res = ca::update_config(
+        client,
+        "consul",
+        Some(UpdateConfigRequest::builder().config(config)),
+    )
+    .await;
+    assert!(res.is_ok());
+}
+
+async fn test_intention_check(client: &impl Client, source: &str, destination: &str) {
+    let res = intention::check(client, source, destination, None).await;
+    assert!(res.is_ok());
+    assert!(!res.unwrap().response.allowed);
+}
+
+async fn test_intention_delete(client: &impl Client, source: &str, destination: &str) {
+    let res = intention::delete(client, source, destination, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_intention_graph(client: &impl Client, source: &str, destination: &str) {
+    let res = intention::graph(client, None).await;
+    assert!(res.is_ok());
+
+    // The test agent has ACLs disabled, so connections are allowed by default
+    let graph = res.unwrap();
+    assert_eq!(graph.default_action(), IntentionAction::Allow);
+    assert!(!graph.is_allowed(source, destination));
+    assert!(graph.sources(destination).is_empty());
+}
+
+#[test]
+fn test_intention_graph_resolution() {
+    let intention = |source: &str, destination: &str, action| {
+        IntentionBuilder::default()
+            .source_name(source)
+            .destination_name(destination)
+            .action(action)
+            .build()
+            .unwrap()
+    };
+    let graph = IntentionGraph::new(
+        vec![
+            intention("*", "db", IntentionAction::Deny),
+            intention("api", "db", IntentionAction::Allow),
+            intention("web", "*", IntentionAction::Allow),
+            intention("*", "*", IntentionAction::Deny),
+        ],
+        IntentionAction::Allow,
+    );
+
+    assert_eq!(graph.services().len(), 3);
+    assert!(graph.is_allowed("api", "db"));
+    // An intention naming the destination wins over one naming the source
+    assert!(!graph.is_allowed("web", "db"));
+    assert!(graph.is_allowed("web", "api"));
+    assert!(!graph.is_allowed("api", "web"));
+    assert_eq!(
+        graph.decisive("db", "api").unwrap().source_name.as_deref(),
+        Some("*")
+    );
+    assert_eq!(graph.sources("db"), vec!["api"]);
+    assert_eq!(graph.destinations("web"), vec!["api"]);
+
+    let graph = IntentionGraph::new(vec![], IntentionAction::Allow);
+    assert!(graph.is_allowed("web", "db"));
+    assert!(graph.decisive("web", "db").is_none());
+}
+
+async fn test_intention_list(client: &impl Client) {
+    let res = intention::list(client, None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.len(), 1);
+}
+
+async fn test_intention_match(client: &impl Client, destination: &str) {
+    let res = intention::matches(client, IntentionMatchType::Destination, destination, None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response[destination].len(), 1);
+}
+
+async fn test_intention_read(client: &impl Client, source: &str, destination: &str) {
+    let res = intention::read(client, source, destination, None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.action, Some(IntentionAction::Deny));
+}
+
+async fn test_intention_upsert(client: &impl Client, source: &str, destination: &str) {
+    let res = intention::upsert(
+        client,
+        source,
+        destination,
+        Some(
+            UpsertIntentionRequest::builder()
+                .action(IntentionAction::Deny)
+                .description("test"),
+        ),
+    )
+    .await;
+    assert!(res.is_ok());
+}
+
+async fn test_proxy_bootstrap(client: &impl Client, service: &str) {
+    let proxy_id = format!("{}-sidecar-proxy", service);
+    service::register(
+        client,
+        &proxy_id,
+        Some(
+            RegisterServiceRequest::builder()
+                .kind("connect-proxy")
+                .port(21000u64)
+                .proxy(
+                    AgentServiceConnectProxyBuilder::default()
+                        .destination_service_name(service)
+                        .build()
+                        .unwrap(),
+                ),
+        ),
+    )
+    .await
+    .unwrap();
+
+    let opts = BootstrapOptions::builder()
+        .grpc_address("127.0.0.1:8502")
+        .token("secret")
+        .build()
+        .unwrap();
+    let res = proxy::bootstrap(client, &proxy_id, &opts).await;
+    assert!(res.is_ok());
+
+    let config = res.unwrap();
+    assert_eq!(config["node"]["id"], proxy_id.as_str());
+    assert_eq!(config["node"]["cluster"], proxy_id.as_str());
+    let cluster = &config["static_resources"]["clusters"][0];
+    let address = &cluster["loaded_assignment"]["endpoints"][0]["lb_endpoints"][0]["endpoint"]
+        ["address"]["socket_address"];
+    assert_eq!(address["port_value"], 8502);
+    assert!(cluster.get("transport_socket").is_none());
+    let metadata = &config["dynamic_resources"]["ads_config"]["grpc_services"]["initial_metadata"];
+    assert_eq!(metadata[0]["value"], "secret");
+
+    service::deregister(client, &proxy_id, None).await.unwrap();
+}
+
+async fn test_upstream_address(client: &impl Client, service: &str) {
+    let res = connect::upstream_address(client, service).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_none());
+
+    service::register(
+        client,
+        service,
+        Some(
+            RegisterServiceRequest::builder()
+                .address("127.0.0.1")
+                .port(8443u64)
+                .connect(Box::new(
+                    AgentServiceConnectBuilder::default()
+                        .native(true)
+                        .build()
+                        .unwrap(),
+                )),
+        ),
+    )
+    .await
+    .unwrap();
+
+    let res = connect::upstream_address(client, service).await;
+    assert!(res.is_ok());
+    let upstream = res.unwrap().unwrap();
+    assert_eq!(upstream.address, "127.0.0.1:8443".parse().unwrap());
+    assert_eq!(upstream.target.service, service);
+
+    service::deregister(client, service, None).await.unwrap();
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "connect-native")]
+
+mod common;
+
+use std::{convert::TryInto, sync::Arc};
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::connect::{common::IntentionAction, requests::UpsertIntentionRequest},
+    client::ConsulClient,
+    connect::{intention, native::TlsProvider},
+};
+use rustls::{ClientConfig, ClientConnection, ServerConfig, ServerConnection};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = Arc::new(server.client());
+
+        test_provider(client).await;
+    });
+}
+
+async fn test_provider(client: Arc<ConsulClient>) {
+    intention::upsert(
+        client.as_ref(),
+        "web",
+        "db",
+        Some(UpsertIntentionRequest::builder().action(IntentionAction::Deny)),
+    )
+    .await
+    .unwrap();
+
+    let db = TlsProvider::new(client.clone(), "db").await;
+    assert!(db.is_ok());
+    let db = db.unwrap();
+    let api = TlsProvider::new(client.clone(), "api").await.unwrap();
+    let web = TlsProvider::new(client, "web").await.unwrap();
+    assert_eq!(db.service(), "db");
+    assert!(!db.trust_domain().is_empty());
+    assert!(!db.intentions().is_allowed("web", "db"));
+
+    // Connections are allowed by default, as the test agent has ACLs disabled
+    assert!(handshake(api.client_config("db"), db.server_config()).is_ok());
+    assert!(handshake(web.client_config("db"), db.server_config()).is_err());
+    // The server has to be the service the client expects
+    assert!(handshake(api.client_config("web"), db.server_config()).is_err());
+}
+
+/// Completes a TLS handshake between the configurations in memory.
+fn handshake(client: Arc<ClientConfig>, server: Arc<ServerConfig>) -> Result<(), rustls::Error> {
+    let mut client = ClientConnection::new(client, "localhost".try_into().unwrap())?;
+    let mut server = ServerConnection::new(server)?;
+    while client.is_handshaking() || server.is_handshaking() {
+        let mut buf = Vec::new();
+        client.write_tls(&mut buf).unwrap();
+        server.read_tls(&mut buf.as_slice()).unwrap();
+        server.process_new_packets()?;
+
+        let mut buf = Vec::new();
+        server.write_tls(&mut buf).unwrap();
+        client.read_tls(&mut buf.as_slice()).unwrap();
+        client.process_new_packets()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::coordinate::common::{Coordinate, CoordinateBuilder},
+    client::Client,
+    coordinate,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+        let node = server.node().await;
+
+        test_datacenters(&client).await;
+        test_nodes(&client).await;
+        test_update(&client, &node).await;
+        test_node(&client, &node).await;
+        test_distance();
+    });
+}
+
+fn assert_rtt(rtt: Option<Duration>, expected: f64) {
+    assert!((rtt.unwrap().as_secs_f64() - expected).abs() < 1e-6);
+}
+
+fn coord(vec: Vec<f64>, height: f64, adjustment: f64) -> Coordinate {
+    CoordinateBuilder::default()
+        .vec(vec)
+        .height(height)
+        .adjustment(adjustment)
+        .build()
+        .unwrap()
+}
+
+async fn test_datacenters(client: &impl Client) {
+    let res = coordinate::datacenters(client, None).await;
+    assert!(res.is_ok());
+}
+
+fn test_distance() {
+    let a = coord(vec![0.0, 0.3], 0.0, 0.0);
+    let b = coord(vec![0.4, 0.0], 0.1, 0.0);
+    assert_rtt(a.distance_to(&b), 0.6);
+
+    // Negative adjustments are ignored when they'd make the distance negative
+    let c = coord(vec![0.4, 0.0], 0.1, -1.0);
+    assert_rtt(a.distance_to(&c), 0.6);
+
+    let d = coord(vec![0.0], 0.0, 0.0);
+    assert_eq!(a.distance_to(&d), None);
+}
+
+async fn test_node(client: &impl Client, node: &str) {
+    let res = coordinate::node(client, node, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_nodes(client: &impl Client) {
+    let res = coordinate::nodes(client, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_update(client: &impl Client, node: &str) {
+    let res = coordinate::update(client, node, coord(vec![0.0; 8], 1.0e-5, 0.0), None).await;
+    assert!(res.is_ok());
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use std::{collections::HashSet, sync::Arc};
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::service::requests::RegisterServiceRequest,
+    client::ConsulClient,
+    discovery::{self, ServiceResolver, Strategy},
+    service,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = Arc::new(server.client());
+        let name = "billing";
+
+        for id in ["billing-1", "billing-2"] {
+            let res = service::register(
+                client.as_ref(),
+                name,
+                Some(
+                    RegisterServiceRequest::builder()
+                        .id(id)
+                        .tags(vec![id.into()]),
+                ),
+            )
+            .await;
+            assert!(res.is_ok());
+        }
+
+        test_least_recently_used(client.clone(), name).await;
+        test_missing(client.clone()).await;
+        test_random(client.clone(), name).await;
+        test_resolve(client.clone(), name).await;
+        test_round_robin(client.clone(), name).await;
+        test_tag(client.clone(), name).await;
+        #[cfg(feature = "consulrs-tower")]
+        test_connector(client.clone()).await;
+        #[cfg(feature = "consulrs-tower")]
+        test_discover(client, name).await;
+    });
+}
+
+async fn pick_ids(resolver: &ServiceResolver<ConsulClient>, count: usize) -> Vec<String> {
+    let mut ids = Vec::new();
+    for _ in 0..count {
+        let res = resolver.pick().await;
+        ids.push(res.unwrap().unwrap().service.id.unwrap());
+    }
+    ids
+}
+
+#[cfg(feature = "consulrs-tower")]
+async fn test_connector(client: Arc<ConsulClient>) {
+    use consulrs::discovery::tower::ConsulConnector;
+    use tokio::{io::AsyncWriteExt, net::TcpListener};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let _ = stream
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+            .await;
+    });
+
+    let res = service::register(
+        client.as_ref(),
+        "local",
+        Some(
+            RegisterServiceRequest::builder()
+                .address("127.0.0.1")
+                .port(port as u64),
+        ),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let http = hyper::Client::builder().build::<_, hyper::Body>(ConsulConnector::new(client));
+    let res = http.get("consul://local/".parse().unwrap()).await;
+    assert_eq!(res.unwrap().status(), 200);
+
+    let res = http.get("consul://missing/".parse().unwrap()).await;
+    assert!(res.is_err());
+}
+
+#[cfg(feature = "consulrs-tower")]
+async fn test_discover(client: Arc<ConsulClient>, name: &str) {
+    use futures::StreamExt;
+    use tower::discover::Change;
+
+    let resolver = Arc::new(ServiceResolver::new(client, name));
+    let discover = resolver.discover(|entry| entry.service.port);
+    let changes: Vec<_> = discover.take(2).collect().await;
+    assert!(changes
+        .iter()
+        .all(|c| matches!(c, Ok(Change::Insert(id, _)) if id.contains("billing-"))));
+}
+
+async fn test_least_recently_used(client: Arc<ConsulClient>, name: &str) {
+    let resolver = ServiceResolver::new(client, name).strategy(Strategy::LeastRecentlyUsed);
+    let ids = pick_ids(&resolver, 4).await;
+    assert_ne!(ids[0], ids[1]);
+    assert_eq!(ids[0], ids[2]);
+    assert_eq!(ids[1], ids[3]);
+}
+
+async fn test_missing(client: Arc<ConsulClient>) {
+    let resolver = ServiceResolver::new(client, "missing").passing(true);
+    let res = resolver.pick().await;
+    assert!(res.unwrap().is_none());
+}
+
+async fn test_random(client: Arc<ConsulClient>, name: &str) {
+    let resolver = ServiceResolver::new(client, name).strategy(Strategy::Random);
+    let ids: HashSet<String> = pick_ids(&resolver, 10).await.into_iter().collect();
+    assert!(ids.iter().all(|id| id.starts_with("billing-")));
+}
+
+async fn test_resolve(client: Arc<ConsulClient>, name: &str) {
+    let res = service::register(
+        client.as_ref(),
+        "web",
+        Some(
+            RegisterServiceRequest::builder()
+                .address("10.0.0.1")
+                .port(8080u64)
+                .tags(vec!["primary".into()]),
+        ),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let res = discovery::resolve(client.as_ref(), "web", Some("primary"), false).await;
+    assert_eq!(res.unwrap(), vec!["10.0.0.1:8080".parse().unwrap()]);
+
+    let res = discovery::resolve(client.as_ref(), "web", Some("missing"), false).await;
+    assert!(res.unwrap().is_empty());
+
+    let res = discovery::resolve(client.as_ref(), name, None, false).await;
+    assert_eq!(res.unwrap().len(), 2);
+}
+
+async fn test_round_robin(client: Arc<ConsulClient>, name: &str) {
+    let resolver = ServiceResolver::new(client, name);
+    let res = resolver.instances().await;
+    assert_eq!(res.unwrap().len(), 2);
+
+    let ids = pick_ids(&resolver, 4).await;
+    assert_ne!(ids[0], ids[1]);
+    assert_eq!(ids[0], ids[2]);
+    assert_eq!(ids[1], ids[3]);
+}
+
+async fn test_tag(client: Arc<ConsulClient>, name: &str) {
+    let resolver = ServiceResolver::new(client, name).tag("billing-2");
+    let ids = pick_ids(&resolver, 2).await;
+    assert_eq!(ids, vec!["billing-2", "billing-2"]);
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::{
+        config::common::{ConfigEntry, ServiceConfigEntryBuilder},
+        discovery_chain::common::DiscoveryGraphNodeType,
+    },
+    client::Client,
+    config, discovery_chain,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+
+        test_get(&client).await;
+        test_get_protocol(&client).await;
+    });
+}
+
+async fn test_get(client: &impl Client) {
+    let res = discovery_chain::get(client, "test", None).await;
+    assert!(res.is_ok());
+
+    let chain = res.unwrap().response.chain;
+    assert_eq!(chain.service_name, "test");
+    assert_eq!(chain.protocol, "tcp");
+
+    let nodes = chain.nodes.unwrap();
+    let start = &nodes[&chain.start_node.unwrap()];
+    assert_eq!(start.node_type, DiscoveryGraphNodeType::Resolver);
+
+    let target = start.resolver.as_ref().unwrap().target.clone();
+    assert!(chain.targets.unwrap().contains_key(&target));
+}
+
+async fn test_get_protocol(client: &impl Client) {
+    let entry = ServiceConfigEntryBuilder::default()
+        .name("test")
+        .protocol("http")
+        .build()
+        .unwrap();
+    config::apply(client, ConfigEntry::ServiceDefaults(entry), None)
+        .await
+        .unwrap();
+
+    let res = discovery_chain::get(client, "test", None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.chain.protocol, "http");
+}
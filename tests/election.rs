@@ -0,0 +1,47 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    client::ConsulClient,
+    election::{Candidate, LeadershipEvent},
+    lock::LockOptions,
+};
+use futures::StreamExt;
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = Arc::new(server.client());
+
+        test_campaign(client).await;
+    });
+}
+
+async fn test_campaign(client: Arc<ConsulClient>) {
+    let candidate = Candidate::new(client, options());
+    let res = candidate.leader().await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_none());
+
+    let events = candidate.campaign();
+    futures::pin_mut!(events);
+    let res = events.next().await;
+    assert!(matches!(res, Some(Ok(LeadershipEvent::Elected))));
+
+    let res = candidate.leader().await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), Some(b"candidate".to_vec()));
+}
+
+fn options() -> LockOptions {
+    LockOptions::builder()
+        .key("test/election")
+        .value(b"candidate".to_vec())
+        .build()
+        .unwrap()
+}
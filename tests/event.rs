@@ -0,0 +1,54 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::event::requests::{FireEventRequest, ListEventsRequest},
+    client::Client,
+    event,
+};
+use futures::StreamExt;
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+
+        test_fire(&client, "test").await;
+        test_list(&client, "test").await;
+        test_watch(&client, "test").await;
+    });
+}
+
+async fn test_fire(client: &impl Client, name: &str) {
+    let res = event::fire(
+        client,
+        name,
+        b"payload",
+        Some(FireEventRequest::builder().service("counting")),
+    )
+    .await;
+    assert!(res.is_ok());
+}
+
+async fn test_list(client: &impl Client, name: &str) {
+    let res = event::list(client, Some(ListEventsRequest::builder().name(name))).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.len(), 1);
+}
+
+async fn test_watch(client: &impl Client, name: &str) {
+    let stream = event::watch(client, Some(ListEventsRequest::builder().name(name)));
+    futures::pin_mut!(stream);
+
+    let fire = async {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        event::fire(client, name, b"second", None).await.unwrap()
+    };
+    let (res, fired) = tokio::join!(stream.next(), fire);
+    assert_eq!(res.unwrap().unwrap().id, fired.response.id);
+}
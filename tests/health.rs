@@ -0,0 +1,101 @@
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper, CountingServer};
+use consulrs::{
+    api::{health::requests::ListServiceNodesRequest, service::requests::RegisterServiceRequest},
+    client::Client,
+    health, service,
+};
+use futures::StreamExt;
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let counting: CountingServer = instance.server();
+        let client = server.client();
+        let node = server.node().await;
+        let service = common::setup(&client, &counting).await;
+
+        test_checks(&client, &service.name).await;
+        test_connect(&client, &service.name).await;
+        test_node(&client, &node).await;
+        test_service(&client, &service.name).await;
+        test_service_stream(&client).await;
+        test_state(&client, "any").await;
+        test_watch(&client, &service.name).await;
+    });
+}
+
+async fn test_checks(client: &impl Client, service: &str) {
+    let res = health::checks(client, service, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_connect(client: &impl Client, service: &str) {
+    let res = health::connect(client, service, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_node(client: &impl Client, node: &str) {
+    let res = health::node(client, node, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_service(client: &impl Client, service: &str) {
+    let res = health::service(
+        client,
+        service,
+        Some(ListServiceNodesRequest::builder().passing(true)),
+    )
+    .await;
+    assert!(res.is_ok());
+}
+
+async fn test_service_stream(client: &impl Client) {
+    let name = "reconciled";
+    let res = service::register(
+        client,
+        name,
+        Some(RegisterServiceRequest::builder().id("reconciled-2")),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let stream = health::service_stream(client, name, None);
+    futures::pin_mut!(stream);
+
+    let res = stream.next().await.unwrap().unwrap();
+    assert_eq!(res.response.len(), 1);
+
+    let res = service::register(
+        client,
+        name,
+        Some(RegisterServiceRequest::builder().id("reconciled-1")),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let res = stream.next().await.unwrap().unwrap();
+    let ids: Vec<_> = res
+        .response
+        .iter()
+        .map(|e| e.service.id.clone().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["reconciled-1", "reconciled-2"]);
+}
+
+async fn test_state(client: &impl Client, state: &str) {
+    let res = health::state(client, state, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_watch(client: &impl Client, service: &str) {
+    let stream = health::watch(client, service, None);
+    futures::pin_mut!(stream);
+
+    let res = stream.next().await;
+    assert!(matches!(res, Some(Ok(_))));
+}
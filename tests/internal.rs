@@ -0,0 +1,79 @@
+#![cfg(feature = "unstable-internal")]
+
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::{
+        features::Filter,
+        internal::requests::ListUiServicesRequest,
+        service::{common::AgentServiceConnectBuilder, requests::RegisterServiceRequest},
+    },
+    client::Client,
+    internal, service,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+        let node = server.node().await;
+
+        test_nodes(&client, &node).await;
+        test_services(&client).await;
+        test_topology(&client, "web").await;
+    });
+}
+
+async fn test_nodes(client: &impl Client, node: &str) {
+    let res = internal::nodes(client, None).await;
+    assert!(res.is_ok());
+
+    let nodes = res.unwrap().response;
+    let summary = nodes.iter().find(|n| n.node == node).unwrap();
+    assert!(summary
+        .services
+        .iter()
+        .flatten()
+        .any(|s| s.service.as_deref() == Some("consul")));
+}
+
+async fn test_services(client: &impl Client) {
+    let res = internal::services(
+        client,
+        Some(ListUiServicesRequest::builder().filter(Filter::selector("Name").eq("consul"))),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let services = res.unwrap().response;
+    assert_eq!(services.len(), 1);
+    assert_eq!(services[0].name, "consul");
+    assert_eq!(services[0].instance_count, Some(1));
+}
+
+async fn test_topology(client: &impl Client, service: &str) {
+    service::register(
+        client,
+        service,
+        Some(
+            RegisterServiceRequest::builder().connect(Box::new(
+                AgentServiceConnectBuilder::default()
+                    .native(true)
+                    .build()
+                    .unwrap(),
+            )),
+        ),
+    )
+    .await
+    .unwrap();
+
+    let res = internal::topology(client, service, None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.filtered_by_acls, Some(false));
+
+    service::deregister(client, service, None).await.unwrap();
+}
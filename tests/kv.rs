@@ -1,7 +1,14 @@
 mod common;
 
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
 use common::{ConsulServer, ConsulServerHelper};
-use consulrs::{client::Client, kv};
+use consulrs::{
+    client::{Client, ConsulClient},
+    error::ClientError,
+    kv, session,
+};
+use futures::{StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use test_env_log::test;
 
@@ -10,6 +17,22 @@ struct TestObject {
     pub field: String,
 }
 
+/// Flags storing the version of the schema a value was written with.
+#[derive(Debug, PartialEq)]
+struct SchemaVersion(u64);
+
+impl From<u64> for SchemaVersion {
+    fn from(flags: u64) -> Self {
+        SchemaVersion(flags)
+    }
+}
+
+impl From<SchemaVersion> for u64 {
+    fn from(version: SchemaVersion) -> Self {
+        version.0
+    }
+}
+
 #[test]
 fn test() {
     let test = common::new_test();
@@ -21,17 +44,351 @@ fn test() {
         test_set(&client, key).await;
         test_keys(&client).await;
         test_read(&client, key).await;
+        test_try_read(&client, key).await;
         test_read_raw(&client, key).await;
+        test_read_many(&client).await;
+        test_read_stream(&client, key).await;
         test_delete(&client, key).await;
+        test_delete_cas(&client).await;
+        test_delete_tree(&client).await;
         test_json(&client, key).await;
+        #[cfg(any(feature = "cbor", feature = "msgpack", feature = "yaml"))]
+        test_formats(&client).await;
+        test_watch(&client, key).await;
+        test_cas_set(&client).await;
+        test_set_if_absent(&client).await;
+        test_lock(&client).await;
+        test_update(&client).await;
+        test_update_flags(&client).await;
+        test_export_import(&client).await;
+        test_sync(&client).await;
+        test_flags(&client).await;
+        test_value_too_large(&client).await;
+        test_large(&client).await;
+        #[cfg(any(feature = "gzip", feature = "zstd"))]
+        test_compression(&server).await;
+        let client = Arc::new(client);
+        test_counter(client.clone()).await;
+        test_ephemeral(client).await;
     });
 }
 
+async fn test_cas_set(client: &impl Client) {
+    let key = "cas";
+    let res = kv::cas_set(client, key, b"first", 0, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response);
+
+    let res = kv::cas_set(client, key, b"second", 0, None).await;
+    assert!(res.is_ok());
+    assert!(!res.unwrap().response);
+
+    let index = kv::read(client, key, None).await.unwrap().response[0].modify_index;
+    let res = kv::cas_set(client, key, b"second", index, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response);
+}
+
+async fn test_counter(client: Arc<ConsulClient>) {
+    let counter = Arc::new(kv::Counter::new(client.clone(), "counters/requests"));
+    assert_eq!(counter.get().await.unwrap(), 0);
+
+    let tasks: Vec<_> = (0..5)
+        .map(|_| {
+            let counter = counter.clone();
+            tokio::spawn(async move { counter.increment().await })
+        })
+        .collect();
+    for task in tasks {
+        assert!(task.await.unwrap().is_ok());
+    }
+    assert_eq!(counter.get().await.unwrap(), 5);
+    assert_eq!(counter.decrement().await.unwrap(), 4);
+
+    let sequencer = kv::Sequencer::new(client.clone(), "counters/ids").block_size(10u64);
+    assert_eq!(sequencer.next().await.unwrap(), 1);
+    assert_eq!(sequencer.next().await.unwrap(), 2);
+    let res = kv::read_raw(client.as_ref(), "counters/ids", None).await;
+    assert_eq!(res.unwrap().response, b"10");
+
+    kv::set(client.as_ref(), "counters/invalid", b"a", None)
+        .await
+        .unwrap();
+    let res = kv::Counter::new(client, "counters/invalid").get().await;
+    assert!(matches!(res, Err(ClientError::InvalidCounterError { .. })));
+}
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+async fn test_compression(server: &ConsulServer) {
+    use consulrs::{
+        api::kv::{common::Compression, requests::SetKeyRequest},
+        client::ConsulClientSettingsBuilder,
+    };
+    use std::convert::TryInto;
+
+    let client = server.client();
+    let decompressing = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address(server.external_url())
+            .decompress(true)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let compressions = [
+        #[cfg(feature = "gzip")]
+        Compression::Gzip,
+        #[cfg(feature = "zstd")]
+        Compression::Zstd,
+    ];
+    let value = vec![b'a'; kv::MAX_VALUE_SIZE * 2];
+    for compression in compressions {
+        let key = format!("compressed/{:?}", compression);
+        let res = kv::set(
+            &client,
+            &key,
+            value.clone(),
+            Some(
+                SetKeyRequest::builder()
+                    .compression(compression)
+                    .flags(1u64),
+            ),
+        )
+        .await;
+        assert!(matches!(
+            res,
+            Err(ClientError::CompressionFlagsError { .. })
+        ));
+
+        let res = kv::set(
+            &client,
+            &key,
+            value.clone(),
+            Some(SetKeyRequest::builder().compression(compression)),
+        )
+        .await;
+        assert!(res.is_ok());
+
+        let res = kv::read_raw(&client, &key, None).await;
+        assert!(res.unwrap().response.len() < value.len());
+
+        // Values are only decompressed when the client is configured to
+        let mut res = kv::read(&client, &key, None).await.unwrap();
+        assert_eq!(res.response.pop().unwrap().flags, compression.flags());
+
+        let mut res = kv::read(&decompressing, &key, None).await.unwrap();
+        let kv = res.response.pop().unwrap();
+        assert_eq!(kv.flags, 0);
+        let read: Vec<u8> = kv.value.unwrap().try_into().unwrap();
+        assert_eq!(read, value);
+    }
+}
+
 async fn test_delete(client: &impl Client, key: &str) {
     let res = kv::delete(client, key, None).await;
     assert!(res.is_ok());
 }
 
+async fn test_delete_cas(client: &impl Client) {
+    let key = "delete-cas";
+    let res = kv::set(client, key, b"value", None).await;
+    assert!(res.is_ok());
+    let index = kv::read(client, key, None).await.unwrap().response[0].modify_index;
+
+    let res = kv::set(client, key, b"changed", None).await;
+    assert!(res.is_ok());
+
+    let res = kv::delete_cas(client, key, index, None).await;
+    assert!(res.is_ok());
+    assert!(!res.unwrap().response);
+
+    let index = kv::read(client, key, None).await.unwrap().response[0].modify_index;
+    let res = kv::delete_cas(client, key, index, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response);
+
+    let res = kv::read(client, key, None).await;
+    assert!(res.is_err());
+}
+
+async fn test_delete_tree(client: &impl Client) {
+    let prefix = "tree/";
+    for name in ["a", "b/c"] {
+        let res = kv::set(client, &format!("{}{}", prefix, name), name, None).await;
+        assert!(res.is_ok());
+    }
+    let res = kv::set(client, "treetop", b"value", None).await;
+    assert!(res.is_ok());
+
+    let res = kv::delete_tree(client, prefix, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response);
+
+    let res = kv::keys(client, prefix, None).await;
+    assert!(res.unwrap_err().is_not_found());
+
+    let res = kv::read_raw(client, "treetop", None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_ephemeral(client: Arc<ConsulClient>) {
+    let key = "presence/a";
+    let res = kv::ephemeral(client.clone(), key, b"here", Duration::from_secs(10)).await;
+    assert!(res.is_ok());
+    let ephemeral = res.unwrap();
+    assert!(ephemeral.is_active());
+    assert_eq!(ephemeral.key(), key);
+
+    let res = kv::read(client.as_ref(), key, None).await;
+    let pair = &res.unwrap().response[0];
+    assert_eq!(pair.session.as_deref(), Some(ephemeral.session()));
+
+    let res = kv::ephemeral(client.clone(), key, b"here", Duration::from_secs(10)).await;
+    assert!(matches!(
+        res,
+        Err(ClientError::EphemeralKeyHeldError { .. })
+    ));
+
+    let res = ephemeral.delete().await;
+    assert!(res.is_ok());
+
+    let res = kv::try_read(client.as_ref(), key, None).await;
+    assert!(res.unwrap().is_none());
+}
+
+async fn test_export_import(client: &impl Client) {
+    let prefix = "export/";
+    let res = kv::export(client, prefix, None).await;
+    assert!(res.unwrap().response.is_empty());
+
+    for name in ["a", "b", "c"] {
+        let res = kv::set(client, &format!("{}{}", prefix, name), name, None).await;
+        assert!(res.is_ok());
+    }
+
+    let res = kv::export(client, prefix, None).await;
+    assert!(res.is_ok());
+    let entries = res.unwrap().response;
+    let json = serde_json::to_value(&entries).unwrap();
+    assert_eq!(
+        json[0],
+        serde_json::json!({"flags": 0, "key": "export/a", "value": "YQ=="})
+    );
+
+    for batch_size in [None, Some(2)] {
+        let res = kv::delete_tree(client, prefix, None).await;
+        assert!(res.is_ok());
+
+        let res = kv::import(client, &entries, batch_size).await;
+        assert!(res.is_ok());
+
+        let res = kv::read_raw(client, "export/c", None).await;
+        assert_eq!(res.unwrap().response, b"c");
+    }
+}
+
+async fn test_read_many(client: &impl Client) {
+    for name in ["a", "b"] {
+        let res = kv::set(client, &format!("many/{}", name), name, None).await;
+        assert!(res.is_ok());
+    }
+
+    let res = kv::read_many(client, &["many/b", "many/a"], None).await;
+    assert!(res.is_ok());
+    let keys: Vec<_> = res.unwrap().response.into_iter().map(|kv| kv.key).collect();
+    assert_eq!(keys, vec!["many/b", "many/a"]);
+
+    let res = kv::read_many(client, &["many/a", "many/c"], None).await;
+    assert!(matches!(
+        res.unwrap_err(),
+        ClientError::TransactionError { .. }
+    ));
+}
+
+async fn test_sync(client: &impl Client) {
+    for name in ["a", "b", "c"] {
+        let res = kv::set(client, &format!("sync/{}", name), name, None).await;
+        assert!(res.is_ok());
+    }
+
+    let desired: HashMap<String, Vec<u8>> = [("a", "a"), ("b", "x"), ("d/e", "e")]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.as_bytes().to_vec()))
+        .collect();
+    let res = kv::sync(client, "sync/", desired.clone()).await;
+    assert!(res.is_ok());
+    let diff = res.unwrap();
+    assert_eq!(diff.created, vec!["sync/d/e"]);
+    assert_eq!(diff.deleted, vec!["sync/c"]);
+    assert_eq!(diff.updated, vec!["sync/b"]);
+
+    let res = kv::read_raw(client, "sync/b", None).await;
+    assert_eq!(res.unwrap().response, b"x");
+    let res = kv::read_raw(client, "sync/c", None).await;
+    assert!(res.unwrap_err().is_not_found());
+
+    let res = kv::sync(client, "sync/", desired).await;
+    assert!(res.unwrap().is_empty());
+}
+
+async fn test_flags(client: &impl Client) {
+    for (name, version) in [("a", 1), ("b", 2), ("c", 2)] {
+        let key = format!("flags/{}", name);
+        let res = kv::set_with_flags(client, &key, name, SchemaVersion(version), None).await;
+        assert!(res.is_ok());
+    }
+
+    let res = kv::read(client, "flags/a", None).await;
+    assert_eq!(
+        res.unwrap().response[0].flags_as::<SchemaVersion>(),
+        SchemaVersion(1)
+    );
+
+    let res = kv::read_by_flags(client, "flags/", |v: &SchemaVersion| v.0 == 2, None).await;
+    assert!(res.is_ok());
+    let keys: Vec<String> = res.unwrap().response.into_iter().map(|kv| kv.key).collect();
+    assert_eq!(keys, vec!["flags/b", "flags/c"]);
+}
+
+#[cfg(any(feature = "cbor", feature = "msgpack", feature = "yaml"))]
+async fn test_formats(client: &impl Client) {
+    #[cfg(feature = "cbor")]
+    {
+        let obj = TestObject {
+            field: "cbor".into(),
+        };
+        let res = kv::set_cbor(client, "format/cbor", &obj, None).await;
+        assert!(res.is_ok());
+        let res = kv::read_cbor::<TestObject, _>(client, "format/cbor", None).await;
+        assert_eq!(res.unwrap().response.value.field, "cbor");
+    }
+
+    #[cfg(feature = "msgpack")]
+    {
+        let obj = TestObject {
+            field: "msgpack".into(),
+        };
+        let res = kv::set_msgpack(client, "format/msgpack", &obj, None).await;
+        assert!(res.is_ok());
+        let res = kv::read_msgpack::<TestObject, _>(client, "format/msgpack", None).await;
+        assert_eq!(res.unwrap().response.value.field, "msgpack");
+    }
+
+    #[cfg(feature = "yaml")]
+    {
+        let obj = TestObject {
+            field: "yaml".into(),
+        };
+        let res = kv::set_yaml(client, "format/yaml", &obj, None).await;
+        assert!(res.is_ok());
+        let res = kv::read_raw(client, "format/yaml", None).await;
+        assert_eq!(res.unwrap().response, b"---\nfield: yaml\n");
+        let res = kv::read_yaml::<TestObject, _>(client, "format/yaml", None).await;
+        assert_eq!(res.unwrap().response.value.field, "yaml");
+    }
+}
+
 async fn test_json(client: &impl Client, key: &str) {
     let obj = TestObject {
         field: "test".into(),
@@ -44,6 +401,24 @@ async fn test_json(client: &impl Client, key: &str) {
     assert!(res.is_ok());
 
     assert_eq!(obj.field, res.unwrap().response.value.field);
+
+    for name in ["a", "b"] {
+        let obj = TestObject { field: name.into() };
+        let res = kv::set_json(client, &format!("tree/{}", name), &obj, None).await;
+        assert!(res.is_ok());
+    }
+    let res = kv::set(client, "tree/folder/", b"", None).await;
+    assert!(res.is_ok());
+
+    let res = kv::read_json_recurse::<TestObject, _>(client, "tree/", None).await;
+    assert!(res.is_ok());
+    let fields: Vec<String> = res
+        .unwrap()
+        .response
+        .into_iter()
+        .map(|kv| kv.value.field)
+        .collect();
+    assert_eq!(fields, vec!["a", "b"]);
 }
 
 async fn test_keys(client: &impl Client) {
@@ -51,17 +426,152 @@ async fn test_keys(client: &impl Client) {
     assert!(res.is_ok());
 }
 
+async fn test_large(client: &impl Client) {
+    let key = "large";
+    let value: Vec<u8> = (0..kv::MAX_VALUE_SIZE * 2 + 100)
+        .map(|i| (i % 251) as u8)
+        .collect();
+    let res = kv::set_large(client, key, value.clone()).await;
+    assert!(res.is_ok());
+
+    let res = kv::keys(client, "large/", None).await;
+    assert_eq!(res.unwrap().response.len(), 3);
+
+    let res = kv::read_large(client, key, None).await;
+    assert_eq!(res.unwrap().response, value);
+
+    let res = kv::set_large(client, key, b"small").await;
+    assert!(res.is_ok());
+
+    let res = kv::keys(client, "large/", None).await;
+    assert_eq!(res.unwrap().response.len(), 1);
+
+    let res = kv::read_large(client, key, None).await;
+    assert_eq!(res.unwrap().response, b"small");
+}
+
+async fn test_lock(client: &impl Client) {
+    let key = "lock";
+    let session = session::create(client, None).await.unwrap().response.id;
+    let other = session::create(client, None).await.unwrap().response.id;
+
+    let res = kv::acquire(client, key, &session, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response);
+
+    let res = kv::acquire(client, key, &other, None).await;
+    assert!(res.is_ok());
+    assert!(!res.unwrap().response);
+
+    let res = kv::release(client, key, &session, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response);
+
+    let res = kv::acquire(client, key, &other, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response);
+}
+
 async fn test_read_raw(client: &impl Client, key: &str) {
     let res = kv::read_raw(client, key, None).await;
     assert!(res.is_ok());
 }
 
+async fn test_read_stream(client: &impl Client, key: &str) {
+    let res = kv::read_stream(client, key, None).await;
+    assert!(res.is_ok());
+
+    let chunks: Vec<_> = res.unwrap().response.try_collect().await.unwrap();
+    let raw = kv::read_raw(client, key, None).await.unwrap().response;
+    assert_eq!(chunks.concat(), raw);
+}
+
 async fn test_read(client: &impl Client, key: &str) {
     let res = kv::read(client, key, None).await;
     assert!(res.is_ok());
 }
 
 async fn test_set(client: &impl Client, key: &str) {
+    let res = kv::set(client, "owned", format!("{}-{}", key, 1), None).await;
+    assert!(res.is_ok());
+
     let res = kv::set(client, key, b"test", None).await;
     assert!(res.is_ok());
 }
+
+async fn test_set_if_absent(client: &impl Client) {
+    let key = "absent";
+    let res = kv::set_if_absent(client, key, b"first", None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response);
+
+    let res = kv::set_if_absent(client, key, b"second", None).await;
+    assert!(res.is_ok());
+    assert!(!res.unwrap().response);
+
+    let res = kv::read_raw(client, key, None).await;
+    assert_eq!(res.unwrap().response, b"first");
+}
+
+async fn test_try_read(client: &impl Client, key: &str) {
+    let res = kv::try_read(client, key, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_some());
+
+    let res = kv::try_read(client, "missing", None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_none());
+
+    let res = kv::try_read_raw(client, "missing", None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_none());
+}
+
+async fn test_update(client: &impl Client) {
+    let key = "counter";
+    let increment = |current: Option<Vec<u8>>| {
+        let count: u64 = current
+            .map(|v| String::from_utf8(v).unwrap().parse().unwrap())
+            .unwrap_or(0);
+        (count + 1).to_string().into_bytes()
+    };
+
+    let res = kv::update(client, key, increment).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response, b"1");
+
+    let res = kv::update(client, key, increment).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response, b"2");
+
+    let res = kv::read_raw(client, key, None).await;
+    assert_eq!(res.unwrap().response, b"2");
+}
+
+async fn test_update_flags(client: &impl Client) {
+    let key = "update-flags";
+    let res = kv::set_with_flags(client, key, b"a", SchemaVersion(2), None).await;
+    assert!(res.is_ok());
+
+    let res = kv::update(client, key, |_| b"b".to_vec()).await;
+    assert!(res.is_ok());
+
+    let res = kv::read(client, key, None).await.unwrap();
+    assert_eq!(
+        res.response[0].flags_as::<SchemaVersion>(),
+        SchemaVersion(2)
+    );
+}
+
+async fn test_value_too_large(client: &impl Client) {
+    let res = kv::set(client, "large", vec![0; kv::MAX_VALUE_SIZE + 1], None).await;
+    assert!(matches!(res, Err(ClientError::ValueTooLarge { .. })));
+}
+
+async fn test_watch(client: &impl Client, key: &str) {
+    let stream = kv::watch(client, key, None);
+    futures::pin_mut!(stream);
+
+    let res = stream.next().await.unwrap();
+    assert!(res.is_ok());
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    client::ConsulClient,
+    error::ClientError,
+    kv,
+    lock::{self, Lock, LockOptions, Semaphore, SemaphoreOptions},
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = Arc::new(server.client());
+
+        test_acquire(client.clone()).await;
+        test_contention(client.clone()).await;
+        test_guard(client.clone()).await;
+        test_semaphore(client).await;
+    });
+}
+
+async fn test_acquire(client: Arc<ConsulClient>) {
+    let mut lock = Lock::new(client.clone(), options("test/acquire"));
+    let res = lock.acquire().await;
+    assert!(res.is_ok());
+    assert!(lock.is_held());
+
+    let res = kv::read(client.as_ref(), "test/acquire", None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response[0].session.as_deref(), lock.session());
+
+    let res = lock.release().await;
+    assert!(res.is_ok());
+    assert!(!lock.is_held());
+}
+
+async fn test_contention(client: Arc<ConsulClient>) {
+    let mut first = Lock::new(client.clone(), options("test/contention"));
+    let mut second = Lock::new(client, options("test/contention"));
+    assert!(first.acquire().await.is_ok());
+
+    let res = tokio::time::timeout(Duration::from_secs(2), second.acquire()).await;
+    assert!(res.is_err());
+
+    assert!(first.release().await.is_ok());
+    let res = tokio::time::timeout(Duration::from_secs(10), second.acquire()).await;
+    assert!(matches!(res, Ok(Ok(_))));
+    assert!(second.release().await.is_ok());
+}
+
+async fn test_guard(client: Arc<ConsulClient>) {
+    let mut lock = Lock::new(client.clone(), options("test/guard"));
+    let guard = lock.guard().await;
+    assert!(guard.is_ok());
+
+    let res = guard.unwrap().release().await;
+    assert!(res.is_ok());
+
+    let res = kv::read(client.as_ref(), "test/guard", None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().response[0].session.is_none());
+}
+
+async fn test_semaphore(client: Arc<ConsulClient>) {
+    let mut first = Semaphore::new(client.clone(), semaphore_options());
+    let mut second = Semaphore::new(client.clone(), semaphore_options());
+    let mut third = Semaphore::new(client.clone(), semaphore_options());
+    assert!(first.acquire(2).await.is_ok());
+    assert!(second.acquire(2).await.is_ok());
+
+    // The keys of a semaphore can be read like any other
+    let res = kv::read(client.as_ref(), "test/semaphore/.lock", None).await;
+    assert_eq!(res.unwrap().response[0].flags, lock::SEMAPHORE_FLAG_VALUE);
+
+    let res = tokio::time::timeout(Duration::from_secs(2), third.acquire(2)).await;
+    assert!(res.is_err());
+
+    let mut conflict = Semaphore::new(client, semaphore_options());
+    let res = conflict.acquire(3).await;
+    assert!(matches!(
+        res,
+        Err(ClientError::SemaphoreConflictError { .. })
+    ));
+
+    assert!(first.release().await.is_ok());
+    let res = tokio::time::timeout(Duration::from_secs(10), third.acquire(2)).await;
+    assert!(matches!(res, Ok(Ok(_))));
+    assert!(second.release().await.is_ok());
+    assert!(third.release().await.is_ok());
+}
+
+fn options(key: &str) -> LockOptions {
+    LockOptions::builder()
+        .key(key)
+        .lock_delay(Duration::from_secs(0))
+        .retry_time(Duration::from_millis(500))
+        .build()
+        .unwrap()
+}
+
+fn semaphore_options() -> SemaphoreOptions {
+    SemaphoreOptions::builder()
+        .prefix("test/semaphore")
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,511 @@
+#![cfg(feature = "mock")]
+
+use std::time::Duration;
+
+use consulrs::{
+    api::{
+        catalog::requests::{DeregisterEntityRequest, RegisterEntityRequest},
+        kv::requests::{ReadKeyRequest, ReadKeysRequest, SetKeyRequest},
+        service::common::AgentServiceBuilder,
+    },
+    catalog,
+    client::ConsulClientSettingsBuilder,
+    kv, lock,
+    mock::MockClient,
+    status,
+};
+use futures::StreamExt;
+
+#[tokio::test]
+async fn test_kv() {
+    let client = MockClient::new();
+
+    let res = kv::read(&client, "missing", None).await;
+    assert!(res.unwrap_err().is_not_found());
+    let res = kv::export(&client, "missing/", None).await;
+    assert!(res.unwrap().response.is_empty());
+
+    kv::set(&client, "test/a", b"a", None).await.unwrap();
+    kv::set(&client, "test/b/c", b"c", None).await.unwrap();
+
+    let res = kv::read_raw(&client, "test/a", None).await.unwrap();
+    assert_eq!(res.response, b"a");
+    let res = kv::read(
+        &client,
+        "test",
+        Some(ReadKeyRequest::builder().recurse(true)),
+    )
+    .await;
+    assert_eq!(res.unwrap().response.len(), 2);
+    let res = kv::keys(
+        &client,
+        "test/",
+        Some(ReadKeysRequest::builder().separator("/")),
+    )
+    .await;
+    assert_eq!(res.unwrap().response, vec!["test/a", "test/b/"]);
+
+    let res = kv::delete_tree(&client, "test/", None).await;
+    assert!(res.unwrap().response);
+    let res = kv::read(&client, "test/a", None).await;
+    assert!(res.unwrap_err().is_not_found());
+}
+
+#[tokio::test]
+async fn test_kv_cas() {
+    let client = MockClient::new();
+
+    assert!(
+        kv::set_if_absent(&client, "test", b"a", None)
+            .await
+            .unwrap()
+            .response
+    );
+    assert!(
+        !kv::set_if_absent(&client, "test", b"b", None)
+            .await
+            .unwrap()
+            .response
+    );
+
+    let index = kv::read(&client, "test", None).await.unwrap().response[0].modify_index;
+    assert!(
+        !kv::cas_set(&client, "test", b"b", index + 1, None)
+            .await
+            .unwrap()
+            .response
+    );
+    assert!(
+        kv::cas_set(&client, "test", b"b", index, None)
+            .await
+            .unwrap()
+            .response
+    );
+    let res = kv::read_raw(&client, "test", None).await.unwrap();
+    assert_eq!(res.response, b"b");
+}
+
+#[tokio::test]
+async fn test_kv_lock() {
+    let client = MockClient::new();
+
+    assert!(
+        kv::acquire(&client, "lock", "a", None)
+            .await
+            .unwrap()
+            .response
+    );
+    assert!(
+        !kv::acquire(&client, "lock", "b", None)
+            .await
+            .unwrap()
+            .response
+    );
+    let res = kv::read(&client, "lock", None).await.unwrap();
+    assert_eq!(res.response[0].session.as_deref(), Some("a"));
+    assert_eq!(res.response[0].lock_index, 1);
+
+    assert!(
+        !kv::release(&client, "lock", "b", None)
+            .await
+            .unwrap()
+            .response
+    );
+    assert!(
+        kv::release(&client, "lock", "a", None)
+            .await
+            .unwrap()
+            .response
+    );
+    assert!(
+        kv::acquire(&client, "lock", "b", None)
+            .await
+            .unwrap()
+            .response
+    );
+}
+
+#[tokio::test]
+async fn test_kv_semaphore_flags() {
+    let client = MockClient::with_settings(
+        ConsulClientSettingsBuilder::default()
+            .decompress(true)
+            .build()
+            .unwrap(),
+    );
+
+    // Flags which don't mark a compressed value are left alone
+    kv::set(
+        &client,
+        "semaphore/.lock",
+        b"{}",
+        Some(SetKeyRequest::builder().flags(lock::SEMAPHORE_FLAG_VALUE)),
+    )
+    .await
+    .unwrap();
+    let res = kv::read(&client, "semaphore/.lock", None).await.unwrap();
+    assert_eq!(res.response[0].flags, lock::SEMAPHORE_FLAG_VALUE);
+    let res = kv::try_read(&client, "semaphore/.lock", None)
+        .await
+        .unwrap();
+    assert!(res.is_some());
+}
+
+#[tokio::test]
+async fn test_kv_update_flags() {
+    let client = MockClient::new();
+    kv::set(
+        &client,
+        "flagged",
+        b"a",
+        Some(SetKeyRequest::builder().flags(42u64)),
+    )
+    .await
+    .unwrap();
+
+    let res = kv::update(&client, "flagged", |_| b"b".to_vec()).await;
+    assert_eq!(res.unwrap().response, b"b");
+    let res = kv::read(&client, "flagged", None).await.unwrap();
+    assert_eq!(res.response[0].flags, 42);
+}
+
+#[tokio::test]
+async fn test_kv_watch() {
+    let client = MockClient::new();
+    kv::set(&client, "test", b"a", None).await.unwrap();
+
+    let mut watch = Box::pin(kv::watch(&client, "test", None));
+    let res = watch.next().await.unwrap().unwrap();
+    assert_eq!(res.response[0].modify_index, client.server().index());
+
+    let (res, _) = tokio::join!(watch.next(), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        kv::set(&client, "test", b"b", None).await.unwrap();
+    });
+    let res = res.unwrap().unwrap();
+    assert_eq!(res.response[0].modify_index, client.server().index());
+}
+
+#[tokio::test]
+async fn test_kv_watch_missing() {
+    let client = MockClient::new();
+    let timeout = Duration::from_millis(500);
+
+    // A missing key is an empty result which is blocked on, not an error
+    let mut watch = Box::pin(kv::watch(&client, "missing", None));
+    let res = watch.next().await.unwrap().unwrap();
+    assert!(res.response.is_empty());
+
+    let (res, _) = tokio::join!(tokio::time::timeout(timeout, watch.next()), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        kv::set(&client, "missing", b"a", None).await.unwrap();
+    });
+    assert_eq!(res.unwrap().unwrap().unwrap().response.len(), 1);
+
+    let (res, _) = tokio::join!(tokio::time::timeout(timeout, watch.next()), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        kv::delete(&client, "missing", None).await.unwrap();
+    });
+    assert!(res.unwrap().unwrap().unwrap().response.is_empty());
+
+    // Recreating the key with the same value yields it again
+    let (res, _) = tokio::join!(tokio::time::timeout(timeout, watch.next()), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        kv::set(&client, "missing", b"a", None).await.unwrap();
+    });
+    assert_eq!(res.unwrap().unwrap().unwrap().response.len(), 1);
+}
+
+#[tokio::test]
+async fn test_catalog() {
+    let client = MockClient::new();
+
+    for (node, tag) in [("node1", "primary"), ("node2", "replica")] {
+        let service = AgentServiceBuilder::default()
+            .id("db")
+            .service("db")
+            .port(5432u64)
+            .tags(vec![tag.to_string()])
+            .build()
+            .unwrap();
+        let res = catalog::register(
+            &client,
+            node,
+            "127.0.0.1",
+            Some(RegisterEntityRequest::builder().service(service)),
+        )
+        .await;
+        assert!(res.is_ok());
+    }
+
+    let res = catalog::datacenters(&client, None).await.unwrap();
+    assert_eq!(res.response, vec!["dc1"]);
+    let res = catalog::nodes(&client, None).await.unwrap();
+    assert_eq!(res.response.len(), 2);
+    let res = catalog::services(&client, None).await.unwrap();
+    assert_eq!(res.response["db"], vec!["primary", "replica"]);
+    let res = catalog::nodes_with_service(&client, "db", None)
+        .await
+        .unwrap();
+    assert_eq!(res.response.len(), 2);
+    assert_eq!(res.response[0].node.as_deref(), Some("node1"));
+    assert_eq!(res.response[0].service_port, Some(5432));
+    assert_eq!(
+        res.response[0].service_tags,
+        Some(vec!["primary".to_string()])
+    );
+    let res = catalog::node(&client, "node2", None).await.unwrap();
+    assert_eq!(res.response.services.len(), 1);
+
+    let res = catalog::deregister(
+        &client,
+        "node1",
+        Some(DeregisterEntityRequest::builder().service_id("db")),
+    )
+    .await;
+    assert!(res.is_ok());
+    let res = catalog::nodes_with_service(&client, "db", None)
+        .await
+        .unwrap();
+    assert_eq!(res.response.len(), 1);
+
+    let res = catalog::deregister(&client, "node2", None).await;
+    assert!(res.is_ok());
+    let res = catalog::nodes(&client, None).await.unwrap();
+    assert_eq!(res.response.len(), 1);
+}
+
+#[tokio::test]
+async fn test_unsupported() {
+    let client = MockClient::new();
+
+    let res = status::leader(&client, None).await;
+    assert_eq!(res.unwrap_err().status(), Some(501));
+}
+
+#[tokio::test]
+async fn test_external() {
+    use consulrs::{
+        api::{catalog::requests::RegisterEntityRequest, check::common::AgentCheckBuilder},
+        external,
+    };
+
+    let client = MockClient::new();
+
+    let service = AgentServiceBuilder::default()
+        .id("db")
+        .service("db")
+        .build()
+        .unwrap();
+    let check = AgentCheckBuilder::default().name("db").build().unwrap();
+    let res =
+        external::register_service(&client, "ext", "10.0.0.1", service, Some(check), None).await;
+    assert!(res.is_ok());
+
+    let res = catalog::node(&client, "ext", None).await.unwrap();
+    let node = res.response.node;
+    assert_eq!(node.address, "10.0.0.1");
+    assert_eq!(node.meta.unwrap()[external::EXTERNAL_NODE], "true");
+
+    let service = AgentServiceBuilder::default()
+        .id("cache")
+        .service("cache")
+        .build()
+        .unwrap();
+    let res = external::register_service(
+        &client,
+        "ext",
+        "10.0.0.2",
+        service,
+        None,
+        Some(RegisterEntityRequest::builder().skip_node_update(true)),
+    )
+    .await;
+    assert!(res.is_ok());
+    let res = catalog::node(&client, "ext", None).await.unwrap();
+    assert_eq!(res.response.node.address, "10.0.0.1");
+    assert_eq!(res.response.services.len(), 2);
+
+    let res = external::deregister_service(&client, "ext", "db", None).await;
+    assert!(res.is_ok());
+    let res = catalog::nodes_with_service(&client, "db", None)
+        .await
+        .unwrap();
+    assert!(res.response.is_empty());
+}
+
+#[tokio::test]
+async fn test_template() {
+    use consulrs::template::{Template, TemplateConfig, TemplateData};
+    use std::sync::Arc;
+
+    let client = Arc::new(MockClient::new());
+    kv::set(client.as_ref(), "app/a", b"1", None).await.unwrap();
+
+    let path = std::env::temp_dir().join(format!("consulrs-template-{}", std::process::id()));
+    let config = TemplateConfig::builder()
+        .destination(path.clone())
+        .keys(vec!["app/".to_string()])
+        .min_wait(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let mut template = Template::start(client.clone(), config, |data: &TemplateData| {
+        Ok(data
+            .keys("app/")
+            .map(|(k, v)| format!("{}={}\n", k, v))
+            .collect())
+    })
+    .await
+    .unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "app/a=1\n");
+
+    // Both changes are rendered at once
+    let (tx, mut renders) = tokio::sync::mpsc::unbounded_channel();
+    template.on_render(move |data| {
+        let _ = tx.send(data);
+    });
+    kv::set(client.as_ref(), "app/b", b"2", None).await.unwrap();
+    kv::set(client.as_ref(), "app/a", b"3", None).await.unwrap();
+    let data = tokio::time::timeout(Duration::from_secs(5), renders.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.key("app/a"), Some("3"));
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "app/a=3\napp/b=2\n"
+    );
+    assert!(renders.try_recv().is_err());
+
+    template.stop();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_watch_group() {
+    use consulrs::watch_group::WatchGroup;
+    use futures::FutureExt;
+    use std::sync::Arc;
+
+    let client = Arc::new(MockClient::new());
+    kv::set(client.as_ref(), "app/a", b"a", None).await.unwrap();
+
+    let group = WatchGroup::new(client.clone());
+    let mut a = group.key("app/a");
+    let mut b = group.key("app/b");
+    let mut all = group.prefix("app/");
+    assert_eq!(group.prefixes(), vec!["app/"]);
+
+    assert_eq!(a.next().await.unwrap().unwrap().key, "app/a");
+    assert!(b.next().await.unwrap().is_none());
+    assert_eq!(all.next().await.unwrap().len(), 1);
+
+    // Only the subscribers of the changed key are notified
+    kv::set(client.as_ref(), "app/b", b"b", None).await.unwrap();
+    assert_eq!(b.next().await.unwrap().unwrap().key, "app/b");
+    assert_eq!(all.next().await.unwrap().len(), 2);
+    assert!(a.next().now_or_never().is_none());
+
+    // The query stops once its subscribers are gone
+    drop((a, b, all));
+    kv::set(client.as_ref(), "app/c", b"c", None).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(group.prefixes().is_empty());
+}
+
+#[tokio::test]
+async fn test_cached_kv() {
+    use consulrs::cache::{CacheConfig, CachedKv};
+    use std::sync::Arc;
+
+    let client = Arc::new(MockClient::new());
+    kv::set(client.as_ref(), "test", b"a", None).await.unwrap();
+
+    let config = CacheConfig::builder()
+        .idle_timeout(Duration::from_millis(500))
+        .max_stale(Duration::from_millis(200))
+        .build()
+        .unwrap();
+    let cache = CachedKv::new(client.clone(), config);
+    assert_eq!(cache.get_raw("test").await.unwrap(), Some(b"a".to_vec()));
+    assert_eq!(cache.get_raw("test").await.unwrap(), Some(b"a".to_vec()));
+    assert_eq!(cache.get_raw("missing").await.unwrap(), None);
+    let stats = cache.stats();
+    assert_eq!((stats.entries, stats.hits, stats.misses), (2, 1, 2));
+
+    // Writes are picked up by the background query
+    kv::set(client.as_ref(), "test", b"b", None).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(cache.get_raw("test").await.unwrap(), Some(b"b".to_vec()));
+    assert_eq!(cache.stats().misses, 2);
+
+    // Idle entries are evicted once their query returns
+    tokio::time::sleep(Duration::from_millis(800)).await;
+    assert_eq!(cache.stats().entries, 0);
+}
+
+#[tokio::test]
+async fn test_cached_kv_snapshot() {
+    use consulrs::{
+        cache::{CacheConfig, CachedKv},
+        client::{ConsulClient, ConsulClientSettingsBuilder},
+    };
+    use std::sync::Arc;
+
+    let path = std::env::temp_dir().join(format!("consulrs-cache-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let config = CacheConfig::builder()
+        .snapshot(path.clone())
+        .build()
+        .unwrap();
+
+    let client = Arc::new(MockClient::new());
+    kv::set(client.as_ref(), "test", b"a", None).await.unwrap();
+    let cache = CachedKv::new(client, config.clone());
+    assert_eq!(cache.get_raw("test").await.unwrap(), Some(b"a".to_vec()));
+    cache.flush().await;
+    drop(cache);
+
+    // A new cache serves the snapshot while Consul can't be reached
+    let client = ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address("http://127.0.0.1:1")
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let cache = CachedKv::new(Arc::new(client), config);
+    assert_eq!(cache.get_raw("test").await.unwrap(), Some(b"a".to_vec()));
+    assert!(cache.get_raw("missing").await.is_err());
+    assert_eq!(cache.stats().fallback, 1);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_counter() {
+    use std::sync::Arc;
+
+    let client = Arc::new(MockClient::new());
+    let counter = Arc::new(kv::Counter::new(client.clone(), "counter"));
+    let tasks: Vec<_> = (0..10)
+        .map(|_| {
+            let counter = counter.clone();
+            tokio::spawn(async move { counter.increment().await })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+    assert_eq!(counter.get().await.unwrap(), 10);
+    assert_eq!(counter.add(-3).await.unwrap(), 7);
+
+    // Blocks are reserved from the counter and handed out in order
+    let sequencer = kv::Sequencer::new(client.clone(), "ids").block_size(3u64);
+    let mut ids = Vec::new();
+    for _ in 0..4 {
+        ids.push(sequencer.next().await.unwrap());
+    }
+    assert_eq!(ids, vec![1, 2, 3, 4]);
+    let other = kv::Sequencer::new(client, "ids").block_size(3u64);
+    assert_eq!(other.next().await.unwrap(), 7);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::catalog::requests::ListNodesRequest, catalog, client::ConsulClient,
+    multi_dc::MultiDcClient,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = MultiDcClient::new(server.client());
+
+        test_datacenters(&client).await;
+        test_merge(&client).await;
+        test_subset(server.client()).await;
+    });
+}
+
+async fn test_datacenters(client: &MultiDcClient<ConsulClient>) {
+    let res = client.datacenters().await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap(), vec!["dc1"]);
+}
+
+async fn test_merge(client: &MultiDcClient<ConsulClient>) {
+    let res = client
+        .merge(|client, dc| async move {
+            catalog::nodes(client, Some(ListNodesRequest::builder().dc(dc))).await
+        })
+        .await;
+    assert!(res.is_ok());
+
+    let nodes = res.unwrap();
+    assert!(!nodes.is_empty());
+    assert!(nodes.iter().all(|n| n.datacenter == "dc1"));
+}
+
+async fn test_subset(client: ConsulClient) {
+    let client = MultiDcClient::new(client).subset(["dc1", "missing"]);
+    let res = client
+        .fan_out(|client, dc| async move {
+            catalog::nodes(client, Some(ListNodesRequest::builder().dc(dc))).await
+        })
+        .await;
+    assert!(res.is_ok());
+
+    let results = res.unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results["dc1"].is_ok());
+    assert!(results["missing"].is_err());
+
+    let res = client
+        .merge(|client, dc| async move {
+            catalog::nodes(client, Some(ListNodesRequest::builder().dc(dc))).await
+        })
+        .await;
+    assert!(res.is_err());
+}
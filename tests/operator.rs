@@ -0,0 +1,62 @@
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    api::operator::requests::{InstallKeyRequest, ListKeysRequest},
+    client::Client,
+    operator::{keyring, raft},
+};
+use test_env_log::test;
+
+const NEW_KEY: &str = "Y29uc3VscnMtdGVzdC1nb3NzaXAtcm90YXRlZC1rZXk=";
+
+#[test]
+fn test() {
+    let test = common::new_encrypt_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+
+        test_keyring_install(&client, NEW_KEY).await;
+        test_keyring_list(&client, NEW_KEY).await;
+        test_keyring_use(&client, NEW_KEY).await;
+        test_keyring_remove(&client, common::ENCRYPT_KEY).await;
+        test_raft_transfer_leader(&client).await;
+    });
+}
+
+async fn test_keyring_install(client: &impl Client, key: &str) {
+    let res = keyring::install(
+        client,
+        key,
+        Some(InstallKeyRequest::builder().relay_factor(1)),
+    )
+    .await;
+    assert!(res.is_ok());
+}
+
+async fn test_keyring_list(client: &impl Client, key: &str) {
+    let res = keyring::list(client, Some(ListKeysRequest::builder().local_only(true))).await;
+    assert!(res.is_ok());
+    assert!(res
+        .unwrap()
+        .response
+        .iter()
+        .all(|r| r.keys.contains_key(key)));
+}
+
+async fn test_keyring_remove(client: &impl Client, key: &str) {
+    let res = keyring::remove(client, key, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_keyring_use(client: &impl Client, key: &str) {
+    let res = keyring::use_key(client, key, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_raft_transfer_leader(client: &impl Client) {
+    // The only server can't hand leadership to a server which isn't a peer
+    let res = raft::transfer_leader(client, "missing", None).await;
+    assert!(res.is_err());
+}
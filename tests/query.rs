@@ -0,0 +1,94 @@
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper, CountingServer};
+use consulrs::{
+    api::query::{
+        common::{QueryFailoverOptionsBuilder, ServiceQuery, ServiceQueryBuilder},
+        requests::{CreateQueryRequest, UpdateQueryRequest},
+    },
+    client::Client,
+    query,
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let counting: CountingServer = instance.server();
+        let client = server.client();
+        let service = common::setup(&client, &counting).await;
+
+        let id = test_create(&client, &service.name).await;
+        test_read(&client, &id).await;
+        test_list(&client).await;
+        test_update(&client, &id, &service.name).await;
+        test_execute(&client, "test").await;
+        test_explain(&client, "test").await;
+        test_delete(&client, &id).await;
+    });
+}
+
+fn service_query(service: &str) -> ServiceQuery {
+    ServiceQueryBuilder::default()
+        .service(service)
+        .only_passing(false)
+        .failover(
+            QueryFailoverOptionsBuilder::default()
+                .nearest_n(3u64)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap()
+}
+
+async fn test_create(client: &impl Client, service: &str) -> String {
+    let res = query::create(
+        client,
+        service_query(service),
+        Some(CreateQueryRequest::builder().name("test")),
+    )
+    .await;
+    assert!(res.is_ok());
+    res.unwrap().response.id
+}
+
+async fn test_delete(client: &impl Client, id: &str) {
+    let res = query::delete(client, id, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_execute(client: &impl Client, name: &str) {
+    let res = query::execute(client, name, None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.nodes.len(), 1);
+}
+
+async fn test_explain(client: &impl Client, name: &str) {
+    let res = query::explain(client, name, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_list(client: &impl Client) {
+    let res = query::list(client, None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.len(), 1);
+}
+
+async fn test_read(client: &impl Client, id: &str) {
+    let res = query::read(client, id, None).await;
+    assert!(res.is_ok());
+}
+
+async fn test_update(client: &impl Client, id: &str, service: &str) {
+    let res = query::update(
+        client,
+        id,
+        service_query(service),
+        Some(UpdateQueryRequest::builder().name("test")),
+    )
+    .await;
+    assert!(res.is_ok());
+}
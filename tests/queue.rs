@@ -0,0 +1,106 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{client::ConsulClient, kv, queue};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = Arc::new(server.client());
+
+        test_order(client.clone()).await;
+        test_nack(client.clone()).await;
+        test_visibility_timeout(client).await;
+    });
+}
+
+async fn test_order(client: Arc<ConsulClient>) {
+    let timeout = Duration::from_secs(10);
+    for payload in ["a", "b", "c"] {
+        let res = queue::push(client.as_ref(), "test/order", payload).await;
+        assert!(res.is_ok());
+    }
+
+    let first = queue::pop(client.clone(), "test/order", timeout)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.payload(), b"a");
+
+    // Held items are skipped by other consumers
+    let second = queue::pop(client.clone(), "test/order", timeout)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(second.payload(), b"b");
+
+    assert!(first.ack().await.unwrap());
+    assert!(second.ack().await.unwrap());
+    let third = queue::pop(client.clone(), "test/order", timeout)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(third.payload(), b"c");
+    assert!(third.ack().await.unwrap());
+
+    let res = queue::pop(client.clone(), "test/order", timeout).await;
+    assert!(res.unwrap().is_none());
+    let res = kv::keys(client.as_ref(), "test/order/", None).await;
+    assert_eq!(res.unwrap().response, vec!["test/order/.sequence"]);
+}
+
+async fn test_nack(client: Arc<ConsulClient>) {
+    let timeout = Duration::from_secs(10);
+    queue::push(client.as_ref(), "test/nack", "a")
+        .await
+        .unwrap();
+
+    let message = queue::pop(client.clone(), "test/nack", timeout)
+        .await
+        .unwrap()
+        .unwrap();
+    let res = queue::pop(client.clone(), "test/nack", timeout).await;
+    assert!(res.unwrap().is_none());
+
+    assert!(message.nack().await.is_ok());
+    let message = queue::pop(client.clone(), "test/nack", timeout)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(message.payload(), b"a");
+    assert!(message.ack().await.unwrap());
+}
+
+async fn test_visibility_timeout(client: Arc<ConsulClient>) {
+    let timeout = Duration::from_secs(10);
+    queue::push(client.as_ref(), "test/timeout", "a")
+        .await
+        .unwrap();
+
+    // A consumer which stops renewing its session loses the item once the
+    // session expires, which Consul may take up to twice the TTL to notice
+    let message = queue::pop(client.clone(), "test/timeout", timeout)
+        .await
+        .unwrap()
+        .unwrap();
+    drop(message);
+
+    let mut message = None;
+    for _ in 0..30 {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        message = queue::pop(client.clone(), "test/timeout", timeout)
+            .await
+            .unwrap();
+        if message.is_some() {
+            break;
+        }
+    }
+    let message = message.unwrap();
+    assert_eq!(message.payload(), b"a");
+    assert!(message.ack().await.unwrap());
+}
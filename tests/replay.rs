@@ -0,0 +1,62 @@
+#![cfg(feature = "replay")]
+
+use consulrs::{
+    client::{ConsulClient, ConsulClientSettingsBuilder},
+    kv,
+    replay::{Cassette, Mode},
+    status,
+};
+
+fn client(cassette: Cassette) -> ConsulClient {
+    ConsulClient::new(
+        ConsulClientSettingsBuilder::default()
+            .address("http://127.0.0.1:1")
+            .cassette(cassette)
+            .build()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_replay() {
+    let cassette = Cassette::replay("tests/fixtures/replay.json").unwrap();
+    assert_eq!(cassette.mode(), Mode::Replay);
+    let client = client(cassette);
+
+    let res = status::leader(&client, None).await;
+    assert_eq!(res.unwrap().response, "127.0.0.1:8300");
+
+    let res = kv::read_raw(&client, "test", None).await.unwrap();
+    assert_eq!(res.response, vec![0, 1, 255]);
+    assert_eq!(res.index.as_deref(), Some("12"));
+    let res = kv::read_raw(&client, "test", None).await;
+    assert!(res.unwrap_err().is_not_found());
+
+    let res = kv::read_raw(&client, "test", None).await;
+    assert_eq!(res.unwrap_err().status(), Some(501));
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn test_record() {
+    use consulrs::mock::MockClient;
+
+    let path = std::env::temp_dir().join(format!("consulrs-{}-record.json", std::process::id()));
+    let recording = MockClient::with_settings(
+        ConsulClientSettingsBuilder::default()
+            .cassette(Cassette::record(&path))
+            .build()
+            .unwrap(),
+    );
+    kv::set(&recording, "test", b"value", None).await.unwrap();
+    let recorded = kv::read_raw(&recording, "test", None).await.unwrap();
+
+    let client = client(Cassette::replay(&path).unwrap());
+    assert!(kv::set(&client, "test", b"other", None).await.is_ok());
+    let res = kv::read_raw(&client, "test", None).await.unwrap();
+    assert_eq!(res.response, b"value");
+    assert_eq!(res.index, recorded.index);
+
+    std::fs::remove_file(&path).unwrap();
+}
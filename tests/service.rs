@@ -1,7 +1,17 @@
 mod common;
 
 use common::{ConsulServer, ConsulServerHelper, CountingServer};
-use consulrs::{client::Client, service};
+use consulrs::{
+    api::{
+        check::common::AgentServiceCheckBuilder,
+        service::{
+            common::{AgentServiceRegistrationBuilder, AgentWeightsBuilder},
+            requests::{EnableMaintenanceRequest, RegisterServiceRequestBuilder},
+        },
+    },
+    client::Client,
+    service,
+};
 use test_env_log::test;
 
 #[test]
@@ -14,10 +24,13 @@ fn test() {
         let service = common::setup(&client, &counting).await;
 
         test_register(&client, "test").await;
+        test_register_definition(&client, "definition").await;
         test_list(&client).await;
         test_read(&client, &service.name).await;
+        test_try_read(&client, &service.name).await;
         test_health(&client, &service.name).await;
         test_maintenance(&client, &service.name).await;
+        test_maintenance_with_reason(&client, &service.name).await;
         test_deregister(&client, &service.name).await;
     });
 }
@@ -42,12 +55,56 @@ async fn test_maintenance(client: &impl Client, name: &str) {
     assert!(res.is_ok());
 }
 
+async fn test_maintenance_with_reason(client: &impl Client, name: &str) {
+    let res = service::maintenance(
+        client,
+        name,
+        true,
+        Some(EnableMaintenanceRequest::builder().reason("testing")),
+    )
+    .await;
+    assert!(res.is_ok());
+}
+
 async fn test_register(client: &impl Client, name: &str) {
     let res = service::register(client, name, None).await;
     assert!(res.is_ok());
 }
 
+async fn test_register_definition(client: &impl Client, name: &str) {
+    let registration = AgentServiceRegistrationBuilder::default()
+        .port(8080u64)
+        .tags(vec!["test".to_string()])
+        .check(
+            AgentServiceCheckBuilder::default()
+                .ttl("10s")
+                .build()
+                .unwrap(),
+        )
+        .weights(
+            AgentWeightsBuilder::default()
+                .passing(2u64)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    let mut opts = RegisterServiceRequestBuilder::from(registration);
+    let res = service::register(client, name, Some(opts.replace_existing_checks(true))).await;
+    assert!(res.is_ok());
+}
+
 async fn test_read(client: &impl Client, name: &str) {
     let res = service::read(client, name, None).await;
     assert!(res.is_ok());
 }
+
+async fn test_try_read(client: &impl Client, name: &str) {
+    let res = service::try_read(client, name, None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_some());
+
+    let res = service::try_read(client, "missing", None).await;
+    assert!(res.is_ok());
+    assert!(res.unwrap().is_none());
+}
@@ -1,7 +1,13 @@
 mod common;
 
+use std::{sync::Arc, time::Duration};
+
 use common::{ConsulServer, ConsulServerHelper};
-use consulrs::{api::session::requests::CreateSessionRequest, client::Client, session};
+use consulrs::{
+    api::session::{common::SessionBehavior, requests::CreateSessionRequest},
+    client::{Client, ConsulClient},
+    session::{self, RenewalTask},
+};
 use test_env_log::test;
 
 #[test]
@@ -13,11 +19,13 @@ fn test() {
         let node = server.node().await;
 
         let uuid = test_create(&client).await;
+        test_create_with_behavior(&client).await;
         test_read(&client, &uuid).await;
         test_list(&client).await;
         test_list_by_node(&client, &node).await;
         test_renew(&client, &uuid).await;
         test_delete(&client, &uuid).await;
+        test_renewal_task(Arc::new(client)).await;
     });
 }
 
@@ -28,6 +36,28 @@ async fn test_create(client: &impl Client) -> String {
     res.unwrap().response.id.clone()
 }
 
+async fn test_create_with_behavior(client: &impl Client) {
+    let res = session::create(
+        client,
+        Some(
+            CreateSessionRequest::builder()
+                .behavior(SessionBehavior::Delete)
+                .ttl("10m"),
+        ),
+    )
+    .await;
+    assert!(res.is_ok());
+
+    let uuid = res.unwrap().response.id;
+    let res = session::read(client, &uuid, None).await;
+    assert!(res.is_ok());
+    assert_eq!(
+        res.unwrap().response[0].behavior,
+        Some(SessionBehavior::Delete)
+    );
+    test_delete(client, &uuid).await;
+}
+
 async fn test_delete(client: &impl Client, name: &str) {
     let res = session::delete(client, name, None).await;
     assert!(res.is_ok());
@@ -52,3 +82,19 @@ async fn test_renew(client: &impl Client, name: &str) {
     let res = session::renew(client, name, None).await;
     assert!(res.is_ok());
 }
+
+async fn test_renewal_task(client: Arc<ConsulClient>) {
+    let uuid = test_create(client.as_ref()).await;
+    let mut renewal = RenewalTask::start(client.clone(), &uuid, Duration::from_secs(2));
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    assert!(renewal.failures().try_recv().is_err());
+    assert!(renewal.is_active());
+    assert_eq!(renewal.session(), uuid);
+
+    test_delete(client.as_ref(), &uuid).await;
+    let mut active = renewal.active();
+    let res = tokio::time::timeout(Duration::from_secs(5), active.changed()).await;
+    assert!(res.is_ok());
+    assert!(!renewal.is_active());
+    renewal.stop();
+}
@@ -1,7 +1,10 @@
 mod common;
 
+use std::io::Cursor;
+
 use common::{ConsulServer, ConsulServerHelper};
 use consulrs::{client::Client, snapshot};
+use futures::TryStreamExt;
 use test_env_log::test;
 
 #[test]
@@ -12,7 +15,9 @@ fn test() {
         let client = server.client();
 
         let snapshot = test_backup(&client).await;
-        test_restore(&client, &snapshot).await;
+        test_restore(&client, snapshot).await;
+        let snapshot = test_save(&client).await;
+        test_restore(&client, snapshot).await;
     });
 }
 
@@ -23,7 +28,17 @@ async fn test_backup(client: &impl Client) -> Vec<u8> {
     res.unwrap().response
 }
 
-async fn test_restore(client: &impl Client, snapshot: &[u8]) {
-    let res = snapshot::restore(client, snapshot, None).await;
+async fn test_restore(client: &impl Client, snapshot: Vec<u8>) {
+    let res = snapshot::restore(client, Cursor::new(snapshot), None).await;
     assert!(res.is_ok());
 }
+
+async fn test_save(client: &impl Client) -> Vec<u8> {
+    let res = snapshot::save(client, None).await;
+    assert!(res.is_ok());
+
+    let chunks: Vec<_> = res.unwrap().response.try_collect().await.unwrap();
+    let snapshot = chunks.concat();
+    assert!(!snapshot.is_empty());
+    snapshot
+}
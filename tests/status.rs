@@ -0,0 +1,29 @@
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{client::Client, status};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+
+        test_leader(&client).await;
+        test_peers(&client).await;
+    });
+}
+
+async fn test_leader(client: &impl Client) {
+    let res = status::leader(client, None).await;
+    assert!(res.is_ok());
+    assert!(!res.unwrap().response.is_empty());
+}
+
+async fn test_peers(client: &impl Client) {
+    let res = status::peers(client, None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.len(), 1);
+}
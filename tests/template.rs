@@ -0,0 +1,57 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    client::ConsulClient,
+    kv,
+    template::{Template, TemplateConfig, TemplateData},
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = Arc::new(server.client());
+
+        test_render(client).await;
+    });
+}
+
+async fn test_render(client: Arc<ConsulClient>) {
+    let res = kv::set(client.as_ref(), "template/port", b"8080", None).await;
+    assert!(res.is_ok());
+
+    let path = std::env::temp_dir().join(format!("consulrs-template-{}", std::process::id()));
+    let config = TemplateConfig::builder()
+        .destination(path.clone())
+        .keys(vec!["template/".to_string(), "missing/".to_string()])
+        .services(vec!["consul".to_string()])
+        .min_wait(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let res = Template::start(client.clone(), config, |data: &TemplateData| {
+        Ok(format!(
+            "{} {}",
+            data.service("consul").len(),
+            data.key("template/port").unwrap_or_default()
+        ))
+    })
+    .await;
+    assert!(res.is_ok());
+    let template = res.unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "1 8080");
+
+    let mut rx = template.subscribe();
+    let res = kv::set(client.as_ref(), "template/port", b"9090", None).await;
+    assert!(res.is_ok());
+    let res = tokio::time::timeout(Duration::from_secs(5), rx.changed()).await;
+    assert!(res.is_ok());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "1 9090");
+
+    template.stop();
+    std::fs::remove_file(&path).unwrap();
+}
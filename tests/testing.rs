@@ -0,0 +1,34 @@
+#![cfg(feature = "consulrs-test")]
+
+use consulrs::{
+    client::ConsulClient,
+    kv,
+    testing::{ConsulServer, ConsulServerConfig},
+};
+
+#[tokio::test]
+async fn test_start() {
+    let server = ConsulServer::start().await.unwrap();
+    assert!(server.token().is_none());
+
+    let client = server.client();
+    kv::set(&client, "test", b"value", None).await.unwrap();
+    let res = kv::read_raw(&client, "test", None).await.unwrap();
+    assert_eq!(res.response, b"value");
+}
+
+#[tokio::test]
+async fn test_acl() {
+    let server = ConsulServer::start_with(ConsulServerConfig::builder().acl(true).build().unwrap())
+        .await
+        .unwrap();
+    assert!(server.token().is_some());
+
+    let res = kv::set(&server.client(), "test", b"value", None).await;
+    assert!(res.is_ok());
+
+    let anonymous =
+        ConsulClient::new(server.settings().token(String::new()).build().unwrap()).unwrap();
+    let res = kv::read(&anonymous, "test", None).await;
+    assert_eq!(res.unwrap_err().status(), Some(403));
+}
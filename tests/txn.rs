@@ -0,0 +1,34 @@
+mod common;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{
+    client::Client,
+    error::ClientError,
+    txn::{self, Transaction},
+};
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = server.client();
+
+        test_execute(&client).await;
+        test_rollback(&client).await;
+    });
+}
+
+async fn test_execute(client: &impl Client) {
+    let t = Transaction::new().set("a", b"1").set("b", b"2").get("a");
+    let res = txn::execute(client, &t, None).await;
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().response.results.unwrap().len(), 3);
+}
+
+async fn test_rollback(client: &impl Client) {
+    let t = Transaction::new().delete("a").check_index("b", 1);
+    let res = txn::execute(client, &t, None).await;
+    assert!(matches!(res, Err(ClientError::TransactionError { .. })));
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{ConsulServer, ConsulServerHelper};
+use consulrs::{client::ConsulClient, kv, watch_group::WatchGroup};
+use futures::StreamExt;
+use test_env_log::test;
+
+#[test]
+fn test() {
+    let test = common::new_test();
+    test.run(|instance| async move {
+        let server: ConsulServer = instance.server();
+        let client = Arc::new(server.client());
+
+        test_shared(client).await;
+    });
+}
+
+async fn test_shared(client: Arc<ConsulClient>) {
+    let res = kv::set(client.as_ref(), "group/db/host", b"localhost", None).await;
+    assert!(res.is_ok());
+
+    let group = WatchGroup::new(client.clone());
+    let mut host = group.key("group/db/host");
+    let mut port = group.key("group/db/port");
+    assert_eq!(group.prefixes(), vec!["group/db/"]);
+
+    assert!(host.next().await.unwrap().is_some());
+    assert!(port.next().await.unwrap().is_none());
+
+    let res = kv::set(client.as_ref(), "group/db/port", b"5432", None).await;
+    assert!(res.is_ok());
+    let res = port.next().await.unwrap();
+    assert_eq!(res.unwrap().key, "group/db/port");
+}